@@ -27,6 +27,17 @@ fn main() -> Result<(), UnbundleError> {
         analysis.min_frames_per_second, analysis.max_frames_per_second
     );
     println!("  Mean FPS: {:.2}", analysis.mean_frames_per_second);
+    println!(
+        "  Predominant FPS: {:.2}",
+        analysis.predominant_frames_per_second
+    );
+    for bucket in &analysis.frame_rate_histogram {
+        println!(
+            "    ~{} fps: {:.1}% of frames",
+            bucket.nominal_frames_per_second,
+            bucket.fraction * 100.0
+        );
+    }
     println!("  Frames analyzed: {}", analysis.frames_analyzed);
 
     if analysis.is_variable_frame_rate {
@@ -0,0 +1,62 @@
+//! Atomic replacement of output files.
+//!
+//! With [`ExtractOptions::with_atomic_write`], file outputs are written to
+//! a hidden sibling of the destination and renamed into place once they are
+//! complete, so a process watching the output directory never sees a
+//! truncated file under the final name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::configuration::ExtractOptions;
+use crate::error::UnbundleError;
+
+/// Distinguishes temporary files of concurrent writes within this process.
+static NEXT_TEMPORARY: AtomicU64 = AtomicU64::new(0);
+
+/// Produce the file at `path` by calling `write` with the path to write to.
+///
+/// Without atomic writes `write` receives `path` itself. Otherwise it
+/// receives a temporary sibling, which is renamed onto `path` when `write`
+/// succeeds and removed when it fails. `write` must close the file before
+/// returning, or the rename fails on Windows.
+pub(crate) fn write_output<T>(
+    path: &Path,
+    config: Option<&ExtractOptions>,
+    write: impl FnOnce(&Path) -> Result<T, UnbundleError>,
+) -> Result<T, UnbundleError> {
+    if !config.is_some_and(|config| config.atomic_write) {
+        return write(path);
+    }
+
+    let temporary = temporary_sibling(path);
+    log::debug!("Writing {} through {}", path.display(), temporary.display());
+    let result = write(&temporary).and_then(|value| {
+        fs::rename(&temporary, path)?;
+        Ok(value)
+    });
+    if result.is_err() {
+        // The writer may have failed before creating the file.
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// A hidden file next to `path` with the same extension, since FFmpeg and
+/// the `image` crate choose the output format from it.
+fn temporary_sibling(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "output".into(), |stem| stem.to_string_lossy());
+    let mut name = format!(
+        ".{stem}.partial-{}-{}",
+        std::process::id(),
+        NEXT_TEMPORARY.fetch_add(1, Ordering::Relaxed)
+    );
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
@@ -12,22 +12,26 @@ use std::{
 };
 
 use ffmpeg_next::{
-    ChannelLayout, Packet, Rational,
+    ChannelLayout, Dictionary, Packet, Rational,
     codec::{Id, context::Context as CodecContext},
     decoder::Audio as AudioDecoder,
     encoder::Audio as AudioEncoder,
+    filter::Graph as FilterGraph,
     format::{Sample, context::Output, sample::Type as SampleType},
     frame::Audio as AudioFrame,
     packet::Mut as PacketMut,
     software::resampling::Context as ResamplingContext,
 };
 use ffmpeg_sys_next::{AVFormatContext, AVRational};
+#[cfg(feature = "async")]
+use std::ffi::{c_int, c_void};
 
 use crate::{
     audio_iterator::AudioIterator,
-    configuration::ExtractOptions,
+    configuration::{ExtractOptions, ResampleQuality},
     error::UnbundleError,
     progress::{OperationType, ProgressTracker},
+    summary::{ExtractSummary, PacketSpan},
     unbundle::MediaFile,
 };
 
@@ -35,8 +39,10 @@ use crate::{
 use crate::loudness::LoudnessInfo;
 
 #[cfg(feature = "async")]
-use crate::stream::AudioFuture;
+use crate::stream::{AudioFuture, AudioStream};
 
+#[cfg(feature = "spectrogram")]
+use crate::spectrogram::MelOptions;
 #[cfg(feature = "waveform")]
 use crate::waveform::{WaveformData, WaveformOptions};
 
@@ -78,6 +84,16 @@ impl AudioFormat {
         }
     }
 
+    /// Return the file extension for this audio format.
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Aac => "aac",
+        }
+    }
+
     /// Return the FFmpeg codec ID for this audio format.
     fn codec_id(&self) -> Id {
         match self {
@@ -89,6 +105,86 @@ impl AudioFormat {
     }
 }
 
+/// Sample layout for headerless PCM from
+/// [`AudioHandle::extract_raw_pcm`].
+///
+/// Named after FFmpeg's sample formats: all variants are native
+/// little-endian, and the `Planar` variants store each channel's samples
+/// contiguously (all of channel 0, then all of channel 1, …) instead of
+/// interleaving them frame by frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcmSampleFormat {
+    /// Signed 16-bit integer, interleaved (`s16le`).
+    S16,
+    /// Signed 32-bit integer, interleaved (`s32le`).
+    S32,
+    /// 32-bit float, interleaved (`f32le`).
+    F32,
+    /// 64-bit float, interleaved (`f64le`).
+    F64,
+    /// Signed 16-bit integer, planar.
+    S16Planar,
+    /// Signed 32-bit integer, planar.
+    S32Planar,
+    /// 32-bit float, planar.
+    F32Planar,
+    /// 64-bit float, planar.
+    F64Planar,
+}
+
+impl PcmSampleFormat {
+    /// Size of a single sample in bytes.
+    pub fn bytes_per_sample(self) -> usize {
+        match self {
+            PcmSampleFormat::S16 | PcmSampleFormat::S16Planar => 2,
+            PcmSampleFormat::S32
+            | PcmSampleFormat::S32Planar
+            | PcmSampleFormat::F32
+            | PcmSampleFormat::F32Planar => 4,
+            PcmSampleFormat::F64 | PcmSampleFormat::F64Planar => 8,
+        }
+    }
+
+    /// Returns `true` for the planar variants.
+    pub fn is_planar(self) -> bool {
+        matches!(
+            self,
+            PcmSampleFormat::S16Planar
+                | PcmSampleFormat::S32Planar
+                | PcmSampleFormat::F32Planar
+                | PcmSampleFormat::F64Planar
+        )
+    }
+
+    /// Map to the corresponding FFmpeg sample format.
+    fn to_ffmpeg_sample(self) -> Sample {
+        let layout = if self.is_planar() {
+            SampleType::Planar
+        } else {
+            SampleType::Packed
+        };
+        match self {
+            PcmSampleFormat::S16 | PcmSampleFormat::S16Planar => Sample::I16(layout),
+            PcmSampleFormat::S32 | PcmSampleFormat::S32Planar => Sample::I32(layout),
+            PcmSampleFormat::F32 | PcmSampleFormat::F32Planar => Sample::F32(layout),
+            PcmSampleFormat::F64 | PcmSampleFormat::F64Planar => Sample::F64(layout),
+        }
+    }
+}
+
+/// Describes the bytes returned by [`AudioHandle::extract_raw_pcm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmInfo {
+    /// Sample layout of the data.
+    pub sample_format: PcmSampleFormat,
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+    /// Number of channels.
+    pub channels: u16,
+    /// Number of samples in each channel.
+    pub samples_per_channel: u64,
+}
+
 /// Audio extraction operations.
 ///
 /// Obtained via [`MediaFile::audio`] or
@@ -108,6 +204,75 @@ impl<'a> AudioHandle<'a> {
             .ok_or(UnbundleError::NoAudioStream)
     }
 
+    /// Where the audio of `stream_index` ends: the track's own duration
+    /// when the container records it, otherwise the media duration.
+    fn audio_end(&self, stream_index: usize) -> Duration {
+        let media_duration = self.unbundler.metadata.duration;
+        self.unbundler
+            .metadata
+            .audio_tracks
+            .iter()
+            .flatten()
+            .find(|track| track.stream_index == stream_index)
+            .and_then(|track| track.duration)
+            .filter(|duration| !duration.is_zero())
+            .map_or(media_duration, |duration| duration.min(media_duration))
+    }
+
+    /// Check a requested range against the media and the audio track.
+    ///
+    /// A start past the audio but within the media (a clip whose audio
+    /// stops before its video) would otherwise seek past the last audio
+    /// packet and quietly produce an empty file.
+    fn validate_range(
+        &self,
+        stream_index: usize,
+        start: Option<Duration>,
+        end: Option<Duration>,
+    ) -> Result<(), UnbundleError> {
+        let media_duration = self.unbundler.metadata.duration;
+        if let Some(start_time) = start
+            && start_time > media_duration
+        {
+            return Err(UnbundleError::InvalidTimestamp(start_time));
+        }
+        if let Some(end_time) = end
+            && end_time > media_duration
+        {
+            return Err(UnbundleError::InvalidTimestamp(end_time));
+        }
+
+        if let Some(start_time) = start {
+            let audio_end = self.audio_end(stream_index);
+            if !start_time.is_zero() && start_time >= audio_end {
+                return Err(UnbundleError::AudioRangeEmpty {
+                    start: start_time,
+                    audio_end,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Report a range that decoded to no samples at all, which happens when
+    /// the container overstates how long the audio runs.
+    fn ensure_samples_in_range(
+        &self,
+        stream_index: usize,
+        samples_written: i64,
+        start: Option<Duration>,
+        end: Option<Duration>,
+    ) -> Result<(), UnbundleError> {
+        if samples_written > 0 || (start.is_none() && end.is_none()) {
+            return Ok(());
+        }
+        log::warn!("Audio range {start:?}..{end:?} decoded to no samples");
+        Err(UnbundleError::AudioRangeEmpty {
+            start: start.unwrap_or_default(),
+            audio_end: self.audio_end(stream_index),
+        })
+    }
+
     /// Extract the complete audio track to memory.
     ///
     /// Returns the encoded audio data as a byte vector in the specified format.
@@ -129,7 +294,7 @@ impl<'a> AudioHandle<'a> {
     /// # Ok::<(), UnbundleError>(())
     /// ```
     pub fn extract(&mut self, format: AudioFormat) -> Result<Vec<u8>, UnbundleError> {
-        self.extract_audio_to_memory(format, None, None, None)
+        self.extract_audio_to_memory(format, None, None, None, None)
     }
 
     /// Extract an audio segment by time range to memory.
@@ -140,7 +305,9 @@ impl<'a> AudioHandle<'a> {
     ///
     /// Returns errors from [`extract`](AudioHandle::extract), plus
     /// [`UnbundleError::InvalidTimestamp`] if either timestamp exceeds the
-    /// media duration.
+    /// media duration, or [`UnbundleError::AudioRangeEmpty`] if `start` is
+    /// at or past the end of the audio track (which can end before the
+    /// media on short clips). No file is left at `path` on these errors.
     ///
     /// # Example
     ///
@@ -169,7 +336,7 @@ impl<'a> AudioHandle<'a> {
                 end: format!("{end:?}"),
             });
         }
-        self.extract_audio_to_memory(format, Some(start), Some(end), None)
+        self.extract_audio_to_memory(format, Some(start), Some(end), None, None)
     }
 
     /// Save the complete audio track to a file.
@@ -196,7 +363,8 @@ impl<'a> AudioHandle<'a> {
         path: P,
         format: AudioFormat,
     ) -> Result<(), UnbundleError> {
-        self.save_audio_to_file(path.as_ref(), format, None, None, None)
+        self.save_audio_to_file(path.as_ref(), format, None, None, None)?;
+        Ok(())
     }
 
     /// Save an audio segment to a file.
@@ -205,7 +373,9 @@ impl<'a> AudioHandle<'a> {
     ///
     /// Returns errors from [`save`](AudioHandle::save), plus
     /// [`UnbundleError::InvalidTimestamp`] if either timestamp exceeds the
-    /// media duration.
+    /// media duration, or [`UnbundleError::AudioRangeEmpty`] if `start` is
+    /// at or past the end of the audio track (which can end before the
+    /// media on short clips).
     ///
     /// # Example
     ///
@@ -236,7 +406,8 @@ impl<'a> AudioHandle<'a> {
                 end: format!("{end:?}"),
             });
         }
-        self.save_audio_to_file(path.as_ref(), format, Some(start), Some(end), None)
+        self.save_audio_to_file(path.as_ref(), format, Some(start), Some(end), None)?;
+        Ok(())
     }
 
     /// Extract the complete audio track to memory with cancellation support.
@@ -267,7 +438,7 @@ impl<'a> AudioHandle<'a> {
         format: AudioFormat,
         config: &ExtractOptions,
     ) -> Result<Vec<u8>, UnbundleError> {
-        self.extract_audio_to_memory(format, None, None, Some(config))
+        self.extract_audio_to_memory(format, None, None, None, Some(config))
     }
 
     /// Extract an audio segment to memory with cancellation support.
@@ -292,7 +463,7 @@ impl<'a> AudioHandle<'a> {
                 end: format!("{end:?}"),
             });
         }
-        self.extract_audio_to_memory(format, Some(start), Some(end), Some(config))
+        self.extract_audio_to_memory(format, Some(start), Some(end), None, Some(config))
     }
 
     /// Save the complete audio track to a file with cancellation support.
@@ -310,7 +481,8 @@ impl<'a> AudioHandle<'a> {
         format: AudioFormat,
         config: &ExtractOptions,
     ) -> Result<(), UnbundleError> {
-        self.save_audio_to_file(path.as_ref(), format, None, None, Some(config))
+        self.save_audio_to_file(path.as_ref(), format, None, None, Some(config))?;
+        Ok(())
     }
 
     /// Save an audio segment to a file with cancellation support.
@@ -330,6 +502,52 @@ impl<'a> AudioHandle<'a> {
         format: AudioFormat,
         config: &ExtractOptions,
     ) -> Result<(), UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.save_audio_to_file(path.as_ref(), format, Some(start), Some(end), Some(config))?;
+        Ok(())
+    }
+
+    /// Save the complete audio track and report what was written.
+    ///
+    /// Like [`save_with_options`](AudioHandle::save_with_options) but
+    /// returns an [`ExtractSummary`] with the output's real duration, size
+    /// and sample count.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`save_with_options`](AudioHandle::save_with_options).
+    pub fn save_with_summary<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: AudioFormat,
+        config: &ExtractOptions,
+    ) -> Result<ExtractSummary, UnbundleError> {
+        self.save_audio_to_file(path.as_ref(), format, None, None, Some(config))
+    }
+
+    /// Save an audio segment and report what was written.
+    ///
+    /// Like [`save_range_with_options`](AudioHandle::save_range_with_options)
+    /// but returns an [`ExtractSummary`]. Its duration is that of the
+    /// encoded output, which a lossy encoder rounds up to a whole frame, so
+    /// it can exceed `end - start` slightly.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`save_range_with_options`](AudioHandle::save_range_with_options).
+    pub fn save_range_with_summary<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        start: Duration,
+        end: Duration,
+        format: AudioFormat,
+        config: &ExtractOptions,
+    ) -> Result<ExtractSummary, UnbundleError> {
         if start >= end {
             return Err(UnbundleError::InvalidRange {
                 start: format!("{start:?}"),
@@ -339,6 +557,127 @@ impl<'a> AudioHandle<'a> {
         self.save_audio_to_file(path.as_ref(), format, Some(start), Some(end), Some(config))
     }
 
+    /// Extract a single channel of the audio track as mono.
+    ///
+    /// `channel_index` is zero-based and follows the source's channel
+    /// order, so in a 5.1 (`FL FR FC LFE BL BR`) stream index 2 is the
+    /// centre (dialogue) channel. The channel is isolated with FFmpeg's
+    /// `pan` filter and copied as is, unlike a downmix, which would blend
+    /// in the other channels.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::ChannelOutOfRange`] if the stream has no channel
+    ///   at `channel_index`.
+    /// - [`UnbundleError::FilterGraphError`] if the channel filter cannot
+    ///   be set up or run.
+    /// - Any error from [`extract`](AudioHandle::extract).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{AudioFormat, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("surround.mkv")?;
+    /// let dialogue = unbundler.audio().extract_channel(2, AudioFormat::Wav)?;
+    /// std::fs::write("center.wav", dialogue)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn extract_channel(
+        &mut self,
+        channel_index: usize,
+        format: AudioFormat,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        self.extract_audio_to_memory(
+            format,
+            None,
+            None,
+            Some(AudioAdjustment::Channel(channel_index)),
+            None,
+        )
+    }
+
+    /// Extract a single channel as mono with progress and cancellation
+    /// support.
+    ///
+    /// Like [`extract_channel`](AudioHandle::extract_channel) but accepts
+    /// an [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested, or
+    /// any error from [`extract_channel`](AudioHandle::extract_channel).
+    pub fn extract_channel_with_options(
+        &mut self,
+        channel_index: usize,
+        format: AudioFormat,
+        config: &ExtractOptions,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        self.extract_audio_to_memory(
+            format,
+            None,
+            None,
+            Some(AudioAdjustment::Channel(channel_index)),
+            Some(config),
+        )
+    }
+
+    /// Decode and resample the complete audio track to headerless PCM.
+    ///
+    /// Unlike [`AudioFormat::Wav`], no container is written: the returned
+    /// bytes are the raw samples in the requested layout, and the
+    /// accompanying [`PcmInfo`] describes them. This suits DSP pipelines
+    /// that would otherwise have to parse a WAV header back off.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoAudioStream`] if the file has no audio stream.
+    /// - [`UnbundleError::AudioDecodeError`] if decoding or resampling fails,
+    ///   or if `sample_rate` or `channels` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, PcmSampleFormat, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let (bytes, info) = unbundler.audio().extract_raw_pcm(PcmSampleFormat::F32, 48_000, 2)?;
+    /// let samples: Vec<f32> = bytes
+    ///     .chunks_exact(4)
+    ///     .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+    ///     .collect();
+    /// assert_eq!(samples.len() as u64, info.samples_per_channel * 2);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn extract_raw_pcm(
+        &mut self,
+        sample_format: PcmSampleFormat,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<(Vec<u8>, PcmInfo), UnbundleError> {
+        self.decode_raw_pcm(sample_format, sample_rate, channels, None)
+    }
+
+    /// Decode the complete audio track to headerless PCM with progress and
+    /// cancellation support.
+    ///
+    /// Like [`extract_raw_pcm`](AudioHandle::extract_raw_pcm) but accepts
+    /// an [`ExtractOptions`], whose resample quality is also honoured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested, or
+    /// any error from [`extract_raw_pcm`](AudioHandle::extract_raw_pcm).
+    pub fn extract_raw_pcm_with_options(
+        &mut self,
+        sample_format: PcmSampleFormat,
+        sample_rate: u32,
+        channels: u16,
+        config: &ExtractOptions,
+    ) -> Result<(Vec<u8>, PcmInfo), UnbundleError> {
+        self.decode_raw_pcm(sample_format, sample_rate, channels, Some(config))
+    }
+
     /// Generate waveform data from the audio stream.
     ///
     /// Decodes audio to mono, buckets samples into the configured number
@@ -368,7 +707,67 @@ impl<'a> AudioHandle<'a> {
         config: &WaveformOptions,
     ) -> Result<WaveformData, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
-        crate::waveform::generate_waveform_impl(self.unbundler, audio_stream_index, config)
+        crate::waveform::generate_waveform_impl(self.unbundler, audio_stream_index, config, None)
+    }
+
+    /// Generate waveform data with progress reporting and cancellation.
+    ///
+    /// Like [`generate_waveform`](AudioHandle::generate_waveform), but
+    /// reports [`OperationType::WaveformGeneration`](crate::OperationType::WaveformGeneration)
+    /// progress where `current` is the number of decoded samples and
+    /// `total` the sample count expected for the requested range.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from [`generate_waveform`](AudioHandle::generate_waveform).
+    #[cfg(feature = "waveform")]
+    pub fn generate_waveform_with_options(
+        &mut self,
+        waveform_config: &WaveformOptions,
+        config: &ExtractOptions,
+    ) -> Result<WaveformData, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        crate::waveform::generate_waveform_impl(
+            self.unbundler,
+            audio_stream_index,
+            waveform_config,
+            Some(config),
+        )
+    }
+
+    /// Compute the audio's amplitude envelope at a fixed rate.
+    ///
+    /// Decodes the track to mono and returns the RMS amplitude of each
+    /// consecutive `1 / samples_per_second` window, so element `i` covers
+    /// the audio starting at `i / samples_per_second` seconds. Unlike
+    /// [`generate_waveform`](AudioHandle::generate_waveform), whose bin
+    /// width depends on the track length, the time resolution here is
+    /// fixed, which is what cross-correlating against a per-frame video
+    /// signal (for example to measure a lip-sync offset) needs. Passing
+    /// the video frame rate yields one value per frame.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidInterval`] if `samples_per_second` is not
+    ///   a positive, finite number or exceeds the track's sample rate.
+    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    /// - [`UnbundleError::WaveformDecodeError`] if decoding fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let envelope = unbundler.audio().envelope(100.0)?;
+    /// println!("{} envelope samples (10 ms each)", envelope.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "waveform")]
+    pub fn envelope(&mut self, samples_per_second: f64) -> Result<Vec<f32>, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        crate::waveform::envelope_impl(self.unbundler, audio_stream_index, samples_per_second)
     }
 
     /// Analyze loudness of the audio stream.
@@ -394,94 +793,352 @@ impl<'a> AudioHandle<'a> {
     #[cfg(feature = "loudness")]
     pub fn analyze_loudness(&mut self) -> Result<LoudnessInfo, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
-        crate::loudness::analyze_loudness_impl(self.unbundler, audio_stream_index)
+        crate::loudness::analyze_loudness_impl(self.unbundler, audio_stream_index, None)
     }
 
-    /// Create a lazy iterator over decoded audio samples.
+    /// Analyze loudness with progress reporting and cancellation.
     ///
-    /// The iterator yields [`AudioChunk`](crate::AudioChunk) values
-    /// containing mono f32 samples. Each chunk corresponds roughly to
-    /// one decoded audio frame, so the caller processes audio
-    /// incrementally without loading the entire track into memory.
+    /// Like [`analyze_loudness`](AudioHandle::analyze_loudness), but
+    /// reports [`OperationType::LoudnessAnalysis`](crate::OperationType::LoudnessAnalysis)
+    /// progress in decoded samples against the file duration, so long
+    /// tracks can drive a progress bar.
     ///
-    /// The iterator borrows the unbundler mutably; drop it to release
-    /// the borrow.
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from [`analyze_loudness`](AudioHandle::analyze_loudness).
+    #[cfg(feature = "loudness")]
+    pub fn analyze_loudness_with_options(
+        &mut self,
+        config: &ExtractOptions,
+    ) -> Result<LoudnessInfo, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        crate::loudness::analyze_loudness_impl(self.unbundler, audio_stream_index, Some(config))
+    }
+
+    /// Measure the integrated loudness of the audio track in LUFS.
+    ///
+    /// Follows ITU-R BS.1770-4 (as used by EBU R 128): K-weighted 400 ms
+    /// blocks, gated at -70 LUFS and at 10 LU below their mean. Tracks with
+    /// more than two channels are measured after a stereo downmix. Returns
+    /// [`f64::NEG_INFINITY`] for a silent track.
     ///
     /// # Errors
     ///
     /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    /// - [`UnbundleError::LoudnessError`] if decoding fails.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use unbundle::{MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let iter = unbundler.audio().sample_iter()?;
-    /// let mut total = 0u64;
-    /// for chunk in iter {
-    ///     total += chunk?.samples.len() as u64;
-    /// }
-    /// println!("Total mono samples: {total}");
+    /// let mut unbundler = MediaFile::open("episode.mp3")?;
+    /// println!("{:.1} LUFS", unbundler.audio().integrated_loudness()?);
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn sample_iter(self) -> Result<AudioIterator<'a>, UnbundleError> {
+    #[cfg(feature = "loudness")]
+    pub fn integrated_loudness(&mut self) -> Result<f64, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
-        AudioIterator::new(self.unbundler, audio_stream_index)
+        crate::loudness::integrated_loudness_impl(self.unbundler, audio_stream_index)
     }
 
-    // ── Stream copy (lossless) ─────────────────────────────────────────
-
-    /// Copy the audio stream verbatim to a file without re-encoding.
+    /// Extract the complete audio track with its level adjusted to
+    /// `target_lufs`.
     ///
-    /// Unlike [`save`](AudioHandle::save) which decodes and re-encodes,
-    /// this copies packets directly from the input, preserving the
-    /// original codec and quality. The output container format is
-    /// inferred from the file extension.
+    /// Measures [`integrated_loudness`](AudioHandle::integrated_loudness),
+    /// then extracts like [`extract`](AudioHandle::extract) with FFmpeg's
+    /// `volume` filter applying the difference as a constant gain. Unlike a
+    /// two-pass `loudnorm`, the dynamics are untouched, so raising a quiet
+    /// track can clip its peaks. A silent track is extracted unchanged.
+    /// -16 LUFS suits podcasts and -14 LUFS music streaming.
     ///
-    /// This is equivalent to `ffmpeg -i input.mp4 -vn -sn -c:a copy output.aac`.
+    /// The track is read twice, so the input must be seekable.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
-    /// - [`UnbundleError::StreamCopyError`] if the output container does
-    ///   not support the source codec.
+    /// - Any error from
+    ///   [`integrated_loudness`](AudioHandle::integrated_loudness) or
+    ///   [`extract`](AudioHandle::extract).
+    /// - [`UnbundleError::FilterGraphError`] if the gain filter cannot be
+    ///   set up or run.
     ///
     /// # Example
     ///
+    /// Bring a playlist of clips to a common level:
+    ///
     /// ```no_run
-    /// use unbundle::{MediaFile, UnbundleError};
+    /// use unbundle::{AudioFormat, MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.audio().stream_copy("output.aac")?;
+    /// for (index, clip) in ["intro.mp4", "interview.mp4", "outro.mp4"].iter().enumerate() {
+    ///     let mut unbundler = MediaFile::open(clip)?;
+    ///     let audio = unbundler.audio().extract_loudness_matched(-16.0, AudioFormat::Wav)?;
+    ///     std::fs::write(format!("clip_{index}.wav"), audio)?;
+    /// }
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn stream_copy<P: AsRef<Path>>(&mut self, path: P) -> Result<(), UnbundleError> {
-        self.copy_stream_to_file(path.as_ref(), None, None, None)
+    #[cfg(feature = "loudness")]
+    pub fn extract_loudness_matched(
+        &mut self,
+        target_lufs: f64,
+        format: AudioFormat,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        let measured = self.integrated_loudness()?;
+        let gain = if measured.is_finite() {
+            target_lufs - measured
+        } else {
+            0.0
+        };
+        log::debug!("Measured {measured:.2} LUFS, applying {gain:+.2} dB");
+
+        // The measurement read to the end; starting at zero seeks back.
+        self.extract_audio_to_memory(
+            format,
+            Some(Duration::ZERO),
+            None,
+            Some(AudioAdjustment::GainDb(gain)),
+            None,
+        )
     }
 
-    /// Copy an audio segment verbatim to a file without re-encoding.
+    /// Find silent stretches of the audio track.
     ///
-    /// Like [`stream_copy`](AudioHandle::stream_copy) but copies only
-    /// packets between `start` and `end`. Because there is no re-encoding,
-    /// the actual boundaries are aligned to the nearest packet — they may
-    /// not match the requested timestamps exactly.
+    /// The track is decoded to mono and measured in 10 ms windows; a
+    /// window is silent when its RMS level is below `threshold_dbfs`
+    /// (for example `-40.0` for speech recorded in a quiet room). Runs of
+    /// silent windows lasting at least `min_silence` are returned as
+    /// `(start, end)` pairs in order.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
-    /// - Plus any errors from [`stream_copy`](AudioHandle::stream_copy).
+    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    /// - [`UnbundleError::AudioDecodeError`] if decoding fails.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use std::time::Duration;
-    ///
     /// use unbundle::{MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.audio().stream_copy_range(
-    ///     "segment.aac",
+    /// let mut unbundler = MediaFile::open("podcast.mp3")?;
+    /// for (start, end) in unbundler.audio().detect_silence(-40.0, Duration::from_secs(2))? {
+    ///     println!("Silence from {start:?} to {end:?}");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "loudness")]
+    pub fn detect_silence(
+        &mut self,
+        threshold_dbfs: f64,
+        min_silence: Duration,
+    ) -> Result<Vec<(Duration, Duration)>, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        crate::loudness::detect_silence_impl(
+            self.unbundler,
+            audio_stream_index,
+            threshold_dbfs,
+            min_silence,
+        )
+    }
+
+    /// Split the audio track at its silences and save each non-silent part
+    /// to its own file.
+    ///
+    /// Silences are found as in
+    /// [`detect_silence`](AudioHandle::detect_silence) and left out of the
+    /// output. The parts between them are written to `output_dir` (created
+    /// if missing) as `segment_000.wav`, `segment_001.wav`, … with the
+    /// extension matching `format`. Returns the `(start, end)` of each
+    /// written segment, in file order.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`detect_silence`](AudioHandle::detect_silence) or
+    /// [`save_range`](AudioHandle::save_range), or I/O errors creating
+    /// `output_dir`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use unbundle::{AudioFormat, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("dj_set.flac")?;
+    /// let tracks = unbundler.audio().split_on_silence(
+    ///     -50.0,
+    ///     Duration::from_secs(3),
+    ///     "tracks",
+    ///     AudioFormat::Flac,
+    /// )?;
+    /// println!("Wrote {} tracks", tracks.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "loudness")]
+    pub fn split_on_silence<P: AsRef<Path>>(
+        &mut self,
+        threshold_dbfs: f64,
+        min_silence: Duration,
+        output_dir: P,
+        format: AudioFormat,
+    ) -> Result<Vec<(Duration, Duration)>, UnbundleError> {
+        let directory = output_dir.as_ref();
+        let silences = self.detect_silence(threshold_dbfs, min_silence)?;
+
+        // Decoding can run slightly past the container duration, which
+        // `save_range` rejects.
+        let duration = self.unbundler.metadata.duration;
+        let mut segments: Vec<(Duration, Duration)> = Vec::new();
+        let mut cursor = Duration::ZERO;
+        for (silence_start, silence_end) in silences {
+            let silence_start = silence_start.min(duration);
+            if silence_start > cursor {
+                segments.push((cursor, silence_start));
+            }
+            cursor = silence_end.min(duration);
+        }
+        if duration > cursor {
+            segments.push((cursor, duration));
+        }
+
+        log::info!(
+            "Splitting audio into {} segments in {}",
+            segments.len(),
+            directory.display()
+        );
+        std::fs::create_dir_all(directory)?;
+        for (index, (start, end)) in segments.iter().enumerate() {
+            let path = directory.join(format!("segment_{index:03}.{}", format.extension()));
+            self.save_range(path, *start, *end, format)?;
+        }
+        Ok(segments)
+    }
+
+    /// Compute the log-mel spectrogram of the audio track.
+    ///
+    /// Decodes the track to mono at
+    /// [`sample_rate`](MelOptions::sample_rate), then returns one row of
+    /// [`n_mels`](MelOptions::n_mels) `log10` mel energies per STFT frame,
+    /// frame `i` being centred on sample `i * hop_length`. The default
+    /// options reproduce Whisper's front end; add
+    /// [`whisper_normalization`](MelOptions::whisper_normalization) for its
+    /// final clamping and rescaling too.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::SpectrogramError`] if any option is zero (or
+    ///   `n_fft` is below 2).
+    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    /// - [`UnbundleError::AudioDecodeError`] if decoding fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, MelOptions, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("speech.mp4")?;
+    /// let mel = unbundler.audio().mel_spectrogram(&MelOptions::new().n_mels(128))?;
+    /// println!("{} frames", mel.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "spectrogram")]
+    pub fn mel_spectrogram(&mut self, config: &MelOptions) -> Result<Vec<Vec<f32>>, UnbundleError> {
+        config.validate()?;
+        let (bytes, _) = self.extract_raw_pcm(PcmSampleFormat::F32, config.sample_rate, 1)?;
+        let samples: Vec<f32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Ok(crate::spectrogram::log_mel_spectrogram(&samples, config))
+    }
+
+    /// Create a lazy iterator over decoded audio samples.
+    ///
+    /// The iterator yields [`AudioChunk`](crate::AudioChunk) values
+    /// containing mono f32 samples. Each chunk corresponds roughly to
+    /// one decoded audio frame, so the caller processes audio
+    /// incrementally without loading the entire track into memory.
+    /// Chunks carry their peak and RMS levels in dBFS, ready to drive a
+    /// level meter.
+    ///
+    /// The iterator borrows the unbundler mutably; drop it to release
+    /// the borrow.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let iter = unbundler.audio().sample_iter()?;
+    /// let mut total = 0u64;
+    /// for chunk in iter {
+    ///     total += chunk?.samples.len() as u64;
+    /// }
+    /// println!("Total mono samples: {total}");
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn sample_iter(self) -> Result<AudioIterator<'a>, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        AudioIterator::new(self.unbundler, audio_stream_index)
+    }
+
+    // ── Stream copy (lossless) ─────────────────────────────────────────
+
+    /// Copy the audio stream verbatim to a file without re-encoding.
+    ///
+    /// Unlike [`save`](AudioHandle::save) which decodes and re-encodes,
+    /// this copies packets directly from the input, preserving the
+    /// original codec and quality. The output container format is
+    /// inferred from the file extension.
+    ///
+    /// This is equivalent to `ffmpeg -i input.mp4 -vn -sn -c:a copy output.aac`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    /// - [`UnbundleError::StreamCopyError`] if the output container does
+    ///   not support the source codec.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.audio().stream_copy("output.aac")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn stream_copy<P: AsRef<Path>>(&mut self, path: P) -> Result<(), UnbundleError> {
+        self.copy_stream_to_file(path.as_ref(), None, None, None)?;
+        Ok(())
+    }
+
+    /// Copy an audio segment verbatim to a file without re-encoding.
+    ///
+    /// Like [`stream_copy`](AudioHandle::stream_copy) but copies only
+    /// packets between `start` and `end`. Because there is no re-encoding,
+    /// the actual boundaries are aligned to the nearest packet — they may
+    /// not match the requested timestamps exactly.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
+    /// - Plus any errors from [`stream_copy`](AudioHandle::stream_copy).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.audio().stream_copy_range(
+    ///     "segment.aac",
     ///     Duration::from_secs(10),
     ///     Duration::from_secs(20),
     /// )?;
@@ -499,7 +1156,8 @@ impl<'a> AudioHandle<'a> {
                 end: format!("{end:?}"),
             });
         }
-        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), None)
+        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), None)?;
+        Ok(())
     }
 
     /// Copy the audio stream verbatim to a file with cancellation support.
@@ -516,7 +1174,8 @@ impl<'a> AudioHandle<'a> {
         path: P,
         config: &ExtractOptions,
     ) -> Result<(), UnbundleError> {
-        self.copy_stream_to_file(path.as_ref(), None, None, Some(config))
+        self.copy_stream_to_file(path.as_ref(), None, None, Some(config))?;
+        Ok(())
     }
 
     /// Copy an audio segment verbatim to a file with cancellation support.
@@ -530,6 +1189,33 @@ impl<'a> AudioHandle<'a> {
         end: Duration,
         config: &ExtractOptions,
     ) -> Result<(), UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), Some(config))?;
+        Ok(())
+    }
+
+    /// Copy an audio segment verbatim and report what was written.
+    ///
+    /// Like [`stream_copy_range_with_options`](AudioHandle::stream_copy_range_with_options)
+    /// but returns an [`ExtractSummary`]. The copy starts and ends on
+    /// packet boundaries, and the summary's duration is the span actually
+    /// copied rather than `end - start`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`stream_copy_range`](AudioHandle::stream_copy_range).
+    pub fn stream_copy_range_with_summary<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        start: Duration,
+        end: Duration,
+        config: &ExtractOptions,
+    ) -> Result<ExtractSummary, UnbundleError> {
         if start >= end {
             return Err(UnbundleError::InvalidRange {
                 start: format!("{start:?}"),
@@ -592,19 +1278,105 @@ impl<'a> AudioHandle<'a> {
         self.copy_stream_to_memory(container_format, Some(start), Some(end), None)
     }
 
-    // ── Private helpers ────────────────────────────────────────────────
+    /// The container [`stream_copy_native_to_memory`](AudioHandle::stream_copy_native_to_memory)
+    /// uses for this stream's codec.
+    ///
+    /// Codecs with an elementary-stream format map to it (`"adts"` for
+    /// AAC, `"mp3"`, `"ac3"`, `"eac3"`, `"flac"`), Opus and Vorbis to
+    /// `"ogg"`, PCM to `"wav"`, and anything else to `"matroska"`, which
+    /// accepts nearly every audio codec.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::NoAudioStream`] if no audio stream exists.
+    pub fn stream_copy_container(&self) -> Result<&'static str, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(audio_stream_index)
+            .ok_or(UnbundleError::NoAudioStream)?;
+        let codec_id = stream.parameters().id();
+        Ok(match codec_id {
+            Id::AAC => "adts",
+            Id::MP3 => "mp3",
+            Id::AC3 => "ac3",
+            Id::EAC3 => "eac3",
+            Id::FLAC => "flac",
+            Id::OPUS | Id::VORBIS => "ogg",
+            _ if codec_id.name().starts_with("pcm_") => "wav",
+            _ => "matroska",
+        })
+    }
 
-    /// Extract audio to an in-memory buffer using FFmpeg's dynamic buffer I/O.
+    /// Copy the audio stream verbatim to memory in the container that
+    /// suits its codec.
+    ///
+    /// Like [`stream_copy_to_memory`](AudioHandle::stream_copy_to_memory)
+    /// with the container chosen by
+    /// [`stream_copy_container`](AudioHandle::stream_copy_container), so
+    /// the original AAC or MP3 track comes out as a plain `.aac` or `.mp3`
+    /// file without the caller knowing the codec in advance. Returns the
+    /// bytes with the container name.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`stream_copy_to_memory`](AudioHandle::stream_copy_to_memory).
+    ///
+    /// # Example
     ///
-    /// This uses `avio_open_dyn_buf` / `avio_close_dyn_buf` from the FFmpeg C
-    /// API (via `ffmpeg_sys_next`) to mux encoded audio into a memory buffer
-    /// without touching the filesystem.
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let (bytes, container) = unbundler.audio().stream_copy_native_to_memory()?;
+    /// let extension = match container {
+    ///     "adts" => "aac",
+    ///     "matroska" => "mka",
+    ///     other => other,
+    /// };
+    /// std::fs::write(format!("track.{extension}"), bytes)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn stream_copy_native_to_memory(
+        &mut self,
+    ) -> Result<(Vec<u8>, &'static str), UnbundleError> {
+        let container_format = self.stream_copy_container()?;
+        let bytes = self.copy_stream_to_memory(container_format, None, None, None)?;
+        Ok((bytes, container_format))
+    }
+
+    // ── Private helpers ────────────────────────────────────────────────
+
+    /// Extract audio to an in-memory buffer, attaching FFmpeg's log output
+    /// to any error.
     fn extract_audio_to_memory(
         &mut self,
         format: AudioFormat,
         start: Option<Duration>,
         end: Option<Duration>,
+        adjustment: Option<AudioAdjustment>,
+        config: Option<&ExtractOptions>,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.encode_audio_to_memory(format, start, end, adjustment, config, MuxSink::Buffer)
+            .map_err(|error| log_capture.attach(error))
+    }
+
+    /// Encode audio into memory without touching the filesystem.
+    ///
+    /// The muxer writes through a custom I/O context owned by `sink`: an
+    /// FFmpeg dynamic buffer whose contents are returned, or a callback
+    /// that receives each chunk as it is written (the returned `Vec` is
+    /// then empty).
+    fn encode_audio_to_memory(
+        &mut self,
+        format: AudioFormat,
+        start: Option<Duration>,
+        end: Option<Duration>,
+        adjustment: Option<AudioAdjustment>,
         config: Option<&ExtractOptions>,
+        mut sink: MuxSink,
     ) -> Result<Vec<u8>, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
         log::debug!(
@@ -614,17 +1386,7 @@ impl<'a> AudioHandle<'a> {
         );
 
         // Validate timestamps.
-        let media_duration = self.unbundler.metadata.duration;
-        if let Some(start_time) = start
-            && start_time > media_duration
-        {
-            return Err(UnbundleError::InvalidTimestamp(start_time));
-        }
-        if let Some(end_time) = end
-            && end_time > media_duration
-        {
-            return Err(UnbundleError::InvalidTimestamp(end_time));
-        }
+        self.validate_range(audio_stream_index, start, end)?;
 
         // Gather stream info before entering the unsafe block.
         let stream = self
@@ -645,6 +1407,14 @@ impl<'a> AudioHandle<'a> {
         let input_sample_rate = decoder.rate();
         let input_channel_layout = decoder.channel_layout();
 
+        // A selected channel leaves the filter as mono and a downmix as one
+        // channel per matrix row; everything after it works on that layout.
+        let adjustments = audio_adjustments(adjustment, config);
+        let mut audio_filter = (!adjustments.is_empty())
+            .then(|| AudioFilter::new(&decoder, &adjustments, input_time_base))
+            .transpose()?;
+        let selected_channel_layout = adjusted_layout(&adjustments, input_channel_layout);
+
         // Determine encoder settings.
         let output_codec = ffmpeg_next::encoder::find(format.codec_id())
             .ok_or(UnbundleError::UnsupportedAudioFormat(format))?;
@@ -658,14 +1428,15 @@ impl<'a> AudioHandle<'a> {
             .unwrap_or(Sample::I16(SampleType::Packed));
 
         let output_sample_rate = input_sample_rate;
-        let output_channel_layout = input_channel_layout;
+        let output_channel_layout = selected_channel_layout;
 
-        // Seek to start position if a range is specified.
-        if let Some(start_time) = start {
-            let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+        // Seek to the start position, or rewind for a whole track.
+        match start {
+            Some(start_time) => {
+                let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
+                crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+            }
+            None => crate::unbundle::rewind_input(&mut self.unbundler.input_context)?,
         }
 
         // Compute end timestamp in stream time base for range filtering.
@@ -676,17 +1447,15 @@ impl<'a> AudioHandle<'a> {
         // ── In-memory muxing via avio_open_dyn_buf ─────────────────
         //
         // SAFETY: We use raw FFmpeg C API calls to create an output format
-        // context backed by a dynamically-growing memory buffer. The sequence
-        // is:
+        // context backed by a memory I/O context. The sequence is:
         //   1. avformat_alloc_output_context2  — allocate muxer context
-        //   2. avio_open_dyn_buf               — attach a memory-backed I/O
+        //   2. MuxSink::open                   — attach a memory-backed I/O
         //   3. add stream, write header, write packets, write trailer
-        //   4. avio_close_dyn_buf              — extract the buffer
-        //   5. null out pb, then free the context
+        //   4. MuxSink::release                — extract the buffer, free
+        //                                        the I/O and muxer contexts
         //
-        // We null out `(*output_format_context).pb` before freeing the context
-        // to prevent FFmpeg from calling `avio_close` on the already-freed
-        // dynamic buffer.
+        // `release` nulls out `(*output_format_context).pb` before freeing
+        // the context so FFmpeg never calls `avio_close` on memory I/O.
 
         unsafe {
             let container_name = format.container_name();
@@ -707,30 +1476,17 @@ impl<'a> AudioHandle<'a> {
                 ));
             }
 
-            // Open dynamic buffer for I/O.
-            let dynamic_buffer_result =
-                ffmpeg_sys_next::avio_open_dyn_buf(&mut (*output_format_context).pb);
-            if dynamic_buffer_result < 0 {
+            // Attach the sink's I/O context.
+            if let Err(error) = sink.open(output_format_context) {
                 ffmpeg_sys_next::avformat_free_context(output_format_context);
-                return Err(UnbundleError::AudioEncodeError(
-                    "Failed to open dynamic buffer for audio output".to_string(),
-                ));
+                return Err(error);
             }
 
             // Add an output audio stream.
             let output_stream =
                 ffmpeg_sys_next::avformat_new_stream(output_format_context, std::ptr::null());
             if output_stream.is_null() {
-                let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-                ffmpeg_sys_next::avio_close_dyn_buf(
-                    (*output_format_context).pb,
-                    &mut buffer_pointer,
-                );
-                if !buffer_pointer.is_null() {
-                    ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
-                }
-                (*output_format_context).pb = std::ptr::null_mut();
-                ffmpeg_sys_next::avformat_free_context(output_format_context);
+                sink.release(output_format_context);
                 return Err(UnbundleError::AudioEncodeError(
                     "Failed to add output stream".to_string(),
                 ));
@@ -747,16 +1503,7 @@ impl<'a> AudioHandle<'a> {
             let (mut encoder, encoder_time_base) = match encoder_result {
                 Ok(value) => value,
                 Err(error) => {
-                    let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-                    ffmpeg_sys_next::avio_close_dyn_buf(
-                        (*output_format_context).pb,
-                        &mut buffer_pointer,
-                    );
-                    if !buffer_pointer.is_null() {
-                        ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
-                    }
-                    (*output_format_context).pb = std::ptr::null_mut();
-                    ffmpeg_sys_next::avformat_free_context(output_format_context);
+                    sink.release(output_format_context);
                     return Err(error);
                 }
             };
@@ -771,35 +1518,31 @@ impl<'a> AudioHandle<'a> {
                 den: encoder_time_base.denominator(),
             };
 
+            // The context takes ownership of the tags and frees them with
+            // itself.
+            if let Some(tags) = output_tags(config) {
+                (*output_format_context).metadata = tags.disown();
+            }
+
             // Write the container header.
             let write_header_result =
                 ffmpeg_sys_next::avformat_write_header(output_format_context, std::ptr::null_mut());
             if write_header_result < 0 {
-                let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-                ffmpeg_sys_next::avio_close_dyn_buf(
-                    (*output_format_context).pb,
-                    &mut buffer_pointer,
-                );
-                if !buffer_pointer.is_null() {
-                    ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
-                }
-                (*output_format_context).pb = std::ptr::null_mut();
-                ffmpeg_sys_next::avformat_free_context(output_format_context);
+                sink.release(output_format_context);
                 return Err(UnbundleError::AudioEncodeError(
                     "Failed to write output header".to_string(),
                 ));
             }
 
             // Set up resampler if the decoder and encoder sample formats differ.
-            let mut resampler = ResamplingContext::get(
-                decoder.format(),
-                decoder.channel_layout(),
-                decoder.rate(),
+            let mut resampler = create_resampler(
+                &decoder,
+                selected_channel_layout,
                 output_sample_format,
                 output_channel_layout,
                 output_sample_rate,
-            )
-            .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
+                config,
+            )?;
 
             // Decode → resample → encode → write loop.
             let mut decoded_audio_frame = AudioFrame::empty();
@@ -813,6 +1556,7 @@ impl<'a> AudioHandle<'a> {
             let transcode_result = self.transcode_audio_packets(
                 audio_stream_index,
                 &mut decoder,
+                audio_filter.as_mut(),
                 &mut resampler,
                 &mut encoder,
                 &mut decoded_audio_frame,
@@ -826,89 +1570,211 @@ impl<'a> AudioHandle<'a> {
             );
 
             if let Err(error) = transcode_result {
-                let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-                ffmpeg_sys_next::avio_close_dyn_buf(
-                    (*output_format_context).pb,
-                    &mut buffer_pointer,
-                );
-                if !buffer_pointer.is_null() {
-                    ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
-                }
-                (*output_format_context).pb = std::ptr::null_mut();
-                ffmpeg_sys_next::avformat_free_context(output_format_context);
+                sink.release(output_format_context);
                 return Err(error);
             }
 
             // Flush the decoder.
             let _ = decoder.send_eof();
             while decoder.receive_frame(&mut decoded_audio_frame).is_ok() {
-                if let Err(error) = resample_encode_write(
-                    &mut resampler,
-                    &mut encoder,
-                    &decoded_audio_frame,
-                    &mut resampled_frame,
-                    &mut encoded_packet,
-                    &mut samples_written,
-                    encoder_time_base,
-                    &mut writer,
-                ) {
-                    let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-                    ffmpeg_sys_next::avio_close_dyn_buf(
-                        (*output_format_context).pb,
-                        &mut buffer_pointer,
-                    );
-                    if !buffer_pointer.is_null() {
-                        ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
-                    }
-                    (*output_format_context).pb = std::ptr::null_mut();
-                    ffmpeg_sys_next::avformat_free_context(output_format_context);
+                let write_result = apply_audio_filter(audio_filter.as_mut(), &decoded_audio_frame)
+                    .and_then(|frame| {
+                        resample_encode_write(
+                            &mut resampler,
+                            &mut encoder,
+                            frame,
+                            &mut resampled_frame,
+                            &mut encoded_packet,
+                            &mut samples_written,
+                            encoder_time_base,
+                            &mut writer,
+                        )
+                    });
+                if let Err(error) = write_result {
+                    sink.release(output_format_context);
                     return Err(error);
                 }
             }
 
-            // Flush the encoder.
-            let _ = encoder.send_eof();
-            while encoder.receive_packet(&mut encoded_packet).is_ok() {
-                encoded_packet.set_stream(0);
-                encoded_packet.rescale_ts(encoder_time_base, encoder_time_base);
-                let write_result = ffmpeg_sys_next::av_interleaved_write_frame(
-                    output_format_context,
-                    encoded_packet.as_mut_ptr(),
-                );
-                if write_result < 0 {
-                    break;
-                }
+            // Flush the resampler and encoder.
+            if let Err(error) = flush_encode_write(
+                &mut resampler,
+                &mut encoder,
+                &mut encoded_packet,
+                &mut samples_written,
+                encoder_time_base,
+                &mut writer,
+            ) {
+                sink.release(output_format_context);
+                return Err(error);
             }
 
             // Write the container trailer.
             ffmpeg_sys_next::av_write_trailer(output_format_context);
 
-            // Extract the dynamic buffer contents.
-            let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
-            let buffer_size = ffmpeg_sys_next::avio_close_dyn_buf(
-                (*output_format_context).pb,
-                &mut buffer_pointer,
-            );
+            // Collect the buffered bytes and free the contexts.
+            let result_bytes = sink.release(output_format_context);
 
-            let result_bytes = if buffer_size > 0 && !buffer_pointer.is_null() {
-                std::slice::from_raw_parts(buffer_pointer, buffer_size as usize).to_vec()
-            } else {
-                Vec::new()
-            };
+            self.ensure_samples_in_range(audio_stream_index, samples_written, start, end)?;
+            Ok(result_bytes)
+        }
+    }
 
-            if !buffer_pointer.is_null() {
-                ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
+    /// Decode and resample the audio stream into raw PCM planes.
+    fn decode_raw_pcm(
+        &mut self,
+        sample_format: PcmSampleFormat,
+        sample_rate: u32,
+        channels: u16,
+        config: Option<&ExtractOptions>,
+    ) -> Result<(Vec<u8>, PcmInfo), UnbundleError> {
+        if sample_rate == 0 || channels == 0 {
+            return Err(UnbundleError::AudioDecodeError(format!(
+                "Invalid PCM output: {sample_rate} Hz, {channels} channels"
+            )));
+        }
+
+        let audio_stream_index = self.resolve_stream_index()?;
+        log::debug!(
+            "Extracting raw PCM (format={:?}, rate={}, channels={}, stream={})",
+            sample_format,
+            sample_rate,
+            channels,
+            audio_stream_index
+        );
+
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(audio_stream_index)
+            .ok_or(UnbundleError::NoAudioStream)?;
+        let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+        let mut decoder = decoder_context
+            .decoder()
+            .audio()
+            .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
+
+        let output_sample = sample_format.to_ffmpeg_sample();
+        let output_layout = ChannelLayout::default(i32::from(channels));
+        let mut resampler = create_resampler(
+            &decoder,
+            decoder.channel_layout(),
+            output_sample,
+            output_layout,
+            sample_rate,
+            config,
+        )?;
+
+        // Size each output frame for a converted input frame plus whatever
+        // the resampler is still holding, so nothing piles up inside it
+        // when upsampling.
+        let input_rate = u64::from(decoder.rate().max(1));
+        let output_frame = |resampler: &ResamplingContext, input_samples: usize| {
+            let converted = (input_samples as u64 * u64::from(sample_rate)).div_ceil(input_rate);
+            let pending = resampler
+                .delay()
+                .map_or(0, |delay| delay.output.max(0) as u64);
+            AudioFrame::new(
+                output_sample,
+                (converted + pending) as usize + 32,
+                output_layout,
+            )
+        };
+
+        let default_config = ExtractOptions::default();
+        let active_config = config.unwrap_or(&default_config);
+        let mut tracker = ProgressTracker::new(
+            active_config.progress.clone(),
+            OperationType::AudioExtraction,
+            None,
+            active_config.batch_size,
+        );
+
+        // One buffer per output plane; interleaved output has a single plane.
+        let (plane_count, channels_per_plane) = if sample_format.is_planar() {
+            (usize::from(channels), 1)
+        } else {
+            (1, usize::from(channels))
+        };
+        let bytes_per_plane_sample = sample_format.bytes_per_sample() * channels_per_plane;
+        let mut planes: Vec<Vec<u8>> = vec![Vec::new(); plane_count];
+        let mut samples_per_channel: u64 = 0;
+
+        let mut append = |frame: &AudioFrame| {
+            let samples = frame.samples();
+            for (index, plane) in planes.iter_mut().enumerate() {
+                plane.extend_from_slice(&frame.data(index)[..samples * bytes_per_plane_sample]);
             }
+            samples_per_channel += samples as u64;
+        };
 
-            // Prevent the destructor from calling avio_close on the freed buffer.
-            (*output_format_context).pb = std::ptr::null_mut();
-            ffmpeg_sys_next::avformat_free_context(output_format_context);
+        let mut decoded_frame = AudioFrame::empty();
+        let mut packets_done = false;
 
-            Ok(result_bytes)
+        crate::unbundle::rewind_input(&mut self.unbundler.input_context)?;
+        let mut packets = self.unbundler.input_context.packets();
+        loop {
+            if active_config.is_cancelled() {
+                return Err(UnbundleError::Cancelled);
+            }
+
+            if !packets_done {
+                match packets.next() {
+                    Some((stream, packet)) => {
+                        if stream.index() != audio_stream_index {
+                            continue;
+                        }
+                        decoder
+                            .send_packet(&packet)
+                            .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
+                    }
+                    None => {
+                        packets_done = true;
+                        decoder
+                            .send_eof()
+                            .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
+                    }
+                }
+            }
+
+            while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                let mut resampled_frame = output_frame(&resampler, decoded_frame.samples());
+                resampler
+                    .run(&decoded_frame, &mut resampled_frame)
+                    .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
+                append(&resampled_frame);
+                tracker.advance(None, None);
+            }
+
+            if packets_done {
+                break;
+            }
         }
+
+        // Drain samples still buffered inside the resampler.
+        loop {
+            let mut resampled_frame = output_frame(&resampler, 0);
+            resampler
+                .flush(&mut resampled_frame)
+                .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
+            if resampled_frame.samples() == 0 {
+                break;
+            }
+            append(&resampled_frame);
+        }
+
+        tracker.finish();
+
+        let info = PcmInfo {
+            sample_format,
+            sample_rate,
+            channels,
+            samples_per_channel,
+        };
+        Ok((planes.concat(), info))
     }
 
-    /// Save audio to a file using the safe `ffmpeg_next::format::output` API.
+    /// Save audio to a file, attaching FFmpeg's log output to any error.
     fn save_audio_to_file(
         &mut self,
         path: &Path,
@@ -916,7 +1782,62 @@ impl<'a> AudioHandle<'a> {
         start: Option<Duration>,
         end: Option<Duration>,
         config: Option<&ExtractOptions>,
-    ) -> Result<(), UnbundleError> {
+    ) -> Result<ExtractSummary, UnbundleError> {
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        crate::atomic_write::write_output(path, config, |path| {
+            self.encode_audio_to_file(path, format, start, end, config, Duration::ZERO)
+        })
+        .map_err(|error| log_capture.attach(error))
+    }
+
+    /// Save the complete track to `path`, preceded by silence so that it
+    /// starts at the container's zero rather than at its own first sample.
+    ///
+    /// Returns the length of the silence. Tracks starting at or before the
+    /// container start get none.
+    pub(crate) fn save_aligned(
+        &mut self,
+        path: &Path,
+        format: AudioFormat,
+    ) -> Result<Duration, UnbundleError> {
+        let audio_stream_index = self.resolve_stream_index()?;
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(audio_stream_index)
+            .ok_or(UnbundleError::NoAudioStream)?;
+        let stream_start = stream.start_time();
+        let time_base = stream.time_base();
+        // SAFETY: the context is open; `start_time` is a plain integer.
+        let file_start = unsafe { (*self.unbundler.input_context.as_ptr()).start_time };
+
+        let lead_in = if stream_start == ffmpeg_sys_next::AV_NOPTS_VALUE
+            || file_start == ffmpeg_sys_next::AV_NOPTS_VALUE
+        {
+            Duration::ZERO
+        } else {
+            let offset = crate::conversion::pts_to_seconds(stream_start, time_base)
+                - file_start as f64 / f64::from(ffmpeg_sys_next::AV_TIME_BASE);
+            Duration::from_secs_f64(offset.max(0.0))
+        };
+
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.encode_audio_to_file(path, format, None, None, None, lead_in)
+            .map_err(|error| log_capture.attach(error))?;
+        Ok(lead_in)
+    }
+
+    /// Encode audio to a file using the safe `ffmpeg_next::format::output`
+    /// API.
+    fn encode_audio_to_file(
+        &mut self,
+        path: &Path,
+        format: AudioFormat,
+        start: Option<Duration>,
+        end: Option<Duration>,
+        config: Option<&ExtractOptions>,
+        lead_in: Duration,
+    ) -> Result<ExtractSummary, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
         log::debug!(
             "Saving audio to file {:?} (format={}, stream={})",
@@ -925,17 +1846,7 @@ impl<'a> AudioHandle<'a> {
             audio_stream_index
         );
 
-        let media_duration = self.unbundler.metadata.duration;
-        if let Some(start_time) = start
-            && start_time > media_duration
-        {
-            return Err(UnbundleError::InvalidTimestamp(start_time));
-        }
-        if let Some(end_time) = end
-            && end_time > media_duration
-        {
-            return Err(UnbundleError::InvalidTimestamp(end_time));
-        }
+        self.validate_range(audio_stream_index, start, end)?;
 
         let stream = self
             .unbundler
@@ -952,7 +1863,11 @@ impl<'a> AudioHandle<'a> {
             .map_err(|error| UnbundleError::AudioDecodeError(error.to_string()))?;
 
         let input_sample_rate = decoder.rate();
-        let input_channel_layout = decoder.channel_layout();
+        let adjustments = audio_adjustments(None, config);
+        let mut audio_filter = (!adjustments.is_empty())
+            .then(|| AudioFilter::new(&decoder, &adjustments, input_time_base))
+            .transpose()?;
+        let input_channel_layout = adjusted_layout(&adjustments, decoder.channel_layout());
 
         let output_codec = ffmpeg_next::encoder::find(format.codec_id())
             .ok_or(UnbundleError::UnsupportedAudioFormat(format))?;
@@ -967,12 +1882,13 @@ impl<'a> AudioHandle<'a> {
         let output_sample_rate = input_sample_rate;
         let output_channel_layout = input_channel_layout;
 
-        // Seek if a start time was specified.
-        if let Some(start_time) = start {
-            let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+        // Seek to the start position, or rewind for a whole track.
+        match start {
+            Some(start_time) => {
+                let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
+                crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+            }
+            None => crate::unbundle::rewind_input(&mut self.unbundler.input_context)?,
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -997,34 +1913,52 @@ impl<'a> AudioHandle<'a> {
             output_stream.set_time_base(encoder_time_base);
         }
 
+        if let Some(tags) = output_tags(config) {
+            output_context.set_metadata(tags);
+        }
         output_context
             .write_header()
             .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
 
-        let mut resampler = ResamplingContext::get(
-            decoder.format(),
-            decoder.channel_layout(),
-            decoder.rate(),
+        let mut resampler = create_resampler(
+            &decoder,
+            input_channel_layout,
             output_sample_format,
             output_channel_layout,
             output_sample_rate,
-        )
-        .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
+            config,
+        )?;
 
         let mut decoded_audio_frame = AudioFrame::empty();
         let mut resampled_frame = AudioFrame::empty();
         let mut encoded_packet = Packet::empty();
         let mut samples_written: i64 = 0;
+        let mut span = PacketSpan::default();
 
         {
             let mut writer = FilePacketWriter {
                 output_context: &mut output_context,
+                span: &mut span,
             };
 
+            if !lead_in.is_zero() {
+                write_silence(
+                    &mut resampler,
+                    &mut encoder,
+                    lead_in,
+                    &mut resampled_frame,
+                    &mut encoded_packet,
+                    &mut samples_written,
+                    encoder_time_base,
+                    &mut writer,
+                )?;
+            }
+
             // Decode → resample → encode → write loop.
             self.transcode_audio_packets(
                 audio_stream_index,
                 &mut decoder,
+                audio_filter.as_mut(),
                 &mut resampler,
                 &mut encoder,
                 &mut decoded_audio_frame,
@@ -1040,10 +1974,11 @@ impl<'a> AudioHandle<'a> {
             // Flush decoder.
             let _ = decoder.send_eof();
             while decoder.receive_frame(&mut decoded_audio_frame).is_ok() {
+                let frame = apply_audio_filter(audio_filter.as_mut(), &decoded_audio_frame)?;
                 resample_encode_write(
                     &mut resampler,
                     &mut encoder,
-                    &decoded_audio_frame,
+                    frame,
                     &mut resampled_frame,
                     &mut encoded_packet,
                     &mut samples_written,
@@ -1052,20 +1987,42 @@ impl<'a> AudioHandle<'a> {
                 )?;
             }
 
-            // Flush encoder.
-            let _ = encoder.send_eof();
-            while encoder.receive_packet(&mut encoded_packet).is_ok() {
-                encoded_packet.set_stream(0);
-                encoded_packet.rescale_ts(encoder_time_base, encoder_time_base);
-                writer.write_packet(&mut encoded_packet)?;
-            }
+            // Flush resampler and encoder.
+            flush_encode_write(
+                &mut resampler,
+                &mut encoder,
+                &mut encoded_packet,
+                &mut samples_written,
+                encoder_time_base,
+                &mut writer,
+            )?;
         }
 
         output_context
             .write_trailer()
             .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
 
-        Ok(())
+        if let Err(error) =
+            self.ensure_samples_in_range(audio_stream_index, samples_written, start, end)
+        {
+            // Only the header was written; do not leave it behind as if it
+            // were the requested audio. The file is closed first so it can
+            // be removed on Windows.
+            drop(output_context);
+            let _ = std::fs::remove_file(path);
+            return Err(error);
+        }
+        let output_time_base = output_context
+            .stream(0)
+            .map(|stream| stream.time_base())
+            .ok_or_else(|| {
+                UnbundleError::AudioEncodeError("Output stream is missing".to_string())
+            })?;
+        Ok(span.summary(
+            &mut output_context,
+            output_time_base,
+            Some(samples_written.max(0) as u64),
+        ))
     }
 
     /// Create an audio encoder configured for the specified output format.
@@ -1114,6 +2071,7 @@ impl<'a> AudioHandle<'a> {
         &mut self,
         audio_stream_index: usize,
         decoder: &mut AudioDecoder,
+        mut audio_filter: Option<&mut AudioFilter>,
         resampler: &mut ResamplingContext,
         encoder: &mut AudioEncoder,
         decoded_audio_frame: &mut AudioFrame,
@@ -1154,10 +2112,11 @@ impl<'a> AudioHandle<'a> {
                     return Ok(());
                 }
 
+                let frame = apply_audio_filter(audio_filter.as_deref_mut(), decoded_audio_frame)?;
                 resample_encode_write(
                     resampler,
                     encoder,
-                    decoded_audio_frame,
+                    frame,
                     resampled_frame,
                     encoded_packet,
                     samples_written,
@@ -1180,7 +2139,20 @@ impl<'a> AudioHandle<'a> {
         start: Option<Duration>,
         end: Option<Duration>,
         config: Option<&ExtractOptions>,
-    ) -> Result<(), UnbundleError> {
+    ) -> Result<ExtractSummary, UnbundleError> {
+        crate::atomic_write::write_output(path, config, |path| {
+            self.write_stream_copy(path, start, end, config)
+        })
+    }
+
+    /// Write the stream copy straight to `path`.
+    fn write_stream_copy(
+        &mut self,
+        path: &Path,
+        start: Option<Duration>,
+        end: Option<Duration>,
+        config: Option<&ExtractOptions>,
+    ) -> Result<ExtractSummary, UnbundleError> {
         let audio_stream_index = self.resolve_stream_index()?;
         log::debug!(
             "Stream-copying audio to file {:?} (stream={})",
@@ -1214,16 +2186,20 @@ impl<'a> AudioHandle<'a> {
             }
         }
 
+        if let Some(tags) = output_tags(config) {
+            output_context.set_metadata(tags);
+        }
         output_context.write_header().map_err(|error| {
             UnbundleError::StreamCopyError(format!("Failed to write header: {error}"))
         })?;
 
-        // Seek to start position if specified.
-        if let Some(start_time) = start {
-            let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+        // Seek to the start position, or rewind for a whole track.
+        match start {
+            Some(start_time) => {
+                let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
+                crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+            }
+            None => crate::unbundle::rewind_input(&mut self.unbundler.input_context)?,
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -1240,6 +2216,7 @@ impl<'a> AudioHandle<'a> {
         });
 
         let output_time_base = output_context.stream(0).unwrap().time_base();
+        let mut span = PacketSpan::default();
 
         // Copy packets.
         for (stream, mut packet) in self.unbundler.input_context.packets() {
@@ -1262,6 +2239,7 @@ impl<'a> AudioHandle<'a> {
             packet.set_stream(0);
             packet.rescale_ts(input_time_base, output_time_base);
             packet.set_position(-1);
+            span.record(&packet);
             packet
                 .write_interleaved(&mut output_context)
                 .map_err(|error| {
@@ -1281,7 +2259,7 @@ impl<'a> AudioHandle<'a> {
             UnbundleError::StreamCopyError(format!("Failed to write trailer: {error}"))
         })?;
 
-        Ok(())
+        Ok(span.summary(&mut output_context, output_time_base, None))
     }
 
     /// Copy the audio stream verbatim to an in-memory buffer using
@@ -1308,12 +2286,13 @@ impl<'a> AudioHandle<'a> {
         let input_time_base = stream.time_base();
         let codec_parameters = stream.parameters();
 
-        // Seek to start position if specified.
-        if let Some(start_time) = start {
-            let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+        // Seek to the start position, or rewind for a whole track.
+        match start {
+            Some(start_time) => {
+                let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
+                crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+            }
+            None => crate::unbundle::rewind_input(&mut self.unbundler.input_context)?,
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -1394,6 +2373,12 @@ impl<'a> AudioHandle<'a> {
                 den: input_time_base.denominator(),
             };
 
+            // The context takes ownership of the tags and frees them with
+            // itself.
+            if let Some(tags) = output_tags(config) {
+                (*output_format_context).metadata = tags.disown();
+            }
+
             // Write the container header.
             let write_header_result =
                 ffmpeg_sys_next::avformat_write_header(output_format_context, std::ptr::null_mut());
@@ -1546,6 +2531,87 @@ impl<'a> AudioHandle<'a> {
         ))
     }
 
+    /// Stream the encoded audio track as it is muxed.
+    ///
+    /// Unlike [`extract_async`](AudioHandle::extract_async), which resolves
+    /// to the whole encoded track, the returned [`AudioStream`] yields the
+    /// container in chunks of up to 64 KiB while a blocking thread is still
+    /// transcoding. The thread waits whenever
+    /// [`with_stream_buffer_size`](ExtractOptions::with_stream_buffer_size)
+    /// chunks (8 by default) are queued, so a slow client bounds memory
+    /// use. Dropping the stream stops the transcode at the next chunk.
+    ///
+    /// The output cannot be seeked back into, so WAV and FLAC headers keep
+    /// the placeholder sizes written before the audio; players handle this
+    /// as they would a live stream. MP3 and AAC (ADTS) need no header
+    /// fix-up.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::NoAudioStream`] if the file has no audio
+    /// stream (validated eagerly before spawning the background thread).
+    /// Transcoding errors arrive as the stream's last item.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use tokio_stream::StreamExt;
+    ///
+    /// use unbundle::{AudioFormat, ExtractOptions, MediaFile, UnbundleError};
+    ///
+    /// # async fn example() -> Result<(), UnbundleError> {
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let mut stream = unbundler
+    ///     .audio()
+    ///     .extract_stream(AudioFormat::Mp3, ExtractOptions::new())?;
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     let chunk: Vec<u8> = chunk?;
+    ///     println!("Sending {} bytes", chunk.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn extract_stream(
+        &mut self,
+        format: AudioFormat,
+        config: ExtractOptions,
+    ) -> Result<AudioStream, UnbundleError> {
+        let _stream_index = self.resolve_stream_index()?;
+        let track_index = self.stream_index.and_then(|stream_index| {
+            self.unbundler
+                .audio_stream_indices
+                .iter()
+                .position(|&index| index == stream_index)
+        });
+        let source = self.unbundler.source.clone();
+        let buffer_size = config.stream_buffer_size;
+        Ok(crate::stream::create_audio_stream(
+            source,
+            format,
+            track_index,
+            config,
+            buffer_size,
+        ))
+    }
+
+    /// Encode the whole track, passing the muxed bytes to `callback` as the
+    /// muxer writes them.
+    #[cfg(feature = "async")]
+    pub(crate) fn extract_to_callback(
+        &mut self,
+        format: AudioFormat,
+        config: &ExtractOptions,
+        callback: Box<ChunkCallback>,
+    ) -> Result<(), UnbundleError> {
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        let sink = MuxSink::Chunks(Box::new(callback));
+        self.encode_audio_to_memory(format, None, None, None, Some(config), sink)
+            .map(|_| ())
+            .map_err(|error| log_capture.attach(error))
+    }
+
     /// Extract an audio time range asynchronously.
     ///
     /// Like [`extract_async`](AudioHandle::extract_async) but extracts only
@@ -1617,6 +2683,10 @@ impl<'a> AudioHandle<'a> {
 trait PacketWriter {
     /// Write a single encoded packet to the output.
     fn write_packet(&mut self, packet: &mut Packet) -> Result<(), UnbundleError>;
+
+    /// Time base of the output stream, as chosen by the muxer when the
+    /// header was written. Packets must be rescaled to it before writing.
+    fn time_base(&self) -> Rational;
 }
 
 /// Writes encoded audio packets to an in-memory FFmpeg dynamic buffer.
@@ -1629,24 +2699,467 @@ struct MemoryPacketWriter {
 
 impl PacketWriter for MemoryPacketWriter {
     fn write_packet(&mut self, packet: &mut Packet) -> Result<(), UnbundleError> {
-        unsafe {
-            ffmpeg_sys_next::av_interleaved_write_frame(self.format_context, packet.as_mut_ptr());
+        // A chunk callback that refuses data surfaces here as an I/O error.
+        let result = unsafe {
+            ffmpeg_sys_next::av_interleaved_write_frame(self.format_context, packet.as_mut_ptr())
+        };
+        if result < 0 {
+            return Err(UnbundleError::AudioEncodeError(format!(
+                "Failed to write packet: {}",
+                ffmpeg_next::Error::from(result)
+            )));
+        }
+        Ok(())
+    }
+
+    fn time_base(&self) -> Rational {
+        // SAFETY: the context has exactly one stream, added before the
+        // header was written, and outlives this writer.
+        unsafe { Rational::from((**(*self.format_context).streams).time_base) }
+    }
+}
+
+/// Size of the I/O buffer behind [`MuxSink::Chunks`], and so the largest
+/// chunk handed to its callback.
+#[cfg(feature = "async")]
+const CHUNK_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Callback receiving muxed bytes; returning `false` stops the encode.
+#[cfg(feature = "async")]
+type ChunkCallback = dyn FnMut(&[u8]) -> bool + Send;
+
+/// Where [`AudioHandle::encode_audio_to_memory`] sends the muxed container.
+enum MuxSink {
+    /// Collect the whole container in an FFmpeg dynamic buffer.
+    Buffer,
+    /// Pass each block to a callback as the muxer flushes it. The outer
+    /// box gives the I/O context a stable, thin pointer to the callback.
+    ///
+    /// The I/O context is not seekable, so muxers that patch their header
+    /// in the trailer (WAV sizes, FLAC stream info) leave it as written.
+    #[cfg(feature = "async")]
+    Chunks(Box<Box<ChunkCallback>>),
+}
+
+impl MuxSink {
+    /// Attach a new I/O context to `format_context`.
+    ///
+    /// # Safety
+    ///
+    /// `format_context` must be a valid output context without an I/O
+    /// context, and `self` must outlive it.
+    unsafe fn open(&mut self, format_context: *mut AVFormatContext) -> Result<(), UnbundleError> {
+        match self {
+            MuxSink::Buffer => {
+                let result =
+                    unsafe { ffmpeg_sys_next::avio_open_dyn_buf(&mut (*format_context).pb) };
+                if result < 0 {
+                    return Err(UnbundleError::AudioEncodeError(
+                        "Failed to open dynamic buffer for audio output".to_string(),
+                    ));
+                }
+            }
+            #[cfg(feature = "async")]
+            MuxSink::Chunks(callback) => unsafe {
+                let buffer = ffmpeg_sys_next::av_malloc(CHUNK_BUFFER_SIZE) as *mut u8;
+                if buffer.is_null() {
+                    return Err(UnbundleError::AudioEncodeError(
+                        "Failed to allocate audio output buffer".to_string(),
+                    ));
+                }
+                let opaque: *mut Box<ChunkCallback> = &mut **callback;
+                let io_context = ffmpeg_sys_next::avio_alloc_context(
+                    buffer,
+                    CHUNK_BUFFER_SIZE as c_int,
+                    1,
+                    opaque as *mut c_void,
+                    None,
+                    Some(write_chunk),
+                    None,
+                );
+                if io_context.is_null() {
+                    ffmpeg_sys_next::av_free(buffer as *mut c_void);
+                    return Err(UnbundleError::AudioEncodeError(
+                        "Failed to allocate audio output I/O context".to_string(),
+                    ));
+                }
+                (*format_context).pb = io_context;
+            },
         }
         Ok(())
     }
+
+    /// Detach and free the I/O context, then free `format_context`.
+    ///
+    /// Returns the buffered container for [`MuxSink::Buffer`] and an empty
+    /// `Vec` for [`MuxSink::Chunks`], whose bytes went to the callback.
+    ///
+    /// # Safety
+    ///
+    /// `format_context` must have been passed to [`open`](MuxSink::open)
+    /// successfully and must not be used afterwards.
+    unsafe fn release(&mut self, format_context: *mut AVFormatContext) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        unsafe {
+            match self {
+                MuxSink::Buffer => {
+                    let mut buffer_pointer: *mut u8 = std::ptr::null_mut();
+                    let buffer_size = ffmpeg_sys_next::avio_close_dyn_buf(
+                        (*format_context).pb,
+                        &mut buffer_pointer,
+                    );
+                    if buffer_size > 0 && !buffer_pointer.is_null() {
+                        bytes = std::slice::from_raw_parts(buffer_pointer, buffer_size as usize)
+                            .to_vec();
+                    }
+                    if !buffer_pointer.is_null() {
+                        ffmpeg_sys_next::av_free(buffer_pointer as *mut _);
+                    }
+                }
+                #[cfg(feature = "async")]
+                MuxSink::Chunks(_) => {
+                    let mut io_context = (*format_context).pb;
+                    ffmpeg_sys_next::avio_flush(io_context);
+                    ffmpeg_sys_next::av_freep((&raw mut (*io_context).buffer).cast::<c_void>());
+                    ffmpeg_sys_next::avio_context_free(&mut io_context);
+                }
+            }
+            // Prevent the destructor from calling avio_close on memory I/O.
+            (*format_context).pb = std::ptr::null_mut();
+            ffmpeg_sys_next::avformat_free_context(format_context);
+        }
+        bytes
+    }
+}
+
+/// AVIO write callback for [`MuxSink::Chunks`].
+#[cfg(feature = "async")]
+unsafe extern "C" fn write_chunk(opaque: *mut c_void, buffer: *const u8, size: c_int) -> c_int {
+    // SAFETY: `opaque` is the boxed callback set in `MuxSink::open`, and
+    // FFmpeg passes `size` readable bytes.
+    let (callback, chunk) = unsafe {
+        (
+            &mut *(opaque as *mut Box<ChunkCallback>),
+            std::slice::from_raw_parts(buffer, size.max(0) as usize),
+        )
+    };
+    if callback(chunk) {
+        size
+    } else {
+        ffmpeg_sys_next::AVERROR_EXIT
+    }
 }
 
 /// Writes encoded audio packets to a file-backed FFmpeg output context.
 struct FilePacketWriter<'a> {
     output_context: &'a mut Output,
+    span: &'a mut PacketSpan,
 }
 
 impl PacketWriter for FilePacketWriter<'_> {
     fn write_packet(&mut self, packet: &mut Packet) -> Result<(), UnbundleError> {
+        self.span.record(packet);
         packet
             .write_interleaved(self.output_context)
             .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))
     }
+
+    fn time_base(&self) -> Rational {
+        self.output_context.stream(0).unwrap().time_base()
+    }
+}
+
+/// Samples per frame of leading silence for encoders without a fixed frame
+/// size.
+const SILENCE_FRAME_SAMPLES: usize = 4096;
+
+/// Most output channels FFmpeg's `pan` filter accepts.
+const MAX_PAN_CHANNELS: usize = 64;
+
+/// Processing applied to decoded audio before it is resampled and encoded.
+#[derive(Debug, Clone)]
+enum AudioAdjustment {
+    /// Keep only this channel, as mono, for [`AudioHandle::extract_channel`].
+    Channel(usize),
+    /// Mix the input channels into one output channel per row, from
+    /// [`ExtractOptions::with_downmix_matrix`].
+    Downmix(Vec<Vec<f64>>),
+    /// Change the level by this many decibels, for
+    /// [`AudioHandle::extract_loudness_matched`].
+    #[cfg(feature = "loudness")]
+    GainDb(f64),
+}
+
+impl AudioAdjustment {
+    /// Channel layout of the frames the filter produces from `input`.
+    #[cfg_attr(not(feature = "loudness"), allow(unused_variables))]
+    fn output_layout(&self, input: ChannelLayout) -> ChannelLayout {
+        match self {
+            Self::Channel(_) => ChannelLayout::MONO,
+            Self::Downmix(matrix) => ChannelLayout::default(matrix.len() as i32),
+            #[cfg(feature = "loudness")]
+            Self::GainDb(_) => input,
+        }
+    }
+}
+
+/// The adjustments to apply, in order: the caller's own, then the downmix
+/// requested by `config`, which then mixes the adjusted channels.
+fn audio_adjustments(
+    adjustment: Option<AudioAdjustment>,
+    config: Option<&ExtractOptions>,
+) -> Vec<AudioAdjustment> {
+    let downmix = config
+        .and_then(|config| config.downmix_matrix.clone())
+        .map(AudioAdjustment::Downmix);
+    adjustment.into_iter().chain(downmix).collect()
+}
+
+/// Channel layout after every adjustment has been applied to `input`.
+fn adjusted_layout(adjustments: &[AudioAdjustment], input: ChannelLayout) -> ChannelLayout {
+    adjustments
+        .iter()
+        .fold(input, |layout, adjustment| adjustment.output_layout(layout))
+}
+
+/// The container tags requested by `config`, in key order so output is
+/// reproducible, or `None` when there are none.
+fn output_tags(config: Option<&ExtractOptions>) -> Option<Dictionary<'static>> {
+    let tags = &config?.tags;
+    if tags.is_empty() {
+        return None;
+    }
+    let mut keys: Vec<&String> = tags.keys().collect();
+    keys.sort();
+    let mut dictionary = Dictionary::new();
+    for key in keys {
+        dictionary.set(key, &tags[key]);
+    }
+    Some(dictionary)
+}
+
+/// Build a `pan` filter for a downmix matrix after checking that every row
+/// has one coefficient per source channel.
+fn downmix_filter_spec(matrix: &[Vec<f64>], channel_count: u16) -> Result<String, UnbundleError> {
+    if matrix.is_empty() || matrix.len() > MAX_PAN_CHANNELS {
+        return Err(UnbundleError::InvalidDownmixMatrix(format!(
+            "expected 1 to {MAX_PAN_CHANNELS} output rows, got {}",
+            matrix.len()
+        )));
+    }
+
+    let layout = ChannelLayout::default(matrix.len() as i32);
+    let mut filter_spec = format!("pan={}", crate::conversion::channel_layout_name(&layout.0));
+    for (output, row) in matrix.iter().enumerate() {
+        if row.len() != usize::from(channel_count) {
+            return Err(UnbundleError::InvalidDownmixMatrix(format!(
+                "row {output} has {} coefficients but the source has {channel_count} channels",
+                row.len()
+            )));
+        }
+        if row.iter().any(|gain| !gain.is_finite()) {
+            return Err(UnbundleError::InvalidDownmixMatrix(format!(
+                "row {output} has a non-finite coefficient"
+            )));
+        }
+        filter_spec.push_str(&format!("|c{output}="));
+        for (input, gain) in row.iter().enumerate() {
+            if input > 0 {
+                filter_spec.push(if *gain < 0.0 { '-' } else { '+' });
+            } else if *gain < 0.0 {
+                filter_spec.push('-');
+            }
+            filter_spec.push_str(&format!("{}*c{input}", gain.abs()));
+        }
+    }
+    Ok(filter_spec)
+}
+
+/// Applies a chain of [`AudioAdjustment`]s to decoded audio with FFmpeg's
+/// `pan` and `volume` filters.
+struct AudioFilter {
+    graph: FilterGraph,
+    filtered_frame: AudioFrame,
+}
+
+impl AudioFilter {
+    /// Build an `abuffer → pan|volume → … → abuffersink` graph for frames
+    /// shaped like the decoder's output, after checking that a selected
+    /// channel exists or that a downmix matrix fits the channels reaching
+    /// it.
+    fn new(
+        decoder: &AudioDecoder,
+        adjustments: &[AudioAdjustment],
+        time_base: Rational,
+    ) -> Result<Self, UnbundleError> {
+        let mut layout = decoder.channel_layout();
+        let mut channel_count = decoder.channels();
+        let mut filters = Vec::with_capacity(adjustments.len());
+        for adjustment in adjustments {
+            filters.push(match *adjustment {
+                AudioAdjustment::Channel(channel_index) => {
+                    if channel_index >= usize::from(channel_count) {
+                        return Err(UnbundleError::ChannelOutOfRange {
+                            channel_index,
+                            channel_count,
+                        });
+                    }
+                    format!("pan=mono|c0=c{channel_index}")
+                }
+                AudioAdjustment::Downmix(ref matrix) => downmix_filter_spec(matrix, channel_count)?,
+                // `volume` outputs float samples; convert back so the
+                // resampler still sees the decoder's format.
+                #[cfg(feature = "loudness")]
+                AudioAdjustment::GainDb(gain) => format!(
+                    "volume={gain:.3}dB,aformat=sample_fmts={}",
+                    decoder.format().name()
+                ),
+            });
+            layout = adjustment.output_layout(layout);
+            channel_count = u16::try_from(layout.channels()).unwrap_or(0);
+        }
+        let filter_spec = filters.join(",");
+
+        let mut graph = FilterGraph::new();
+        let buffer_args = format!(
+            "time_base={}/{}:sample_rate={}:sample_fmt={}:channel_layout={}",
+            time_base.numerator(),
+            time_base.denominator(),
+            decoder.rate(),
+            decoder.format().name(),
+            crate::conversion::channel_layout_name(&decoder.channel_layout().0),
+        );
+        graph
+            .add(
+                &ffmpeg_next::filter::find("abuffer").ok_or_else(|| {
+                    UnbundleError::FilterGraphError("FFmpeg 'abuffer' filter not found".to_string())
+                })?,
+                "in",
+                &buffer_args,
+            )
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!("Failed to add abuffer filter: {error}"))
+            })?;
+        graph
+            .add(
+                &ffmpeg_next::filter::find("abuffersink").ok_or_else(|| {
+                    UnbundleError::FilterGraphError(
+                        "FFmpeg 'abuffersink' filter not found".to_string(),
+                    )
+                })?,
+                "out",
+                "",
+            )
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!(
+                    "Failed to add abuffersink filter: {error}"
+                ))
+            })?;
+
+        graph
+            .output("in", 0)
+            .and_then(|parser| parser.input("out", 0))
+            .and_then(|parser| parser.parse(&filter_spec))
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!("Failed to parse '{filter_spec}': {error}"))
+            })?;
+        graph.validate().map_err(|error| {
+            UnbundleError::FilterGraphError(format!("Filter graph validation: {error}"))
+        })?;
+
+        Ok(Self {
+            graph,
+            filtered_frame: AudioFrame::empty(),
+        })
+    }
+
+    /// Run one decoded frame through the filter. `pan` and `volume` work
+    /// sample by sample, so every input frame yields exactly one frame.
+    fn apply(&mut self, frame: &AudioFrame) -> Result<&AudioFrame, UnbundleError> {
+        self.graph
+            .get("in")
+            .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'in' not found".to_string()))?
+            .source()
+            .add(frame)
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!("Failed to feed audio filter: {error}"))
+            })?;
+        self.graph
+            .get("out")
+            .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'out' not found".to_string()))?
+            .sink()
+            .frame(&mut self.filtered_frame)
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!("Failed to read audio filter: {error}"))
+            })?;
+        Ok(&self.filtered_frame)
+    }
+}
+
+/// Pass a decoded frame through the audio filter when one is active, or
+/// return it unchanged.
+fn apply_audio_filter<'f>(
+    filter: Option<&'f mut AudioFilter>,
+    frame: &'f AudioFrame,
+) -> Result<&'f AudioFrame, UnbundleError> {
+    match filter {
+        Some(filter) => filter.apply(frame),
+        None => Ok(frame),
+    }
+}
+
+/// Create the resampler converting decoder output to the encoder's input,
+/// honouring the configured [`ResampleQuality`].
+///
+/// `input_channel_layout` is normally the decoder's, but differs when the
+/// decoded frames pass through an [`AudioFilter`] first.
+///
+/// If the requested engine is unavailable in this FFmpeg build (soxr is an
+/// optional dependency), falls back to the built-in engine's high-quality
+/// settings and then to its defaults.
+fn create_resampler(
+    decoder: &AudioDecoder,
+    input_channel_layout: ChannelLayout,
+    output_sample_format: Sample,
+    output_channel_layout: ChannelLayout,
+    output_sample_rate: u32,
+    config: Option<&ExtractOptions>,
+) -> Result<ResamplingContext, UnbundleError> {
+    let quality = config.map_or(ResampleQuality::Default, |config| config.resample_quality);
+    let fallbacks: &[ResampleQuality] = match quality {
+        ResampleQuality::Soxr { .. } => &[quality, ResampleQuality::High],
+        _ => &[quality],
+    };
+
+    for &candidate in fallbacks {
+        let Some(options) = candidate.to_options() else {
+            break;
+        };
+        match ResamplingContext::get_with(
+            decoder.format(),
+            input_channel_layout,
+            decoder.rate(),
+            output_sample_format,
+            output_channel_layout,
+            output_sample_rate,
+            options,
+        ) {
+            Ok(resampler) => return Ok(resampler),
+            Err(error) => {
+                log::warn!("Resampler {candidate:?} unavailable ({error}), falling back");
+            }
+        }
+    }
+
+    ResamplingContext::get(
+        decoder.format(),
+        input_channel_layout,
+        decoder.rate(),
+        output_sample_format,
+        output_channel_layout,
+        output_sample_rate,
+    )
+    .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))
 }
 
 /// Resample a decoded frame, encode it, and write packets to the output.
@@ -1672,11 +3185,133 @@ fn resample_encode_write<W: PacketWriter>(
         .send_frame(resampled_frame)
         .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
 
+    drain_encoder(encoder, encoded_packet, encoder_time_base, writer)
+}
+
+/// Encode `duration` of silence through the resampler, as if it had been
+/// decoded ahead of the first real frame.
+///
+/// Fixed-frame-size encoders (AAC, MP3, FLAC) only accept whole frames
+/// before the last one, so for them the silence is rounded down to a
+/// multiple of the encoder's frame size.
+#[allow(clippy::too_many_arguments)]
+fn write_silence<W: PacketWriter>(
+    resampler: &mut ResamplingContext,
+    encoder: &mut AudioEncoder,
+    duration: Duration,
+    resampled_frame: &mut AudioFrame,
+    encoded_packet: &mut Packet,
+    samples_written: &mut i64,
+    encoder_time_base: Rational,
+    writer: &mut W,
+) -> Result<(), UnbundleError> {
+    let input = *resampler.input();
+    let frame_size = encoder.frame_size() as usize;
+    let mut remaining = (duration.as_secs_f64() * f64::from(input.rate)).round() as usize;
+    let chunk_size = if frame_size > 0 {
+        remaining -= remaining % frame_size;
+        frame_size
+    } else {
+        SILENCE_FRAME_SAMPLES
+    };
+
+    while remaining > 0 {
+        let samples = remaining.min(chunk_size);
+        let mut silence = AudioFrame::new(input.format, samples, input.channel_layout);
+        silence.set_rate(input.rate);
+        // SAFETY: the frame's buffers were just allocated for `samples`
+        // samples of this format and channel count.
+        unsafe {
+            let raw = silence.as_mut_ptr();
+            ffmpeg_sys_next::av_samples_set_silence(
+                (*raw).extended_data,
+                0,
+                samples as i32,
+                (*raw).ch_layout.nb_channels,
+                input.format.into(),
+            );
+        }
+        resample_encode_write(
+            resampler,
+            encoder,
+            &silence,
+            resampled_frame,
+            encoded_packet,
+            samples_written,
+            encoder_time_base,
+            writer,
+        )?;
+        remaining -= samples;
+    }
+    Ok(())
+}
+
+/// Flush the end of the encode pipeline: push samples still buffered in
+/// the resampler through the encoder, then drain the encoder's own delay
+/// (the tail of the audio for AAC and MP3).
+///
+/// Flushed frames continue the PTS sequence from `samples_written` so the
+/// encoder timestamps its final packets correctly.
+fn flush_encode_write<W: PacketWriter>(
+    resampler: &mut ResamplingContext,
+    encoder: &mut AudioEncoder,
+    encoded_packet: &mut Packet,
+    samples_written: &mut i64,
+    encoder_time_base: Rational,
+    writer: &mut W,
+) -> Result<(), UnbundleError> {
+    let output = *resampler.output();
+    // Fixed-frame-size encoders reject frames larger than their frame size.
+    let frame_size = encoder.frame_size() as usize;
+
+    loop {
+        let pending = resampler
+            .delay()
+            .map_or(0, |delay| delay.output.max(0) as usize);
+        if pending == 0 {
+            break;
+        }
+        let capacity = if frame_size > 0 {
+            pending.min(frame_size)
+        } else {
+            pending
+        };
+
+        let mut tail_frame = AudioFrame::new(output.format, capacity, output.channel_layout);
+        resampler
+            .flush(&mut tail_frame)
+            .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
+        if tail_frame.samples() == 0 {
+            break;
+        }
+
+        tail_frame.set_pts(Some(*samples_written));
+        *samples_written += tail_frame.samples() as i64;
+        encoder
+            .send_frame(&tail_frame)
+            .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
+        drain_encoder(encoder, encoded_packet, encoder_time_base, writer)?;
+    }
+
+    encoder
+        .send_eof()
+        .map_err(|error| UnbundleError::AudioEncodeError(error.to_string()))?;
+    drain_encoder(encoder, encoded_packet, encoder_time_base, writer)
+}
+
+/// Write every packet the encoder has ready, rescaled from the encoder's
+/// time base to the output stream's.
+fn drain_encoder<W: PacketWriter>(
+    encoder: &mut AudioEncoder,
+    encoded_packet: &mut Packet,
+    encoder_time_base: Rational,
+    writer: &mut W,
+) -> Result<(), UnbundleError> {
+    let output_time_base = writer.time_base();
     while encoder.receive_packet(encoded_packet).is_ok() {
         encoded_packet.set_stream(0);
-        encoded_packet.rescale_ts(encoder_time_base, encoder_time_base);
+        encoded_packet.rescale_ts(encoder_time_base, output_time_base);
         writer.write_packet(encoded_packet)?;
     }
-
     Ok(())
 }
@@ -2,7 +2,8 @@
 //!
 //! This module provides [`AudioIterator`] for streaming decoded audio
 //! samples without collecting the entire track into memory. Audio is
-//! decoded, resampled to mono f32, and yielded in chunks.
+//! decoded, resampled to mono f32, and yielded in chunks, each with its
+//! peak and RMS level for metering.
 //!
 //! # Example
 //!
@@ -13,7 +14,10 @@
 //! let iter = unbundler.audio().sample_iter()?;
 //! for result in iter {
 //!     let chunk = result?;
-//!     println!("Got {} samples at {:?}", chunk.samples.len(), chunk.timestamp);
+//!     println!(
+//!         "{:?}: peak {:.1} dBFS, RMS {:.1} dBFS",
+//!         chunk.timestamp, chunk.peak_dbfs, chunk.rms_dbfs
+//!     );
 //! }
 //! # Ok::<(), UnbundleError>(())
 //! ```
@@ -29,7 +33,7 @@ use ffmpeg_next::{
     software::resampling::Context as ResamplingContext,
 };
 
-use crate::{error::UnbundleError, unbundle::MediaFile};
+use crate::{conversion::amplitude_to_dbfs, error::UnbundleError, unbundle::MediaFile};
 
 /// A chunk of decoded audio samples.
 #[derive(Debug, Clone)]
@@ -40,6 +44,11 @@ pub struct AudioChunk {
     pub timestamp: Duration,
     /// Sample rate of the decoded audio.
     pub sample_rate: u32,
+    /// Largest absolute sample in this chunk, in dBFS (0.0 is full scale,
+    /// negative infinity is silence).
+    pub peak_dbfs: f64,
+    /// RMS level of this chunk in dBFS, negative infinity for silence.
+    pub rms_dbfs: f64,
 }
 
 /// A lazy iterator over decoded audio samples.
@@ -132,10 +141,13 @@ impl<'a> Iterator for AudioIterator<'a> {
 
                         self.samples_yielded += sample_count as u64;
 
+                        let (peak_dbfs, rms_dbfs) = chunk_levels(float_samples);
                         return Some(Ok(AudioChunk {
                             samples: float_samples.to_vec(),
                             timestamp,
                             sample_rate: self.sample_rate,
+                            peak_dbfs,
+                            rms_dbfs,
                         }));
                     }
                     Err(e) => {
@@ -177,3 +189,19 @@ impl<'a> Iterator for AudioIterator<'a> {
         }
     }
 }
+
+/// Peak and RMS level of `samples` in dBFS, for metering.
+fn chunk_levels(samples: &[f32]) -> (f64, f64) {
+    if samples.is_empty() {
+        return (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    }
+    let mut peak = 0.0f64;
+    let mut sum_squares = 0.0f64;
+    for &sample in samples {
+        let sample = f64::from(sample);
+        peak = peak.max(sample.abs());
+        sum_squares += sample * sample;
+    }
+    let rms = (sum_squares / samples.len() as f64).sqrt();
+    (amplitude_to_dbfs(peak), amplitude_to_dbfs(rms))
+}
@@ -1,5 +1,6 @@
 use std::{
-    fs,
+    fs::{self, File},
+    io::BufWriter,
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
@@ -8,9 +9,16 @@ use std::{
 #[cfg(feature = "waveform")]
 use std::io::Write;
 
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use colored::Colorize;
+use image::{
+    DynamicImage,
+    codecs::{
+        jpeg::JpegEncoder,
+        png::{CompressionType, FilterType, PngEncoder},
+    },
+};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde_json::json;
 use unbundle::{
@@ -19,7 +27,10 @@ use unbundle::{
 };
 
 #[cfg(feature = "hardware")]
-use unbundle::{HardwareAccelerationMode, HardwareDeviceType};
+use unbundle::HardwareAccelerationMode;
+
+#[cfg(feature = "rayon")]
+use unbundle::FrameRange;
 
 #[cfg(feature = "loudness")]
 use unbundle::LoudnessInfo;
@@ -65,7 +76,7 @@ struct GlobalOptions {
     #[arg(long)]
     log_level: Option<String>,
 
-    /// Preferred frame pixel format for extraction (rgb8, rgba8, gray8).
+    /// Preferred frame pixel format for extraction (rgb8, rgba8, gray8, bgr8, bgra8).
     #[arg(long)]
     pixel_format: Option<String>,
 
@@ -85,7 +96,7 @@ enum Commands {
         about = "Print media metadata",
         visible_alias = "probe",
         visible_alias = "info",
-        after_help = "Examples:\n  unbundle metadata input.mp4\n  unbundle metadata input.mp4 --json"
+        after_help = "Examples:\n  unbundle metadata input.mp4\n  unbundle metadata input.mp4 --json\n  unbundle metadata input.mp4 --checksums"
     )]
     Metadata {
         /// Input media path or URL.
@@ -94,12 +105,30 @@ enum Commands {
         /// Output metadata as machine-readable JSON.
         #[arg(long)]
         json: bool,
+
+        /// Also checksum each stream's packet payloads (reads the whole file).
+        #[arg(long)]
+        checksums: bool,
+    },
+
+    /// List every stream in a media file.
+    #[command(
+        about = "List streams with their track numbers",
+        after_help = "Examples:\n  unbundle streams input.mkv\n  unbundle streams input.mkv --json"
+    )]
+    Streams {
+        /// Input media path or URL.
+        input: String,
+
+        /// Output the stream list as machine-readable JSON.
+        #[arg(long)]
+        json: bool,
     },
 
     /// Extract frames to an output directory.
     #[command(
         about = "Extract video frames",
-        after_help = "Examples:\n  unbundle extract-frames input.mp4 --out frames --every 10 --ext jpg\n  unbundle extract-frames input.mp4 --out frames --start 0:00:10 --end 0:00:20 --progress"
+        after_help = "Examples:\n  unbundle extract-frames input.mp4 --out frames --every 10 --ext jpg --quality 85\n  unbundle extract-frames input.mp4 --out frames --png-compression best\n  unbundle extract-frames input.mp4 --out frames --start 0:00:10 --end 0:00:20 --progress"
     )]
     ExtractFrames {
         /// Input media path or URL.
@@ -119,6 +148,12 @@ enum Commands {
         /// Output image extension (png, jpg, jpeg, bmp, tiff).
         #[arg(long, default_value = "png")]
         ext: String,
+        /// JPEG quality from 1 (smallest) to 100 (best).
+        #[arg(long, default_value_t = 75, value_parser = clap::value_parser!(u8).range(1..=100))]
+        quality: u8,
+        /// PNG compression effort: fast | default | best.
+        #[arg(long, value_enum, default_value_t = PngCompression::Default)]
+        png_compression: PngCompression,
     },
 
     /// Extract audio track to a file.
@@ -201,6 +236,12 @@ enum Commands {
         exclude_audio: bool,
         #[arg(long)]
         exclude_subtitles: bool,
+        /// Regenerate missing and repair non-monotonic timestamps.
+        #[arg(long)]
+        fix_timestamps: bool,
+        /// Write the output without the input's metadata tags.
+        #[arg(long)]
+        strip_metadata: bool,
     },
 
     /// Validate media structure and print a report.
@@ -252,6 +293,55 @@ enum Commands {
     },
 }
 
+/// PNG compression effort for `extract-frames`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PngCompression {
+    /// Fastest encoding, largest files.
+    Fast,
+    /// The `image` crate's balanced default.
+    Default,
+    /// Slowest encoding, smallest files.
+    Best,
+}
+
+impl From<PngCompression> for CompressionType {
+    fn from(value: PngCompression) -> Self {
+        match value {
+            PngCompression::Fast => CompressionType::Fast,
+            PngCompression::Default => CompressionType::Default,
+            PngCompression::Best => CompressionType::Best,
+        }
+    }
+}
+
+/// Write a frame image, choosing the encoder explicitly for JPEG and PNG so
+/// the quality and compression settings apply. Other extensions fall back to
+/// `DynamicImage::save`.
+fn save_frame_image(
+    image: &DynamicImage,
+    path: &Path,
+    extension: &str,
+    quality: u8,
+    png_compression: PngCompression,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match extension {
+        "jpg" | "jpeg" => {
+            let writer = BufWriter::new(File::create(path)?);
+            let encoder = JpegEncoder::new_with_quality(writer, quality);
+            // JPEG has no alpha channel.
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+        }
+        "png" => {
+            let writer = BufWriter::new(File::create(path)?);
+            let encoder =
+                PngEncoder::new_with_quality(writer, png_compression.into(), FilterType::Adaptive);
+            image.write_with_encoder(encoder)?;
+        }
+        _ => image.save(path)?,
+    }
+    Ok(())
+}
+
 fn parse_audio_format(value: &str) -> Option<AudioFormat> {
     match value.to_ascii_lowercase().as_str() {
         "wav" => Some(AudioFormat::Wav),
@@ -309,50 +399,15 @@ fn open_input(input: &str) -> Result<MediaFile, Box<dyn std::error::Error>> {
     }
 }
 
-fn parse_pixel_format(value: &str) -> Option<PixelFormat> {
-    match value.to_ascii_lowercase().as_str() {
-        "rgb8" | "rgb" => Some(PixelFormat::Rgb8),
-        "rgba8" | "rgba" => Some(PixelFormat::Rgba8),
-        "gray8" | "gray" | "greyscale" | "grayscale" => Some(PixelFormat::Gray8),
-        _ => None,
-    }
-}
-
-fn parse_log_level(value: &str) -> Option<FfmpegLogLevel> {
-    match value.to_ascii_lowercase().as_str() {
-        "quiet" => Some(FfmpegLogLevel::Quiet),
-        "panic" => Some(FfmpegLogLevel::Panic),
-        "fatal" => Some(FfmpegLogLevel::Fatal),
-        "error" => Some(FfmpegLogLevel::Error),
-        "warning" | "warn" => Some(FfmpegLogLevel::Warning),
-        "info" => Some(FfmpegLogLevel::Info),
-        "verbose" => Some(FfmpegLogLevel::Verbose),
-        "debug" => Some(FfmpegLogLevel::Debug),
-        "trace" => Some(FfmpegLogLevel::Trace),
-        _ => None,
-    }
-}
-
-#[cfg(feature = "hardware")]
-fn parse_hardware_mode(value: &str) -> Option<HardwareAccelerationMode> {
-    match value.to_ascii_lowercase().as_str() {
-        "auto" => Some(HardwareAccelerationMode::Auto),
-        "software" | "sw" | "cpu" => Some(HardwareAccelerationMode::Software),
-        "cuda" => Some(HardwareAccelerationMode::Specific(HardwareDeviceType::Cuda)),
-        "vaapi" => Some(HardwareAccelerationMode::Specific(
-            HardwareDeviceType::Vaapi,
-        )),
-        "dxva2" => Some(HardwareAccelerationMode::Specific(
-            HardwareDeviceType::Dxva2,
-        )),
-        "d3d11va" => Some(HardwareAccelerationMode::Specific(
-            HardwareDeviceType::D3d11va,
-        )),
-        "videotoolbox" => Some(HardwareAccelerationMode::Specific(
-            HardwareDeviceType::VideoToolbox,
-        )),
-        "qsv" => Some(HardwareAccelerationMode::Specific(HardwareDeviceType::Qsv)),
-        _ => None,
+fn require_stream(
+    available: bool,
+    kind: &str,
+    input: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if available {
+        Ok(())
+    } else {
+        Err(format!("{input} has no {kind} stream").into())
     }
 }
 
@@ -381,18 +436,25 @@ fn base_extract_options(
     let mut options = ExtractOptions::new();
 
     if let Some(pixel_str) = &global.pixel_format {
-        let pixel = parse_pixel_format(pixel_str)
-            .ok_or(format!("unsupported --pixel-format: {pixel_str}"))?;
+        let pixel: PixelFormat = pixel_str
+            .parse()
+            .map_err(|_| format!("unsupported --pixel-format: {pixel_str}"))?;
         options = options.with_pixel_format(pixel);
     }
 
     #[cfg(feature = "hardware")]
     if let Some(hardware) = &global.hardware {
-        let mode = parse_hardware_mode(hardware)
-            .ok_or(format!("unsupported --hardware mode: {hardware}"))?;
+        let mode: HardwareAccelerationMode = hardware
+            .parse()
+            .map_err(|_| format!("unsupported --hardware mode: {hardware}"))?;
         options = options.with_hardware_acceleration(mode);
     }
 
+    #[cfg(feature = "rayon")]
+    if let Some(threads) = global.threads {
+        options = options.with_parallel_threads(threads);
+    }
+
     if global.progress {
         options = options.with_progress(Arc::new(TerminalProgress::new()));
     }
@@ -402,16 +464,19 @@ fn base_extract_options(
 
 fn apply_global_options(global: &GlobalOptions) -> Result<(), Box<dyn std::error::Error>> {
     if let Some(level) = &global.log_level {
-        let parsed = parse_log_level(level).ok_or(format!("unsupported --log-level: {level}"))?;
+        let parsed: FfmpegLogLevel = level
+            .parse()
+            .map_err(|_| format!("unsupported --log-level: {level}"))?;
         unbundle::set_ffmpeg_log_level(parsed);
     }
 
-    if let Some(threads) = global.threads {
-        if threads > 0 {
-            unsafe {
-                std::env::set_var("RAYON_NUM_THREADS", threads.to_string());
-            }
-        }
+    #[cfg(not(feature = "rayon"))]
+    if global.threads.is_some() {
+        eprintln!(
+            "{} {}",
+            "warning:".yellow().bold(),
+            "--threads requires building with the `rayon` feature".yellow()
+        );
     }
 
     #[cfg(not(feature = "hardware"))]
@@ -448,8 +513,17 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     apply_global_options(&cli.global)?;
 
     match cli.command {
-        Commands::Metadata { input, json } => {
-            let unbundler = open_input(&input)?;
+        Commands::Metadata {
+            input,
+            json,
+            checksums,
+        } => {
+            let mut unbundler = open_input(&input)?;
+            let checksums = if checksums {
+                Some(unbundler.stream_checksums()?)
+            } else {
+                None
+            };
             let metadata = unbundler.metadata();
             if json {
                 let payload = json!({
@@ -461,18 +535,31 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                         "fps": video.frames_per_second,
                         "frame_count": video.frame_count,
                         "codec": video.codec,
+                        "duration_seconds": video.duration.map(|d| d.as_secs_f64()),
                     })),
                     "audio": metadata.audio.as_ref().map(|audio| json!({
                         "sample_rate": audio.sample_rate,
                         "channels": audio.channels,
                         "codec": audio.codec,
                         "bit_rate": audio.bit_rate,
+                        "duration_seconds": audio.duration.map(|d| d.as_secs_f64()),
                     })),
                     "subtitle": metadata.subtitle.as_ref().map(|sub| json!({
                         "codec": sub.codec,
                         "language": sub.language,
                     })),
                     "chapters": metadata.chapters.as_ref().map(|chapters| chapters.len()).unwrap_or(0),
+                    "checksums": checksums.as_ref().map(|checksums| checksums
+                        .iter()
+                        .map(|checksum| json!({
+                            "index": checksum.stream_index,
+                            "kind": checksum.kind.to_string(),
+                            "codec": checksum.codec,
+                            "packets": checksum.packet_count,
+                            "bytes": checksum.byte_count,
+                            "crc32": format!("{:08x}", checksum.crc32),
+                        }))
+                        .collect::<Vec<_>>()),
                 });
                 println!("{}", serde_json::to_string_pretty(&payload)?);
             } else {
@@ -496,6 +583,71 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 if let Some(subtitle) = &metadata.subtitle {
                     println!("Subtitle: {}", subtitle.codec);
                 }
+                for checksum in checksums.iter().flatten() {
+                    println!(
+                        "Checksum #{} {} [{}]: crc32 {:08x}, {} packets, {} bytes",
+                        checksum.stream_index,
+                        checksum.kind,
+                        checksum.codec,
+                        checksum.crc32,
+                        checksum.packet_count,
+                        checksum.byte_count,
+                    );
+                }
+            }
+        }
+        Commands::Streams { input, json } => {
+            let unbundler = open_input(&input)?;
+            let streams = unbundler.streams();
+            if json {
+                let payload: Vec<_> = streams
+                    .iter()
+                    .map(|stream| {
+                        json!({
+                            "index": stream.index,
+                            "kind": stream.kind.to_string(),
+                            "codec": stream.codec,
+                            "language": stream.language,
+                            "track": stream.track_index,
+                            "width": stream.dimensions.map(|(width, _)| width),
+                            "height": stream.dimensions.map(|(_, height)| height),
+                            "sample_rate": stream.sample_rate,
+                            "channels": stream.channels,
+                            "default": stream.default,
+                            "forced": stream.forced,
+                            "attached_picture": stream.attached_picture,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+            } else {
+                for stream in &streams {
+                    let mut line = format!("#{} {} [{}]", stream.index, stream.kind, stream.codec);
+                    if let Some(track) = stream.track_index {
+                        line.push_str(&format!(" track {track}"));
+                    }
+                    if let Some((width, height)) = stream.dimensions {
+                        line.push_str(&format!(" {width}x{height}"));
+                    }
+                    if let (Some(rate), Some(channels)) = (stream.sample_rate, stream.channels) {
+                        line.push_str(&format!(" {rate} Hz, {channels} ch"));
+                    }
+                    if let Some(language) = &stream.language {
+                        line.push_str(&format!(" ({language})"));
+                    }
+                    let flags: Vec<&str> = [
+                        (stream.default, "default"),
+                        (stream.forced, "forced"),
+                        (stream.attached_picture, "cover art"),
+                    ]
+                    .into_iter()
+                    .filter_map(|(set, name)| set.then_some(name))
+                    .collect();
+                    if !flags.is_empty() {
+                        line.push_str(&format!(" {{{}}}", flags.join(", ")));
+                    }
+                    println!("{line}");
+                }
             }
         }
         Commands::ExtractFrames {
@@ -505,6 +657,8 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             start,
             end,
             ext,
+            quality,
+            png_compression,
         } => {
             if every == 0 {
                 return Err("--every must be greater than 0".into());
@@ -527,6 +681,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             fs::create_dir_all(&out)?;
 
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_video(), "video", &input)?;
             let metadata = unbundler
                 .metadata()
                 .video
@@ -578,8 +733,9 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 None
             };
 
-            let mut handle = unbundler.video();
-            for frame_number in frame_numbers {
+            let mut save = |frame_number: u64,
+                            image: &DynamicImage|
+             -> Result<(), Box<dyn std::error::Error>> {
                 let output_path = out.join(format!("frame_{frame_number:06}.{ext_clean}"));
                 if output_path.exists() && !cli.global.overwrite {
                     return Err(format!(
@@ -588,9 +744,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                     )
                     .into());
                 }
-
-                let image = handle.frame_with_options(frame_number, &options)?;
-                image.save(&output_path)?;
+                save_frame_image(image, &output_path, &ext_clean, quality, png_compression)?;
                 extracted += 1;
 
                 if let Some(pb) = &progress_bar {
@@ -600,6 +754,31 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 if cli.global.verbose {
                     eprintln!("saved frame {} -> {}", frame_number, output_path.display());
                 }
+                Ok(())
+            };
+
+            #[cfg(feature = "rayon")]
+            let parallel = cli.global.threads.is_some();
+            #[cfg(not(feature = "rayon"))]
+            let parallel = false;
+
+            if parallel {
+                // With --threads, decode everything up front on a bounded
+                // pool. Frames the decoder dropped are missing from the
+                // result, so each is named by the number it comes back with.
+                #[cfg(feature = "rayon")]
+                for (frame_number, image) in unbundler
+                    .video()
+                    .numbered_frames_parallel(FrameRange::Specific(frame_numbers), &options)?
+                {
+                    save(frame_number, &image)?;
+                }
+            } else {
+                let mut handle = unbundler.video();
+                for frame_number in frame_numbers {
+                    let image = handle.frame_with_options(frame_number, &options)?;
+                    save(frame_number, &image)?;
+                }
             }
 
             if let Some(pb) = progress_bar {
@@ -624,6 +803,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
 
             ensure_writable_path(&out, cli.global.overwrite)?;
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_audio(), "audio", &input)?;
 
             match (start, end) {
                 (Some(start_time), Some(end_time)) => {
@@ -656,6 +836,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
 
             ensure_writable_path(&out, cli.global.overwrite)?;
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_subtitles(), "subtitle", &input)?;
 
             match (start, end) {
                 (Some(start_time), Some(end_time)) => {
@@ -690,6 +871,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             ensure_writable_path(&out, cli.global.overwrite)?;
 
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_video(), "video", &input)?;
             let image = match mode.to_ascii_lowercase().as_str() {
                 "single" => {
                     if let Some(frame_number) = frame {
@@ -726,9 +908,13 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             exclude_video,
             exclude_audio,
             exclude_subtitles,
+            fix_timestamps,
+            strip_metadata,
         } => {
             ensure_writable_path(&output, cli.global.overwrite)?;
-            let mut remuxer = unbundle::Remuxer::new(input, &output)?;
+            let mut remuxer = unbundle::Remuxer::new(input, &output)?
+                .with_fix_timestamps(fix_timestamps)
+                .with_strip_metadata(strip_metadata);
             if exclude_video {
                 remuxer = remuxer.exclude_video();
             }
@@ -753,6 +939,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
             json,
         } => {
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_video(), "video", &input)?;
             let changes = unbundler
                 .video()
                 .detect_scenes(Some(SceneDetectionOptions::new().threshold(threshold)))?;
@@ -783,6 +970,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         #[cfg(feature = "waveform")]
         Commands::Waveform { input, bins, out } => {
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_audio(), "audio", &input)?;
             let waveform = unbundler
                 .audio()
                 .generate_waveform(&WaveformOptions::new().bins(bins))?;
@@ -806,6 +994,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         #[cfg(feature = "loudness")]
         Commands::Loudness { input, json } => {
             let mut unbundler = open_input(&input)?;
+            require_stream(unbundler.has_audio(), "audio", &input)?;
             let info: LoudnessInfo = unbundler.audio().analyze_loudness()?;
             if json {
                 let payload = json!({
@@ -840,7 +1029,11 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_audio_format, parse_subtitle_format, parse_timecode};
+    use image::{DynamicImage, RgbImage};
+
+    use super::{
+        PngCompression, parse_audio_format, parse_subtitle_format, parse_timecode, save_frame_image,
+    };
 
     #[test]
     fn parse_audio_format_aliases() {
@@ -872,4 +1065,21 @@ mod tests {
         let hh_mm_ss = parse_timecode("00:01:15.5").unwrap();
         assert_eq!(hh_mm_ss.as_secs(), 75);
     }
+
+    #[test]
+    fn save_frame_image_honours_jpeg_quality() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(64, 64, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8])
+        }));
+        let directory = tempfile::tempdir().unwrap();
+        let low = directory.path().join("low.jpg");
+        let high = directory.path().join("high.jpg");
+
+        save_frame_image(&image, &low, "jpg", 10, PngCompression::Default).unwrap();
+        save_frame_image(&image, &high, "jpg", 100, PngCompression::Default).unwrap();
+
+        let low_size = std::fs::metadata(&low).unwrap().len();
+        let high_size = std::fs::metadata(&high).unwrap().len();
+        assert!(low_size < high_size);
+    }
 }
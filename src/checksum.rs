@@ -0,0 +1,106 @@
+//! Per-stream payload checksums.
+//!
+//! [`MediaFile::stream_checksums`](crate::MediaFile::stream_checksums)
+//! hashes the packet payloads of every stream, ignoring timestamps and
+//! everything the container adds around them. Two files whose streams carry
+//! the same encoded data therefore produce the same checksums even when
+//! they were muxed differently, which is what deduplication and integrity
+//! checks need.
+//!
+//! Some codecs are stored differently depending on the container: H.264
+//! and HEVC use length-prefixed NAL units in MP4 and Matroska but start
+//! codes in MPEG-TS, and AAC carries ADTS headers in raw and TS streams.
+//! Such streams hash differently across those containers even though they
+//! decode identically.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{MediaFile, UnbundleError};
+//!
+//! let mut original = MediaFile::open("input.mkv")?;
+//! let mut remuxed = MediaFile::open("remuxed.mkv")?;
+//! let same = original.stream_checksums()? == remuxed.stream_checksums()?;
+//! println!("identical streams: {same}");
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use ffmpeg_next::{Error as FfmpegError, Packet};
+use ffmpeg_sys_next::{AVCRCId, av_crc, av_crc_get_table};
+
+use crate::error::UnbundleError;
+use crate::metadata::StreamKind;
+use crate::unbundle::MediaFile;
+
+/// Checksum of one stream's packet payloads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StreamChecksum {
+    /// Container stream index.
+    pub stream_index: usize,
+    /// Media type of the stream.
+    pub kind: StreamKind,
+    /// Codec name, as in [`StreamMetadata::codec`](crate::StreamMetadata).
+    pub codec: String,
+    /// Number of packets read.
+    pub packet_count: u64,
+    /// Total payload size in bytes.
+    pub byte_count: u64,
+    /// CRC-32 (IEEE, as used by zlib and PNG) of the payloads concatenated
+    /// in file order. Empty streams have a checksum of 0.
+    pub crc32: u32,
+}
+
+/// Read every packet from the start of the file and checksum each stream.
+pub(crate) fn stream_checksums_impl(
+    unbundler: &mut MediaFile,
+) -> Result<Vec<StreamChecksum>, UnbundleError> {
+    let mut checksums: Vec<StreamChecksum> = unbundler
+        .streams()
+        .into_iter()
+        .map(|stream| StreamChecksum {
+            stream_index: stream.index,
+            kind: stream.kind,
+            codec: stream.codec,
+            packet_count: 0,
+            byte_count: 0,
+            crc32: 0,
+        })
+        .collect();
+    log::debug!("Computing checksums for {} streams", checksums.len());
+
+    // SAFETY: the table is a static owned by FFmpeg and never freed.
+    let table = unsafe { av_crc_get_table(AVCRCId::AV_CRC_32_IEEE_LE) };
+    // Running CRCs are kept inverted, as zlib does, and inverted back at
+    // the end.
+    let mut states = vec![u32::MAX; checksums.len()];
+
+    crate::unbundle::seek_input(&mut unbundler.input_context, 0)?;
+    let mut packet = Packet::empty();
+    loop {
+        match packet.read(&mut unbundler.input_context) {
+            Ok(()) => {
+                let index = packet.stream();
+                let (Some(checksum), Some(state)) =
+                    (checksums.get_mut(index), states.get_mut(index))
+                else {
+                    continue;
+                };
+                let data = packet.data().unwrap_or_default();
+                checksum.packet_count += 1;
+                checksum.byte_count += data.len() as u64;
+                if !data.is_empty() {
+                    // SAFETY: `data` is valid for `data.len()` bytes for the
+                    // duration of the call.
+                    *state = unsafe { av_crc(table, *state, data.as_ptr(), data.len()) };
+                }
+            }
+            Err(FfmpegError::Eof) => break,
+            Err(e) => return Err(UnbundleError::from(e)),
+        }
+    }
+
+    for (checksum, state) in checksums.iter_mut().zip(states) {
+        checksum.crc32 = !state;
+    }
+    Ok(checksums)
+}
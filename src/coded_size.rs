@@ -0,0 +1,184 @@
+//! Coded picture size of H.264 streams.
+//!
+//! Block-based codecs code whole macroblocks, so a 1080-line H.264 stream
+//! is coded as 1088 lines and cropped on decode. FFmpeg only learns the
+//! coded size once a frame has been decoded, so this module reads it from
+//! the sequence parameter set in the stream's extradata instead.
+
+use ffmpeg_next::codec::Id;
+
+/// Profiles whose SPS carries chroma format, bit depth and scaling lists.
+const HIGH_PROFILES: [u8; 13] = [100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134, 135];
+
+/// Coded `(width, height)` of a stream, before cropping.
+///
+/// Returns `None` for codecs other than H.264 and when the extradata holds
+/// no readable SPS.
+pub(crate) fn coded_dimensions(codec: Id, extradata: &[u8]) -> Option<(u32, u32)> {
+    if codec != Id::H264 {
+        return None;
+    }
+    let sps = first_sps(extradata)?;
+    parse_sps(&remove_emulation_prevention(sps))
+}
+
+/// Find the first SPS NAL unit in `avcC` or Annex B extradata.
+fn first_sps(extradata: &[u8]) -> Option<&[u8]> {
+    if extradata.first() == Some(&1) {
+        // avcC: version, profile, compatibility, level, length size, then
+        // a count of SPS units each prefixed by a 16-bit length.
+        if *extradata.get(5)? & 0x1f == 0 {
+            return None;
+        }
+        let length = usize::from(u16::from_be_bytes([*extradata.get(6)?, *extradata.get(7)?]));
+        return extradata.get(8..8 + length);
+    }
+
+    // Annex B: NAL units separated by 00 00 01 start codes.
+    let mut units = extradata
+        .windows(3)
+        .enumerate()
+        .filter(|(_, window)| *window == [0, 0, 1])
+        .map(|(position, _)| position + 3)
+        .peekable();
+    while let Some(start) = units.next() {
+        let end = units.peek().map_or(extradata.len(), |next| next - 3);
+        let unit = extradata.get(start..end)?;
+        if unit.first().is_some_and(|header| header & 0x1f == 7) {
+            return Some(unit);
+        }
+    }
+    None
+}
+
+/// Drop the `03` byte the encoder inserts after every `00 00` pair.
+fn remove_emulation_prevention(unit: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(unit.len());
+    let mut zeros = 0;
+    for &byte in unit {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        output.push(byte);
+    }
+    output
+}
+
+/// Read the macroblock dimensions from an SPS NAL unit (header included).
+fn parse_sps(unit: &[u8]) -> Option<(u32, u32)> {
+    let mut reader = BitReader::new(unit.get(1..)?);
+    let profile_idc = reader.bits(8)? as u8;
+    reader.bits(16)?; // constraint flags and level
+    reader.unsigned()?; // seq_parameter_set_id
+
+    if HIGH_PROFILES.contains(&profile_idc) {
+        let chroma_format_idc = reader.unsigned()?;
+        if chroma_format_idc == 3 {
+            reader.bits(1)?; // separate_colour_plane_flag
+        }
+        reader.unsigned()?; // bit_depth_luma_minus8
+        reader.unsigned()?; // bit_depth_chroma_minus8
+        reader.bits(1)?; // qpprime_y_zero_transform_bypass_flag
+        if reader.bits(1)? == 1 {
+            let lists = if chroma_format_idc == 3 { 12 } else { 8 };
+            for list in 0..lists {
+                if reader.bits(1)? == 1 {
+                    skip_scaling_list(&mut reader, if list < 6 { 16 } else { 64 })?;
+                }
+            }
+        }
+    }
+
+    reader.unsigned()?; // log2_max_frame_num_minus4
+    match reader.unsigned()? {
+        0 => {
+            reader.unsigned()?; // log2_max_pic_order_cnt_lsb_minus4
+        }
+        1 => {
+            reader.bits(1)?; // delta_pic_order_always_zero_flag
+            reader.signed()?; // offset_for_non_ref_pic
+            reader.signed()?; // offset_for_top_to_bottom_field
+            for _ in 0..reader.unsigned()? {
+                reader.signed()?;
+            }
+        }
+        _ => {}
+    }
+    reader.unsigned()?; // max_num_ref_frames
+    reader.bits(1)?; // gaps_in_frame_num_value_allowed_flag
+    let width_in_macroblocks = reader.unsigned()?.checked_add(1)?;
+    let height_in_map_units = reader.unsigned()?.checked_add(1)?;
+    let frame_mbs_only = reader.bits(1)?;
+
+    // Field-coded streams count map units per field.
+    let height_in_macroblocks = height_in_map_units.checked_mul(2 - frame_mbs_only)?;
+    Some((
+        width_in_macroblocks.checked_mul(16)?,
+        height_in_macroblocks.checked_mul(16)?,
+    ))
+}
+
+fn skip_scaling_list(reader: &mut BitReader<'_>, size: usize) -> Option<()> {
+    let mut last_scale = 8i64;
+    let mut next_scale = 8i64;
+    for _ in 0..size {
+        if next_scale != 0 {
+            let delta = reader.signed()?;
+            next_scale = (last_scale + delta).rem_euclid(256);
+        }
+        if next_scale != 0 {
+            last_scale = next_scale;
+        }
+    }
+    Some(())
+}
+
+/// MSB-first bit reader with Exp-Golomb decoding.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn bits(&mut self, count: u32) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = self.data.get(self.position / 8)?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    /// `ue(v)`.
+    fn unsigned(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while self.bits(1)? == 0 {
+            leading_zeros += 1;
+            if leading_zeros > 31 {
+                return None;
+            }
+        }
+        let suffix = self.bits(leading_zeros)?;
+        ((1u64 << leading_zeros) - 1 + u64::from(suffix))
+            .try_into()
+            .ok()
+    }
+
+    /// `se(v)`.
+    fn signed(&mut self) -> Option<i64> {
+        let code = i64::from(self.unsigned()?);
+        Some(if code % 2 == 1 {
+            (code + 1) / 2
+        } else {
+            -(code / 2)
+        })
+    }
+}
@@ -0,0 +1,316 @@
+//! Full-reference video quality metrics.
+//!
+//! [`MediaFile::compare`](crate::MediaFile::compare) decodes a reference and
+//! a distorted file side by side, pairs their frames by timestamp, and
+//! measures PSNR and SSIM on the luma plane of each pair — the usual check
+//! that a re-encode or a processing step has not visibly damaged the
+//! picture.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{MediaFile, MetricOptions, UnbundleError};
+//!
+//! let mut original = MediaFile::open("master.mov")?;
+//! let mut encode = MediaFile::open("encode.mp4")?;
+//! let report = original.compare(&mut encode, MetricOptions::new())?;
+//! println!(
+//!     "{} frames: PSNR {:.2} dB, SSIM {:.4}",
+//!     report.frames.len(),
+//!     report.average_psnr.unwrap_or_default(),
+//!     report.average_ssim.unwrap_or_default(),
+//! );
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use image::{DynamicImage, GrayImage};
+
+use crate::configuration::{FrameOutputOptions, PixelFormat};
+use crate::error::UnbundleError;
+use crate::unbundle::MediaFile;
+use crate::video::{FrameMetadata, FrameRange};
+use crate::video_iterator::FrameMetadataIterator;
+
+/// Side of the square SSIM window, in pixels.
+const SSIM_WINDOW: u32 = 8;
+/// Step between SSIM windows, so neighbouring windows overlap by half.
+const SSIM_STRIDE: u32 = 4;
+/// SSIM stabilising constants `(0.01 × 255)²` and `(0.03 × 255)²`.
+const SSIM_C1: f64 = 6.5025;
+const SSIM_C2: f64 = 58.5225;
+
+/// Which metrics [`MediaFile::compare`](crate::MediaFile::compare) computes
+/// and how frames are paired.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricOptions {
+    /// Compute PSNR. Default: `true`.
+    pub psnr: bool,
+    /// Compute SSIM. Default: `true`.
+    pub ssim: bool,
+    /// Largest timestamp difference at which two frames are still paired.
+    /// `None` uses half a reference frame interval. Default: `None`.
+    pub tolerance: Option<Duration>,
+}
+
+impl Default for MetricOptions {
+    fn default() -> Self {
+        Self {
+            psnr: true,
+            ssim: true,
+            tolerance: None,
+        }
+    }
+}
+
+impl MetricOptions {
+    /// Create options that compute both PSNR and SSIM.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable PSNR.
+    #[must_use]
+    pub fn with_psnr(mut self, enabled: bool) -> Self {
+        self.psnr = enabled;
+        self
+    }
+
+    /// Enable or disable SSIM, which costs several times more than PSNR.
+    #[must_use]
+    pub fn with_ssim(mut self, enabled: bool) -> Self {
+        self.ssim = enabled;
+        self
+    }
+
+    /// Set the largest timestamp difference at which frames are paired.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+}
+
+/// Metrics for one pair of matched frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameMetrics {
+    /// Frame number in the reference file.
+    pub reference_frame: u64,
+    /// Frame number in the distorted file.
+    pub distorted_frame: u64,
+    /// Timestamp of the reference frame.
+    pub timestamp: Duration,
+    /// Luma PSNR in decibels; infinite for identical frames. `None` when
+    /// PSNR is disabled.
+    pub psnr: Option<f64>,
+    /// Luma SSIM, 1.0 for identical frames. `None` when SSIM is disabled.
+    pub ssim: Option<f64>,
+}
+
+/// Result of [`MediaFile::compare`](crate::MediaFile::compare).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareReport {
+    /// Metrics of every matched frame pair, in presentation order.
+    pub frames: Vec<FrameMetrics>,
+    /// PSNR of the mean squared error over all pairs, as FFmpeg's `psnr`
+    /// filter reports it. `None` when PSNR is disabled or no frames matched.
+    pub average_psnr: Option<f64>,
+    /// Mean of the per-frame SSIM values. `None` when SSIM is disabled or
+    /// no frames matched.
+    pub average_ssim: Option<f64>,
+    /// Reference frames with no distorted frame within the tolerance.
+    pub unmatched_reference: u64,
+    /// Distorted frames with no reference frame within the tolerance.
+    pub unmatched_distorted: u64,
+}
+
+/// Decode `reference` and `distorted` in lockstep and measure each pair.
+pub(crate) fn compare_impl(
+    reference: &mut MediaFile,
+    distorted: &mut MediaFile,
+    options: MetricOptions,
+) -> Result<CompareReport, UnbundleError> {
+    let reference_video = reference
+        .metadata
+        .video
+        .as_ref()
+        .ok_or(UnbundleError::NoVideoStream)?;
+    let tolerance = options.tolerance.unwrap_or_else(|| {
+        let frame_interval = 1.0 / reference_video.frames_per_second.max(1.0);
+        Duration::from_secs_f64(frame_interval / 2.0)
+    });
+    log::debug!(
+        "Comparing {}x{} video (psnr={}, ssim={}, tolerance={tolerance:?})",
+        reference_video.width,
+        reference_video.height,
+        options.psnr,
+        options.ssim,
+    );
+
+    // The distorted frames are scaled to the reference size, so a
+    // downscaled encode is judged as it would be displayed.
+    let gray = |width, height| FrameOutputOptions {
+        pixel_format: PixelFormat::Gray8,
+        width,
+        height,
+        maintain_aspect_ratio: false,
+        ..FrameOutputOptions::default()
+    };
+    let reference_output = gray(None, None);
+    let distorted_output = gray(Some(reference_video.width), Some(reference_video.height));
+
+    let mut reference_frames = FrameMetadataIterator::new(
+        reference
+            .video()
+            .frame_iter_with_options(FrameRange::Interval(1), reference_output)?,
+    );
+    let mut distorted_frames = FrameMetadataIterator::new(
+        distorted
+            .video()
+            .frame_iter_with_options(FrameRange::Interval(1), distorted_output)?,
+    );
+
+    let mut frames = Vec::new();
+    let mut squared_error_total = 0.0;
+    let mut unmatched_reference = 0;
+    let mut unmatched_distorted = 0;
+    let mut next_reference = reference_frames.next().transpose()?;
+    let mut next_distorted = distorted_frames.next().transpose()?;
+
+    while let (Some((reference_image, reference_info)), Some((distorted_image, distorted_info))) =
+        (&next_reference, &next_distorted)
+    {
+        if reference_info.timestamp.abs_diff(distorted_info.timestamp) <= tolerance {
+            let reference_luma = luma(reference_image);
+            let distorted_luma = luma(distorted_image);
+            let mse = mean_squared_error(&reference_luma, &distorted_luma);
+            squared_error_total += mse;
+            frames.push(FrameMetrics {
+                reference_frame: reference_info.frame_number,
+                distorted_frame: distorted_info.frame_number,
+                timestamp: reference_info.timestamp,
+                psnr: options.psnr.then(|| psnr(mse)),
+                ssim: options.ssim.then(|| ssim(&reference_luma, &distorted_luma)),
+            });
+            next_reference = reference_frames.next().transpose()?;
+            next_distorted = distorted_frames.next().transpose()?;
+        } else if reference_info.timestamp < distorted_info.timestamp {
+            unmatched_reference += 1;
+            next_reference = reference_frames.next().transpose()?;
+        } else {
+            unmatched_distorted += 1;
+            next_distorted = distorted_frames.next().transpose()?;
+        }
+    }
+    unmatched_reference += count_remaining(next_reference, reference_frames)?;
+    unmatched_distorted += count_remaining(next_distorted, distorted_frames)?;
+
+    let matched = frames.len();
+    let average_psnr =
+        (options.psnr && matched > 0).then(|| psnr(squared_error_total / matched as f64));
+    let average_ssim = (options.ssim && matched > 0)
+        .then(|| frames.iter().filter_map(|frame| frame.ssim).sum::<f64>() / matched as f64);
+    log::debug!(
+        "Compared {matched} frame pairs ({unmatched_reference} reference and \
+         {unmatched_distorted} distorted frames unmatched)"
+    );
+
+    Ok(CompareReport {
+        frames,
+        average_psnr,
+        average_ssim,
+        unmatched_reference,
+        unmatched_distorted,
+    })
+}
+
+/// Count the pending frame and everything left in `iterator`.
+fn count_remaining(
+    pending: Option<(DynamicImage, FrameMetadata)>,
+    iterator: FrameMetadataIterator<'_>,
+) -> Result<u64, UnbundleError> {
+    let mut count = u64::from(pending.is_some());
+    for result in iterator {
+        result?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// The frame as GRAY8, borrowed when the decoder already produced it.
+fn luma(image: &DynamicImage) -> Cow<'_, GrayImage> {
+    match image.as_luma8() {
+        Some(gray) => Cow::Borrowed(gray),
+        None => Cow::Owned(image.to_luma8()),
+    }
+}
+
+/// Mean squared difference of two equally sized images.
+fn mean_squared_error(reference: &GrayImage, distorted: &GrayImage) -> f64 {
+    let pixels = reference.as_raw().len();
+    if pixels == 0 {
+        return 0.0;
+    }
+    let total: u64 = reference
+        .as_raw()
+        .iter()
+        .zip(distorted.as_raw())
+        .map(|(&a, &b)| {
+            let difference = u64::from(a.abs_diff(b));
+            difference * difference
+        })
+        .sum();
+    total as f64 / pixels as f64
+}
+
+/// PSNR in decibels for 8-bit samples.
+fn psnr(mean_squared_error: f64) -> f64 {
+    if mean_squared_error == 0.0 {
+        return f64::INFINITY;
+    }
+    10.0 * (255.0 * 255.0 / mean_squared_error).log10()
+}
+
+/// Mean SSIM over overlapping windows, shrunk to fit frames smaller than
+/// one window.
+fn ssim(reference: &GrayImage, distorted: &GrayImage) -> f64 {
+    let (width, height) = reference.dimensions();
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+    let window_width = SSIM_WINDOW.min(width);
+    let window_height = SSIM_WINDOW.min(height);
+    let count = f64::from(window_width * window_height);
+
+    let mut total = 0.0;
+    let mut windows = 0u64;
+    for top in (0..=height - window_height).step_by(SSIM_STRIDE as usize) {
+        for left in (0..=width - window_width).step_by(SSIM_STRIDE as usize) {
+            let (mut sum_a, mut sum_b) = (0u64, 0u64);
+            let (mut sum_aa, mut sum_bb, mut sum_ab) = (0u64, 0u64, 0u64);
+            for y in top..top + window_height {
+                for x in left..left + window_width {
+                    let a = u64::from(reference.get_pixel(x, y)[0]);
+                    let b = u64::from(distorted.get_pixel(x, y)[0]);
+                    sum_a += a;
+                    sum_b += b;
+                    sum_aa += a * a;
+                    sum_bb += b * b;
+                    sum_ab += a * b;
+                }
+            }
+            let mean_a = sum_a as f64 / count;
+            let mean_b = sum_b as f64 / count;
+            let variance_a = sum_aa as f64 / count - mean_a * mean_a;
+            let variance_b = sum_bb as f64 / count - mean_b * mean_b;
+            let covariance = sum_ab as f64 / count - mean_a * mean_b;
+            total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1)
+                    * (variance_a + variance_b + SSIM_C2));
+            windows += 1;
+        }
+    }
+    total / windows as f64
+}
@@ -25,16 +25,50 @@
 //!     .with_batch_size(10);
 //! ```
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use ffmpeg_next::format::Pixel;
+use ffmpeg_next::{Dictionary, format::Pixel, software::scaling::Context as ScalingContext};
+use image::DynamicImage;
 
+use crate::error::UnbundleError;
+use crate::overlay::OverlayOptions;
 use crate::progress::{CancellationToken, NoOpProgress, ProgressCallback};
+use crate::stats::{ExtractionTimer, StatsCollector};
 
+#[cfg(feature = "cache")]
+use crate::frame_cache::FrameCache;
 #[cfg(feature = "hardware")]
 use crate::hardware_acceleration::HardwareAccelerationMode;
 
+/// Default cap on frame width and height, in pixels.
+///
+/// Large enough for 16K video while keeping a single RGBA frame buffer
+/// around 1 GiB at most.
+pub(crate) const DEFAULT_MAX_DIMENSION: u32 = 16_384;
+
+/// Reject dimensions beyond `(max_width, max_height)` before any frame
+/// buffer is allocated for them.
+pub(crate) fn check_dimensions(
+    width: u32,
+    height: u32,
+    (max_width, max_height): (u32, u32),
+) -> Result<(), UnbundleError> {
+    if width > max_width || height > max_height {
+        return Err(UnbundleError::DimensionsTooLarge {
+            width,
+            height,
+            max_width,
+            max_height,
+        });
+    }
+    Ok(())
+}
+
 /// Output pixel format for extracted frames.
 ///
 /// Controls the colour model and depth of the [`image::DynamicImage`] values
@@ -44,10 +78,24 @@ pub enum PixelFormat {
     /// 8-bit RGB (24 bpp). This is the default.
     #[default]
     Rgb8,
-    /// 8-bit RGBA with alpha pre-set to 255 (32 bpp).
+    /// 8-bit RGBA (32 bpp). Alpha comes from the source when it has an
+    /// alpha plane, such as ProRes 4444 or VP9 with alpha, and is 255
+    /// otherwise.
     Rgba8,
     /// 8-bit grayscale (8 bpp).
     Gray8,
+    /// 8-bit BGR (24 bpp), the channel order OpenCV's `Mat` expects.
+    ///
+    /// [`image`] has no BGR type, so frames come back as
+    /// [`DynamicImage::ImageRgb8`](image::DynamicImage::ImageRgb8) holding
+    /// the bytes in B, G, R order. Hand the raw buffer to OpenCV; encoding
+    /// the image with `image` would swap red and blue.
+    Bgr8,
+    /// 8-bit BGRA (32 bpp), with alpha as for [`Rgba8`](PixelFormat::Rgba8).
+    ///
+    /// Returned as [`DynamicImage::ImageRgba8`](image::DynamicImage::ImageRgba8)
+    /// holding B, G, R, A bytes, as with [`Bgr8`](PixelFormat::Bgr8).
+    Bgra8,
 }
 
 impl PixelFormat {
@@ -57,6 +105,191 @@ impl PixelFormat {
             PixelFormat::Rgb8 => Pixel::RGB24,
             PixelFormat::Rgba8 => Pixel::RGBA,
             PixelFormat::Gray8 => Pixel::GRAY8,
+            PixelFormat::Bgr8 => Pixel::BGR24,
+            PixelFormat::Bgra8 => Pixel::BGRA,
+        }
+    }
+
+    /// Number of bytes each pixel occupies in a packed buffer.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgb8 | PixelFormat::Bgr8 => 3,
+            PixelFormat::Rgba8 | PixelFormat::Bgra8 => 4,
+            PixelFormat::Gray8 => 1,
+        }
+    }
+
+    /// Whether the format has an alpha channel.
+    pub(crate) fn has_alpha(self) -> bool {
+        matches!(self, PixelFormat::Rgba8 | PixelFormat::Bgra8)
+    }
+}
+
+/// Parses the names the CLI accepts, case-insensitively: `rgb8` (`rgb`,
+/// `rgb24`), `rgba8` (`rgba`), `gray8` (`gray`, `grey`, `grayscale`,
+/// `greyscale`), `bgr8` (`bgr`, `bgr24`) and `bgra8` (`bgra`).
+///
+/// ```
+/// use unbundle::PixelFormat;
+///
+/// assert_eq!("RGBA".parse::<PixelFormat>().ok(), Some(PixelFormat::Rgba8));
+/// ```
+impl FromStr for PixelFormat {
+    type Err = UnbundleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "rgb8" | "rgb" | "rgb24" => Ok(PixelFormat::Rgb8),
+            "rgba8" | "rgba" => Ok(PixelFormat::Rgba8),
+            "gray8" | "gray" | "grey" | "grayscale" | "greyscale" => Ok(PixelFormat::Gray8),
+            "bgr8" | "bgr" | "bgr24" => Ok(PixelFormat::Bgr8),
+            "bgra8" | "bgra" => Ok(PixelFormat::Bgra8),
+            _ => Err(UnbundleError::InvalidOptionValue {
+                option: "pixel format",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// Resampler quality for audio extraction.
+///
+/// Selects the libswresample engine and filter settings used when decoded
+/// audio is converted to the output sample format and rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleQuality {
+    /// libswresample's built-in defaults. This is the default.
+    #[default]
+    Default,
+    /// The built-in engine with a longer filter and finer phase steps.
+    /// Slower, but with noticeably less aliasing on rate conversions.
+    High,
+    /// The SoX resampler (`resampler=soxr`) at the given bit precision
+    /// (15–33; 20 is SoX's "high", 28 its "very high"). Requires an FFmpeg
+    /// build with libsoxr; otherwise [`High`](ResampleQuality::High) is
+    /// used instead.
+    Soxr {
+        /// Bit precision of the resampling filter.
+        precision: u8,
+    },
+}
+
+impl ResampleQuality {
+    /// Build the swresample option dictionary for this quality level.
+    ///
+    /// Returns `None` for [`Default`](ResampleQuality::Default) so callers
+    /// keep using the plain constructor.
+    pub(crate) fn to_options(self) -> Option<Dictionary<'static>> {
+        match self {
+            ResampleQuality::Default => None,
+            ResampleQuality::High => Some(
+                [("filter_size", "64"), ("phase_shift", "12")]
+                    .into_iter()
+                    .collect(),
+            ),
+            ResampleQuality::Soxr { precision } => {
+                let precision = precision.clamp(15, 33).to_string();
+                Some(
+                    [("resampler", "soxr"), ("precision", precision.as_str())]
+                        .into_iter()
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Threading model for video decoders.
+///
+/// Used with [`ExtractOptions::with_decoder_threads`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadType {
+    /// Let the decoder use frame or slice threading, whichever it
+    /// supports. This is the default.
+    #[default]
+    Auto,
+    /// Decode several frames concurrently. Works for almost every codec
+    /// but delays output by roughly one frame per thread.
+    Frame,
+    /// Decode the slices of one frame concurrently. Adds no delay, but only
+    /// helps for streams encoded with several slices per frame.
+    Slice,
+}
+
+impl ThreadType {
+    /// Map to FFmpeg's `FF_THREAD_*` flags for `AVCodecContext.thread_type`.
+    pub(crate) fn to_ffmpeg_flags(self) -> i32 {
+        match self {
+            ThreadType::Auto => ffmpeg_sys_next::FF_THREAD_FRAME | ffmpeg_sys_next::FF_THREAD_SLICE,
+            ThreadType::Frame => ffmpeg_sys_next::FF_THREAD_FRAME,
+            ThreadType::Slice => ffmpeg_sys_next::FF_THREAD_SLICE,
+        }
+    }
+}
+
+/// Which frames the video decoder skips without decoding.
+///
+/// Used with [`ExtractOptions::with_frame_discard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameDiscard {
+    /// Decode every frame. This is the default.
+    #[default]
+    Default,
+    /// Skip frames no other frame is predicted from, which in most streams
+    /// are the B-frames. I- and P-frames are still decoded.
+    NonRef,
+    /// Decode keyframes only.
+    NonKey,
+}
+
+impl FrameDiscard {
+    /// Map to the `AVDiscard` value for `AVCodecContext.skip_frame`.
+    pub(crate) fn to_ffmpeg(self) -> ffmpeg_sys_next::AVDiscard {
+        match self {
+            FrameDiscard::Default => ffmpeg_sys_next::AVDiscard::AVDISCARD_DEFAULT,
+            FrameDiscard::NonRef => ffmpeg_sys_next::AVDiscard::AVDISCARD_NONREF,
+            FrameDiscard::NonKey => ffmpeg_sys_next::AVDiscard::AVDISCARD_NONKEY,
+        }
+    }
+}
+
+/// What frame extraction does when a frame fails to decode or convert.
+///
+/// Used with [`ExtractOptions::with_error_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Stop and return the error. This is the default.
+    #[default]
+    Abort,
+    /// Log the error, drop the frame and keep going. The number of dropped
+    /// frames is available from [`ExtractOptions::skipped_frames`]; it is
+    /// shared by clones of the config and is never reset.
+    SkipCorrupt,
+}
+
+/// YUV→RGB conversion matrix for decoded video.
+///
+/// By default the scaler picks the matrix from the stream's colour tags,
+/// falling back to BT.601 when the stream is untagged. HD content that is
+/// untagged or mistagged then comes out with slightly shifted colours;
+/// forcing the right matrix fixes that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMatrix {
+    /// ITU-R BT.601 (standard-definition video).
+    Bt601,
+    /// ITU-R BT.709 (HD video).
+    Bt709,
+    /// ITU-R BT.2020 non-constant luminance (UHD/HDR video).
+    Bt2020,
+}
+
+impl ColorMatrix {
+    /// Map to the corresponding swscale `SWS_CS_*` constant.
+    fn to_sws_colorspace(self) -> i32 {
+        match self {
+            ColorMatrix::Bt601 => ffmpeg_sys_next::SWS_CS_ITU601 as i32,
+            ColorMatrix::Bt709 => ffmpeg_sys_next::SWS_CS_ITU709 as i32,
+            ColorMatrix::Bt2020 => ffmpeg_sys_next::SWS_CS_BT2020 as i32,
         }
     }
 }
@@ -67,7 +300,12 @@ impl PixelFormat {
 /// dimensions are set the source resolution is used. Setting one dimension
 /// together with [`maintain_aspect_ratio`](FrameOutputOptions::maintain_aspect_ratio)
 /// computes the other dimension automatically.
+///
+/// New settings may be added in minor releases, so build it from
+/// [`FrameOutputOptions::default`] and set fields or use the builder
+/// methods rather than writing a struct literal.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct FrameOutputOptions {
     /// Output pixel format.
     pub pixel_format: PixelFormat,
@@ -78,6 +316,17 @@ pub struct FrameOutputOptions {
     /// When `true` and only one dimension is specified, the other is
     /// computed to preserve the source aspect ratio.
     pub maintain_aspect_ratio: bool,
+    /// Matrix used to convert the decoded YUV data to RGB. `None` uses the
+    /// stream's colour tags.
+    pub input_color_matrix: Option<ColorMatrix>,
+    /// When `true`, each frame's levels are stretched so its darkest and
+    /// brightest samples map to 0 and 255.
+    pub auto_levels: bool,
+    /// When `true`, frames flagged as interlaced are deinterlaced before
+    /// conversion. Progressive frames pass through untouched.
+    pub deinterlace: bool,
+    /// Timecode burned into each extracted image. `None` draws nothing.
+    pub timecode_overlay: Option<OverlayOptions>,
 }
 
 impl Default for FrameOutputOptions {
@@ -87,11 +336,127 @@ impl Default for FrameOutputOptions {
             width: None,
             height: None,
             maintain_aspect_ratio: true,
+            input_color_matrix: None,
+            auto_levels: false,
+            deinterlace: false,
+            timecode_overlay: None,
         }
     }
 }
 
 impl FrameOutputOptions {
+    /// Force the YUV→RGB matrix used for decoded frames, overriding the
+    /// stream's colour tags.
+    #[must_use]
+    pub fn with_input_color_matrix(mut self, matrix: ColorMatrix) -> Self {
+        self.input_color_matrix = Some(matrix);
+        self
+    }
+
+    /// Stretch each frame's contrast to the full 0–255 range.
+    ///
+    /// The stretch is linear and computed per frame from the darkest and
+    /// brightest colour samples (alpha is left alone), which helps with
+    /// dark or washed-out footage. Frames that are a single flat level are
+    /// returned unchanged. It applies to every image and
+    /// [`decode_into`](crate::VideoHandle::decode_into) buffer, but not to
+    /// the decoder's own frames handed out by
+    /// [`for_each_raw_frame`](crate::VideoHandle::for_each_raw_frame).
+    #[must_use]
+    pub fn with_auto_levels(mut self, enabled: bool) -> Self {
+        self.auto_levels = enabled;
+        self
+    }
+
+    /// Remove comb artefacts from interlaced sources such as DVD or
+    /// broadcast captures.
+    ///
+    /// Detection is per frame: only frames the decoder flags as interlaced
+    /// go through FFmpeg's `bwdif` filter, so enabling this on progressive
+    /// material costs nothing. Check
+    /// [`VideoMetadata::field_order`](crate::VideoMetadata::field_order)
+    /// to see what the stream declares.
+    #[must_use]
+    pub fn with_deinterlace(mut self, enabled: bool) -> Self {
+        self.deinterlace = enabled;
+        self
+    }
+
+    /// Burn each frame's `HH:MM:SS:FF` timecode into a corner of the
+    /// extracted image, for annotated reference stills.
+    ///
+    /// The timecode is derived from the frame number (itself computed from
+    /// the frame's PTS) and the stream's frame rate, and is drawn after
+    /// scaling, so it keeps the same size whatever the source resolution.
+    /// It applies to the [`DynamicImage`](image::DynamicImage)s returned by
+    /// [`VideoHandle`](crate::VideoHandle) frame methods; raw buffers and
+    /// frame iterators are left untouched.
+    #[must_use]
+    pub fn with_timecode_overlay(mut self, options: OverlayOptions) -> Self {
+        self.timecode_overlay = Some(options);
+        self
+    }
+
+    /// Draw the [`timecode_overlay`](FrameOutputOptions::timecode_overlay),
+    /// if one is set, onto an extracted image.
+    pub(crate) fn annotate(
+        &self,
+        image: &mut DynamicImage,
+        frame_number: u64,
+        frames_per_second: f64,
+    ) {
+        if let Some(overlay) = &self.timecode_overlay {
+            overlay.draw(image, frame_number, frames_per_second);
+        }
+    }
+
+    /// Apply [`input_color_matrix`](FrameOutputOptions::input_color_matrix)
+    /// to a freshly created scaler.
+    ///
+    /// Only the source matrix is replaced; the source and destination
+    /// ranges the scaler derived from the formats are kept.
+    pub(crate) fn apply_color_matrix(&self, scaler: &mut ScalingContext) {
+        let Some(matrix) = self.input_color_matrix else {
+            return;
+        };
+
+        // SAFETY: the scaler context is valid for the lifetime of `scaler`.
+        // `sws_getColorspaceDetails` only writes to the provided locals, and
+        // `sws_getCoefficients` returns a pointer to a static table.
+        unsafe {
+            let context = scaler.as_mut_ptr();
+            let mut inverse_table: *mut i32 = std::ptr::null_mut();
+            let mut table: *mut i32 = std::ptr::null_mut();
+            let (mut source_range, mut destination_range) = (0, 0);
+            let (mut brightness, mut contrast, mut saturation) = (0, 0, 0);
+            let result = ffmpeg_sys_next::sws_getColorspaceDetails(
+                context,
+                &mut inverse_table,
+                &mut source_range,
+                &mut table,
+                &mut destination_range,
+                &mut brightness,
+                &mut contrast,
+                &mut saturation,
+            );
+            if result < 0 {
+                log::debug!("Scaler does not support colour details; ignoring {matrix:?}");
+                return;
+            }
+
+            ffmpeg_sys_next::sws_setColorspaceDetails(
+                context,
+                ffmpeg_sys_next::sws_getCoefficients(matrix.to_sws_colorspace()),
+                source_range,
+                table,
+                destination_range,
+                brightness,
+                contrast,
+                saturation,
+            );
+        }
+    }
+
     /// Resolve the final output dimensions given the source size.
     ///
     /// Returns `(width, height)`.
@@ -138,6 +503,54 @@ pub struct ExtractOptions {
     /// Hardware acceleration mode (only used when `hardware` feature is enabled).
     #[cfg(feature = "hardware")]
     pub(crate) hardware_acceleration: HardwareAccelerationMode,
+    /// Decode-time downscale level (0 = full resolution).
+    pub(crate) lowres: u8,
+    /// Video decoder thread count (0 = one per CPU core).
+    pub(crate) decoder_threads: usize,
+    /// Video decoder threading model.
+    pub(crate) decoder_thread_type: ThreadType,
+    /// Frames the video decoder skips without decoding.
+    pub(crate) frame_discard: FrameDiscard,
+    /// Resampler quality used by audio extraction.
+    pub(crate) resample_quality: ResampleQuality,
+    /// Channel mixing weights applied during audio extraction, one row
+    /// per output channel.
+    pub(crate) downmix_matrix: Option<Vec<Vec<f64>>>,
+    /// Container tags written into saved or extracted audio.
+    pub(crate) tags: HashMap<String, String>,
+    /// Largest accepted `(width, height)` for source and output frames.
+    pub(crate) max_dimensions: (u32, u32),
+    /// First frame number to process; earlier frames are skipped.
+    pub(crate) resume_from: u64,
+    /// Decode frame ranges forward from the current position, never seeking.
+    pub(crate) no_seek: bool,
+    /// Decode only every nth keyframe packet during interval extraction.
+    pub(crate) packet_stride: Option<u64>,
+    /// Hold back frames so range handlers see them in presentation order.
+    pub(crate) reorder_frames: bool,
+    /// Wall-clock time a frame extraction may run before it stops early.
+    pub(crate) time_budget: Option<Duration>,
+    /// Whether frame extraction aborts on or skips corrupt frames.
+    pub(crate) error_policy: ErrorPolicy,
+    /// Frames dropped under [`ErrorPolicy::SkipCorrupt`], shared by clones.
+    pub(crate) skipped_frames: Arc<AtomicU64>,
+    /// Decode throughput counters. `None` records nothing.
+    pub(crate) stats: Option<Arc<StatsCollector>>,
+    /// Write file outputs to a temporary sibling and rename on success.
+    pub(crate) atomic_write: bool,
+    /// Worker thread count for parallel extraction. `None` uses the global
+    /// rayon pool (only used when `rayon` feature is enabled).
+    #[cfg(feature = "rayon")]
+    pub(crate) parallel_threads: Option<usize>,
+    /// Frames buffered ahead of the consumer by a
+    /// [`FrameStream`](crate::FrameStream). `None` uses the stream default
+    /// (only used when `async` feature is enabled).
+    #[cfg(feature = "async")]
+    pub(crate) stream_buffer_size: Option<usize>,
+    /// Cache consulted and filled by single-frame extraction (only used
+    /// when `cache` feature is enabled).
+    #[cfg(feature = "cache")]
+    pub(crate) frame_cache: Option<Arc<FrameCache>>,
 }
 
 impl Debug for ExtractOptions {
@@ -145,6 +558,7 @@ impl Debug for ExtractOptions {
         f.debug_struct("ExtractOptions")
             .field("has_progress", &true)
             .field("has_cancellation", &self.cancellation.is_some())
+            .field("has_stats", &self.stats.is_some())
             .field("batch_size", &self.batch_size)
             .finish()
     }
@@ -166,8 +580,31 @@ impl ExtractOptions {
             cancellation: None,
             batch_size: 1,
             frame_output: FrameOutputOptions::default(),
+            lowres: 0,
+            decoder_threads: 0,
+            decoder_thread_type: ThreadType::Auto,
+            frame_discard: FrameDiscard::Default,
+            resample_quality: ResampleQuality::Default,
+            downmix_matrix: None,
+            tags: HashMap::new(),
+            max_dimensions: (DEFAULT_MAX_DIMENSION, DEFAULT_MAX_DIMENSION),
+            resume_from: 0,
+            no_seek: false,
+            packet_stride: None,
+            reorder_frames: true,
+            time_budget: None,
+            error_policy: ErrorPolicy::Abort,
+            skipped_frames: Arc::new(AtomicU64::new(0)),
+            stats: None,
+            atomic_write: false,
             #[cfg(feature = "hardware")]
             hardware_acceleration: HardwareAccelerationMode::Auto,
+            #[cfg(feature = "rayon")]
+            parallel_threads: None,
+            #[cfg(feature = "async")]
+            stream_buffer_size: None,
+            #[cfg(feature = "cache")]
+            frame_cache: None,
         }
     }
 
@@ -228,6 +665,42 @@ impl ExtractOptions {
         self
     }
 
+    /// Force the YUV→RGB matrix used for extracted frames.
+    ///
+    /// See [`ColorMatrix`] for when this is needed.
+    #[must_use]
+    pub fn with_input_color_matrix(mut self, matrix: ColorMatrix) -> Self {
+        self.frame_output.input_color_matrix = Some(matrix);
+        self
+    }
+
+    /// Stretch extracted frames to the full brightness range.
+    ///
+    /// See [`FrameOutputOptions::with_auto_levels`].
+    #[must_use]
+    pub fn with_auto_levels(mut self, enabled: bool) -> Self {
+        self.frame_output.auto_levels = enabled;
+        self
+    }
+
+    /// Deinterlace extracted frames that are flagged as interlaced.
+    ///
+    /// See [`FrameOutputOptions::with_deinterlace`].
+    #[must_use]
+    pub fn with_deinterlace(mut self, enabled: bool) -> Self {
+        self.frame_output.deinterlace = enabled;
+        self
+    }
+
+    /// Burn the timecode into extracted frames.
+    ///
+    /// See [`FrameOutputOptions::with_timecode_overlay`].
+    #[must_use]
+    pub fn with_timecode_overlay(mut self, options: OverlayOptions) -> Self {
+        self.frame_output.timecode_overlay = Some(options);
+        self
+    }
+
     /// Set the complete frame output configuration.
     #[must_use]
     pub fn with_frame_output(mut self, config: FrameOutputOptions) -> Self {
@@ -235,6 +708,483 @@ impl ExtractOptions {
         self
     }
 
+    /// Downscale video while decoding, using the decoder's `lowres` option.
+    ///
+    /// Each level halves both dimensions: 1 decodes at 1/2 size, 2 at 1/4,
+    /// and 3 at 1/8. Because the decoder skips the work for discarded
+    /// detail, this can be an order of magnitude faster than decoding at
+    /// full size and scaling afterwards, which makes it well suited to
+    /// thumbnailing very large footage.
+    ///
+    /// Only a few codecs support it (notably MJPEG and some older
+    /// DCT-based codecs). The level is clamped to what the stream's
+    /// decoder reports, so it is a no-op for H.264, HEVC, AV1 and most
+    /// other modern codecs. Quality is lower than a proper scale-down
+    /// since high-frequency detail is simply dropped. Values above 3 are
+    /// clamped to 3. Defaults to 0 (full resolution).
+    ///
+    /// When no explicit [`with_resolution`](ExtractOptions::with_resolution)
+    /// is set, frames are returned at the reduced size.
+    #[must_use]
+    pub fn with_lowres(mut self, level: u8) -> Self {
+        self.lowres = level.min(3);
+        self
+    }
+
+    /// Set how many threads the video decoder uses, and how it splits the
+    /// work between them.
+    ///
+    /// A `count` of 0 lets FFmpeg start one thread per CPU core; 1 decodes
+    /// on the calling thread only. Defaults to `(0, ThreadType::Auto)`,
+    /// which is what makes high-resolution footage decode at a usable
+    /// speed. Lower the count when running many extractions side by side
+    /// (for instance with `frames_parallel`), where per-decoder threads
+    /// only oversubscribe the CPU.
+    #[must_use]
+    pub fn with_decoder_threads(mut self, count: usize, thread_type: ThreadType) -> Self {
+        self.decoder_threads = count;
+        self.decoder_thread_type = thread_type;
+        self
+    }
+
+    /// The video decoder thread count and threading model, as set by
+    /// [`with_decoder_threads`](ExtractOptions::with_decoder_threads).
+    pub fn decoder_threads(&self) -> (usize, ThreadType) {
+        (self.decoder_threads, self.decoder_thread_type)
+    }
+
+    /// Have the video decoder skip non-reference or non-key frames.
+    ///
+    /// Skipped frames are dropped by the decoder before any decoding work,
+    /// which makes scrubbing previews and scene scans several times faster
+    /// on streams with many B-frames. Frames keep the numbers their
+    /// timestamps give them, so skipped numbers are simply missing: range
+    /// and interval extraction hand fewer frames to the caller than the
+    /// range names, and a single-frame lookup returns the first kept frame
+    /// at or after the requested one. Defaults to [`FrameDiscard::Default`].
+    ///
+    /// Only calls taking `ExtractOptions` apply it. Lazy iterators from
+    /// [`frame_iter`](crate::VideoHandle::frame_iter),
+    /// [`frame_iter_with_options`](crate::VideoHandle::frame_iter_with_options)
+    /// and [`frame_iter_with_metadata`](crate::VideoHandle::frame_iter_with_metadata)
+    /// always decode every frame, as does scene detection.
+    #[must_use]
+    pub fn with_frame_discard(mut self, discard: FrameDiscard) -> Self {
+        self.frame_discard = discard;
+        self
+    }
+
+    /// Limit the frame dimensions video extraction will accept.
+    ///
+    /// Frames whose source or requested output size exceeds either limit
+    /// are rejected with
+    /// [`UnbundleError::DimensionsTooLarge`](crate::UnbundleError::DimensionsTooLarge)
+    /// before any frame buffer is allocated, so a malformed or hostile file
+    /// claiming enormous dimensions cannot exhaust memory. Zero is clamped
+    /// to one. Defaults to 16384×16384.
+    #[must_use]
+    pub fn with_max_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.max_dimensions = (width.max(1), height.max(1));
+        self
+    }
+
+    /// Skip every frame numbered below `frame_number`.
+    ///
+    /// Lets a long extraction pick up where an interrupted run stopped
+    /// instead of starting over. Frame-range and frame-list extraction
+    /// seek straight to the resume point and never hand earlier frames to
+    /// the handler, so passing the same [`FrameRange`](crate::FrameRange)
+    /// with a resume point yields exactly the frames the first run did not
+    /// finish. A range that ends before the resume point yields nothing.
+    /// [`VideoHandle::frames_parallel`](crate::VideoHandle::frames_parallel)
+    /// honours it too. The lazy iterators such as
+    /// [`VideoHandle::frame_iter`](crate::VideoHandle::frame_iter) take no
+    /// `ExtractOptions`; start their range at the resume point instead.
+    ///
+    /// To know where to resume, persist
+    /// [`ProgressInfo::current_frame`](crate::ProgressInfo::current_frame)
+    /// from a progress callback: the tracker reports a frame only after its
+    /// handler has returned, so the reported frame is the last one fully
+    /// processed. Defaults to 0 (process everything).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use unbundle::{
+    ///     ExtractOptions, FrameRange, MediaFile, ProgressCallback, ProgressInfo, UnbundleError,
+    /// };
+    ///
+    /// struct Checkpoint(AtomicU64);
+    /// impl ProgressCallback for Checkpoint {
+    ///     fn on_progress(&self, info: &ProgressInfo) {
+    ///         if let Some(frame) = info.current_frame {
+    ///             // Write this somewhere durable in a real application.
+    ///             self.0.store(frame + 1, Ordering::Relaxed);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let checkpoint = Arc::new(Checkpoint(AtomicU64::new(0)));
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = ExtractOptions::new()
+    ///     .with_progress(checkpoint.clone())
+    ///     .with_resume_from(checkpoint.0.load(Ordering::Relaxed));
+    /// unbundler
+    ///     .video()
+    ///     .for_each_frame_with_options(FrameRange::Range(0, 999), &config, |number, image| {
+    ///         image.save(format!("frame_{number:05}.png"))?;
+    ///         Ok(())
+    ///     })?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_resume_from(mut self, frame_number: u64) -> Self {
+        self.resume_from = frame_number;
+        self
+    }
+
+    /// Extract frame ranges by decoding forward instead of seeking.
+    ///
+    /// Range, interval and specific-frame extraction normally seek near the
+    /// first requested frame. In linear mode they decode from the current
+    /// read position instead, which is the only option for pipes, stdin and
+    /// live streams. [`FrameRange::Interval`](crate::FrameRange::Interval)
+    /// then decodes to the end of the input and keeps every nth frame
+    /// without needing a frame count.
+    ///
+    /// Linear mode is enabled automatically when
+    /// [`MediaFile::is_seekable`](crate::MediaFile::is_seekable) is `false`.
+    /// Frames before the current position cannot be revisited, so requests
+    /// for them are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open_url("pipe:0")?;
+    /// let config = ExtractOptions::new().with_no_seek(true);
+    /// unbundler.video().for_each_frame_with_options(
+    ///     FrameRange::Interval(30),
+    ///     &config,
+    ///     |number, image| {
+    ///         image.save(format!("frame_{number:05}.png"))?;
+    ///         Ok(())
+    ///     },
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_no_seek(mut self, no_seek: bool) -> Self {
+        self.no_seek = no_seek;
+        self
+    }
+
+    /// Sample interval extraction from keyframe packets only, decoding
+    /// every `stride`th one.
+    ///
+    /// [`FrameRange::Interval`](crate::FrameRange::Interval) normally seeks
+    /// to and decodes each requested frame exactly. With a packet stride it
+    /// instead reads the file once, tells the demuxer to drop non-keyframe
+    /// packets (`AVDISCARD_NONKEY`), and sends only every `stride`th
+    /// keyframe to the decoder, skipping any that land less than the
+    /// interval after the previous frame. Nothing but intra frames is ever
+    /// decoded, so a rough set of thumbnails from a multi-hour file takes
+    /// seconds.
+    ///
+    /// The frames are approximately spaced: each one is a keyframe, and the
+    /// frame numbers handed to the caller are those keyframes' own numbers,
+    /// not multiples of the interval. How close they come depends on the
+    /// encoder's GOP length. A `stride` of 0 is treated as 1 (every
+    /// keyframe). Other frame ranges are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("four_hours.mkv")?;
+    /// let config = ExtractOptions::new().with_packet_stride(4);
+    /// unbundler.video().for_each_frame_with_options(
+    ///     FrameRange::Interval(1),
+    ///     &config,
+    ///     |number, image| {
+    ///         image.thumbnail(320, 180).save(format!("rough_{number:07}.jpg"))?;
+    ///         Ok(())
+    ///     },
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_packet_stride(mut self, stride: u64) -> Self {
+        self.packet_stride = Some(stride.max(1));
+        self
+    }
+
+    /// Guarantee that range extraction hands frames over in presentation
+    /// order (default: `true`).
+    ///
+    /// Decoders return frames in presentation order for well-formed
+    /// streams, but files with B-frames and missing or damaged timestamps
+    /// can yield frames whose timestamps step backwards. With reordering
+    /// enabled, range extraction holds up to the decoder's reorder depth
+    /// (the number of frames B-frames can be delayed, usually 0 to 3) of
+    /// converted images and releases them sorted by PTS; a frame that still
+    /// arrives after a later one has been released, or that repeats a frame
+    /// number, is dropped. Disabling it passes frames through as the
+    /// decoder produces them, saving the buffered images.
+    ///
+    /// Reordering applies where a range is decoded in one forward pass:
+    /// [`FrameRange::Range`](crate::FrameRange::Range),
+    /// [`FrameRange::TimeRange`](crate::FrameRange::TimeRange), and
+    /// [`FrameRange::Interval`](crate::FrameRange::Interval) in linear mode,
+    /// with or without [`FrameMetadata`](crate::FrameMetadata). Lists of
+    /// individual frames (specific numbers, seeking intervals, keyframes,
+    /// segments) are matched one target at a time and do not use it, nor do
+    /// raw-frame callbacks, lazy iterators, parallel extraction or
+    /// [`with_packet_stride`](ExtractOptions::with_packet_stride).
+    #[must_use]
+    pub fn with_frame_reordering(mut self, enabled: bool) -> Self {
+        self.reorder_frames = enabled;
+        self
+    }
+
+    /// Stop frame extraction once `budget` of wall-clock time has passed.
+    ///
+    /// The clock starts when an extraction call begins decoding. When it
+    /// runs out the call returns `Ok` with the frames produced so far,
+    /// which suits best-effort work such as thumbnailing in a request
+    /// handler. Use a [`CancellationToken`] instead when stopping early
+    /// should be reported as an error.
+    ///
+    /// [`VideoHandle::frames_parallel`](crate::VideoHandle::frames_parallel)
+    /// shares one budget between its workers, so chunks not started in time
+    /// are left out of the result. The lazy iterators such as
+    /// [`VideoHandle::frame_iter`](crate::VideoHandle::frame_iter) take no
+    /// `ExtractOptions`; stop iterating when the time is up instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = ExtractOptions::new().with_time_budget(Duration::from_secs(2));
+    /// let frames = unbundler
+    ///     .video()
+    ///     .frames_with_options(FrameRange::Interval(100), &config)?;
+    /// println!("Decoded {} frames in time", frames.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Choose whether frame extraction stops at the first frame that fails
+    /// to decode or convert, or skips it.
+    ///
+    /// With [`ErrorPolicy::SkipCorrupt`], packets the decoder rejects and
+    /// frames that fail to scale or convert are logged as warnings and
+    /// left out of the result, which suits recovering what is left of a
+    /// damaged file. Cancellation is still reported as an error. Defaults
+    /// to [`ErrorPolicy::Abort`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{ErrorPolicy, ExtractOptions, FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("damaged.mp4")?;
+    /// let config = ExtractOptions::new().with_error_policy(ErrorPolicy::SkipCorrupt);
+    /// let frames = unbundler
+    ///     .video()
+    ///     .frames_with_options(FrameRange::Range(0, 999), &config)?;
+    /// println!("Recovered {} frames, skipped {}", frames.len(), config.skipped_frames());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Number of frames skipped under [`ErrorPolicy::SkipCorrupt`].
+    ///
+    /// The count accumulates over every extraction run with this config
+    /// or any clone of it, so use a fresh config to count a single call.
+    pub fn skipped_frames(&self) -> u64 {
+        self.skipped_frames.load(Ordering::Relaxed)
+    }
+
+    /// Record decode throughput in `collector`.
+    ///
+    /// Video frame extraction counts every frame the decoder produces and
+    /// every frame it scales into an output, and adds each call's wall time,
+    /// so [`StatsCollector::snapshot`] can report the decode rate achieved.
+    /// Only measured calls count: range and single-frame extraction are,
+    /// while helpers such as storyboards decode without being counted.
+    /// Run the same extraction with hardware and software decoding against
+    /// separate collectors to compare the two.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    ///
+    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, StatsCollector, UnbundleError};
+    ///
+    /// let stats = Arc::new(StatsCollector::new());
+    /// let config = ExtractOptions::new().with_stats(stats.clone());
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler
+    ///     .video()
+    ///     .frames_with_options(FrameRange::Interval(30), &config)?;
+    /// println!("{:.1} fps", stats.snapshot().average_decode_fps);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_stats(mut self, collector: Arc<StatsCollector>) -> Self {
+        self.stats = Some(collector);
+        self
+    }
+
+    /// Write output files atomically.
+    ///
+    /// When enabled, methods that write a file and accept these options
+    /// (audio [`save_with_options`](crate::AudioHandle::save_with_options),
+    /// the `stream_copy*_with_options` methods and
+    /// [`save_frame_with_options`](crate::VideoHandle::save_frame_with_options))
+    /// write to a hidden temporary file next to the destination and rename
+    /// it over the destination once it is complete. A failed, cancelled or
+    /// interrupted write leaves any existing file at the destination
+    /// untouched instead of truncating it, so directory watchers only ever
+    /// see finished outputs. A crash can still leave a
+    /// `.<name>.partial-*` file behind.
+    ///
+    /// Defaults to `false`.
+    #[must_use]
+    pub fn with_atomic_write(mut self, atomic: bool) -> Self {
+        self.atomic_write = atomic;
+        self
+    }
+
+    /// Count a frame received from the decoder.
+    pub(crate) fn record_decoded(&self) {
+        if let Some(stats) = &self.stats {
+            stats.record_decoded();
+        }
+    }
+
+    /// Count a frame scaled into an output image or buffer.
+    pub(crate) fn record_scaled(&self) {
+        if let Some(stats) = &self.stats {
+            stats.record_scaled();
+        }
+    }
+
+    /// Start timing an extraction call; the time is recorded when the
+    /// returned guard is dropped.
+    pub(crate) fn start_timer(&self) -> Option<ExtractionTimer<'_>> {
+        self.stats.as_deref().map(ExtractionTimer::start)
+    }
+
+    /// Set the resampler quality used when extracting audio.
+    ///
+    /// Affects sample format and rate conversion in
+    /// [`AudioHandle`](crate::AudioHandle) extraction. Higher settings cost
+    /// extra CPU time, which is worth it for mastering or archival copies.
+    /// Defaults to [`ResampleQuality::Default`].
+    #[must_use]
+    pub fn with_resample_quality(mut self, quality: ResampleQuality) -> Self {
+        self.resample_quality = quality;
+        self
+    }
+
+    /// Remix audio channels with a custom matrix during extraction.
+    ///
+    /// Each row describes one output channel as a weighted sum of the
+    /// source channels, so `coefficients[out][in]` is the gain applied to
+    /// input channel `in` in output channel `out`. The output takes
+    /// FFmpeg's default layout for the number of rows. Weights are used as
+    /// given, without normalisation, so rows summing past 1.0 can clip.
+    ///
+    /// Applies to [`AudioHandle`](crate::AudioHandle) extraction to memory
+    /// and to files.
+    /// [`extract_channel_with_options`](crate::AudioHandle::extract_channel_with_options)
+    /// selects its channel first and then applies the matrix to that mono
+    /// signal, so each row needs a single weight.
+    ///
+    /// Extraction fails with
+    /// [`UnbundleError::InvalidDownmixMatrix`](crate::UnbundleError::InvalidDownmixMatrix)
+    /// when the matrix has no rows, more than 64 rows, a row whose length
+    /// differs from the channel count it is applied to, or a non-finite
+    /// weight.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{AudioFormat, ExtractOptions, MediaFile, UnbundleError};
+    ///
+    /// // 5.1 (FL FR FC LFE BL BR) to stereo, keeping dialogue prominent.
+    /// let config = ExtractOptions::new().with_downmix_matrix(vec![
+    ///     vec![0.5, 0.0, 0.6, 0.1, 0.35, 0.0],
+    ///     vec![0.0, 0.5, 0.6, 0.1, 0.0, 0.35],
+    /// ]);
+    /// let mut unbundler = MediaFile::open("surround.mkv")?;
+    /// let stereo = unbundler
+    ///     .audio()
+    ///     .extract_with_options(AudioFormat::Wav, &config)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_downmix_matrix(mut self, coefficients: Vec<Vec<f64>>) -> Self {
+        self.downmix_matrix = Some(coefficients);
+        self
+    }
+
+    /// Write container tags such as `title`, `artist` and `album` into
+    /// audio output.
+    ///
+    /// The tags are handed to the muxer before it writes the header, so
+    /// each format stores them its own way: ID3v2 frames in MP3, Vorbis
+    /// comments in FLAC and `INFO` chunk entries in WAV. Keys a format has
+    /// no field for are dropped by the muxer, and
+    /// [`AudioFormat::Aac`](crate::AudioFormat::Aac)
+    /// output, being raw ADTS, stores none at all. Applies to every
+    /// [`AudioHandle`](crate::AudioHandle) method that writes a container,
+    /// to files and to memory, including stream copies.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use unbundle::{AudioFormat, ExtractOptions, MediaFile, UnbundleError};
+    ///
+    /// let tags = HashMap::from([
+    ///     ("title".to_string(), "Opening Theme".to_string()),
+    ///     ("artist".to_string(), "The Composers".to_string()),
+    /// ]);
+    /// let config = ExtractOptions::new().with_tags(tags);
+    /// let mut unbundler = MediaFile::open("episode.mkv")?;
+    /// unbundler
+    ///     .audio()
+    ///     .save_with_options("theme.mp3", AudioFormat::Mp3, &config)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[must_use]
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
     /// Set the hardware acceleration mode.
     ///
     /// Only available when the `hardware` feature is enabled.
@@ -246,10 +1196,97 @@ impl ExtractOptions {
         self
     }
 
+    /// Bound the number of worker threads used by
+    /// [`frames_parallel`](crate::VideoHandle::frames_parallel).
+    ///
+    /// By default parallel extraction runs on rayon's global pool, which is
+    /// sized to every CPU on the machine. Setting a thread count builds a
+    /// dedicated pool of that size for each call instead, so concurrent
+    /// requests in a server do not oversubscribe the CPU. Zero is clamped
+    /// to one.
+    ///
+    /// Only available when the `rayon` feature is enabled.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn with_parallel_threads(mut self, threads: usize) -> Self {
+        self.parallel_threads = Some(threads.max(1));
+        self
+    }
+
+    /// Set how many decoded frames a
+    /// [`FrameStream`](crate::FrameStream) may buffer ahead of its consumer.
+    ///
+    /// The background decoder blocks once this many frames are waiting, so
+    /// a slow consumer bounds memory use instead of letting frames pile up.
+    /// Lower values suit large frames (a 4K RGB frame is about 25 MB);
+    /// higher values smooth out bursty consumers. Zero is clamped to one.
+    /// Defaults to 8.
+    ///
+    /// Only available when the `async` feature is enabled.
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub fn with_stream_buffer_size(mut self, frames: usize) -> Self {
+        self.stream_buffer_size = Some(frames.max(1));
+        self
+    }
+
+    /// Serve repeated single-frame requests from `cache`.
+    ///
+    /// [`frame_with_options`](crate::VideoHandle::frame_with_options) and
+    /// the methods that resolve to it look the frame up first and store it
+    /// after a miss. Only calls given these options are cached; methods
+    /// without options, such as [`frame`](crate::VideoHandle::frame),
+    /// always decode. Share one cache between the options of every request,
+    /// even across different [`MediaFile`](crate::MediaFile)s; see
+    /// [`FrameCache`] for how frames are identified.
+    ///
+    /// Only available when the `cache` feature is enabled.
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub fn with_frame_cache(mut self, cache: Arc<FrameCache>) -> Self {
+        self.frame_cache = Some(cache);
+        self
+    }
+
+    /// Check `width`×`height` against the limit set by
+    /// [`with_max_dimensions`](ExtractOptions::with_max_dimensions).
+    pub(crate) fn check_dimensions(&self, width: u32, height: u32) -> Result<(), UnbundleError> {
+        check_dimensions(width, height, self.max_dimensions)
+    }
+
     /// Returns `true` if cancellation has been requested.
     pub(crate) fn is_cancelled(&self) -> bool {
         self.cancellation
             .as_ref()
             .is_some_and(|token| token.is_cancelled())
     }
+
+    /// The instant an extraction starting now must stop by, if a time
+    /// budget is set.
+    pub(crate) fn budget_deadline(&self) -> Option<Instant> {
+        self.time_budget.map(|budget| Instant::now() + budget)
+    }
+
+    /// Apply the error policy to a decode or conversion step for
+    /// `frame_number` (`None` for a packet not yet tied to a frame).
+    /// `Ok(None)` means the frame was skipped.
+    pub(crate) fn skip_corrupt_frame<T>(
+        &self,
+        frame_number: Option<u64>,
+        result: Result<T, UnbundleError>,
+    ) -> Result<Option<T>, UnbundleError> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(UnbundleError::Cancelled) => Err(UnbundleError::Cancelled),
+            Err(error) if self.error_policy == ErrorPolicy::SkipCorrupt => {
+                match frame_number {
+                    Some(number) => log::warn!("Skipping corrupt frame {number}: {error}"),
+                    None => log::warn!("Skipping undecodable packet: {error}"),
+                }
+                self.skipped_frames.fetch_add(1, Ordering::Relaxed);
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
 }
@@ -3,9 +3,10 @@
 //! Helpers for pixel-data copying, timestamp conversion, and other shared
 //! logic that does not belong in any single public module.
 
-use std::time::Duration;
+use std::{ffi::CStr, time::Duration};
 
 use ffmpeg_next::{Rational, frame::Video as VideoFrame};
+use ffmpeg_sys_next::AVChannelLayout;
 
 /// Copy pixel data from an FFmpeg video frame into a tightly-packed buffer.
 ///
@@ -17,19 +18,35 @@ pub fn frame_to_buffer(
     height: u32,
     bytes_per_pixel: usize,
 ) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    frame_into_buffer(video_frame, width, height, bytes_per_pixel, &mut buffer);
+    buffer
+}
+
+/// Copy pixel data from an FFmpeg video frame into a caller-owned buffer.
+///
+/// Like [`frame_to_buffer`] but reuses `buffer`'s allocation: it is cleared
+/// and refilled, growing only when the frame is larger than its capacity.
+pub fn frame_into_buffer(
+    video_frame: &VideoFrame,
+    width: u32,
+    height: u32,
+    bytes_per_pixel: usize,
+    buffer: &mut Vec<u8>,
+) {
     let stride = video_frame.stride(0);
     let expected_stride = (width as usize) * bytes_per_pixel;
     let data = video_frame.data(0);
 
+    buffer.clear();
     if stride == expected_stride {
-        data[..expected_stride * (height as usize)].to_vec()
+        buffer.extend_from_slice(&data[..expected_stride * (height as usize)]);
     } else {
-        let mut buffer = Vec::with_capacity(expected_stride * (height as usize));
+        buffer.reserve(expected_stride * (height as usize));
         for row in 0..(height as usize) {
             let row_start = row * stride;
             buffer.extend_from_slice(&data[row_start..row_start + expected_stride]);
         }
-        buffer
     }
 }
 
@@ -78,3 +95,26 @@ pub fn frame_number_to_seek_timestamp(frame_number: u64, frames_per_second: f64)
 pub fn duration_to_seek_timestamp(duration: Duration) -> i64 {
     duration.as_micros() as i64
 }
+
+/// FFmpeg's textual name for a channel layout (for example `5.1(side)`),
+/// as accepted by the `abuffer` filter.
+pub fn channel_layout_name(layout: &AVChannelLayout) -> String {
+    let mut buffer = [0 as std::ffi::c_char; 128];
+    // SAFETY: the buffer outlives the call and its length is passed along;
+    // FFmpeg always NUL-terminates what it writes.
+    unsafe {
+        ffmpeg_sys_next::av_channel_layout_describe(layout, buffer.as_mut_ptr(), buffer.len());
+        CStr::from_ptr(buffer.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Convert a linear sample amplitude to dBFS; silence is negative infinity.
+pub fn amplitude_to_dbfs(amplitude: f64) -> f64 {
+    if amplitude > 0.0 {
+        20.0 * amplitude.log10()
+    } else {
+        f64::NEG_INFINITY
+    }
+}
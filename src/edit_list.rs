@@ -0,0 +1,180 @@
+//! MP4/MOV edit list (`elst`) detection.
+//!
+//! FFmpeg's mov demuxer applies edit lists to packet timestamps but does
+//! not say whether a track had one. This module walks the `moov` box of a
+//! local file to find each track's edit list and the start offset it
+//! implies, so metadata and validation can report it.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Largest `moov` box read into memory. Real files stay far below this;
+/// anything larger is treated as having no edit lists.
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Presentation start offset implied by one track's edit list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct EditList {
+    /// Seconds the track's presentation is shifted on the movie timeline:
+    /// positive when leading empty edits delay it, negative when the first
+    /// edit skips media at the start (typically encoder delay).
+    pub(crate) offset_seconds: f64,
+}
+
+/// Read the edit lists of every track in the MP4/MOV file at `path`, keyed
+/// by track ID (FFmpeg's stream `id` for this demuxer).
+///
+/// Returns an empty map when the file cannot be read or has no `moov` box;
+/// edit lists are advisory, so I/O problems are logged rather than raised.
+pub(crate) fn read_edit_lists(path: &Path) -> HashMap<i32, EditList> {
+    match read_moov(path) {
+        Ok(Some(moov)) => parse_moov(&moov),
+        Ok(None) => HashMap::new(),
+        Err(error) => {
+            log::debug!("Could not scan {} for edit lists: {error}", path.display());
+            HashMap::new()
+        }
+    }
+}
+
+/// Find the top-level `moov` box and return its payload.
+fn read_moov(path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    let mut file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut position = 0;
+
+    while file_size.saturating_sub(position) >= 8 {
+        file.seek(SeekFrom::Start(position))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+        let mut size = u64::from(read_u32(&header, 0).unwrap_or_default());
+        let mut header_size = 8;
+        if size == 1 {
+            let mut large_size = [0u8; 8];
+            file.read_exact(&mut large_size)?;
+            size = u64::from_be_bytes(large_size);
+            header_size = 16;
+        } else if size == 0 {
+            size = file_size - position;
+        }
+        if size < header_size {
+            return Ok(None);
+        }
+
+        if &header[4..8] == b"moov" {
+            let payload_size = size - header_size;
+            if payload_size > MAX_MOOV_SIZE {
+                return Ok(None);
+            }
+            let mut payload = vec![0u8; payload_size as usize];
+            file.read_exact(&mut payload)?;
+            return Ok(Some(payload));
+        }
+        // A corrupt size could wrap the position back to the start.
+        let Some(next) = position.checked_add(size) else {
+            return Ok(None);
+        };
+        position = next;
+    }
+    Ok(None)
+}
+
+/// Iterate over the `(type, payload)` boxes directly inside `data`.
+fn child_boxes(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut rest = data;
+    std::iter::from_fn(move || {
+        if rest.len() < 8 {
+            return None;
+        }
+        let size = read_u32(rest, 0)? as usize;
+        let (header_size, size) = match size {
+            0 => (8, rest.len()),
+            1 => (16, usize::try_from(read_u64(rest, 8)?).ok()?),
+            size => (8, size),
+        };
+        if size < header_size || size > rest.len() {
+            return None;
+        }
+        let (current, next) = rest.split_at(size);
+        rest = next;
+        Some((&current[4..8], &current[header_size..]))
+    })
+}
+
+fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    child_boxes(data)
+        .find(|(box_type, _)| *box_type == kind)
+        .map(|(_, payload)| payload)
+}
+
+fn parse_moov(moov: &[u8]) -> HashMap<i32, EditList> {
+    let Some(movie_timescale) = find_box(moov, b"mvhd").and_then(header_timescale) else {
+        return HashMap::new();
+    };
+
+    child_boxes(moov)
+        .filter(|(box_type, _)| *box_type == b"trak")
+        .filter_map(|(_, trak)| {
+            let track_id = find_box(trak, b"tkhd").and_then(track_id)?;
+            let elst = find_box(trak, b"edts").and_then(|edts| find_box(edts, b"elst"))?;
+            let media_timescale = find_box(trak, b"mdia")
+                .and_then(|mdia| find_box(mdia, b"mdhd"))
+                .and_then(header_timescale)?;
+            let offset_seconds = edit_list_offset(elst, movie_timescale, media_timescale)?;
+            Some((track_id, EditList { offset_seconds }))
+        })
+        .collect()
+}
+
+/// Timescale field of an `mvhd` or `mdhd` payload.
+fn header_timescale(payload: &[u8]) -> Option<u32> {
+    let offset = if payload.first()? == &1 { 20 } else { 12 };
+    read_u32(payload, offset).filter(|timescale| *timescale > 0)
+}
+
+/// Track ID field of a `tkhd` payload.
+fn track_id(payload: &[u8]) -> Option<i32> {
+    let offset = if payload.first()? == &1 { 20 } else { 12 };
+    read_u32(payload, offset).and_then(|id| i32::try_from(id).ok())
+}
+
+/// Sum the leading empty edits (durations in the movie timescale) and
+/// subtract the media time where the first real edit starts (in the
+/// track's own timescale).
+fn edit_list_offset(elst: &[u8], movie_timescale: u32, media_timescale: u32) -> Option<f64> {
+    let version = *elst.first()?;
+    let entry_count = read_u32(elst, 4)? as usize;
+    let entry_size = if version == 1 { 20 } else { 12 };
+
+    let mut delay = 0u64;
+    for entry in 0..entry_count {
+        let start = 8 + entry * entry_size;
+        let (segment_duration, media_time) = if version == 1 {
+            (read_u64(elst, start)?, read_u64(elst, start + 8)? as i64)
+        } else {
+            (
+                u64::from(read_u32(elst, start)?),
+                i64::from(read_u32(elst, start + 4)? as i32),
+            )
+        };
+        if media_time == -1 {
+            delay += segment_duration;
+            continue;
+        }
+        let skipped = media_time as f64 / f64::from(media_timescale);
+        return Some(delay as f64 / f64::from(movie_timescale) - skipped);
+    }
+    Some(delay as f64 / f64::from(movie_timescale))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(u64::from_be_bytes(bytes.try_into().ok()?))
+}
@@ -179,10 +179,17 @@ impl VideoEncoder {
         path: P,
         frames: &[DynamicImage],
     ) -> Result<(), UnbundleError> {
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.write_frames(path.as_ref(), frames)
+            .map_err(|error| log_capture.attach(error))
+    }
+
+    /// Encode and mux `frames`, backing [`write`](VideoEncoder::write).
+    fn write_frames(&self, path: &Path, frames: &[DynamicImage]) -> Result<(), UnbundleError> {
         log::info!(
             "Writing {} frames to {:?} (codec={:?}, fps={})",
             frames.len(),
-            path.as_ref(),
+            path,
             self.config.codec,
             self.config.frames_per_second,
         );
@@ -192,8 +199,6 @@ impl VideoEncoder {
             ));
         }
 
-        let path = path.as_ref();
-
         // Determine output resolution from config or first frame.
         let first = &frames[0];
         let width = self.config.width.unwrap_or(first.width());
@@ -33,7 +33,8 @@ pub enum UnbundleError {
     #[error("Failed to open media source '{input_source}': {reason}")]
     SourceOpen {
         /// Source string that was passed to [`crate::MediaFile::open_url`] or
-        /// [`crate::MediaFile::open_source`].
+        /// [`crate::MediaFile::open_source`], or `<reader>` for
+        /// [`crate::MediaFile::open_reader`].
         input_source: String,
         /// Underlying reason the open failed.
         reason: String,
@@ -59,6 +60,19 @@ pub enum UnbundleError {
     #[error("Failed to encode audio: {0}")]
     AudioEncodeError(String),
 
+    /// A frame's source or output dimensions exceed the configured limit.
+    #[error("Frame dimensions {width}x{height} exceed the maximum of {max_width}x{max_height}")]
+    DimensionsTooLarge {
+        /// The offending width in pixels.
+        width: u32,
+        /// The offending height in pixels.
+        height: u32,
+        /// The maximum allowed width.
+        max_width: u32,
+        /// The maximum allowed height.
+        max_height: u32,
+    },
+
     /// The requested frame number exceeds the total frame count.
     #[error("Frame {frame_number} is out of range (video has {total_frames} frames)")]
     FrameOutOfRange {
@@ -72,6 +86,16 @@ pub enum UnbundleError {
     #[error("Invalid timestamp: {0:?}")]
     InvalidTimestamp(Duration),
 
+    /// The requested audio range starts at or after the end of the audio
+    /// track, so there is nothing to decode.
+    #[error("No audio in range: start ({start:?}) is past the end of the audio ({audio_end:?})")]
+    AudioRangeEmpty {
+        /// The requested start of the range.
+        start: Duration,
+        /// Where the decodable audio ends.
+        audio_end: Duration,
+    },
+
     /// A range's start value is greater than or equal to its end value.
     #[error("Invalid range: start ({start:?}) must be less than end ({end:?})")]
     InvalidRange {
@@ -81,6 +105,16 @@ pub enum UnbundleError {
         end: String,
     },
 
+    /// The operation needs to seek, but the input does not support random
+    /// access (for example a pipe or live stream).
+    #[error("Input is not seekable")]
+    NotSeekable,
+
+    /// The operation is planned against the media duration, but the input
+    /// is a live stream without one.
+    #[error("Operation needs a finite duration, but the input is a live stream")]
+    LiveStream,
+
     /// An interval or step value of zero was provided.
     #[error("Interval must be greater than zero")]
     InvalidInterval,
@@ -113,6 +147,24 @@ pub enum UnbundleError {
     #[error("Failed to decode subtitle: {0}")]
     SubtitleDecodeError(String),
 
+    /// Text was requested from an image-based subtitle stream (PGS, DVD or
+    /// DVB), which has none. Use
+    /// [`SubtitleHandle::extract_bitmaps`](crate::SubtitleHandle::extract_bitmaps)
+    /// or [`SubtitleHandle::ocr_bitmaps`](crate::SubtitleHandle::ocr_bitmaps)
+    /// instead.
+    #[error("Subtitle stream {stream_index} ({codec}) is image-based and has no text")]
+    BitmapSubtitleNotText {
+        /// The subtitle stream's index in the container.
+        stream_index: usize,
+        /// Codec name (e.g. `"hdmv_pgs_subtitle"`, `"dvd_subtitle"`).
+        codec: String,
+    },
+
+    /// A subtitle timing rescale factor or frame rate was zero, negative,
+    /// or not finite, or the factor overflowed a timestamp.
+    #[error("Invalid timing rescale factor: {0} (must be finite and positive)")]
+    InvalidRescaleFactor(f64),
+
     /// GIF encoding failed.
     #[cfg(feature = "gif")]
     #[error("GIF encoding error: {0}")]
@@ -142,6 +194,11 @@ pub enum UnbundleError {
     #[error("Loudness analysis error: {0}")]
     LoudnessError(String),
 
+    /// Mel spectrogram computation failed or was misconfigured.
+    #[cfg(feature = "spectrogram")]
+    #[error("Spectrogram error: {0}")]
+    SpectrogramError(String),
+
     /// The requested video track index is out of range.
     #[error("Video track {track_index} is out of range (file has {track_count} video tracks)")]
     VideoTrackOutOfRange {
@@ -151,13 +208,120 @@ pub enum UnbundleError {
         track_count: usize,
     },
 
+    /// The requested audio channel does not exist in the source layout.
+    #[error("Audio channel {channel_index} is out of range (stream has {channel_count} channels)")]
+    ChannelOutOfRange {
+        /// Requested zero-based channel index.
+        channel_index: usize,
+        /// Number of channels in the audio stream.
+        channel_count: u16,
+    },
+
+    /// A SMPTE timecode could not be parsed or does not exist at the
+    /// stream's frame rate.
+    #[error("Invalid timecode: {0}")]
+    InvalidTimecode(String),
+
+    /// A downmix matrix does not fit the source or target channel count.
+    #[error("Invalid downmix matrix: {0}")]
+    InvalidDownmixMatrix(String),
+
+    /// The requested scene index is past the last detected scene.
+    #[cfg(feature = "scene")]
+    #[error("Scene {scene_index} is out of range (video has {scene_count} scenes)")]
+    SceneOutOfRange {
+        /// Requested scene index.
+        scene_index: usize,
+        /// Number of scenes found by detection.
+        scene_count: usize,
+    },
+
+    /// The requested keyframe index is past the last keyframe.
+    #[error("Keyframe {keyframe_index} is out of range (video has {keyframe_count} keyframes)")]
+    KeyframeOutOfRange {
+        /// Requested keyframe index.
+        keyframe_index: usize,
+        /// Number of keyframes in the video stream.
+        keyframe_count: usize,
+    },
+
+    /// The video stream has no keyframe with a presentation timestamp to
+    /// place it on the timeline.
+    #[error("No keyframe in the video stream has a timestamp")]
+    NoTimestampedKeyframes,
+
+    /// No program with the requested ID exists in the container.
+    #[error("Program {program_id} not found")]
+    ProgramNotFound {
+        /// Requested program ID.
+        program_id: i32,
+    },
+
     /// Raw stream copy (packet-level extraction) failed.
     #[error("Stream copy error: {0}")]
     StreamCopyError(String),
 
+    /// A file passed to [`crate::MediaFile::concat`] cannot be joined to
+    /// the first one without re-encoding.
+    #[error("Cannot concatenate {path} without re-encoding: {reason}")]
+    ConcatIncompatible {
+        /// The first file whose streams differ from the first input's.
+        path: PathBuf,
+        /// The differences found, as reported by
+        /// [`crate::MediaFile::concat_compatibility`].
+        reason: String,
+    },
+
     /// FFmpeg filter graph setup or processing failed.
     #[error("Filter graph error: {0}")]
     FilterGraphError(String),
+
+    /// A string could not be parsed as an option value such as a
+    /// [`PixelFormat`](crate::PixelFormat).
+    #[error("Invalid {option}: {value:?}")]
+    InvalidOptionValue {
+        /// The kind of option being parsed (e.g. `"pixel format"`).
+        option: &'static str,
+        /// The string that was rejected.
+        value: String,
+    },
+
+    /// A dedicated worker thread pool could not be created.
+    #[cfg(feature = "rayon")]
+    #[error("Thread pool error: {0}")]
+    ThreadPoolError(String),
+}
+
+impl UnbundleError {
+    /// Append captured FFmpeg log lines to the message of an error that
+    /// carries one. Other variants are returned unchanged.
+    pub(crate) fn with_ffmpeg_log(mut self, lines: &[&str]) -> Self {
+        if lines.is_empty() {
+            return self;
+        }
+        let message = match &mut self {
+            UnbundleError::VideoDecodeError(message)
+            | UnbundleError::AudioDecodeError(message)
+            | UnbundleError::AudioEncodeError(message)
+            | UnbundleError::FfmpegError(message)
+            | UnbundleError::SubtitleDecodeError(message)
+            | UnbundleError::VideoEncodeError(message)
+            | UnbundleError::StreamCopyError(message)
+            | UnbundleError::FilterGraphError(message)
+            | UnbundleError::FileOpen {
+                reason: message, ..
+            } => message,
+            #[cfg(feature = "transcode")]
+            UnbundleError::TranscodeError(message) => message,
+            #[cfg(feature = "encode")]
+            UnbundleError::VideoWriteError(message) => message,
+            _ => return self,
+        };
+        message.push_str(" (FFmpeg: ");
+        message.push_str(&lines.join("; "));
+        message.push(')');
+        self
+    }
 }
 
 impl From<FfmpegError> for UnbundleError {
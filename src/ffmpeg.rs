@@ -26,8 +26,83 @@
 //! This controls **FFmpeg's own console output**, not the Rust-side
 //! diagnostic messages emitted via the `log` crate. To configure those,
 //! use a standard `log` subscriber such as `env_logger` or `tracing`.
+//!
+//! # Errors can carry FFmpeg's diagnostics
+//!
+//! After [`set_ffmpeg_log_capture(true)`](set_ffmpeg_log_capture), FFmpeg's
+//! warnings and errors are also captured while decoding, encoding, or
+//! muxing, whatever the log level. When the operation fails, the last few
+//! captured lines are appended to the returned [`UnbundleError`]'s message,
+//! so the reason for an opaque failure such as "Invalid data found when
+//! processing input" travels with the error.
+
+use std::collections::VecDeque;
+use std::ffi::{CStr, c_char, c_int, c_void};
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
 
 use ffmpeg_next::util::log::Level;
+use ffmpeg_sys_next::{
+    AV_LOG_WARNING, av_log_default_callback, av_log_format_line2, av_log_get_level,
+    av_log_set_callback,
+};
+
+use crate::error::UnbundleError;
+
+/// Captured FFmpeg lines kept in memory across all operations.
+const CAPTURED_LINE_LIMIT: usize = 64;
+
+/// Captured lines appended to a failed operation's error.
+const ERROR_CONTEXT_LINES: usize = 5;
+
+/// Longest single FFmpeg log line kept, including the terminator.
+const LOG_LINE_SIZE: usize = 1024;
+
+// The callback receives the C `va_list` as FFmpeg passes it, which bindgen
+// spells differently per ABI.
+#[cfg(all(target_arch = "x86_64", not(windows)))]
+type VaList = *mut ffmpeg_sys_next::__va_list_tag;
+#[cfg(not(all(target_arch = "x86_64", not(windows))))]
+type VaList = ffmpeg_sys_next::va_list;
+
+/// FFmpeg's log callback signature.
+type LogCallback = unsafe extern "C" fn(*mut c_void, c_int, *const c_char, VaList);
+
+/// Whether [`capture_callback`] is installed.
+static CAPTURE_INSTALLED: Mutex<bool> = Mutex::new(false);
+static ACTIVE_CAPTURES: AtomicUsize = AtomicUsize::new(0);
+static CAPTURED: Mutex<CapturedLog> = Mutex::new(CapturedLog {
+    next_sequence: 0,
+    lines: VecDeque::new(),
+    capturing_threads: Vec::new(),
+});
+/// Whether the next line starts a new one and so gets the
+/// `[context @ address]` prefix. FFmpeg's default callback keeps the same
+/// flag across calls, since a line may be logged in several pieces; the
+/// lock also keeps concurrent lines from interleaving on stderr.
+static PRINT_PREFIX: Mutex<c_int> = Mutex::new(1);
+
+/// Recent FFmpeg warnings and errors, numbered so an operation can pick
+/// out the lines logged since it started.
+struct CapturedLog {
+    next_sequence: u64,
+    lines: VecDeque<CapturedLine>,
+    /// Thread of every active [`LogCapture`], once per capture.
+    capturing_threads: Vec<ThreadId>,
+}
+
+struct CapturedLine {
+    sequence: u64,
+    /// The thread that logged the line, when it was running a capture.
+    /// `None` for FFmpeg's own worker threads (frame-threaded decoders in
+    /// particular), whose lines cannot be traced to one operation and so
+    /// are shown to every capture.
+    thread: Option<ThreadId>,
+    text: String,
+}
 
 /// FFmpeg internal log verbosity level.
 ///
@@ -93,6 +168,30 @@ impl FfmpegLogLevel {
     }
 }
 
+/// Parses the lowercase level names FFmpeg's `-loglevel` takes (`quiet`
+/// through `trace`, plus `warn`), case-insensitively.
+impl FromStr for FfmpegLogLevel {
+    type Err = UnbundleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "quiet" => Ok(FfmpegLogLevel::Quiet),
+            "panic" => Ok(FfmpegLogLevel::Panic),
+            "fatal" => Ok(FfmpegLogLevel::Fatal),
+            "error" => Ok(FfmpegLogLevel::Error),
+            "warning" | "warn" => Ok(FfmpegLogLevel::Warning),
+            "info" => Ok(FfmpegLogLevel::Info),
+            "verbose" => Ok(FfmpegLogLevel::Verbose),
+            "debug" => Ok(FfmpegLogLevel::Debug),
+            "trace" => Ok(FfmpegLogLevel::Trace),
+            _ => Err(UnbundleError::InvalidOptionValue {
+                option: "FFmpeg log level",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
 /// Set the FFmpeg internal log verbosity level.
 ///
 /// This controls what FFmpeg prints to stderr. It does **not** affect
@@ -128,3 +227,176 @@ pub fn get_ffmpeg_log_level() -> Option<FfmpegLogLevel> {
         .ok()
         .map(FfmpegLogLevel::from_ffmpeg_level)
 }
+
+/// Append FFmpeg's warnings and errors to the errors of failed operations.
+///
+/// When enabled, an FFmpeg log callback keeps the warnings and errors
+/// logged while a decode, encode, or mux operation runs, and the last few
+/// are appended to the message of the error the operation returns. What
+/// FFmpeg prints to stderr still follows [`set_ffmpeg_log_level`]. Off by
+/// default.
+///
+/// FFmpeg has a single, process-wide log callback and no way to read it
+/// back, so enabling capture replaces any callback installed with
+/// `av_log_set_callback`, and disabling it restores FFmpeg's default
+/// callback rather than the one it replaced.
+///
+/// Lines are matched to the thread that started the operation. Lines
+/// FFmpeg logs from its own worker threads, as frame-threaded decoders do,
+/// are attached to every operation running at the time.
+///
+/// # Example
+///
+/// ```no_run
+/// use unbundle::{MediaFile, UnbundleError};
+///
+/// unbundle::set_ffmpeg_log_capture(true);
+/// let mut unbundler = MediaFile::open("damaged.mp4")?;
+/// if let Err(error) = unbundler.video().frame(0) {
+///     // e.g. "... (FFmpeg: Invalid NAL unit size)"
+///     eprintln!("{error}");
+/// }
+/// # Ok::<(), UnbundleError>(())
+/// ```
+pub fn set_ffmpeg_log_capture(enabled: bool) {
+    let mut installed = CAPTURE_INSTALLED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if *installed == enabled {
+        return;
+    }
+    let callback: LogCallback = if enabled {
+        capture_callback
+    } else {
+        av_log_default_callback
+    };
+    // SAFETY: both callbacks match FFmpeg's signature, and
+    // `capture_callback` only touches process-wide state guarded by
+    // atomics and mutexes.
+    unsafe { av_log_set_callback(Some(callback)) };
+    *installed = enabled;
+}
+
+/// Captures FFmpeg's warnings and errors while a decode, encode, or mux
+/// operation runs, if [`set_ffmpeg_log_capture`] has enabled capture.
+///
+/// Capture stays on until the value is dropped; pass a failed operation's
+/// error through [`attach`](LogCapture::attach) to append what FFmpeg
+/// logged in the meantime on this thread or FFmpeg's worker threads.
+pub(crate) struct LogCapture {
+    start: u64,
+    thread: ThreadId,
+}
+
+impl LogCapture {
+    /// Start capturing on the current thread.
+    pub(crate) fn start() -> Self {
+        let thread = thread::current().id();
+        let mut captured = lock_captured();
+        captured.capturing_threads.push(thread);
+        ACTIVE_CAPTURES.fetch_add(1, Ordering::SeqCst);
+        Self {
+            start: captured.next_sequence,
+            thread,
+        }
+    }
+
+    /// Append the last lines FFmpeg logged since this capture started to
+    /// `error`'s message.
+    pub(crate) fn attach(&self, error: UnbundleError) -> UnbundleError {
+        let captured = lock_captured();
+        let lines: Vec<&str> = captured
+            .lines
+            .iter()
+            .filter(|line| line.sequence >= self.start)
+            .filter(|line| line.thread.is_none_or(|thread| thread == self.thread))
+            .map(|line| line.text.as_str())
+            .collect();
+        let tail = &lines[lines.len().saturating_sub(ERROR_CONTEXT_LINES)..];
+        error.with_ffmpeg_log(tail)
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        let mut captured = lock_captured();
+        if let Some(position) = captured
+            .capturing_threads
+            .iter()
+            .position(|thread| *thread == self.thread)
+        {
+            captured.capturing_threads.swap_remove(position);
+        }
+        ACTIVE_CAPTURES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// A panic while the lock was held cannot leave the ring buffer itself in
+// a broken state, so poisoning is ignored.
+fn lock_captured() -> MutexGuard<'static, CapturedLog> {
+    CAPTURED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// FFmpeg log callback. Outside a capture it defers to FFmpeg's default
+/// callback; during one it formats the line once, prints it as the default
+/// callback would, and keeps warnings and errors for [`LogCapture`].
+unsafe extern "C" fn capture_callback(
+    context: *mut c_void,
+    level: c_int,
+    format: *const c_char,
+    arguments: VaList,
+) {
+    if ACTIVE_CAPTURES.load(Ordering::SeqCst) == 0 {
+        // SAFETY: the arguments are passed through unchanged.
+        unsafe { av_log_default_callback(context, level, format, arguments) };
+        return;
+    }
+
+    let mut buffer = [0 as c_char; LOG_LINE_SIZE];
+    let line = {
+        let mut print_prefix = PRINT_PREFIX.lock().unwrap_or_else(PoisonError::into_inner);
+        // SAFETY: `buffer` is writable for its full length, and FFmpeg
+        // always NUL-terminates the line it writes into it.
+        let line = unsafe {
+            av_log_format_line2(
+                context,
+                level,
+                format,
+                arguments,
+                buffer.as_mut_ptr(),
+                LOG_LINE_SIZE as c_int,
+                &mut *print_prefix,
+            );
+            CStr::from_ptr(buffer.as_ptr())
+        }
+        .to_string_lossy();
+
+        // SAFETY: reading the global log level has no preconditions.
+        if level <= unsafe { av_log_get_level() } {
+            // Nothing sensible can be done if stderr is gone.
+            let _ = std::io::stderr().write_all(line.as_bytes());
+        }
+        line
+    };
+
+    let line = line.trim();
+    if level > AV_LOG_WARNING || line.is_empty() {
+        return;
+    }
+    let thread = thread::current().id();
+    let mut captured = lock_captured();
+    let thread = captured
+        .capturing_threads
+        .contains(&thread)
+        .then_some(thread);
+    let sequence = captured.next_sequence;
+    captured.next_sequence += 1;
+    if captured.lines.len() == CAPTURED_LINE_LIMIT {
+        captured.lines.pop_front();
+    }
+    captured.lines.push_back(CapturedLine {
+        sequence,
+        thread,
+        text: line.to_string(),
+    });
+}
@@ -0,0 +1,290 @@
+//! In-memory cache of extracted frames.
+//!
+//! A [`FrameCache`] attached with
+//! [`ExtractOptions::with_frame_cache`](crate::ExtractOptions::with_frame_cache)
+//! keeps the frames returned by
+//! [`VideoHandle::frame_with_options`](crate::VideoHandle::frame_with_options)
+//! and the methods built on it, such as
+//! [`frame_at_with_options`](crate::VideoHandle::frame_at_with_options).
+//! Asking for the same frame again — through the same `MediaFile` or one
+//! opened later — then skips the seek and decode. Extraction is
+//! deterministic, so a cached frame is identical to a freshly decoded one.
+//! Methods without options, such as
+//! [`frame`](crate::VideoHandle::frame), use default options and never
+//! consult a cache.
+//!
+//! Entries are keyed by source, video stream, frame number and output
+//! settings. Local files are also identified by their size and
+//! modification time, so replacing a file does not serve its old frames.
+//! Inputs opened with [`MediaFile::open_reader`](crate::MediaFile::open_reader)
+//! have no stable identity and are never cached, and neither are live
+//! inputs, whose frame numbers do not name fixed pictures.
+//!
+//! Frames are stored PNG-encoded. When a byte or entry limit is exceeded,
+//! the least recently used frames are evicted first.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use unbundle::{ExtractOptions, FrameCache, MediaFile, UnbundleError};
+//!
+//! let cache = Arc::new(FrameCache::new(256 * 1024 * 1024));
+//! let config = ExtractOptions::new().with_frame_cache(cache.clone());
+//!
+//! for _ in 0..2 {
+//!     let mut unbundler = MediaFile::open("input.mp4")?;
+//!     unbundler.video().frame_with_options(100, &config)?;
+//! }
+//! assert_eq!(cache.stats().hits, 1);
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::{DynamicImage, ImageFormat};
+
+use crate::configuration::ExtractOptions;
+use crate::reader_io::READER_SOURCE;
+
+/// Thread-safe LRU cache of extracted frames.
+///
+/// Share it behind an [`Arc`](std::sync::Arc) between every
+/// [`ExtractOptions`] that should read from and fill it.
+#[derive(Debug)]
+pub struct FrameCache {
+    max_bytes: usize,
+    max_entries: usize,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Counters read from a [`FrameCache`] at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameCacheStats {
+    /// Frames currently cached.
+    pub entries: usize,
+    /// Encoded size of the cached frames in bytes.
+    pub bytes: usize,
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to decode the frame.
+    pub misses: u64,
+}
+
+/// Identifies one extracted frame.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FrameKey {
+    source: String,
+    /// Size and modification time when the source is a local file.
+    file_version: Option<(u64, SystemTime)>,
+    stream_index: usize,
+    frame_number: u64,
+    /// Every setting that changes the output image.
+    output: String,
+}
+
+impl FrameKey {
+    /// Key for `frame_number` of `stream_index`, or `None` if the source
+    /// cannot be identified.
+    pub(crate) fn new(
+        source: &str,
+        stream_index: usize,
+        frame_number: u64,
+        config: &ExtractOptions,
+    ) -> Option<Self> {
+        if source == READER_SOURCE {
+            return None;
+        }
+        let file_version = std::fs::metadata(source)
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .and_then(|metadata| {
+                let modified = metadata.modified().ok()?;
+                Some((metadata.len(), modified))
+            });
+        // `FrameOutputOptions` is not `Hash`; its `Debug` form covers every
+        // field, including any added later. Skipped frames and hardware
+        // decoding change which picture a frame number lands on.
+        let output = format!(
+            "{:?} lowres={} discard={:?}",
+            config.frame_output, config.lowres, config.frame_discard
+        );
+        #[cfg(feature = "hardware")]
+        let output = format!("{output} hardware={:?}", config.hardware_acceleration);
+        Some(Self {
+            source: source.to_string(),
+            file_version,
+            stream_index,
+            frame_number,
+            output,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<FrameKey, CacheEntry>,
+    /// Keys by the tick of their last use; the first is evicted next.
+    recency: BTreeMap<u64, FrameKey>,
+    next_tick: u64,
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    /// Shared so a hit can decode it after releasing the lock.
+    png: Arc<[u8]>,
+    last_used: u64,
+}
+
+impl CacheState {
+    fn tick(&mut self) -> u64 {
+        self.next_tick += 1;
+        self.next_tick
+    }
+
+    fn remove(&mut self, key: &FrameKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+            self.bytes -= entry.png.len();
+        }
+    }
+}
+
+impl FrameCache {
+    /// Create a cache holding at most `max_bytes` of encoded frames.
+    ///
+    /// Frames larger than `max_bytes` on their own are not cached.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            max_entries: usize::MAX,
+            state: Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Also limit the number of cached frames. Unlimited by default.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// The byte limit set by [`new`](FrameCache::new).
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// The entry limit set by [`with_max_entries`](FrameCache::with_max_entries).
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Read the current size and hit counters.
+    pub fn stats(&self) -> FrameCacheStats {
+        let state = self.lock();
+        FrameCacheStats {
+            entries: state.entries.len(),
+            bytes: state.bytes,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drop every cached frame. The hit and miss counters are kept.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.recency.clear();
+        state.bytes = 0;
+    }
+
+    /// The cached frame for `key`, marking it as recently used.
+    pub(crate) fn get(&self, key: &FrameKey) -> Option<DynamicImage> {
+        let png = {
+            let mut state = self.lock();
+            let tick = state.tick();
+            let CacheState {
+                entries, recency, ..
+            } = &mut *state;
+            match entries.get_mut(key) {
+                Some(entry) => {
+                    recency.remove(&entry.last_used);
+                    recency.insert(tick, key.clone());
+                    entry.last_used = tick;
+                    Arc::clone(&entry.png)
+                }
+                None => {
+                    self.misses.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+            }
+        };
+        // Decode outside the lock so concurrent lookups do not queue up.
+        match image::load_from_memory_with_format(&png, ImageFormat::Png) {
+            Ok(image) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(image)
+            }
+            Err(error) => {
+                log::warn!("Dropping unreadable cached frame: {error}");
+                self.lock().remove(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Cache `image` under `key`, evicting older frames to make room.
+    pub(crate) fn insert(&self, key: FrameKey, image: &DynamicImage) {
+        let mut png = Vec::new();
+        // Fast compression: the cache trades some memory for cheap misses.
+        let encoder = PngEncoder::new_with_quality(
+            Cursor::new(&mut png),
+            CompressionType::Fast,
+            FilterType::Adaptive,
+        );
+        if let Err(error) = image.write_with_encoder(encoder) {
+            log::warn!("Not caching frame {}: {error}", key.frame_number);
+            return;
+        }
+        if png.len() > self.max_bytes || self.max_entries == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+        state.remove(&key);
+        while state.bytes + png.len() > self.max_bytes || state.entries.len() >= self.max_entries {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = state.entries.remove(&oldest) {
+                state.bytes -= entry.png.len();
+            }
+        }
+        let tick = state.tick();
+        state.bytes += png.len();
+        state.recency.insert(tick, key.clone());
+        state.entries.insert(
+            key,
+            CacheEntry {
+                png: png.into(),
+                last_used: tick,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -35,7 +35,10 @@ use crate::error::UnbundleError;
 /// Configuration for animated GIF export.
 ///
 /// Controls output dimensions, frame delay, repeat behaviour, and quality.
+/// Build it with [`GifOptions::new`] and the `with_*` methods; new settings
+/// may be added in minor releases.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct GifOptions {
     /// Target width in pixels. Height is computed to preserve aspect ratio.
     /// `None` means use source resolution.
@@ -44,6 +47,10 @@ pub struct GifOptions {
     pub frame_delay: u16,
     /// How many times the GIF should repeat. `None` means loop forever.
     pub repeat: Option<u16>,
+    /// Synthesize frames at the GIF's playback rate with motion
+    /// interpolation instead of using the selected frames directly
+    /// (default: `false`).
+    pub motion_interpolation: bool,
 }
 
 impl Default for GifOptions {
@@ -52,6 +59,7 @@ impl Default for GifOptions {
             width: None,
             frame_delay: 10,
             repeat: None,
+            motion_interpolation: false,
         }
     }
 }
@@ -103,6 +111,31 @@ impl GifOptions {
         self.repeat(repeat)
     }
 
+    /// Generate smooth intermediate frames at the playback rate.
+    ///
+    /// Normally the frames selected by the [`FrameRange`](crate::FrameRange)
+    /// are shown one after another, so a GIF slower than its source either
+    /// plays in slow motion or, with an interval range, skips frames and
+    /// looks jerky. When enabled, every source frame spanned by the range
+    /// is decoded and FFmpeg's `minterpolate` filter resamples the span to
+    /// `100 / frame_delay` frames per second of source time, estimating
+    /// motion to build frames that fall between source frames.
+    ///
+    /// Motion estimation is expensive: expect export to take several times
+    /// longer than without interpolation, growing with the source
+    /// resolution and the length of the span.
+    pub fn motion_interpolation(mut self, enabled: bool) -> Self {
+        self.motion_interpolation = enabled;
+        self
+    }
+
+    /// Generate smooth intermediate frames at the playback rate.
+    ///
+    /// Alias for [`motion_interpolation`](GifOptions::motion_interpolation).
+    pub fn with_motion_interpolation(self, enabled: bool) -> Self {
+        self.motion_interpolation(enabled)
+    }
+
     /// Build a [`FrameOutputOptions`] matching this GIF configuration.
     pub(crate) fn to_frame_output_config(
         &self,
@@ -15,13 +15,15 @@
 //! the host system's GPU drivers. When auto-detection fails, the decoder
 //! silently falls back to software decoding.
 
+use std::str::FromStr;
+
 use ffmpeg_next::{
     codec::context::Context as CodecContext, decoder::Video as VideoDecoder,
     frame::Video as VideoFrame,
 };
 use ffmpeg_sys_next::{
-    AV_CODEC_HW_CONFIG_METHOD_HW_DEVICE_CTX, AVBufferRef, AVCodecContext, AVCodecHWConfig,
-    AVHWDeviceType,
+    AV_CODEC_HW_CONFIG_METHOD_HW_DEVICE_CTX, AV_FRAME_CROP_UNALIGNED, AVBufferRef, AVCodecContext,
+    AVCodecHWConfig, AVHWDeviceType,
 };
 
 use crate::error::UnbundleError;
@@ -89,6 +91,55 @@ impl HardwareDeviceType {
     }
 }
 
+/// Parses FFmpeg's `-hwaccel` device names (`cuda`, `vaapi`, `dxva2`,
+/// `d3d11va`, `videotoolbox`, `qsv`), case-insensitively.
+impl FromStr for HardwareDeviceType {
+    type Err = UnbundleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cuda" => Ok(HardwareDeviceType::Cuda),
+            "vaapi" => Ok(HardwareDeviceType::Vaapi),
+            "dxva2" => Ok(HardwareDeviceType::Dxva2),
+            "d3d11va" => Ok(HardwareDeviceType::D3d11va),
+            "videotoolbox" => Ok(HardwareDeviceType::VideoToolbox),
+            "qsv" => Ok(HardwareDeviceType::Qsv),
+            _ => Err(UnbundleError::InvalidOptionValue {
+                option: "hardware device",
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses `auto`, `software` (`sw`, `cpu`) or any
+/// [`HardwareDeviceType`] name, case-insensitively.
+///
+/// ```
+/// use unbundle::{HardwareAccelerationMode, HardwareDeviceType};
+///
+/// let mode: HardwareAccelerationMode = "cuda".parse()?;
+/// assert_eq!(mode, HardwareAccelerationMode::Specific(HardwareDeviceType::Cuda));
+/// # Ok::<(), unbundle::UnbundleError>(())
+/// ```
+impl FromStr for HardwareAccelerationMode {
+    type Err = UnbundleError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(HardwareAccelerationMode::Auto),
+            "software" | "sw" | "cpu" => Ok(HardwareAccelerationMode::Software),
+            _ => value
+                .parse()
+                .map(HardwareAccelerationMode::Specific)
+                .map_err(|_| UnbundleError::InvalidOptionValue {
+                    option: "hardware acceleration mode",
+                    value: value.to_string(),
+                }),
+        }
+    }
+}
+
 /// List all hardware device types supported by the FFmpeg build.
 pub fn available_hardware_devices() -> Vec<HardwareDeviceType> {
     let mut devices = Vec::new();
@@ -234,10 +285,46 @@ pub(crate) fn transfer_hardware_frame(
             (*software_frame.as_mut_ptr()).pts = (*hardware_frame.as_ptr()).pts;
             (*software_frame.as_mut_ptr()).pkt_dts = (*hardware_frame.as_ptr()).pkt_dts;
         }
+        apply_remaining_cropping(hardware_frame, &mut software_frame)?;
         Ok(software_frame)
     }
 }
 
+/// Crop a downloaded frame by what the decoder left on the hardware frame.
+///
+/// Decoders can only trim the right and bottom edges of a GPU surface, so
+/// the left and top offsets of the display window are still pending on
+/// `hardware_frame`, and the transfer does not carry them over.
+fn apply_remaining_cropping(
+    hardware_frame: &VideoFrame,
+    software_frame: &mut VideoFrame,
+) -> Result<(), UnbundleError> {
+    unsafe {
+        let source = hardware_frame.as_ptr();
+        let target = software_frame.as_mut_ptr();
+        if (*source).crop_left == 0
+            && (*source).crop_top == 0
+            && (*source).crop_right == 0
+            && (*source).crop_bottom == 0
+        {
+            return Ok(());
+        }
+        (*target).crop_left = (*source).crop_left;
+        (*target).crop_top = (*source).crop_top;
+        (*target).crop_right = (*source).crop_right;
+        (*target).crop_bottom = (*source).crop_bottom;
+
+        let result =
+            ffmpeg_sys_next::av_frame_apply_cropping(target, AV_FRAME_CROP_UNALIGNED as i32);
+        if result < 0 {
+            return Err(UnbundleError::VideoDecodeError(format!(
+                "Cropping a transferred hardware frame failed (result={result})"
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Find the best hardware device type supported by the codec.
 fn find_best_hardware_device_for_codec(codec_context: &CodecContext) -> Option<AVHWDeviceType> {
     let codec_ptr = unsafe { (*codec_context.as_ptr()).codec };
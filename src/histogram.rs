@@ -0,0 +1,83 @@
+//! Per-frame luma (brightness) histograms.
+//!
+//! This module provides [`LumaHistogram`], a 256-bin count of the 8-bit
+//! luma values in a decoded frame, along with its mean and median. It is
+//! intended for exposure and contrast analysis without handing every
+//! decoded frame back to the caller.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{FrameRange, MediaFile, UnbundleError};
+//!
+//! let mut unbundler = MediaFile::open("input.mp4")?;
+//! let histograms = unbundler.video().luma_histograms(FrameRange::Interval(30))?;
+//! for histogram in &histograms {
+//!     println!(
+//!         "frame {}: mean {:.1}, median {}",
+//!         histogram.frame_number,
+//!         histogram.mean(),
+//!         histogram.median(),
+//!     );
+//! }
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use image::GrayImage;
+
+/// Distribution of luma values in a single decoded frame.
+///
+/// Luma is taken from the frame's GRAY8 conversion, so values range from
+/// 0 (black) to 255 (white).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LumaHistogram {
+    /// The zero-indexed frame number the histogram was computed from.
+    pub frame_number: u64,
+    /// Number of pixels with each luma value, indexed by value.
+    pub bins: [u32; 256],
+}
+
+impl LumaHistogram {
+    /// Count the luma values of a grayscale frame.
+    pub(crate) fn from_gray_image(frame_number: u64, image: &GrayImage) -> Self {
+        let mut bins = [0u32; 256];
+        for &value in image.as_raw() {
+            bins[value as usize] += 1;
+        }
+        Self { frame_number, bins }
+    }
+
+    /// Total number of pixels counted.
+    pub fn pixel_count(&self) -> u64 {
+        self.bins.iter().map(|&count| u64::from(count)).sum()
+    }
+
+    /// Mean luma value, or `0.0` for an empty frame.
+    pub fn mean(&self) -> f64 {
+        let total = self.pixel_count();
+        if total == 0 {
+            return 0.0;
+        }
+        let weighted: u64 = self
+            .bins
+            .iter()
+            .enumerate()
+            .map(|(value, &count)| value as u64 * u64::from(count))
+            .sum();
+        weighted as f64 / total as f64
+    }
+
+    /// Median luma value: the lowest value at which at least half of the
+    /// pixels have been counted. Returns `0` for an empty frame.
+    pub fn median(&self) -> u8 {
+        let half = self.pixel_count().div_ceil(2);
+        let mut seen = 0u64;
+        for (value, &count) in self.bins.iter().enumerate() {
+            seen += u64::from(count);
+            if seen >= half && seen > 0 {
+                return value as u8;
+            }
+        }
+        0
+    }
+}
@@ -14,15 +14,24 @@
 //! println!("Total keyframes: {}", group_of_pictures.keyframes.len());
 //! println!("Average Group of Pictures size: {:.1}", group_of_pictures.average_group_of_pictures_size);
 //! println!("Max Group of Pictures size: {}", group_of_pictures.max_group_of_pictures_size);
+//! println!("Average keyframe interval: {:?}", group_of_pictures.average_keyframe_interval);
 //! # Ok::<(), UnbundleError>(())
 //! ```
 
 use std::time::Duration;
 
-use ffmpeg_next::{Error as FfmpegError, Packet, Rational};
+use ffmpeg_next::{Error as FfmpegError, Packet, Rational, format::context::Input};
+use image::DynamicImage;
 
 use crate::error::UnbundleError;
 use crate::unbundle::MediaFile;
+use crate::video::FrameMetadata;
+
+/// Most windows a bitrate timeline may have.
+const MAX_TIMELINE_WINDOWS: usize = 1_000_000;
+
+/// Keyframe interval at or below which seeking scores full marks.
+const GOOD_KEYFRAME_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Information about a single keyframe (sync point).
 #[derive(Debug, Clone)]
@@ -35,6 +44,37 @@ pub struct KeyFrameMetadata {
     pub timestamp: Option<Duration>,
     /// Packet size in bytes.
     pub size: usize,
+    /// Byte offset of the packet in the input, or `None` when the demuxer
+    /// does not report one.
+    pub position: Option<u64>,
+}
+
+/// One entry of a keyframe index built by
+/// [`VideoHandle::keyframe_index`](crate::VideoHandle::keyframe_index).
+#[derive(Debug, Clone)]
+pub struct KeyframeEntry {
+    /// The decoded keyframe, scaled to the requested thumbnail width.
+    pub image: DynamicImage,
+    /// Presentation time of the keyframe.
+    pub timestamp: Duration,
+    /// Zero-based frame number of the keyframe.
+    pub frame_number: u64,
+    /// Byte offset of the keyframe's packet in the input, or `None` when
+    /// the demuxer does not report one.
+    pub position: Option<u64>,
+}
+
+/// Decoded frames of one Group of Pictures, from
+/// [`VideoHandle::frames_by_gop`](crate::VideoHandle::frames_by_gop).
+#[derive(Debug, Clone)]
+pub struct GopFrames {
+    /// Frame number of the keyframe that opens the group. When the
+    /// requested range starts mid-group this keyframe is not among
+    /// [`frames`](GopFrames::frames).
+    pub keyframe_number: u64,
+    /// The group's frames within the requested range, in display order.
+    /// [`FrameMetadata::frame_type`] gives each frame's I/P/B type.
+    pub frames: Vec<(DynamicImage, FrameMetadata)>,
 }
 
 /// Summary of the Group of Pictures structure.
@@ -54,6 +94,100 @@ pub struct GroupOfPicturesInfo {
     pub max_group_of_pictures_size: u64,
     /// Total number of video packets scanned.
     pub total_video_packets: u64,
+    /// Time between each keyframe and the next, from their presentation
+    /// timestamps. Keyframes without a PTS are skipped, so this can have
+    /// fewer than `keyframes.len() - 1` entries.
+    ///
+    /// For HLS or DASH, segments can only be cut on keyframes: every
+    /// segment duration must be a whole multiple of these intervals, and
+    /// the largest interval bounds how long a segment may be forced to
+    /// run.
+    pub keyframe_intervals: Vec<Duration>,
+    /// Mean of [`keyframe_intervals`](GroupOfPicturesInfo::keyframe_intervals),
+    /// or zero when fewer than two keyframes carry a PTS.
+    pub average_keyframe_interval: Duration,
+}
+
+/// How well a video stream supports random access, from
+/// [`VideoHandle::seekability_report`](crate::VideoHandle::seekability_report).
+///
+/// Every seek lands on a keyframe and decodes forward to the target, so
+/// the cost of scrubbing grows with the keyframe interval. Without a
+/// container index FFmpeg also has to search the file for the keyframe
+/// before it can start decoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeekabilityReport {
+    /// Overall score from `0.0` (no keyframes) to `1.0` (keyframes at
+    /// most two seconds apart, with an index). The interval contributes
+    /// `2 s / average_keyframe_interval`, capped at one, and a missing
+    /// index halves it. Below `0.5`, expect scrubbing to lag visibly.
+    pub score: f64,
+    /// Mean time between keyframes. When fewer than two keyframes carry a
+    /// timestamp this is the stream duration divided by the keyframe
+    /// count.
+    pub average_keyframe_interval: Duration,
+    /// Number of keyframes in the stream.
+    pub keyframe_count: usize,
+    /// Whether the container carries a seek index for the stream (an MP4
+    /// `stss` table, Matroska cues, an AVI `idx1` chunk), as found when
+    /// the file was opened, so earlier reads do not change it.
+    pub has_index: bool,
+}
+
+impl SeekabilityReport {
+    /// Score a stream from its Group of Pictures analysis.
+    pub(crate) fn new(
+        group_of_pictures: &GroupOfPicturesInfo,
+        has_index: bool,
+        duration: Duration,
+    ) -> Self {
+        let keyframe_count = group_of_pictures.keyframes.len();
+        let average_keyframe_interval = if group_of_pictures.keyframe_intervals.is_empty() {
+            duration / keyframe_count.max(1) as u32
+        } else {
+            group_of_pictures.average_keyframe_interval
+        };
+
+        let interval_score = if keyframe_count == 0 {
+            0.0
+        } else if average_keyframe_interval <= GOOD_KEYFRAME_INTERVAL {
+            1.0
+        } else {
+            GOOD_KEYFRAME_INTERVAL.as_secs_f64() / average_keyframe_interval.as_secs_f64()
+        };
+        let index_factor = if has_index { 1.0 } else { 0.5 };
+
+        Self {
+            score: interval_score * index_factor,
+            average_keyframe_interval,
+            keyframe_count,
+            has_index,
+        }
+    }
+}
+
+/// Whether the demuxer read a seek index for the stream from the container.
+///
+/// Demuxers flagged `AVFMT_GENERIC_INDEX` fill the index from packets as
+/// they are read, so their entries say nothing about the file and are
+/// ignored. Others (Matroska without cues, for one) may still add entries
+/// while demuxing, so this is only meaningful right after opening; it is
+/// recorded in [`MediaFile::indexed_streams`] then.
+pub(crate) fn has_seek_index(input_context: &Input, stream_index: usize) -> bool {
+    let Some(stream) = input_context.stream(stream_index) else {
+        return false;
+    };
+    // SAFETY: the input context and its stream are valid for the lifetime
+    // of `input_context`.
+    unsafe {
+        let input_format = (*input_context.as_ptr()).iformat;
+        if !input_format.is_null()
+            && (*input_format).flags & ffmpeg_sys_next::AVFMT_GENERIC_INDEX as i32 != 0
+        {
+            return false;
+        }
+        ffmpeg_sys_next::avformat_index_get_entries_count(stream.as_ptr()) > 0
+    }
 }
 
 /// Scan the video stream for keyframes and compute Group of Pictures statistics.
@@ -76,6 +210,8 @@ pub(crate) fn analyze_group_of_pictures_impl(
     let mut keyframes: Vec<KeyFrameMetadata> = Vec::new();
     let mut video_packet_count: u64 = 0;
 
+    // Earlier reads and decodes leave the demuxer mid-file.
+    crate::unbundle::seek_input(&mut unbundler.input_context, 0)?;
     let mut packet = Packet::empty();
     loop {
         match packet.read(&mut unbundler.input_context) {
@@ -97,6 +233,7 @@ pub(crate) fn analyze_group_of_pictures_impl(
                         pts,
                         timestamp,
                         size: packet.size(),
+                        position: u64::try_from(packet.position()).ok(),
                     });
                 }
 
@@ -127,6 +264,23 @@ pub(crate) fn analyze_group_of_pictures_impl(
     let min_group_of_pictures_size = group_of_pictures_sizes.iter().copied().min().unwrap_or(0);
     let max_group_of_pictures_size = group_of_pictures_sizes.iter().copied().max().unwrap_or(0);
 
+    let keyframe_pts: Vec<i64> = keyframes
+        .iter()
+        .filter_map(|keyframe| keyframe.pts)
+        .collect();
+    let keyframe_intervals: Vec<Duration> = keyframe_pts
+        .windows(2)
+        .map(|pair| {
+            let seconds = crate::conversion::pts_to_seconds(pair[1] - pair[0], time_base);
+            Duration::from_secs_f64(seconds.max(0.0))
+        })
+        .collect();
+    let average_keyframe_interval = if keyframe_intervals.is_empty() {
+        Duration::ZERO
+    } else {
+        keyframe_intervals.iter().sum::<Duration>() / keyframe_intervals.len() as u32
+    };
+
     Ok(GroupOfPicturesInfo {
         keyframes,
         group_of_pictures_sizes,
@@ -134,5 +288,114 @@ pub(crate) fn analyze_group_of_pictures_impl(
         min_group_of_pictures_size,
         max_group_of_pictures_size,
         total_video_packets: video_packet_count,
+        keyframe_intervals,
+        average_keyframe_interval,
     })
 }
+
+/// Frame number each of `keyframes` starts, in the same order.
+///
+/// Keyframes are placed on the frame grid of the stream's average frame
+/// rate; one without a PTS falls back to its packet number.
+pub(crate) fn keyframe_frame_numbers(
+    unbundler: &MediaFile,
+    video_stream_index: usize,
+    keyframes: &[KeyFrameMetadata],
+) -> Result<Vec<u64>, UnbundleError> {
+    let frames_per_second = unbundler
+        .metadata
+        .video
+        .as_ref()
+        .ok_or(UnbundleError::NoVideoStream)?
+        .frames_per_second;
+    let time_base = unbundler
+        .input_context
+        .stream(video_stream_index)
+        .ok_or(UnbundleError::NoVideoStream)?
+        .time_base();
+    Ok(keyframes
+        .iter()
+        .map(|keyframe| {
+            keyframe.pts.map_or(keyframe.packet_number, |pts| {
+                crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second)
+            })
+        })
+        .collect())
+}
+
+/// Sum the sizes of the video stream's packets into consecutive windows of
+/// `window` length, starting at the stream's start time.
+///
+/// Like the Group of Pictures scan this reads packets without decoding.
+/// Packets without a timestamp, or stamped past the start time plus the
+/// media duration, are skipped.
+pub(crate) fn bitrate_timeline_impl(
+    unbundler: &mut MediaFile,
+    video_stream_index: usize,
+    window: Duration,
+) -> Result<Vec<(Duration, u64)>, UnbundleError> {
+    if window.is_zero() {
+        return Err(UnbundleError::InvalidInterval);
+    }
+    log::debug!(
+        "Building bitrate timeline (stream={}, window={:?})",
+        video_stream_index,
+        window
+    );
+    let stream = unbundler
+        .input_context
+        .stream(video_stream_index)
+        .ok_or(UnbundleError::NoVideoStream)?;
+    let time_base: Rational = stream.time_base();
+    // Windows count from the first timestamp, not from zero.
+    let start_seconds =
+        crate::unbundle::stream_start(&unbundler.input_context, &stream).as_secs_f64();
+    let window_seconds = window.as_secs_f64();
+    // Packet timestamps are untrusted, so the windows are bounded by the
+    // duration; without one, by a fixed cap.
+    let duration_seconds = unbundler.metadata.duration.as_secs_f64();
+    let window_limit = if duration_seconds > 0.0 {
+        let windows = (duration_seconds / window_seconds).ceil() + 1.0;
+        if windows > MAX_TIMELINE_WINDOWS as f64 {
+            return Err(UnbundleError::InvalidInterval);
+        }
+        windows as usize
+    } else {
+        MAX_TIMELINE_WINDOWS
+    };
+
+    let mut window_bytes: Vec<u64> = Vec::new();
+    crate::unbundle::seek_input(&mut unbundler.input_context, 0)?;
+    let mut packet = Packet::empty();
+    loop {
+        match packet.read(&mut unbundler.input_context) {
+            Ok(()) => {
+                if packet.stream() as usize != video_stream_index {
+                    continue;
+                }
+                let Some(timestamp) = packet.pts().or(packet.dts()) else {
+                    continue;
+                };
+                let seconds = (crate::conversion::pts_to_seconds(timestamp, time_base)
+                    - start_seconds)
+                    .max(0.0);
+                let window_index = (seconds / window_seconds) as usize;
+                if window_index >= window_limit {
+                    continue;
+                }
+                if window_bytes.len() <= window_index {
+                    window_bytes.resize(window_index + 1, 0);
+                }
+                window_bytes[window_index] += packet.size() as u64;
+            }
+            Err(FfmpegError::Eof) => break,
+            Err(e) => return Err(UnbundleError::from(e)),
+        }
+    }
+
+    Ok(window_bytes
+        .into_iter()
+        .enumerate()
+        .map(|(index, bytes)| (window.mul_f64(index as f64), bytes))
+        .collect())
+}
@@ -92,21 +92,26 @@
 //! - **Thumbnail helpers** — single thumbnails, grids, and smart selection
 //! - **Efficient seeking** — seeks to nearest keyframe, then decodes forward
 //! - **Zero-copy in-memory audio** — uses FFmpeg's dynamic buffer I/O
-//! - **Flexible source opening** — open from local paths, URL inputs, and FFmpeg source strings
+//! - **Flexible source opening** — open from local paths, URL inputs, FFmpeg source strings,
+//!   and any `Read + Seek` value
+//! - **File pooling** — `MediaFilePool` lends per-thread `MediaFile`s for
+//!   concurrent reads of one source
 //!
 //! ### Optional Features
 //!
 //! | Feature | Description |
 //! |---------|-------------|
-//! | `async` | `FrameStream` and `AudioFuture` for async extraction via Tokio |
+//! | `async` | `FrameStream`, `AudioFuture` and `AudioStream` for async extraction via Tokio |
 //! | `rayon` | `frames_parallel()` distributes decoding across rayon threads |
 //! | `hardware` | Hardware-accelerated decoding (CUDA, VAAPI, DXVA2, D3D11VA, VideoToolbox, QSV) |
 //! | `scene` | Scene change detection via FFmpeg's `scdet` filter |
 //! | `gif` | Animated GIF export from video frames |
 //! | `waveform` | Audio waveform visualization data (min/max/RMS per bin) |
 //! | `loudness` | Peak/RMS loudness analysis with dBFS conversion |
+//! | `spectrogram` | Log-mel spectrograms for audio ML models |
 //! | `transcode` | Audio re-encoding between formats |
 //! | `encode` | Encode `DynamicImage` sequences into video files |
+//! | `cache` | LRU `FrameCache` serving repeated frame requests from memory |
 //! | `full` | Enables all of the above |
 //!
 //! ## Requirements
@@ -115,34 +120,50 @@
 //! [README](https://github.com/skanderjeddi/unbundle#installation) for
 //! platform-specific instructions.
 
+mod atomic_write;
 pub mod audio;
 pub mod audio_iterator;
+pub mod checksum;
+mod coded_size;
+pub mod compare;
 pub mod configuration;
 mod conversion;
+mod edit_list;
 #[cfg(feature = "encode")]
 pub mod encode;
 pub mod error;
 pub mod ffmpeg;
+#[cfg(feature = "cache")]
+pub mod frame_cache;
 #[cfg(feature = "gif")]
 pub mod gif;
 #[cfg(feature = "hardware")]
 pub mod hardware_acceleration;
+pub mod histogram;
 pub mod keyframe;
 #[cfg(feature = "loudness")]
 pub mod loudness;
 pub mod metadata;
+pub mod overlay;
 pub mod packet_iterator;
+pub mod pool;
 pub mod probe;
 pub mod progress;
 #[cfg(feature = "rayon")]
 mod rayon;
+mod reader_io;
 pub mod remux;
 #[cfg(feature = "scene")]
 pub mod scene;
+#[cfg(feature = "spectrogram")]
+pub mod spectrogram;
+pub mod stats;
 #[cfg(feature = "async")]
 pub mod stream;
 pub mod subtitle;
+pub mod summary;
 pub mod thumbnail;
+pub mod timecode;
 #[cfg(feature = "transcode")]
 pub mod transcode;
 pub mod unbundle;
@@ -153,41 +174,65 @@ pub mod video_iterator;
 #[cfg(feature = "waveform")]
 pub mod waveform;
 
-pub use audio::{AudioFormat, AudioHandle};
+pub use audio::{AudioFormat, AudioHandle, PcmInfo, PcmSampleFormat};
 pub use audio_iterator::{AudioChunk, AudioIterator};
-pub use configuration::{ExtractOptions, FrameOutputOptions, PixelFormat};
+pub use checksum::StreamChecksum;
+pub use compare::{CompareReport, FrameMetrics, MetricOptions};
+pub use configuration::{
+    ColorMatrix, ErrorPolicy, ExtractOptions, FrameDiscard, FrameOutputOptions, PixelFormat,
+    ResampleQuality, ThreadType,
+};
 #[cfg(feature = "encode")]
 pub use encode::{VideoCodec, VideoEncoder, VideoEncoderOptions};
 pub use error::UnbundleError;
-pub use ffmpeg::{FfmpegLogLevel, get_ffmpeg_log_level, set_ffmpeg_log_level};
+pub use ffmpeg::{
+    FfmpegLogLevel, get_ffmpeg_log_level, set_ffmpeg_log_capture, set_ffmpeg_log_level,
+};
+#[cfg(feature = "cache")]
+pub use frame_cache::{FrameCache, FrameCacheStats};
 #[cfg(feature = "gif")]
 pub use gif::GifOptions;
 #[cfg(feature = "hardware")]
 pub use hardware_acceleration::{HardwareAccelerationMode, HardwareDeviceType};
-pub use keyframe::{GroupOfPicturesInfo, KeyFrameMetadata};
+pub use histogram::LumaHistogram;
+pub use keyframe::{
+    GopFrames, GroupOfPicturesInfo, KeyFrameMetadata, KeyframeEntry, SeekabilityReport,
+};
 #[cfg(feature = "loudness")]
 pub use loudness::LoudnessInfo;
 pub use metadata::{
-    AudioMetadata, ChapterMetadata, MediaMetadata, SubtitleMetadata, VideoMetadata,
+    AudioMetadata, ChapterMetadata, FieldOrder, MediaMetadata, ProgramMetadata, StreamKind,
+    StreamMetadata, SubtitleMetadata, VideoMetadata,
 };
+pub use overlay::{OverlayCorner, OverlayOptions};
 pub use packet_iterator::{PacketInfo, PacketIterator};
+pub use pool::{MediaFilePool, PooledMediaFile};
 pub use probe::MediaProbe;
 pub use progress::{CancellationToken, OperationType, ProgressCallback, ProgressInfo};
-pub use remux::Remuxer;
+pub use remux::{Remuxer, SegmentInfo};
 #[cfg(feature = "scene")]
 pub use scene::{SceneChange, SceneDetectionMode, SceneDetectionOptions};
+#[cfg(feature = "spectrogram")]
+pub use spectrogram::MelOptions;
+pub use stats::{DecodeStats, StatsCollector};
 #[cfg(feature = "async")]
-pub use stream::{AudioFuture, FrameStream};
-pub use subtitle::{BitmapSubtitleEvent, SubtitleEvent, SubtitleFormat, SubtitleHandle};
+pub use stream::{AudioFuture, AudioStream, FrameStream};
+pub use subtitle::{
+    AssBundle, BitmapOcr, BitmapSubtitleEvent, SubtitleEvent, SubtitleFormat, SubtitleHandle,
+    SubtitleWriteOptions,
+};
+pub use summary::ExtractSummary;
 pub use thumbnail::{ThumbnailHandle, ThumbnailOptions};
+pub use timecode::Timecode;
 #[cfg(feature = "transcode")]
 pub use transcode::Transcoder;
 pub use unbundle::MediaFile;
-pub use validation::ValidationReport;
-pub use variable_framerate::VariableFrameRateAnalysis;
+pub use validation::{ConcatReport, ValidationReport};
+pub use variable_framerate::{FrameRateBucket, VariableFrameRateAnalysis};
 pub use video::{
-    FilterChainHandle, FrameMetadata, FrameRange, FrameType, RawFrameView, VideoHandle,
+    FilterChainHandle, FrameBufferLayout, FrameMetadata, FrameRange, FrameType, RawFrameView,
+    VideoHandle,
 };
-pub use video_iterator::FrameIterator;
+pub use video_iterator::{FrameIterator, FrameMetadataIterator};
 #[cfg(feature = "waveform")]
 pub use waveform::{WaveformBin, WaveformData, WaveformOptions};
@@ -24,9 +24,22 @@ use ffmpeg_next::format::{Sample, sample::Type as SampleType};
 use ffmpeg_next::frame::Audio as AudioFrame;
 use ffmpeg_next::software::resampling::Context as ResamplingContext;
 
+use crate::audio_iterator::AudioIterator;
+use crate::configuration::ExtractOptions;
 use crate::error::UnbundleError;
+use crate::progress::{OperationType, ProgressTracker};
 use crate::unbundle::MediaFile;
 
+/// Length of the windows whose RMS level is compared with the silence
+/// threshold.
+const SILENCE_WINDOW_MILLISECONDS: u64 = 10;
+
+/// Sample rate the K-weighting filter coefficients below are defined for.
+const LOUDNESS_SAMPLE_RATE: u32 = 48_000;
+
+/// Samples in one 100 ms step; a gating block spans four steps (400 ms).
+const LOUDNESS_STEP_SAMPLES: usize = LOUDNESS_SAMPLE_RATE as usize / 10;
+
 /// Audio loudness statistics.
 #[derive(Debug, Clone, Copy)]
 pub struct LoudnessInfo {
@@ -45,9 +58,13 @@ pub struct LoudnessInfo {
 }
 
 /// Decode audio to mono f32 and compute loudness statistics.
+///
+/// Progress, when `config` is given, is counted in decoded samples against
+/// the file duration.
 pub(crate) fn analyze_loudness_impl(
     unbundler: &mut MediaFile,
     audio_stream_index: usize,
+    config: Option<&ExtractOptions>,
 ) -> Result<LoudnessInfo, UnbundleError> {
     log::debug!("Analyzing loudness (stream={})", audio_stream_index);
     let stream = unbundler
@@ -73,6 +90,16 @@ pub(crate) fn analyze_loudness_impl(
     )
     .map_err(|e| UnbundleError::LoudnessError(format!("Failed to create resampler: {e}")))?;
 
+    let expected_samples = (unbundler.metadata.duration.as_secs_f64() * sample_rate as f64) as u64;
+    let mut tracker = config.map(|active_config| {
+        ProgressTracker::new(
+            active_config.progress.clone(),
+            OperationType::LoudnessAnalysis,
+            Some(expected_samples),
+            active_config.batch_size,
+        )
+    });
+
     let mut peak: f32 = 0.0;
     let mut sum_sq: f64 = 0.0;
     let mut total_samples: u64 = 0;
@@ -80,6 +107,11 @@ pub(crate) fn analyze_loudness_impl(
     let mut resampled_frame = AudioFrame::empty();
 
     for (stream, packet) in unbundler.input_context.packets() {
+        if let Some(active_config) = config
+            && active_config.is_cancelled()
+        {
+            return Err(UnbundleError::Cancelled);
+        }
         if stream.index() != audio_stream_index {
             continue;
         }
@@ -106,26 +138,26 @@ pub(crate) fn analyze_loudness_impl(
                 sum_sq += (s as f64) * (s as f64);
             }
             total_samples += sample_count as u64;
+
+            if let Some(active_tracker) = tracker.as_mut() {
+                let position = Duration::from_secs_f64(total_samples as f64 / sample_rate as f64);
+                active_tracker.advance_to(total_samples, Some(position));
+            }
         }
     }
 
+    if let Some(active_tracker) = tracker.as_mut() {
+        active_tracker.finish();
+    }
+
     let rms = if total_samples > 0 {
         (sum_sq / total_samples as f64).sqrt() as f32
     } else {
         0.0
     };
 
-    let peak_dbfs = if peak > 0.0 {
-        20.0 * (peak as f64).log10()
-    } else {
-        f64::NEG_INFINITY
-    };
-
-    let rms_dbfs = if rms > 0.0 {
-        20.0 * (rms as f64).log10()
-    } else {
-        f64::NEG_INFINITY
-    };
+    let peak_dbfs = crate::conversion::amplitude_to_dbfs(f64::from(peak));
+    let rms_dbfs = crate::conversion::amplitude_to_dbfs(f64::from(rms));
 
     let duration = Duration::from_secs_f64(total_samples as f64 / sample_rate as f64);
 
@@ -138,3 +170,288 @@ pub(crate) fn analyze_loudness_impl(
         total_samples,
     })
 }
+
+/// Find stretches of at least `min_silence` in which every 10 ms window
+/// has an RMS level below `threshold_dbfs`.
+///
+/// Returns `(start, end)` pairs in order. A partial window at the end of
+/// the track is ignored.
+pub(crate) fn detect_silence_impl(
+    unbundler: &mut MediaFile,
+    audio_stream_index: usize,
+    threshold_dbfs: f64,
+    min_silence: Duration,
+) -> Result<Vec<(Duration, Duration)>, UnbundleError> {
+    log::debug!(
+        "Detecting silence below {threshold_dbfs} dBFS for at least {min_silence:?} (stream={})",
+        audio_stream_index
+    );
+    let threshold = 10f64.powf(threshold_dbfs / 20.0);
+
+    let mut spans: Vec<(Duration, Duration)> = Vec::new();
+    let mut sample_rate: u32 = 0;
+    let mut window_length: u64 = 0;
+    let mut window_fill: u64 = 0;
+    let mut window_sum_sq: f64 = 0.0;
+    // Samples consumed so far, and where the current silent run began.
+    let mut position: u64 = 0;
+    let mut silence_start: Option<u64> = None;
+
+    let mut push_span = |start: u64, end: u64, sample_rate: u32| {
+        let start = samples_to_duration(start, sample_rate);
+        let end = samples_to_duration(end, sample_rate);
+        if end - start >= min_silence {
+            spans.push((start, end));
+        }
+    };
+
+    // Earlier reads leave the demuxer mid-file.
+    crate::unbundle::rewind_input(&mut unbundler.input_context)?;
+    for chunk in AudioIterator::new(unbundler, audio_stream_index)? {
+        let chunk = chunk?;
+        if window_length == 0 {
+            sample_rate = chunk.sample_rate;
+            window_length = (u64::from(sample_rate) * SILENCE_WINDOW_MILLISECONDS / 1000).max(1);
+        }
+
+        for &sample in &chunk.samples {
+            window_sum_sq += f64::from(sample) * f64::from(sample);
+            window_fill += 1;
+            position += 1;
+            if window_fill < window_length {
+                continue;
+            }
+
+            let rms = (window_sum_sq / window_length as f64).sqrt();
+            let window_start = position - window_length;
+            if rms < threshold {
+                silence_start.get_or_insert(window_start);
+            } else if let Some(start) = silence_start.take() {
+                push_span(start, window_start, sample_rate);
+            }
+            window_fill = 0;
+            window_sum_sq = 0.0;
+        }
+    }
+
+    if let Some(start) = silence_start {
+        push_span(start, position - window_fill, sample_rate);
+    }
+    Ok(spans)
+}
+
+fn samples_to_duration(samples: u64, sample_rate: u32) -> Duration {
+    Duration::from_secs_f64(samples as f64 / f64::from(sample_rate.max(1)))
+}
+
+/// Measure integrated loudness in LUFS following ITU-R BS.1770-4.
+///
+/// Audio is resampled to 48 kHz and K-weighted, then 400 ms blocks with
+/// 75 % overlap are gated at -70 LUFS and at 10 LU below the mean of the
+/// blocks that pass. Mono and stereo are measured as is; layouts with more
+/// channels are downmixed to stereo first, which skips the standard's
+/// surround channel weights. Returns negative infinity for silence.
+pub(crate) fn integrated_loudness_impl(
+    unbundler: &mut MediaFile,
+    audio_stream_index: usize,
+) -> Result<f64, UnbundleError> {
+    log::debug!(
+        "Measuring integrated loudness (stream={})",
+        audio_stream_index
+    );
+    let stream = unbundler
+        .input_context
+        .stream(audio_stream_index)
+        .ok_or(UnbundleError::NoAudioStream)?;
+
+    let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+    let mut decoder = decoder_context.decoder().audio().map_err(|e| {
+        UnbundleError::LoudnessError(format!("Failed to create audio decoder: {e}"))
+    })?;
+
+    let output_layout = if decoder.channels() <= 2 {
+        decoder.channel_layout()
+    } else {
+        ChannelLayout::STEREO
+    };
+    let channels = output_layout.channels().max(1) as usize;
+    let mut resampler = ResamplingContext::get(
+        decoder.format(),
+        decoder.channel_layout(),
+        decoder.rate(),
+        Sample::F32(SampleType::Packed),
+        output_layout,
+        LOUDNESS_SAMPLE_RATE,
+    )
+    .map_err(|e| UnbundleError::LoudnessError(format!("Failed to create resampler: {e}")))?;
+
+    let mut filters = vec![KWeighting::new(); channels];
+    // Sum of squared K-weighted samples over all channels, per 100 ms step.
+    let mut step_energies: Vec<f64> = Vec::new();
+    let mut step_energy = 0.0;
+    let mut step_fill = 0;
+
+    let mut accumulate = |resampled: &AudioFrame| {
+        let sample_count = resampled.samples() * channels;
+        let data = resampled.data(0);
+        let float_samples: &[f32] =
+            unsafe { std::slice::from_raw_parts(data.as_ptr() as *const f32, sample_count) };
+        for frame in float_samples.chunks_exact(channels) {
+            for (filter, &sample) in filters.iter_mut().zip(frame) {
+                let weighted = filter.process(f64::from(sample));
+                step_energy += weighted * weighted;
+            }
+            step_fill += 1;
+            if step_fill == LOUDNESS_STEP_SAMPLES {
+                step_energies.push(step_energy);
+                step_energy = 0.0;
+                step_fill = 0;
+            }
+        }
+    };
+
+    // Size each output frame for a converted input frame plus whatever the
+    // resampler is still holding, so nothing piles up inside it when
+    // upsampling.
+    let input_rate = u64::from(decoder.rate().max(1));
+    let output_frame = |resampler: &ResamplingContext, input_samples: usize| {
+        let converted =
+            (input_samples as u64 * u64::from(LOUDNESS_SAMPLE_RATE)).div_ceil(input_rate);
+        let pending = resampler
+            .delay()
+            .map_or(0, |delay| delay.output.max(0) as u64);
+        AudioFrame::new(
+            Sample::F32(SampleType::Packed),
+            (converted + pending) as usize + 32,
+            output_layout,
+        )
+    };
+    let mut resample = |resampler: &mut ResamplingContext, decoded: &AudioFrame| {
+        let mut resampled_frame = output_frame(resampler, decoded.samples());
+        resampler
+            .run(decoded, &mut resampled_frame)
+            .map_err(|e| UnbundleError::LoudnessError(format!("Resample error: {e}")))?;
+        accumulate(&resampled_frame);
+        Ok::<(), UnbundleError>(())
+    };
+
+    // Earlier reads leave the demuxer mid-file.
+    crate::unbundle::rewind_input(&mut unbundler.input_context)?;
+    let mut decoded_frame = AudioFrame::empty();
+    for (stream, packet) in unbundler.input_context.packets() {
+        if stream.index() != audio_stream_index {
+            continue;
+        }
+        decoder
+            .send_packet(&packet)
+            .map_err(|e| UnbundleError::LoudnessError(format!("Audio decode error: {e}")))?;
+        while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            resample(&mut resampler, &decoded_frame)?;
+        }
+    }
+
+    // Drain the frames the decoder still holds, then the resampler's tail.
+    decoder
+        .send_eof()
+        .map_err(|e| UnbundleError::LoudnessError(format!("Audio decode error: {e}")))?;
+    while decoder.receive_frame(&mut decoded_frame).is_ok() {
+        resample(&mut resampler, &decoded_frame)?;
+    }
+    loop {
+        let mut resampled_frame = output_frame(&resampler, 0);
+        resampler
+            .flush(&mut resampled_frame)
+            .map_err(|e| UnbundleError::LoudnessError(format!("Resample error: {e}")))?;
+        if resampled_frame.samples() == 0 {
+            break;
+        }
+        accumulate(&resampled_frame);
+    }
+
+    Ok(gated_loudness(&step_energies))
+}
+
+/// Apply the BS.1770 absolute and relative gates to 400 ms blocks built
+/// from consecutive 100 ms step energies.
+fn gated_loudness(step_energies: &[f64]) -> f64 {
+    const ABSOLUTE_GATE: f64 = -70.0;
+    const RELATIVE_GATE: f64 = -10.0;
+    let block_samples = (4 * LOUDNESS_STEP_SAMPLES) as f64;
+    let loudness = |mean_square: f64| -0.691 + 10.0 * mean_square.log10();
+
+    let blocks: Vec<f64> = step_energies
+        .windows(4)
+        .map(|steps| steps.iter().sum::<f64>() / block_samples)
+        .filter(|&mean_square| loudness(mean_square) > ABSOLUTE_GATE)
+        .collect();
+    if blocks.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    let relative_gate = loudness(mean(&blocks)) + RELATIVE_GATE;
+    let gated: Vec<f64> = blocks
+        .into_iter()
+        .filter(|&mean_square| loudness(mean_square) > relative_gate)
+        .collect();
+    if gated.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    loudness(mean(&gated))
+}
+
+/// The two-stage K-weighting pre-filter of BS.1770 at 48 kHz: a high
+/// shelf modelling the head, then a high-pass (the RLB curve).
+#[derive(Debug, Clone)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new() -> Self {
+        Self {
+            shelf: Biquad::new(
+                [
+                    1.535_124_859_586_97,
+                    -2.691_696_189_406_38,
+                    1.198_392_810_852_85,
+                ],
+                [-1.690_659_293_182_41, 0.732_480_774_215_85],
+            ),
+            high_pass: Biquad::new(
+                [1.0, -2.0, 1.0],
+                [-1.990_047_454_833_98, 0.990_072_250_366_21],
+            ),
+        }
+    }
+
+    fn process(&mut self, sample: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(sample))
+    }
+}
+
+/// Second-order IIR section in transposed direct form II.
+#[derive(Debug, Clone)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            state: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.state[0];
+        self.state[0] = self.b[1] * input - self.a[0] * output + self.state[1];
+        self.state[1] = self.b[2] * input - self.a[1] * output;
+        output
+    }
+}
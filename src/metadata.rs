@@ -6,8 +6,11 @@
 //! unbundler.
 
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::time::Duration;
 
+use crate::timecode::Timecode;
+
 /// Complete metadata for a media file.
 ///
 /// Contains optional video and audio stream metadata, plus container-level
@@ -50,7 +53,8 @@ pub struct MediaMetadata {
     /// Chapter metadata, if the container contains chapters.
     ///
     /// Chapters represent named time segments (e.g. scenes, acts) embedded in
-    /// the container. `None` when no chapters are present.
+    /// the container, sorted by start time. `None` when no chapters are
+    /// present.
     pub chapters: Option<Vec<ChapterMetadata>>,
     /// Total duration of the media file.
     pub duration: Duration,
@@ -60,6 +64,13 @@ pub struct MediaMetadata {
     ///
     /// `None` when the container has no metadata tags.
     pub tags: Option<HashMap<String, String>>,
+    /// Whether the input looks like a live stream with no end, such as a
+    /// live HLS playlist, an RTSP camera or a pipe.
+    ///
+    /// Inferred when opening: the duration is unknown and the input is
+    /// either not seekable or read by a streaming demuxer. A live input
+    /// reports a [`duration`](MediaMetadata::duration) of zero.
+    pub is_live: bool,
 }
 
 /// Metadata for a video stream.
@@ -73,6 +84,16 @@ pub struct VideoMetadata {
     pub width: u32,
     /// Frame height in pixels.
     pub height: u32,
+    /// Width the codec actually codes, before cropping to
+    /// [`width`](VideoMetadata::width). Equal to `width` unless the stream
+    /// is padded to whole blocks.
+    pub coded_width: u32,
+    /// Height the codec actually codes, before cropping to
+    /// [`height`](VideoMetadata::height); 1088 for most 1080p H.264.
+    ///
+    /// Only H.264 is inspected; other codecs report `height`. Extracted
+    /// frames are always cropped to the display size.
+    pub coded_height: u32,
     /// Frames per second (may be approximate for variable-frame-rate content).
     pub frames_per_second: f64,
     /// Estimated total number of frames, computed from duration and frame rate.
@@ -91,12 +112,85 @@ pub struct VideoMetadata {
     pub bits_per_raw_sample: Option<u32>,
     /// Pixel format name (e.g. `"yuv420p"`, `"yuv420p10le"`), if available.
     pub pixel_format_name: Option<String>,
+    /// Whether frames are progressive or interlaced, and which field comes
+    /// first. `None` when the stream does not say.
+    pub field_order: Option<FieldOrder>,
+    /// Clockwise rotation in degrees (0, 90, 180, or 270) a player applies
+    /// before display, from the stream's display matrix. Phones record
+    /// portrait video as landscape frames with a 90° or 270° rotation.
+    pub rotation: u32,
+    /// Shape of a stored pixel as `(width, height)`, e.g. `(32, 27)` for
+    /// widescreen PAL DVD video. `None` when the stream does not say, in
+    /// which case pixels are square.
+    pub sample_aspect_ratio: Option<(u32, u32)>,
+    /// Duration of this stream alone, if the container records it.
+    ///
+    /// May differ from [`MediaMetadata::duration`], which spans every
+    /// stream; for example an audio track can run past the end of the
+    /// video. `None` for containers that only store a file-level duration,
+    /// such as Matroska.
+    pub duration: Option<Duration>,
+    /// Whether the container gives this track an edit list (an MP4/MOV
+    /// `elst` box). Always `false` for other containers.
+    pub has_edit_list: bool,
+    /// Seconds the edit list shifts this track's start on the file
+    /// timeline: positive when the track starts late, negative when
+    /// leading media such as encoder delay is skipped. `0.0` without an
+    /// edit list.
+    ///
+    /// FFmpeg applies the edit list while demuxing, so extracted frames,
+    /// audio and their timestamps are already on the shifted timeline;
+    /// tools that read the raw media directly are not.
+    pub edit_list_offset: f64,
+    /// SMPTE timecode of the first frame, from the stream's or the
+    /// container's `timecode` tag (MOV `tmcd` tracks, MXF, MPEG-TS GOP
+    /// headers). `None` when untagged or unparsable.
+    pub start_timecode: Option<Timecode>,
     /// Zero-based track number among all video streams in the file.
     pub track_index: usize,
     /// FFmpeg stream index within the container.
     pub(crate) stream_index: usize,
 }
 
+impl VideoMetadata {
+    /// Width and height of the picture as a player shows it: stretched by
+    /// the [`sample_aspect_ratio`](VideoMetadata::sample_aspect_ratio),
+    /// then turned by the [`rotation`](VideoMetadata::rotation).
+    pub fn display_dimensions(&self) -> (u32, u32) {
+        let width = match self.sample_aspect_ratio {
+            Some((numerator, denominator)) if numerator > 0 && denominator > 0 => {
+                let stretched =
+                    f64::from(self.width) * f64::from(numerator) / f64::from(denominator);
+                (stretched.round() as u32).max(1)
+            }
+            _ => self.width,
+        };
+        if self.rotation % 180 == 90 {
+            (self.height, width)
+        } else {
+            (width, self.height)
+        }
+    }
+}
+
+/// Field order of a video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOrder {
+    /// Whole frames, no interlacing.
+    Progressive,
+    /// Interlaced, top field displayed first.
+    TopFieldFirst,
+    /// Interlaced, bottom field displayed first.
+    BottomFieldFirst,
+}
+
+impl FieldOrder {
+    /// Returns `true` for the interlaced orders.
+    pub fn is_interlaced(self) -> bool {
+        self != FieldOrder::Progressive
+    }
+}
+
 /// Metadata for an audio stream.
 ///
 /// Includes sample rate, channel count, codec name, and bit rate.
@@ -113,6 +207,23 @@ pub struct AudioMetadata {
     pub codec: String,
     /// Bit rate in bits per second.
     pub bit_rate: u64,
+    /// Duration of this audio track alone, if the container records it.
+    ///
+    /// Compare with [`VideoMetadata::duration`] to detect audio that runs
+    /// past the end of the picture or stops short of it.
+    pub duration: Option<Duration>,
+    /// Whether the container gives this track an edit list (an MP4/MOV
+    /// `elst` box). Always `false` for other containers.
+    pub has_edit_list: bool,
+    /// Seconds the edit list shifts this track's start on the file
+    /// timeline: positive when the track starts late, negative when
+    /// leading media such as encoder delay is skipped. `0.0` without an
+    /// edit list.
+    ///
+    /// FFmpeg applies the edit list while demuxing, so extracted samples
+    /// and their timestamps are already on the shifted timeline;
+    /// tools that read the raw media directly are not.
+    pub edit_list_offset: f64,
     /// Zero-based track number among all audio streams in the file.
     pub track_index: usize,
     /// FFmpeg stream index within the container.
@@ -126,6 +237,11 @@ pub struct AudioMetadata {
 /// support chapters; when present they are extracted at open time and
 /// stored in [`MediaMetadata::chapters`].
 ///
+/// Matroska chapters, MP4/M4B chapter tracks and Nero chapter atoms, and
+/// the ID3v2 `CHAP` frames audiobook MP3s use are all read into this
+/// form. Formats that store only start times get end times from the next
+/// chapter's start, or from the file duration for the last chapter.
+///
 /// # Example
 ///
 /// ```no_run
@@ -149,12 +265,102 @@ pub struct ChapterMetadata {
     pub start: Duration,
     /// End time of the chapter.
     pub end: Duration,
-    /// Zero-based chapter index within the container.
+    /// Zero-based chapter index in container order, which may differ from
+    /// the chapter's position in [`MediaMetadata::chapters`].
     pub index: usize,
     /// The chapter's unique identifier as stored in the container.
     pub id: i64,
 }
 
+/// A program (channel) of a multi-program container, such as one service
+/// of a broadcast MPEG-TS multiplex.
+///
+/// Returned by [`MediaFile::programs`](crate::MediaFile::programs); pass
+/// [`id`](ProgramMetadata::id) to
+/// [`MediaFile::select_program`](crate::MediaFile::select_program) to
+/// extract from this program only.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ProgramMetadata {
+    /// Program number as listed in the program map table (the DVB
+    /// service ID).
+    pub id: i32,
+    /// Service name (e.g. `"BBC ONE"`), if the multiplex carries one.
+    pub name: Option<String>,
+    /// Service provider name, if the multiplex carries one.
+    pub provider: Option<String>,
+    /// Container stream indices belonging to the program.
+    pub stream_indices: Vec<usize>,
+}
+
+/// The media type of a container stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamKind {
+    /// A video stream, including attached cover art.
+    Video,
+    /// An audio stream.
+    Audio,
+    /// A subtitle stream.
+    Subtitle,
+    /// A data stream, such as timed metadata or a timecode track.
+    Data,
+    /// An attachment, such as a font embedded in a Matroska file.
+    Attachment,
+    /// A stream FFmpeg cannot classify.
+    Unknown,
+}
+
+impl Display for StreamKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            StreamKind::Video => write!(f, "video"),
+            StreamKind::Audio => write!(f, "audio"),
+            StreamKind::Subtitle => write!(f, "subtitle"),
+            StreamKind::Data => write!(f, "data"),
+            StreamKind::Attachment => write!(f, "attachment"),
+            StreamKind::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// One entry of the container's stream list, from
+/// [`MediaFile::streams`](crate::MediaFile::streams).
+///
+/// Unlike the per-type track lists in [`MediaMetadata`], this covers every
+/// stream, including data streams, attachments and cover art, in container
+/// order.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct StreamMetadata {
+    /// FFmpeg stream index within the container.
+    pub index: usize,
+    /// The stream's media type.
+    pub kind: StreamKind,
+    /// Codec name (e.g. `"h264"`, `"aac"`, `"subrip"`), or `"none"` when
+    /// FFmpeg does not know the codec.
+    pub codec: String,
+    /// Language tag from stream metadata (e.g. `"eng"`), if available.
+    pub language: Option<String>,
+    /// Track number to pass to
+    /// [`video_track`](crate::MediaFile::video_track),
+    /// [`audio_track`](crate::MediaFile::audio_track) or
+    /// [`subtitle_track`](crate::MediaFile::subtitle_track). `None` for
+    /// streams those methods cannot select.
+    pub track_index: Option<usize>,
+    /// `(width, height)` of a selectable video track.
+    pub dimensions: Option<(u32, u32)>,
+    /// Sample rate in hertz of an audio track.
+    pub sample_rate: Option<u32>,
+    /// Channel count of an audio track.
+    pub channels: Option<u16>,
+    /// Whether the stream is flagged as the default of its type.
+    pub default: bool,
+    /// Whether the stream is flagged as forced (usually forced subtitles).
+    pub forced: bool,
+    /// Whether the stream is an attached picture such as cover art.
+    pub attached_picture: bool,
+}
+
 /// Metadata for a subtitle stream.
 ///
 /// Includes codec name, language (if tagged), and track index.
@@ -165,6 +371,8 @@ pub struct SubtitleMetadata {
     pub codec: String,
     /// Language tag from stream metadata (e.g. `"eng"`, `"fre"`), if available.
     pub language: Option<String>,
+    /// Duration of this subtitle track alone, if the container records it.
+    pub duration: Option<Duration>,
     /// Zero-based track number among all subtitle streams in the file.
     pub track_index: usize,
     /// FFmpeg stream index within the container.
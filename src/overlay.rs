@@ -0,0 +1,177 @@
+//! Burned-in timecode overlays.
+//!
+//! [`OverlayOptions`] configures a `HH:MM:SS:FF` timecode drawn into a
+//! corner of each extracted frame, as on reference stills and review
+//! dailies. Text is rendered with a built-in 5×7 pixel font, so no font
+//! files or FFmpeg `drawtext` support are needed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{
+//!     ExtractOptions, FrameRange, MediaFile, OverlayCorner, OverlayOptions, UnbundleError,
+//! };
+//!
+//! let mut unbundler = MediaFile::open("dailies.mov")?;
+//! let config = ExtractOptions::new()
+//!     .with_timecode_overlay(OverlayOptions::new().with_corner(OverlayCorner::TopRight));
+//! let stills = unbundler
+//!     .video()
+//!     .frames_with_options(FrameRange::Interval(240), &config)?;
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+/// Glyph width in font pixels; each glyph is followed by one blank column.
+const GLYPH_WIDTH: u32 = 5;
+/// Glyph height in font pixels.
+const GLYPH_HEIGHT: u32 = 7;
+/// Font pixels of background around the text.
+const PADDING: u32 = 2;
+/// Largest text scale; bigger values are clamped so box sizes cannot
+/// overflow.
+const MAX_SCALE: u32 = 256;
+
+/// Corner of the frame the timecode is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayCorner {
+    /// Top-left corner.
+    TopLeft,
+    /// Top-right corner.
+    TopRight,
+    /// Bottom-left corner. This is the default.
+    #[default]
+    BottomLeft,
+    /// Bottom-right corner.
+    BottomRight,
+}
+
+/// Placement and size of a burned-in timecode.
+///
+/// The timecode is white on a black box. Frame numbers are counted at the
+/// stream's frame rate rounded to a whole number, so 29.97 fps material
+/// gets non-drop-frame timecode running at 30 frames per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OverlayOptions {
+    /// Corner the timecode is drawn in. Default: bottom-left.
+    pub corner: OverlayCorner,
+    /// Screen pixels per font pixel. `None` scales the text with the frame
+    /// height (one step per 240 lines, at least 1). Values are clamped to
+    /// 1–256 when drawing. Default: `None`.
+    pub scale: Option<u32>,
+}
+
+impl OverlayOptions {
+    /// Create options with the default placement and automatic size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the corner the timecode is drawn in.
+    #[must_use]
+    pub fn with_corner(mut self, corner: OverlayCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Set a fixed text scale (screen pixels per font pixel), clamped to
+    /// 1–256.
+    #[must_use]
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = Some(scale.clamp(1, MAX_SCALE));
+        self
+    }
+
+    /// Draw the timecode of `frame_number` into `image`.
+    pub(crate) fn draw(&self, image: &mut DynamicImage, frame_number: u64, frames_per_second: f64) {
+        let text = format_timecode(frame_number, frames_per_second);
+        let scale = self
+            .scale
+            .unwrap_or(image.height() / 240)
+            .clamp(1, MAX_SCALE);
+        let box_width = (text.len() as u32 * (GLYPH_WIDTH + 1) - 1 + 2 * PADDING) * scale;
+        let box_height = (GLYPH_HEIGHT + 2 * PADDING) * scale;
+        let margin = 2 * scale;
+
+        let right = image.width().saturating_sub(box_width + margin);
+        let bottom = image.height().saturating_sub(box_height + margin);
+        let (left, top) = match self.corner {
+            OverlayCorner::TopLeft => (margin, margin),
+            OverlayCorner::TopRight => (right, margin),
+            OverlayCorner::BottomLeft => (margin, bottom),
+            OverlayCorner::BottomRight => (right, bottom),
+        };
+
+        fill(
+            image,
+            left,
+            top,
+            box_width,
+            box_height,
+            Rgba([0, 0, 0, 255]),
+        );
+        let mut glyph_left = left + PADDING * scale;
+        let glyph_top = top + PADDING * scale;
+        for character in text.chars() {
+            let rows = glyph(character);
+            for (row, bits) in rows.iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                        fill(
+                            image,
+                            glyph_left + column * scale,
+                            glyph_top + row as u32 * scale,
+                            scale,
+                            scale,
+                            Rgba([255, 255, 255, 255]),
+                        );
+                    }
+                }
+            }
+            glyph_left += (GLYPH_WIDTH + 1) * scale;
+        }
+    }
+}
+
+/// Format a frame number as `HH:MM:SS:FF` at the rounded frame rate.
+pub(crate) fn format_timecode(frame_number: u64, frames_per_second: f64) -> String {
+    let rate = (frames_per_second.round() as u64).max(1);
+    let seconds = frame_number / rate;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        frame_number % rate
+    )
+}
+
+/// Fill a rectangle, clipped to the image.
+fn fill(image: &mut DynamicImage, left: u32, top: u32, width: u32, height: u32, colour: Rgba<u8>) {
+    let right = left.saturating_add(width).min(image.width());
+    let bottom = top.saturating_add(height).min(image.height());
+    for y in top..bottom {
+        for x in left..right {
+            image.put_pixel(x, y, colour);
+        }
+    }
+}
+
+/// Rows of a 5×7 glyph, most significant of the low five bits leftmost.
+fn glyph(character: char) -> [u8; GLYPH_HEIGHT as usize] {
+    match character {
+        '0' => [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e],
+        '1' => [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        '2' => [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f],
+        '3' => [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e],
+        '4' => [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02],
+        '5' => [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e],
+        '6' => [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e],
+        '7' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e],
+        '9' => [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c],
+        ':' => [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00],
+        _ => [0; GLYPH_HEIGHT as usize],
+    }
+}
@@ -0,0 +1,160 @@
+//! Pooled [`MediaFile`]s for concurrent reads of one source.
+//!
+//! A [`MediaFile`] owns its demuxer and its handles borrow it mutably, so
+//! it cannot be shared between threads. [`MediaFilePool`] keeps a set of
+//! independently opened files for the same source and lends one out per
+//! caller; a file goes back to the pool when its [`PooledMediaFile`] guard
+//! is dropped, so a busy server opens roughly one demuxer per concurrent
+//! request instead of one per request.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{MediaFilePool, UnbundleError};
+//!
+//! let pool = MediaFilePool::open("input.mp4")?;
+//! std::thread::scope(|scope| {
+//!     for frame_number in [0, 100, 200, 300] {
+//!         let pool = &pool;
+//!         scope.spawn(move || -> Result<(), UnbundleError> {
+//!             let mut file = pool.get()?;
+//!             file.video().frame(frame_number)?.save(format!("{frame_number}.png"))?;
+//!             Ok(())
+//!         });
+//!     }
+//! });
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::UnbundleError;
+use crate::unbundle::MediaFile;
+
+/// A pool of [`MediaFile`]s opened from the same source.
+///
+/// Files are opened lazily: [`get`](MediaFilePool::get) reuses an idle file
+/// when there is one and opens a new one otherwise. The pool never closes
+/// files on its own, so it grows to the peak number of concurrent users.
+#[derive(Debug)]
+pub struct MediaFilePool {
+    source: String,
+    idle: Mutex<Vec<MediaFile>>,
+}
+
+impl MediaFilePool {
+    /// Open a media file and create a pool seeded with it.
+    ///
+    /// The initial open validates the source, so later
+    /// [`get`](MediaFilePool::get) calls fail only if the source changes or
+    /// disappears.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`MediaFile::open`].
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, UnbundleError> {
+        MediaFile::open(path).map(Self::seeded)
+    }
+
+    /// Open a URL (or any FFmpeg input string) and create a pool seeded
+    /// with it.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`MediaFile::open_url`].
+    pub fn open_url(url: &str) -> Result<Self, UnbundleError> {
+        MediaFile::open_url(url).map(Self::seeded)
+    }
+
+    /// Create an empty pool that opens `source` on first use.
+    pub(crate) fn from_source(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn seeded(file: MediaFile) -> Self {
+        Self {
+            source: file.source.clone(),
+            idle: Mutex::new(vec![file]),
+        }
+    }
+
+    /// Borrow a [`MediaFile`] for the calling thread.
+    ///
+    /// The file may have been used before; extraction methods seek as they
+    /// need to, so this is the same as reusing a single `MediaFile` for
+    /// consecutive calls.
+    ///
+    /// # Errors
+    ///
+    /// Any error from reopening the source when no idle file is available.
+    pub fn get(&self) -> Result<PooledMediaFile<'_>, UnbundleError> {
+        let idle = self.lock_idle().pop();
+        let file = match idle {
+            Some(file) => file,
+            None => {
+                log::debug!("Opening another pooled media file for {}", self.source);
+                MediaFile::open_source(&self.source)?
+            }
+        };
+        Ok(PooledMediaFile {
+            pool: self,
+            file: Some(file),
+        })
+    }
+
+    /// Number of opened files currently waiting in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.lock_idle().len()
+    }
+
+    /// The path or URL every pooled file is opened from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    // A panic while a caller held a file cannot leave the list itself in
+    // a broken state, so poisoning is ignored.
+    fn lock_idle(&self) -> std::sync::MutexGuard<'_, Vec<MediaFile>> {
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A [`MediaFile`] borrowed from a [`MediaFilePool`].
+///
+/// Dereferences to the file and returns it to the pool on drop.
+#[derive(Debug)]
+pub struct PooledMediaFile<'a> {
+    pool: &'a MediaFilePool,
+    file: Option<MediaFile>,
+}
+
+impl Deref for PooledMediaFile<'_> {
+    type Target = MediaFile;
+
+    fn deref(&self) -> &MediaFile {
+        self.file
+            .as_ref()
+            .expect("pooled file is present until drop")
+    }
+}
+
+impl DerefMut for PooledMediaFile<'_> {
+    fn deref_mut(&mut self) -> &mut MediaFile {
+        self.file
+            .as_mut()
+            .expect("pooled file is present until drop")
+    }
+}
+
+impl Drop for PooledMediaFile<'_> {
+    fn drop(&mut self) {
+        if let Some(file) = self.file.take() {
+            self.pool.lock_idle().push(file);
+        }
+    }
+}
@@ -62,6 +62,59 @@ impl MediaProbe {
         Ok(unbundler.metadata.clone())
     }
 
+    /// Check whether a media source supports random access.
+    ///
+    /// Opens the source, reads its I/O seekable flag and closes it again.
+    /// See [`MediaFile::is_seekable`](crate::MediaFile::is_seekable) for
+    /// which operations depend on seeking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::FileOpen`] if the source cannot be opened or
+    /// recognised as a media file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaProbe, UnbundleError};
+    ///
+    /// if !MediaProbe::is_seekable("input.ts")? {
+    ///     println!("Only sequential processing is available");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn is_seekable<P: AsRef<Path>>(path: P) -> Result<bool, UnbundleError> {
+        let unbundler = MediaFile::open(path)?;
+        Ok(unbundler.is_seekable())
+    }
+
+    /// Check whether a media source looks like a live stream.
+    ///
+    /// Opens the source, infers liveness from its duration and demuxer and
+    /// closes it again. Accepts URLs as well as paths. See
+    /// [`MediaFile::is_live`](crate::MediaFile::is_live) for which
+    /// operations refuse live inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::FileOpen`] if the source cannot be opened or
+    /// recognised as a media file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaProbe, UnbundleError};
+    ///
+    /// if MediaProbe::is_live("https://example.com/live/index.m3u8")? {
+    ///     println!("Live stream: no fixed duration");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn is_live<P: AsRef<Path>>(path: P) -> Result<bool, UnbundleError> {
+        let unbundler = MediaFile::open(path)?;
+        Ok(unbundler.is_live())
+    }
+
     /// Probe multiple media files and return their metadata.
     ///
     /// Files that cannot be probed produce an `Err` entry in the result
@@ -79,7 +79,8 @@ pub enum OperationType {
 pub struct ProgressInfo {
     /// What kind of work is being performed.
     pub operation: OperationType,
-    /// How many items (frames / packets) have been processed so far.
+    /// How many items (frames / packets) have been processed so far. For
+    /// whole-track audio analysis this is the number of decoded samples.
     pub current: u64,
     /// Total items expected, if known ahead of time.
     pub total: Option<u64>,
@@ -209,6 +210,18 @@ impl ProgressTracker {
         }
     }
 
+    /// Move the position to `position` items (for example decoded samples)
+    /// and count one unit of work towards the reporting batch.
+    pub(crate) fn advance_to(&mut self, position: u64, timestamp: Option<Duration>) {
+        self.current = position;
+        self.items_since_last_report += 1;
+
+        if self.items_since_last_report >= self.batch_size {
+            self.report(None, timestamp);
+            self.items_since_last_report = 0;
+        }
+    }
+
     /// Unconditionally emit a final progress report.
     pub(crate) fn finish(&mut self) {
         self.report(None, None);
@@ -1,33 +1,39 @@
 //! Parallel video frame extraction.
 //!
 //! This module provides [`parallel_extract_frames`] which distributes frame
-//! decoding across multiple threads using [`rayon`]. Each worker opens its
-//! own demuxer and decoder so there is no shared mutable state.
+//! decoding across multiple threads using [`rayon`]. Each worker borrows its
+//! own demuxer from a [`MediaFilePool`] so there is no shared mutable state.
 //!
 //! The public API is exposed through
 //! [`VideoHandle::frames_parallel`](crate::VideoHandle) — this module
 //! contains only the internal implementation.
 
+use std::time::Instant;
+
+use ::rayon::ThreadPoolBuilder;
 use ::rayon::iter::{IntoParallelIterator, ParallelIterator};
 use image::DynamicImage;
 
 use crate::configuration::ExtractOptions;
 use crate::error::UnbundleError;
 use crate::metadata::VideoMetadata;
-use crate::unbundle::MediaFile;
+use crate::pool::MediaFilePool;
 use crate::video::FrameRange;
 
 /// Extract frames in parallel by splitting work across rayon threads.
 ///
-/// Each worker opens its own file context and decodes a contiguous sub-range
-/// of frames. Results are collected and returned in frame-number order.
+/// Each worker borrows a file context from a pool shared by the call and
+/// decodes a contiguous sub-range of frames, so a worker handling several
+/// chunks opens the source only once. Results are collected and returned in frame-number order.
 ///
 /// # Arguments
 ///
 /// * `source` — Input source string (path/URL).
 /// * `frame_numbers` — Sorted, deduplicated frame numbers to extract.
 /// * `video_metadata` — Cached video metadata (used for validation only).
-/// * `config` — Extraction settings forwarded to each worker.
+/// * `config` — Extraction settings forwarded to each worker. When
+///   [`ExtractOptions::with_parallel_threads`] is set, work runs on a
+///   dedicated pool of that size instead of the global one.
 pub(crate) fn parallel_extract_frames(
     source: &str,
     frame_numbers: &[u64],
@@ -43,18 +49,45 @@ pub(crate) fn parallel_extract_frames(
     // to decode sequentially than to seek to individually.
     let chunks = split_into_runs(frame_numbers, 30);
 
-    let source = source.to_string();
+    let pool = MediaFilePool::from_source(source);
     let config = config.clone();
+    // One budget covers the whole call, not each worker's chunk.
+    let deadline = config.budget_deadline();
+
+    let extract = || -> Result<Vec<Vec<(u64, DynamicImage)>>, UnbundleError> {
+        chunks
+            .into_par_iter()
+            .map(|chunk| {
+                if config.is_cancelled() {
+                    return Err(UnbundleError::Cancelled);
+                }
+                let Some(deadline) = deadline else {
+                    return decode_chunk(&pool, &chunk, &config);
+                };
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(Vec::new());
+                }
+                let mut chunk_config = config.clone();
+                chunk_config.time_budget = Some(remaining);
+                decode_chunk(&pool, &chunk, &chunk_config)
+            })
+            .collect()
+    };
 
-    let results: Result<Vec<Vec<(u64, DynamicImage)>>, UnbundleError> = chunks
-        .into_par_iter()
-        .map(|chunk| {
-            if config.is_cancelled() {
-                return Err(UnbundleError::Cancelled);
-            }
-            decode_chunk(&source, &chunk, &config)
-        })
-        .collect();
+    // A caller-bounded pool keeps this request from competing with others
+    // for the whole global pool.
+    let results = match config.parallel_threads {
+        Some(threads) => {
+            log::debug!("Extracting frames on a dedicated pool of {threads} threads");
+            ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| UnbundleError::ThreadPoolError(e.to_string()))?
+                .install(extract)
+        }
+        None => extract(),
+    };
 
     let mut all_frames: Vec<(u64, DynamicImage)> = results?.into_iter().flatten().collect();
     all_frames.sort_by_key(|(number, _)| *number);
@@ -87,13 +120,13 @@ fn split_into_runs(frame_numbers: &[u64], gap_threshold: u64) -> Vec<Vec<u64>> {
     runs
 }
 
-/// Decode a chunk of frame numbers from a fresh file context.
+/// Decode a chunk of frame numbers from a pooled file context.
 fn decode_chunk(
-    source: &str,
+    pool: &MediaFilePool,
     frame_numbers: &[u64],
     config: &ExtractOptions,
 ) -> Result<Vec<(u64, DynamicImage)>, UnbundleError> {
-    let mut unbundler = MediaFile::open_source(source)?;
+    let mut unbundler = pool.get()?;
     let mut frames = Vec::with_capacity(frame_numbers.len());
 
     // Use for_each_frame_with_options with Specific to leverage sequential
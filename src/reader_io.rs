@@ -0,0 +1,208 @@
+//! Demuxing from a Rust reader.
+//!
+//! [`MediaFile::open_reader`](crate::MediaFile::open_reader) gives FFmpeg an
+//! AVIO context whose read and seek callbacks forward to a [`Read`] +
+//! [`Seek`] value, so the input is pulled in as the demuxer needs it rather
+//! than copied into memory first.
+
+use std::ffi::{c_int, c_void};
+use std::io::{ErrorKind, Read, Result as IoResult, Seek, SeekFrom};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use ffmpeg_next::Error as FfmpegError;
+use ffmpeg_next::format::context::Input;
+use ffmpeg_sys_next::{
+    AVERROR_EOF, AVERROR_EXTERNAL, AVFMT_FLAG_CUSTOM_IO, AVIOContext, AVSEEK_FORCE, AVSEEK_SIZE,
+};
+
+use crate::error::UnbundleError;
+
+/// Source string recorded for inputs opened from a reader.
+pub(crate) const READER_SOURCE: &str = "<reader>";
+
+/// Size of the buffer FFmpeg reads into.
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// `whence` values of the seek callback, as in C's `fseek`.
+const SEEK_SET: c_int = 0;
+const SEEK_CUR: c_int = 1;
+const SEEK_END: c_int = 2;
+
+/// A reader FFmpeg can demux from.
+pub(crate) trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// The AVIO context of a reader-backed input and the reader behind it.
+///
+/// Must be dropped after the [`Input`] using it, since the demuxer does
+/// not free custom I/O itself.
+pub(crate) struct ReaderInput {
+    io_context: *mut AVIOContext,
+    /// The outer box gives the I/O context a stable, thin pointer to the
+    /// reader.
+    _reader: Box<Box<dyn ReadSeek>>,
+}
+
+// SAFETY: the I/O context is only used by the demuxer that owns it, which
+// moves between threads together with this value, and the reader is `Send`.
+unsafe impl Send for ReaderInput {}
+
+impl ReaderInput {
+    /// Open `reader` as a demuxer input and read its stream information.
+    pub(crate) fn open(reader: Box<dyn ReadSeek>) -> Result<(Input, Self), UnbundleError> {
+        let mut reader = Box::new(reader);
+        // SAFETY: the buffer and I/O context are checked for allocation
+        // failure before use, the opaque pointer targets the heap-allocated
+        // reader kept in the returned guard, and `avformat_open_input`
+        // frees the format context itself when it fails.
+        unsafe {
+            let buffer = ffmpeg_sys_next::av_malloc(READ_BUFFER_SIZE) as *mut u8;
+            if buffer.is_null() {
+                return Err(open_error("failed to allocate the read buffer".to_string()));
+            }
+            let opaque: *mut Box<dyn ReadSeek> = &mut *reader;
+            let io_context = ffmpeg_sys_next::avio_alloc_context(
+                buffer,
+                READ_BUFFER_SIZE as c_int,
+                0,
+                opaque as *mut c_void,
+                Some(read_packet),
+                None,
+                Some(seek),
+            );
+            if io_context.is_null() {
+                ffmpeg_sys_next::av_free(buffer as *mut c_void);
+                return Err(open_error("failed to allocate the I/O context".to_string()));
+            }
+            // From here on the guard frees the I/O context on every path.
+            let guard = Self {
+                io_context,
+                _reader: reader,
+            };
+
+            let mut format_context = ffmpeg_sys_next::avformat_alloc_context();
+            if format_context.is_null() {
+                return Err(open_error(
+                    "failed to allocate the format context".to_string(),
+                ));
+            }
+            (*format_context).pb = io_context;
+            (*format_context).flags |= AVFMT_FLAG_CUSTOM_IO;
+
+            let result = ffmpeg_sys_next::avformat_open_input(
+                &mut format_context,
+                ptr::null(),
+                ptr::null(),
+                ptr::null_mut(),
+            );
+            if result < 0 {
+                return Err(open_error(FfmpegError::from(result).to_string()));
+            }
+            let result =
+                ffmpeg_sys_next::avformat_find_stream_info(format_context, ptr::null_mut());
+            if result < 0 {
+                ffmpeg_sys_next::avformat_close_input(&mut format_context);
+                return Err(open_error(FfmpegError::from(result).to_string()));
+            }
+            Ok((Input::wrap(format_context), guard))
+        }
+    }
+}
+
+impl Drop for ReaderInput {
+    fn drop(&mut self) {
+        // SAFETY: the demuxer that used the context has been closed, and
+        // the buffer may have been replaced by FFmpeg, so it is freed
+        // through the context rather than the original pointer.
+        unsafe {
+            ffmpeg_sys_next::av_freep((&raw mut (*self.io_context).buffer).cast::<c_void>());
+            ffmpeg_sys_next::avio_context_free(&mut self.io_context);
+        }
+    }
+}
+
+fn open_error(reason: String) -> UnbundleError {
+    UnbundleError::SourceOpen {
+        input_source: READER_SOURCE.to_string(),
+        reason,
+    }
+}
+
+/// AVIO read callback for [`ReaderInput`].
+unsafe extern "C" fn read_packet(opaque: *mut c_void, buffer: *mut u8, size: c_int) -> c_int {
+    // SAFETY: `opaque` is the boxed reader set in `ReaderInput::open`, and
+    // FFmpeg passes `size` writable bytes.
+    let (reader, buffer) = unsafe {
+        (
+            &mut *(opaque as *mut Box<dyn ReadSeek>),
+            std::slice::from_raw_parts_mut(buffer, size.max(0) as usize),
+        )
+    };
+    // A panic must not unwind into FFmpeg; report it as a read error.
+    catch_unwind(AssertUnwindSafe(|| read_into(reader, buffer))).unwrap_or_else(|_| {
+        log::error!("Media input reader panicked");
+        AVERROR_EXTERNAL
+    })
+}
+
+/// Fill `buffer` from `reader`, returning the byte count or an AVERROR.
+fn read_into(reader: &mut dyn ReadSeek, buffer: &mut [u8]) -> c_int {
+    loop {
+        match reader.read(buffer) {
+            Ok(0) => return AVERROR_EOF,
+            Ok(read) => return read as c_int,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => {
+                log::warn!("Reading media input failed: {error}");
+                return AVERROR_EXTERNAL;
+            }
+        }
+    }
+}
+
+/// AVIO seek callback for [`ReaderInput`]. Returns the new position, or
+/// the total size for `AVSEEK_SIZE`.
+unsafe extern "C" fn seek(opaque: *mut c_void, offset: i64, whence: c_int) -> i64 {
+    // SAFETY: `opaque` is the boxed reader set in `ReaderInput::open`.
+    let reader = unsafe { &mut *(opaque as *mut Box<dyn ReadSeek>) };
+    // A panic must not unwind into FFmpeg; report it as a seek error.
+    catch_unwind(AssertUnwindSafe(|| seek_to(reader, offset, whence))).unwrap_or_else(|_| {
+        log::error!("Media input reader panicked while seeking");
+        i64::from(AVERROR_EXTERNAL)
+    })
+}
+
+/// Move `reader` as FFmpeg's seek callback asks, returning the new
+/// position, the total size for `AVSEEK_SIZE`, or an AVERROR.
+fn seek_to(reader: &mut dyn ReadSeek, offset: i64, whence: c_int) -> i64 {
+    // AVSEEK_FORCE only says the seek may be slow, which readers ignore.
+    let position = match whence & !AVSEEK_FORCE {
+        AVSEEK_SIZE => stream_size(reader),
+        SEEK_SET => match u64::try_from(offset) {
+            Ok(offset) => reader.seek(SeekFrom::Start(offset)),
+            Err(_) => return i64::from(AVERROR_EXTERNAL),
+        },
+        SEEK_CUR => reader.seek(SeekFrom::Current(offset)),
+        SEEK_END => reader.seek(SeekFrom::End(offset)),
+        _ => return i64::from(AVERROR_EXTERNAL),
+    };
+    match position {
+        Ok(position) => i64::try_from(position).unwrap_or(i64::from(AVERROR_EXTERNAL)),
+        Err(error) => {
+            log::debug!("Seeking media input failed: {error}");
+            i64::from(AVERROR_EXTERNAL)
+        }
+    }
+}
+
+/// Length of the reader's stream, leaving its position unchanged.
+fn stream_size(reader: &mut dyn ReadSeek) -> IoResult<u64> {
+    let position = reader.stream_position()?;
+    let size = reader.seek(SeekFrom::End(0))?;
+    if size != position {
+        reader.seek(SeekFrom::Start(position))?;
+    }
+    Ok(size)
+}
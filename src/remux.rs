@@ -2,7 +2,15 @@
 //!
 //! This module provides [`Remuxer`] for converting media files between
 //! container formats without re-encoding. This is equivalent to
-//! `ffmpeg -i input.mkv -c copy output.mp4`.
+//! `ffmpeg -i input.mkv -c copy output.mp4`. It also backs
+//! [`MediaFile::split_segments`](crate::MediaFile::split_segments), which
+//! copies a file into consecutive fixed-length pieces,
+//! [`MediaFile::concat`](crate::MediaFile::concat), which joins compatible
+//! files back into one, and
+//! [`MediaFile::byte_range_for`](crate::MediaFile::byte_range_for), which
+//! locates a clip's packets in the source file without copying them, and
+//! [`MediaFile::extract_clip`](crate::MediaFile::extract_clip), which
+//! copies one time window with its streams kept in sync.
 //!
 //! # Example
 //!
@@ -14,13 +22,18 @@
 //! # Ok::<(), UnbundleError>(())
 //! ```
 
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use ffmpeg_next::{codec::Id, media::Type};
+use ffmpeg_next::format::context::{Input, Output};
+use ffmpeg_next::format::stream::{Disposition, Stream};
+use ffmpeg_next::{Packet, Rational, Rescale, codec::Id, codec::Parameters, media::Type, rescale};
 
 use crate::configuration::ExtractOptions;
 use crate::error::UnbundleError;
 use crate::progress::{OperationType, ProgressTracker};
+use crate::unbundle::MediaFile;
 
 /// Lossless container format converter.
 ///
@@ -50,6 +63,9 @@ pub struct Remuxer {
     copy_video: bool,
     copy_audio: bool,
     copy_subtitles: bool,
+    copy_chapters: bool,
+    strip_metadata: bool,
+    fix_timestamps: bool,
 }
 
 impl Remuxer {
@@ -86,6 +102,9 @@ impl Remuxer {
             copy_video: true,
             copy_audio: true,
             copy_subtitles: true,
+            copy_chapters: true,
+            strip_metadata: false,
+            fix_timestamps: false,
         })
     }
 
@@ -134,6 +153,50 @@ impl Remuxer {
         self.exclude_subtitles()
     }
 
+    /// Copy chapter markers from the input to the output container.
+    ///
+    /// Each chapter keeps its ID, time base, start/end and metadata (such as
+    /// its title), so an MKV → MP4 remux of an audiobook or lecture keeps its
+    /// chapter navigation. Containers without chapter support ignore them.
+    ///
+    /// Enabled by default.
+    #[must_use]
+    pub fn with_chapters(mut self, copy: bool) -> Self {
+        self.copy_chapters = copy;
+        self
+    }
+
+    /// Drop metadata tags instead of carrying them over.
+    ///
+    /// By default the input's container-level tags (title, artist,
+    /// creation time, …) and each kept stream's tags (language, handler
+    /// name, …) are copied to the output. Pass `true` to write the output
+    /// without them.
+    #[must_use]
+    pub fn with_strip_metadata(mut self, strip: bool) -> Self {
+        self.strip_metadata = strip;
+        self
+    }
+
+    /// Regenerate missing timestamps and repair non-monotonic ones while
+    /// copying.
+    ///
+    /// Partially downloaded or naively concatenated files often carry
+    /// packets without a PTS, timestamps that jump backwards, or a negative
+    /// start time, all of which break playback and most muxers. When
+    /// enabled, the demuxer is asked to generate missing PTS (the library
+    /// equivalent of `-fflags +genpts`), each output stream's DTS is forced
+    /// to be strictly increasing with PTS never earlier than DTS, and the
+    /// muxer shifts the output so it starts at zero.
+    ///
+    /// Disabled by default, since it rewrites timestamps that are usually
+    /// correct.
+    #[must_use]
+    pub fn with_fix_timestamps(mut self, fix: bool) -> Self {
+        self.fix_timestamps = fix;
+        self
+    }
+
     /// Execute the remuxing operation.
     ///
     /// Reads all packets from the input, remaps stream indices, and writes
@@ -178,13 +241,22 @@ impl Remuxer {
     /// # Ok::<(), UnbundleError>(())
     /// ```
     pub fn run_with_options(&self, config: &ExtractOptions) -> Result<(), UnbundleError> {
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.remux(config)
+            .map_err(|error| log_capture.attach(error))
+    }
+
+    /// Copy the selected streams, backing
+    /// [`run_with_options`](Remuxer::run_with_options).
+    fn remux(&self, config: &ExtractOptions) -> Result<(), UnbundleError> {
         log::info!(
-            "Remuxing {} → {} (video={}, audio={}, subtitles={})",
+            "Remuxing {} → {} (video={}, audio={}, subtitles={}, chapters={})",
             self.input_path.display(),
             self.output_path.display(),
             self.copy_video,
             self.copy_audio,
             self.copy_subtitles,
+            self.copy_chapters,
         );
         let mut input_context =
             ffmpeg_next::format::input(&self.input_path).map_err(|e| UnbundleError::FileOpen {
@@ -199,6 +271,17 @@ impl Remuxer {
             }
         })?;
 
+        if self.fix_timestamps {
+            // SAFETY: both contexts are open and exclusively owned here;
+            // these flags are plain integers read by av_read_frame and
+            // the muxer respectively.
+            unsafe {
+                (*input_context.as_mut_ptr()).flags |= ffmpeg_sys_next::AVFMT_FLAG_GENPTS as i32;
+                (*output_context.as_mut_ptr()).avoid_negative_ts =
+                    ffmpeg_sys_next::AVFMT_AVOID_NEG_TS_MAKE_ZERO as i32;
+            }
+        }
+
         // Build stream mapping: input_stream_index → output_stream_index.
         // Streams that are excluded get None.
         let mut stream_map: Vec<Option<usize>> = Vec::new();
@@ -222,6 +305,9 @@ impl Remuxer {
                 unsafe {
                     (*out_stream.parameters().as_mut_ptr()).codec_tag = 0;
                 }
+                if !self.strip_metadata {
+                    out_stream.set_metadata(stream.metadata().to_owned());
+                }
                 stream_map.push(Some(output_stream_count));
                 output_stream_count += 1;
             } else {
@@ -229,6 +315,13 @@ impl Remuxer {
             }
         }
 
+        if !self.strip_metadata {
+            output_context.set_metadata(input_context.metadata().to_owned());
+        }
+        if self.copy_chapters {
+            copy_chapters(&input_context, &mut output_context)?;
+        }
+
         output_context.write_header()?;
 
         // Estimate total packets from the input duration (rough approximation).
@@ -240,6 +333,10 @@ impl Remuxer {
             config.batch_size,
         );
 
+        // Last DTS written per output stream, used to keep timestamps
+        // monotonic when `fix_timestamps` is enabled.
+        let mut last_dts: Vec<Option<i64>> = vec![None; output_stream_count];
+
         // Copy packets, remapping stream indices.
         for (stream, mut packet) in input_context.packets() {
             if config.is_cancelled() {
@@ -257,6 +354,11 @@ impl Remuxer {
             packet.set_stream(output_idx);
             packet.rescale_ts(input_time_base, output_time_base);
             packet.set_position(-1);
+
+            if self.fix_timestamps {
+                fix_packet_timestamps(&mut packet, &mut last_dts[output_idx]);
+            }
+
             packet.write_interleaved(&mut output_context)?;
 
             tracker.advance(None, None);
@@ -268,3 +370,609 @@ impl Remuxer {
         Ok(())
     }
 }
+
+/// Duplicate every chapter of `input` into `output`, including its metadata.
+///
+/// Must run before the output header is written, since muxers read the
+/// chapter list there.
+fn copy_chapters(input: &Input, output: &mut Output) -> Result<(), UnbundleError> {
+    for chapter in input.chapters() {
+        let metadata = chapter.metadata();
+        let title = metadata.get("title").unwrap_or_default().to_string();
+        let entries: Vec<(String, String)> = metadata
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        let mut copied = output.add_chapter(
+            chapter.id(),
+            chapter.time_base(),
+            chapter.start(),
+            chapter.end(),
+            title,
+        )?;
+        for (key, value) in entries {
+            copied.set_metadata(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Force a packet's DTS to be strictly increasing relative to `last_dts`
+/// and its PTS to be no earlier than its DTS.
+///
+/// Missing values are filled from whichever of PTS/DTS is present. Packets
+/// with neither are left untouched for the muxer to handle.
+fn fix_packet_timestamps(packet: &mut Packet, last_dts: &mut Option<i64>) {
+    let (pts, dts) = match (packet.pts(), packet.dts()) {
+        (Some(pts), Some(dts)) => (pts, dts),
+        (Some(pts), None) => (pts, pts),
+        (None, Some(dts)) => (dts, dts),
+        (None, None) => return,
+    };
+
+    let dts = match *last_dts {
+        Some(previous) if dts <= previous => {
+            log::debug!("Non-monotonic DTS {dts} after {previous}, clamping");
+            previous + 1
+        }
+        _ => dts,
+    };
+    let pts = pts.max(dts);
+
+    packet.set_dts(Some(dts));
+    packet.set_pts(Some(pts));
+    *last_dts = Some(dts);
+}
+
+/// One file written by [`MediaFile::split_segments`](crate::MediaFile::split_segments).
+#[derive(Debug, Clone)]
+pub struct SegmentInfo {
+    /// Path of the written segment file.
+    pub path: PathBuf,
+    /// Source timestamp of the segment's first keyframe.
+    pub start: Duration,
+    /// Source timestamp where the next segment starts, or the end of the
+    /// last packet for the final segment.
+    pub end: Duration,
+}
+
+/// Stream-copy every audio, video, and subtitle stream into consecutive
+/// files of roughly `segment_duration`, cutting only on keyframes.
+pub(crate) fn split_segments_impl(
+    unbundler: &mut MediaFile,
+    directory: &Path,
+    segment_duration: Duration,
+    extension: &str,
+) -> Result<Vec<SegmentInfo>, UnbundleError> {
+    if segment_duration.is_zero() {
+        return Err(UnbundleError::InvalidInterval);
+    }
+    log::info!(
+        "Splitting into {:?} segments in {} (.{})",
+        segment_duration,
+        directory.display(),
+        extension,
+    );
+    fs::create_dir_all(directory)?;
+
+    // Cut on video keyframes when there is video; otherwise on keyframes
+    // of whichever stream comes first (usually every audio packet).
+    let reference_stream = unbundler.video_stream_index;
+
+    // Parameters of each copied stream, and the output index for each
+    // input stream (`None` for streams that are not copied).
+    let mut copied: Vec<Parameters> = Vec::new();
+    let mut stream_map: Vec<Option<usize>> = Vec::new();
+    for stream in unbundler.input_context.streams() {
+        let medium = stream.parameters().medium();
+        if matches!(medium, Type::Video | Type::Audio | Type::Subtitle) {
+            stream_map.push(Some(copied.len()));
+            copied.push(stream.parameters().clone());
+        } else {
+            stream_map.push(None);
+        }
+    }
+    if copied.is_empty() {
+        return Err(UnbundleError::StreamCopyError(
+            "No audio, video, or subtitle streams to copy".to_string(),
+        ));
+    }
+
+    crate::unbundle::seek_input(&mut unbundler.input_context, 0)?;
+
+    let mut segments: Vec<SegmentInfo> = Vec::new();
+    let mut output: Option<Output> = None;
+    let mut next_boundary = Duration::ZERO;
+    let mut last_end = Duration::ZERO;
+
+    for (stream, mut packet) in unbundler.input_context.packets() {
+        let input_index = stream.index();
+        let Some(output_index) = stream_map.get(input_index).copied().flatten() else {
+            continue;
+        };
+        let input_time_base = stream.time_base();
+        let packet_time = packet.pts().or(packet.dts()).map(|pts| {
+            let seconds = crate::conversion::pts_to_seconds(pts, input_time_base);
+            Duration::from_secs_f64(seconds.max(0.0))
+        });
+
+        let is_reference = reference_stream.is_none_or(|reference| reference == input_index);
+        if is_reference
+            && packet.is_key()
+            && let Some(time) = packet_time
+            && (output.is_none() || time >= next_boundary)
+        {
+            if let Some(mut finished) = output.take() {
+                finish_segment(&mut finished)?;
+                if let Some(previous) = segments.last_mut() {
+                    previous.end = time;
+                }
+            }
+
+            let path = directory.join(format!("segment_{:03}.{extension}", segments.len()));
+            output = Some(open_segment(&path, &copied)?);
+            segments.push(SegmentInfo {
+                path,
+                start: time,
+                end: time,
+            });
+            // The first boundary after `time`, skipping any a long GOP has
+            // already passed. Computed directly, since a far-off timestamp
+            // would take a step per skipped boundary.
+            let boundary_index = time.as_nanos() / segment_duration.as_nanos() + 1;
+            next_boundary = u64::try_from(boundary_index * segment_duration.as_nanos())
+                .map_or(Duration::MAX, Duration::from_nanos);
+        }
+
+        // Packets before the first keyframe cannot be decoded on their own.
+        let Some(current) = output.as_mut() else {
+            continue;
+        };
+
+        if let Some(time) = packet_time {
+            let seconds = crate::conversion::pts_to_seconds(packet.duration(), input_time_base);
+            last_end = last_end.max(time + Duration::from_secs_f64(seconds.max(0.0)));
+        }
+
+        let output_time_base = current.stream(output_index).unwrap().time_base();
+        packet.set_stream(output_index);
+        packet.rescale_ts(input_time_base, output_time_base);
+        packet.set_position(-1);
+        packet.write_interleaved(current).map_err(|error| {
+            UnbundleError::StreamCopyError(format!("Failed to write packet: {error}"))
+        })?;
+    }
+
+    if let Some(mut finished) = output.take() {
+        finish_segment(&mut finished)?;
+        if let Some(last) = segments.last_mut() {
+            last.end = last_end.max(last.start);
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Create a segment file with one output stream per copied input stream.
+fn open_segment(path: &Path, streams: &[Parameters]) -> Result<Output, UnbundleError> {
+    let mut output = ffmpeg_next::format::output(&path).map_err(|error| {
+        UnbundleError::StreamCopyError(format!("Failed to create {}: {error}", path.display()))
+    })?;
+
+    for parameters in streams {
+        let mut out_stream = output
+            .add_stream(ffmpeg_next::encoder::find(Id::None))
+            .map_err(|error| {
+                UnbundleError::StreamCopyError(format!("Failed to add stream: {error}"))
+            })?;
+        out_stream.set_parameters(parameters.clone());
+        // SAFETY: the stream was just added and its parameters are owned
+        // by the output context; resetting the tag lets the muxer choose.
+        unsafe {
+            (*out_stream.parameters().as_mut_ptr()).codec_tag = 0;
+        }
+    }
+
+    // SAFETY: the context is open and exclusively owned; the muxer reads
+    // this plain integer when writing. Shifting to zero makes each
+    // segment start at time zero instead of at its source offset.
+    unsafe {
+        (*output.as_mut_ptr()).avoid_negative_ts =
+            ffmpeg_sys_next::AVFMT_AVOID_NEG_TS_MAKE_ZERO as i32;
+    }
+
+    output.write_header().map_err(|error| {
+        UnbundleError::StreamCopyError(format!("Failed to write header: {error}"))
+    })?;
+    Ok(output)
+}
+
+/// Write the trailer that completes a segment file.
+fn finish_segment(output: &mut Output) -> Result<(), UnbundleError> {
+    output.write_trailer().map_err(|error| {
+        UnbundleError::StreamCopyError(format!("Failed to write trailer: {error}"))
+    })
+}
+
+/// Whether stream copies carry `stream` over: audio and video, except
+/// attached pictures such as cover art, which are not part of the timeline.
+fn is_copied_stream(stream: &Stream) -> bool {
+    matches!(stream.parameters().medium(), Type::Video | Type::Audio)
+        && !stream.disposition().contains(Disposition::ATTACHED_PIC)
+}
+
+/// Time base of output stream `output_index`.
+fn output_stream_time_base(
+    output: &Output,
+    output_index: usize,
+) -> Result<Rational, UnbundleError> {
+    output
+        .stream(output_index)
+        .map(|stream| stream.time_base())
+        .ok_or_else(|| {
+            UnbundleError::StreamCopyError(format!("Output stream {output_index} does not exist"))
+        })
+}
+
+/// Stream-copy the audio and video of `inputs` one after another into
+/// `output_path`, shifting each file's timestamps to start where the
+/// previous file ended.
+pub(crate) fn concat_impl(inputs: &[&Path], output_path: &Path) -> Result<(), UnbundleError> {
+    let Some(first_path) = inputs.first() else {
+        return Err(UnbundleError::StreamCopyError(
+            "No input files to concatenate".to_string(),
+        ));
+    };
+    log::info!(
+        "Concatenating {} files into {}",
+        inputs.len(),
+        output_path.display()
+    );
+
+    // Check every file before writing anything, so an incompatible file
+    // late in the list does not leave a partial output behind.
+    let mut files = vec![MediaFile::open(first_path)?];
+    for path in &inputs[1..] {
+        let file = MediaFile::open(path)?;
+        let report = files[0].concat_compatibility(&file);
+        if !report.can_stream_copy() {
+            return Err(UnbundleError::ConcatIncompatible {
+                path: path.to_path_buf(),
+                reason: report.mismatches.join("; "),
+            });
+        }
+        files.push(file);
+    }
+
+    let copied: Vec<Parameters> = files[0]
+        .input_context
+        .streams()
+        .filter(is_copied_stream)
+        .map(|stream| stream.parameters())
+        .collect();
+    // Media type of each output stream, in output order.
+    let output_media: Vec<Type> = copied.iter().map(Parameters::medium).collect();
+    if copied.is_empty() {
+        return Err(UnbundleError::StreamCopyError(
+            "No audio or video streams to copy".to_string(),
+        ));
+    }
+    let mut output = open_segment(output_path, &copied)?;
+
+    let mut last_dts: Vec<Option<i64>> = vec![None; copied.len()];
+    // Where the next file starts in the output, in `AV_TIME_BASE` units.
+    let mut offset: i64 = 0;
+    for file in &mut files {
+        // Output index for each input stream. As in the compatibility
+        // check, the n-th video stream maps to the n-th video output and
+        // likewise for audio, whatever order the file lists them in.
+        let mut seen_video = 0;
+        let mut seen_audio = 0;
+        let stream_map: Vec<Option<usize>> = file
+            .input_context
+            .streams()
+            .map(|stream| {
+                if !is_copied_stream(&stream) {
+                    return None;
+                }
+                let medium = stream.parameters().medium();
+                let seen = if medium == Type::Video {
+                    &mut seen_video
+                } else {
+                    &mut seen_audio
+                };
+                let nth = *seen;
+                *seen += 1;
+                output_media
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, output_medium)| **output_medium == medium)
+                    .nth(nth)
+                    .map(|(output_index, _)| output_index)
+            })
+            .collect();
+
+        // SAFETY: the context is open; `start_time` is a plain integer.
+        let start_time = unsafe { (*file.input_context.as_ptr()).start_time };
+        let start_time = if start_time == ffmpeg_sys_next::AV_NOPTS_VALUE {
+            0
+        } else {
+            start_time
+        };
+        let shift = offset - start_time;
+        let mut file_end = offset;
+
+        for (stream, mut packet) in file.input_context.packets() {
+            let Some(output_index) = stream_map.get(stream.index()).copied().flatten() else {
+                continue;
+            };
+            let input_time_base = stream.time_base();
+            let output_time_base = output_stream_time_base(&output, output_index)?;
+
+            if let Some(pts) = packet.pts().or(packet.dts()) {
+                let end = (pts + packet.duration()).rescale(input_time_base, rescale::TIME_BASE);
+                file_end = file_end.max(end + shift);
+            }
+
+            packet.rescale_ts(input_time_base, output_time_base);
+            let shift_in_output = shift.rescale(rescale::TIME_BASE, output_time_base);
+            packet.set_pts(packet.pts().map(|pts| pts + shift_in_output));
+            packet.set_dts(packet.dts().map(|dts| dts + shift_in_output));
+            fix_packet_timestamps(&mut packet, &mut last_dts[output_index]);
+
+            packet.set_stream(output_index);
+            packet.set_position(-1);
+            packet.write_interleaved(&mut output).map_err(|error| {
+                UnbundleError::StreamCopyError(format!("Failed to write packet: {error}"))
+            })?;
+        }
+
+        offset = file_end;
+    }
+
+    finish_segment(&mut output)
+}
+
+/// Byte span of the source file holding `stream_index`'s packets from the
+/// last keyframe at or before `start` through `end`.
+pub(crate) fn byte_range_impl(
+    unbundler: &mut MediaFile,
+    stream_index: usize,
+    start: Duration,
+    end: Duration,
+) -> Result<(u64, u64), UnbundleError> {
+    if start >= end {
+        return Err(UnbundleError::InvalidRange {
+            start: format!("{start:?}"),
+            end: format!("{end:?}"),
+        });
+    }
+    if unbundler.input_context.stream(stream_index).is_none() {
+        return Err(UnbundleError::StreamCopyError(format!(
+            "Stream {stream_index} does not exist"
+        )));
+    }
+
+    // The seek lands on a keyframe at or before `start`, so the window's
+    // opening keyframe is among the first packets read.
+    let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start);
+    crate::unbundle::seek_input(&mut unbundler.input_context, seek_timestamp)?;
+
+    // Packets arrive in decode order, so a B-frame shown before `end` can
+    // follow a frame shown after it. Reading stops once a decode timestamp
+    // passes `end`, since no later packet can be shown earlier than that;
+    // without decode timestamps, after this many packets past `end`.
+    const MAX_REORDERED_PACKETS: usize = 16;
+
+    let mut range: Option<(u64, u64)> = None;
+    let mut packets_past_end = 0;
+    for (stream, packet) in unbundler.input_context.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        let to_time = |timestamp: i64| {
+            let seconds = crate::conversion::pts_to_seconds(timestamp, stream.time_base());
+            Duration::from_secs_f64(seconds.max(0.0))
+        };
+        if packet.dts().is_some_and(|dts| to_time(dts) > end) {
+            break;
+        }
+        let Some(pts) = packet.pts().or(packet.dts()) else {
+            continue;
+        };
+        let time = to_time(pts);
+        if time > end {
+            if packet.dts().is_none() {
+                packets_past_end += 1;
+                if packets_past_end >= MAX_REORDERED_PACKETS {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        // A later keyframe that still precedes `start` is a closer
+        // starting point than the one the seek found.
+        if packet.is_key() && time <= start {
+            range = None;
+        }
+        // Packets before the opening keyframe cannot be decoded on their own.
+        if range.is_none() && !packet.is_key() {
+            continue;
+        }
+        // Positions are -1 when the demuxer does not know them.
+        let Ok(position) = u64::try_from(packet.position()) else {
+            continue;
+        };
+        let packet_end = position + packet.size() as u64;
+        range = Some(match range {
+            Some((first, last)) => (first.min(position), last.max(packet_end)),
+            None => (position, packet_end),
+        });
+    }
+
+    range.ok_or_else(|| {
+        UnbundleError::StreamCopyError(format!(
+            "No packets with known byte positions in stream {stream_index} between \
+             {start:?} and {end:?}"
+        ))
+    })
+}
+
+/// Stream-copy the audio and video between `start` and `end` into
+/// `output_path`, cutting every stream against the same reference
+/// keyframe so they share one zeroed timeline.
+pub(crate) fn extract_clip_impl(
+    unbundler: &mut MediaFile,
+    start: Duration,
+    end: Duration,
+    output_path: &Path,
+) -> Result<SegmentInfo, UnbundleError> {
+    if start >= end {
+        return Err(UnbundleError::InvalidRange {
+            start: format!("{start:?}"),
+            end: format!("{end:?}"),
+        });
+    }
+    log::info!(
+        "Extracting clip {start:?}–{end:?} to {}",
+        output_path.display()
+    );
+
+    // The clip opens on a video keyframe when there is video; otherwise
+    // on the first packet of whichever stream comes first.
+    let reference_stream = unbundler.video_stream_index;
+
+    let mut copied: Vec<Parameters> = Vec::new();
+    let mut stream_map: Vec<Option<usize>> = Vec::new();
+    for stream in unbundler.input_context.streams() {
+        if is_copied_stream(&stream) {
+            stream_map.push(Some(copied.len()));
+            copied.push(stream.parameters().clone());
+        } else {
+            stream_map.push(None);
+        }
+    }
+    if copied.is_empty() {
+        return Err(UnbundleError::StreamCopyError(
+            "No audio or video streams to copy".to_string(),
+        ));
+    }
+
+    let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start);
+    crate::unbundle::seek_input(&mut unbundler.input_context, seek_timestamp)?;
+    let end_timestamp = crate::conversion::duration_to_seek_timestamp(end);
+
+    let mut output = open_segment(output_path, &copied)?;
+
+    // Source time of the opening keyframe, in `AV_TIME_BASE` units. Every
+    // stream is trimmed against and shifted by this one value, which is
+    // what keeps audio aligned with video in the clip.
+    let mut origin: Option<i64> = None;
+    // Packets of other streams demuxed before the opening keyframe; the
+    // ones at or after it belong in the clip.
+    let mut pending: Vec<(usize, Packet, Rational, i64)> = Vec::new();
+    let mut finished = vec![false; copied.len()];
+    let mut clip_end: i64 = 0;
+
+    for (stream, packet) in unbundler.input_context.packets() {
+        let Some(output_index) = stream_map.get(stream.index()).copied().flatten() else {
+            continue;
+        };
+        if finished[output_index] {
+            continue;
+        }
+        let input_time_base = stream.time_base();
+        let Some(pts) = packet.pts().or(packet.dts()) else {
+            continue;
+        };
+        let time = pts.rescale(input_time_base, rescale::TIME_BASE);
+
+        // Stop each stream in decode order so every copied frame keeps
+        // the references it was predicted from.
+        let decode_time = packet.dts().unwrap_or(pts);
+        if decode_time.rescale(input_time_base, rescale::TIME_BASE) >= end_timestamp {
+            finished[output_index] = true;
+            if finished.iter().all(|done| *done) {
+                break;
+            }
+            continue;
+        }
+
+        let is_reference = reference_stream.is_none_or(|reference| reference == stream.index());
+        let origin = match origin {
+            Some(origin) => origin,
+            None if is_reference && packet.is_key() => {
+                origin = Some(time);
+                for (index, early, time_base, early_time) in pending.drain(..) {
+                    if early_time >= time {
+                        let early_end =
+                            write_clip_packet(&mut output, early, index, time_base, time)?;
+                        clip_end = clip_end.max(early_end);
+                    }
+                }
+                time
+            }
+            None => {
+                if !is_reference {
+                    pending.push((output_index, packet, input_time_base, time));
+                }
+                continue;
+            }
+        };
+
+        // Audio from before the cut, and leading frames of an open GOP,
+        // precede the clip's first picture.
+        if time < origin {
+            continue;
+        }
+        let packet_end =
+            write_clip_packet(&mut output, packet, output_index, input_time_base, origin)?;
+        clip_end = clip_end.max(packet_end);
+    }
+
+    finish_segment(&mut output)?;
+    let Some(origin) = origin else {
+        // Nothing was written beyond the header, so the file is useless.
+        let _ = fs::remove_file(output_path);
+        return Err(UnbundleError::StreamCopyError(format!(
+            "No keyframe found between {start:?} and {end:?}"
+        )));
+    };
+
+    // `AV_TIME_BASE` units are microseconds.
+    let to_duration = |timestamp: i64| Duration::from_micros(timestamp.max(0) as u64);
+    Ok(SegmentInfo {
+        path: output_path.to_path_buf(),
+        start: to_duration(origin),
+        end: to_duration(clip_end.max(origin)),
+    })
+}
+
+/// Write one clip packet, shifted so `origin` lands at time zero.
+///
+/// Returns the source time where the packet ends, in `AV_TIME_BASE` units.
+fn write_clip_packet(
+    output: &mut Output,
+    mut packet: Packet,
+    output_index: usize,
+    input_time_base: Rational,
+    origin: i64,
+) -> Result<i64, UnbundleError> {
+    let packet_end = packet.pts().or(packet.dts()).map_or(origin, |pts| {
+        (pts + packet.duration()).rescale(input_time_base, rescale::TIME_BASE)
+    });
+    let shift = origin.rescale(rescale::TIME_BASE, input_time_base);
+    packet.set_pts(packet.pts().map(|pts| pts - shift));
+    packet.set_dts(packet.dts().map(|dts| dts - shift));
+
+    let output_time_base = output_stream_time_base(output, output_index)?;
+    packet.set_stream(output_index);
+    packet.rescale_ts(input_time_base, output_time_base);
+    packet.set_position(-1);
+    packet.write_interleaved(output).map_err(|error| {
+        UnbundleError::StreamCopyError(format!("Failed to write packet: {error}"))
+    })?;
+    Ok(packet_end)
+}
@@ -541,6 +541,33 @@ fn detect_scenes_from_keyframes(
     Ok(scenes)
 }
 
+/// Compute the `[start, end)` time span of the `index`-th scene.
+///
+/// Scene 0 runs from the start of the video to the first change; the last
+/// scene runs from the final change to `total_duration`. Returns the
+/// number of scenes as the error when `index` is out of range.
+pub(crate) fn scene_bounds(
+    changes: &[SceneChange],
+    total_duration: Duration,
+    index: usize,
+) -> Result<(Duration, Duration), usize> {
+    let scene_count = changes.len() + 1;
+    if index >= scene_count {
+        return Err(scene_count);
+    }
+
+    let start = match index {
+        0 => Duration::ZERO,
+        _ => changes[index - 1].timestamp,
+    };
+    let end = changes
+        .get(index)
+        .map(|change| change.timestamp)
+        .unwrap_or(total_duration)
+        .max(start);
+    Ok((start, end))
+}
+
 /// Read the `lavfi.scd.score` metadata value from a filtered frame.
 ///
 /// The `scdet` filter adds this key to frames where it detects a scene change.
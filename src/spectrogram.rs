@@ -0,0 +1,466 @@
+//! Log-mel spectrograms.
+//!
+//! This module provides [`MelOptions`] for computing the log-mel
+//! spectrogram that speech and audio models such as Whisper and wav2vec
+//! take as input. The track is decoded to mono at the requested sample
+//! rate, split into Hann-windowed frames (centred, with reflect padding),
+//! transformed with an FFT, projected onto a Slaney-style mel filterbank,
+//! and converted to `log10` power. With
+//! [`whisper_normalization`](MelOptions::whisper_normalization) the result
+//! is also clamped and rescaled exactly as Whisper's own front end does.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{MediaFile, MelOptions, UnbundleError};
+//!
+//! let mut unbundler = MediaFile::open("speech.wav")?;
+//! let mel = unbundler.audio().mel_spectrogram(&MelOptions::new())?;
+//! println!("{} frames of {} mel bands", mel.len(), mel[0].len());
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use crate::error::UnbundleError;
+
+/// Smallest power kept before taking the logarithm, so silence maps to
+/// `-10.0` instead of negative infinity.
+const POWER_FLOOR: f32 = 1e-10;
+
+/// Configuration for mel spectrogram computation.
+///
+/// The defaults match Whisper's front end: 16 kHz audio, a 400-sample
+/// (25 ms) window, a 160-sample (10 ms) hop and 80 mel bands. Enable
+/// [`whisper_normalization`](MelOptions::whisper_normalization) as well to
+/// get values a Whisper model can take directly.
+#[derive(Debug, Clone)]
+pub struct MelOptions {
+    /// Number of mel bands per frame. Default: 80.
+    pub n_mels: usize,
+    /// FFT and window size in samples. Default: 400.
+    pub n_fft: usize,
+    /// Samples between the starts of consecutive frames. Default: 160.
+    pub hop_length: usize,
+    /// Rate the audio is resampled to before analysis. Default: 16000.
+    pub sample_rate: u32,
+    /// Clamp every value to at most 8 below the spectrogram's maximum and
+    /// map `x` to `(x + 4) / 4`, as Whisper does. Default: `false`, which
+    /// returns plain `log10` power.
+    pub whisper_normalization: bool,
+}
+
+impl Default for MelOptions {
+    fn default() -> Self {
+        Self {
+            n_mels: 80,
+            n_fft: 400,
+            hop_length: 160,
+            sample_rate: 16_000,
+            whisper_normalization: false,
+        }
+    }
+}
+
+impl MelOptions {
+    /// Create a new [`MelOptions`] with Whisper's settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of mel bands.
+    pub fn n_mels(mut self, n_mels: usize) -> Self {
+        self.n_mels = n_mels;
+        self
+    }
+
+    /// Set the number of mel bands.
+    ///
+    /// Alias for [`n_mels`](MelOptions::n_mels).
+    pub fn with_n_mels(self, n_mels: usize) -> Self {
+        self.n_mels(n_mels)
+    }
+
+    /// Set the FFT and window size.
+    pub fn n_fft(mut self, n_fft: usize) -> Self {
+        self.n_fft = n_fft;
+        self
+    }
+
+    /// Set the FFT and window size.
+    ///
+    /// Alias for [`n_fft`](MelOptions::n_fft).
+    pub fn with_n_fft(self, n_fft: usize) -> Self {
+        self.n_fft(n_fft)
+    }
+
+    /// Set the hop between frames.
+    pub fn hop_length(mut self, hop_length: usize) -> Self {
+        self.hop_length = hop_length;
+        self
+    }
+
+    /// Set the hop between frames.
+    ///
+    /// Alias for [`hop_length`](MelOptions::hop_length).
+    pub fn with_hop_length(self, hop_length: usize) -> Self {
+        self.hop_length(hop_length)
+    }
+
+    /// Set the analysis sample rate.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Set the analysis sample rate.
+    ///
+    /// Alias for [`sample_rate`](MelOptions::sample_rate).
+    pub fn with_sample_rate(self, sample_rate: u32) -> Self {
+        self.sample_rate(sample_rate)
+    }
+
+    /// Apply Whisper's dynamic range clamp and scaling to the output.
+    pub fn whisper_normalization(mut self, enabled: bool) -> Self {
+        self.whisper_normalization = enabled;
+        self
+    }
+
+    /// Apply Whisper's dynamic range clamp and scaling to the output.
+    ///
+    /// Alias for [`whisper_normalization`](MelOptions::whisper_normalization).
+    pub fn with_whisper_normalization(self, enabled: bool) -> Self {
+        self.whisper_normalization(enabled)
+    }
+
+    /// Reject settings that cannot produce a spectrogram.
+    pub(crate) fn validate(&self) -> Result<(), UnbundleError> {
+        if self.n_mels == 0 || self.n_fft < 2 || self.hop_length == 0 || self.sample_rate == 0 {
+            return Err(UnbundleError::SpectrogramError(format!(
+                "Invalid mel options: n_mels={}, n_fft={}, hop_length={}, sample_rate={}",
+                self.n_mels, self.n_fft, self.hop_length, self.sample_rate
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Compute the log-mel spectrogram of mono samples taken at
+/// `config.sample_rate`.
+///
+/// Returns one `Vec` of `config.n_mels` values per frame; frame `i` is
+/// centred on sample `i * hop_length`.
+pub(crate) fn log_mel_spectrogram(samples: &[f32], config: &MelOptions) -> Vec<Vec<f32>> {
+    let n_fft = config.n_fft;
+    let padded = reflect_pad(samples, n_fft / 2);
+    let frame_count = 1 + samples.len() / config.hop_length;
+    let window = hann_window(n_fft);
+    let filterbank = mel_filterbank(config.n_mels, n_fft, config.sample_rate);
+    let mut fft = Fft::new(n_fft);
+
+    let mut buffer = vec![Complex::ZERO; n_fft];
+    let mut power = vec![0.0_f32; n_fft / 2 + 1];
+    let mut spectrogram = Vec::with_capacity(frame_count);
+    for frame_index in 0..frame_count {
+        let start = frame_index * config.hop_length;
+        for (offset, slot) in buffer.iter_mut().enumerate() {
+            let sample = padded.get(start + offset).copied().unwrap_or(0.0);
+            *slot = Complex::new(f64::from(sample) * window[offset], 0.0);
+        }
+        fft.transform(&mut buffer);
+        for (slot, bin) in power.iter_mut().zip(&buffer) {
+            *slot = bin.norm_squared() as f32;
+        }
+
+        spectrogram.push(
+            filterbank
+                .iter()
+                .map(|filter| {
+                    let energy: f32 = filter.iter().zip(&power).map(|(w, p)| w * p).sum();
+                    energy.max(POWER_FLOOR).log10()
+                })
+                .collect(),
+        );
+    }
+
+    if config.whisper_normalization {
+        normalize_like_whisper(&mut spectrogram);
+    }
+    spectrogram
+}
+
+/// Whisper's final step: keep 8 orders of magnitude (80 dB) below the
+/// loudest value, then scale to roughly `-1..=1`.
+fn normalize_like_whisper(spectrogram: &mut [Vec<f32>]) {
+    let maximum = spectrogram
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    for value in spectrogram.iter_mut().flatten() {
+        *value = (value.max(maximum - 8.0) + 4.0) / 4.0;
+    }
+}
+
+/// Pad both ends by mirroring the signal around its first and last
+/// samples, as `librosa` and `torch.stft(center=True)` do. Signals too
+/// short to mirror are zero-padded instead.
+fn reflect_pad(samples: &[f32], padding: usize) -> Vec<f32> {
+    let mut padded = Vec::with_capacity(samples.len() + 2 * padding);
+    if samples.len() <= padding {
+        padded.resize(padding, 0.0);
+        padded.extend_from_slice(samples);
+        padded.resize(samples.len() + 2 * padding, 0.0);
+        return padded;
+    }
+
+    padded.extend(samples[1..=padding].iter().rev());
+    padded.extend_from_slice(samples);
+    let last = samples.len() - 1;
+    padded.extend(samples[last - padding..last].iter().rev());
+    padded
+}
+
+/// Periodic Hann window, matching `torch.hann_window`.
+fn hann_window(size: usize) -> Vec<f64> {
+    (0..size)
+        .map(|index| 0.5 - 0.5 * (2.0 * PI * index as f64 / size as f64).cos())
+        .collect()
+}
+
+/// Slaney mel scale: linear below 1 kHz, logarithmic above.
+fn hz_to_mel(hz: f64) -> f64 {
+    const LINEAR_STEP: f64 = 200.0 / 3.0;
+    if hz < 1000.0 {
+        hz / LINEAR_STEP
+    } else {
+        15.0 + (hz / 1000.0).ln() / (6.4_f64.ln() / 27.0)
+    }
+}
+
+/// Inverse of [`hz_to_mel`].
+fn mel_to_hz(mel: f64) -> f64 {
+    const LINEAR_STEP: f64 = 200.0 / 3.0;
+    if mel < 15.0 {
+        mel * LINEAR_STEP
+    } else {
+        1000.0 * ((mel - 15.0) * 6.4_f64.ln() / 27.0).exp()
+    }
+}
+
+/// Triangular mel filters spanning 0 Hz to Nyquist with Slaney area
+/// normalisation, one row of `n_fft / 2 + 1` weights per band.
+fn mel_filterbank(n_mels: usize, n_fft: usize, sample_rate: u32) -> Vec<Vec<f32>> {
+    let nyquist = f64::from(sample_rate) / 2.0;
+    let max_mel = hz_to_mel(nyquist);
+    let edges: Vec<f64> = (0..n_mels + 2)
+        .map(|index| mel_to_hz(max_mel * index as f64 / (n_mels + 1) as f64))
+        .collect();
+    let bin_frequency = |bin: usize| bin as f64 * f64::from(sample_rate) / n_fft as f64;
+
+    edges
+        .windows(3)
+        .map(|edge| {
+            let (lower, center, upper) = (edge[0], edge[1], edge[2]);
+            let normalisation = 2.0 / (upper - lower);
+            (0..=n_fft / 2)
+                .map(|bin| {
+                    let frequency = bin_frequency(bin);
+                    let rising = (frequency - lower) / (center - lower);
+                    let falling = (upper - frequency) / (upper - center);
+                    (rising.min(falling).max(0.0) * normalisation) as f32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// `exp(i·angle)`.
+    fn from_angle(angle: f64) -> Self {
+        Self::new(angle.cos(), angle.sin())
+    }
+
+    fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    fn scale(self, factor: f64) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+
+    fn norm_squared(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+/// In-place FFT of one fixed size, planned once per spectrogram.
+///
+/// Powers of two use an iterative radix-2 transform. Any other size, such
+/// as Whisper's 400-point window or a prime length, goes through
+/// Bluestein's algorithm, which rewrites the DFT as a convolution with a
+/// chirp and computes it with power-of-two transforms, so every size costs
+/// O(n log n). Tables and scratch space are allocated here and reused for
+/// every frame.
+enum Fft {
+    Radix2(Radix2),
+    Bluestein {
+        /// `exp(-iπk²/n)` for each input index `k`.
+        chirp: Vec<Complex>,
+        /// Transform of the conjugate chirp, wrapped to the inner size.
+        filter: Vec<Complex>,
+        inner: Radix2,
+        scratch: Vec<Complex>,
+    },
+}
+
+impl Fft {
+    fn new(size: usize) -> Self {
+        if size.is_power_of_two() {
+            return Fft::Radix2(Radix2::new(size));
+        }
+
+        // k² is reduced modulo 2n before scaling so large indices keep
+        // their precision.
+        let chirp: Vec<Complex> = (0..size)
+            .map(|k| {
+                let exponent = (k * k) % (2 * size);
+                Complex::from_angle(-PI * exponent as f64 / size as f64)
+            })
+            .collect();
+        let inner_size = (2 * size - 1).next_power_of_two();
+        let inner = Radix2::new(inner_size);
+        let mut filter = vec![Complex::ZERO; inner_size];
+        filter[0] = chirp[0].conj();
+        for k in 1..size {
+            filter[k] = chirp[k].conj();
+            filter[inner_size - k] = chirp[k].conj();
+        }
+        inner.transform(&mut filter, false);
+
+        Fft::Bluestein {
+            chirp,
+            filter,
+            inner,
+            scratch: vec![Complex::ZERO; inner_size],
+        }
+    }
+
+    /// Replace `data` with its DFT. `data` must have the planned size.
+    fn transform(&mut self, data: &mut [Complex]) {
+        match self {
+            Fft::Radix2(radix2) => radix2.transform(data, false),
+            Fft::Bluestein {
+                chirp,
+                filter,
+                inner,
+                scratch,
+            } => {
+                scratch.fill(Complex::ZERO);
+                for ((slot, &x), &w) in scratch.iter_mut().zip(&*data).zip(&*chirp) {
+                    *slot = x * w;
+                }
+                inner.transform(scratch, false);
+                for (slot, &h) in scratch.iter_mut().zip(&*filter) {
+                    *slot = *slot * h;
+                }
+                inner.transform(scratch, true);
+                let normalisation = 1.0 / scratch.len() as f64;
+                for ((out, &y), &w) in data.iter_mut().zip(&*scratch).zip(&*chirp) {
+                    *out = (y * w).scale(normalisation);
+                }
+            }
+        }
+    }
+}
+
+/// Iterative radix-2 FFT for a power-of-two size.
+struct Radix2 {
+    /// `exp(-2πi·k/size)` for `k < size / 2`.
+    twiddles: Vec<Complex>,
+}
+
+impl Radix2 {
+    fn new(size: usize) -> Self {
+        let twiddles = (0..size / 2)
+            .map(|k| Complex::from_angle(-2.0 * PI * k as f64 / size as f64))
+            .collect();
+        Self { twiddles }
+    }
+
+    /// Transform `data` in place; `inverse` skips the `1 / size` scaling.
+    fn transform(&self, data: &mut [Complex], inverse: bool) {
+        let size = data.len();
+
+        // Bit-reversal permutation.
+        let mut reversed = 0;
+        for index in 1..size {
+            let mut bit = size >> 1;
+            while reversed & bit != 0 {
+                reversed ^= bit;
+                bit >>= 1;
+            }
+            reversed |= bit;
+            if index < reversed {
+                data.swap(index, reversed);
+            }
+        }
+
+        let mut length = 2;
+        while length <= size {
+            let half = length / 2;
+            let stride = size / length;
+            for start in (0..size).step_by(length) {
+                for k in 0..half {
+                    let twiddle = self.twiddles[k * stride];
+                    let twiddle = if inverse { twiddle.conj() } else { twiddle };
+                    let even = data[start + k];
+                    let odd = data[start + k + half] * twiddle;
+                    data[start + k] = even + odd;
+                    data[start + k + half] = even - odd;
+                }
+            }
+            length *= 2;
+        }
+    }
+}
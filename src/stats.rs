@@ -0,0 +1,144 @@
+//! Decode throughput statistics.
+//!
+//! A [`StatsCollector`] attached with
+//! [`ExtractOptions::with_stats`](crate::ExtractOptions::with_stats) counts
+//! the frames each video extraction decodes and scales, and the wall time
+//! it takes. Counters accumulate across calls and threads, so one collector
+//! can measure a whole batch job; [`StatsCollector::snapshot`] reads them.
+//!
+//! Wall time is summed per call: two extractions running side by side for
+//! one second add two seconds. [`DecodeStats::average_decode_fps`] is
+//! therefore the rate of a single decode, not the combined throughput of
+//! parallel ones.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use unbundle::{ExtractOptions, FrameRange, MediaFile, StatsCollector, UnbundleError};
+//!
+//! let stats = Arc::new(StatsCollector::new());
+//! let config = ExtractOptions::new().with_stats(stats.clone());
+//!
+//! let mut unbundler = MediaFile::open("input.mp4")?;
+//! unbundler
+//!     .video()
+//!     .frames_with_options(FrameRange::Range(0, 299), &config)?;
+//!
+//! let snapshot = stats.snapshot();
+//! println!(
+//!     "{} frames decoded in {:?} ({:.1} fps)",
+//!     snapshot.frames_decoded, snapshot.wall_time, snapshot.average_decode_fps
+//! );
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Thread-safe counters for decode throughput.
+///
+/// Share it behind an [`Arc`](std::sync::Arc) between the
+/// [`ExtractOptions`](crate::ExtractOptions) it is attached to and the code
+/// that reads it.
+#[derive(Debug, Default)]
+pub struct StatsCollector {
+    frames_decoded: AtomicU64,
+    frames_scaled: AtomicU64,
+    wall_time_nanos: AtomicU64,
+    extractions: AtomicU64,
+}
+
+/// Counters read from a [`StatsCollector`] at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecodeStats {
+    /// Frames the decoder produced during extraction calls, including
+    /// frames decoded only to reach a seek target or skipped by an
+    /// interval.
+    pub frames_decoded: u64,
+    /// Frames scaled and converted to output images or buffers.
+    pub frames_scaled: u64,
+    /// Wall time of every extraction call, summed; calls that overlap on
+    /// different threads each add their own time.
+    pub wall_time: Duration,
+    /// Number of extraction calls.
+    pub extractions: u64,
+    /// `frames_decoded` divided by `wall_time`, or `0.0` before any time
+    /// was measured. This is the mean rate of one call, not the combined
+    /// rate of calls running in parallel.
+    pub average_decode_fps: f64,
+}
+
+impl StatsCollector {
+    /// Create a collector with all counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the current counters.
+    ///
+    /// Counters are read one at a time, so a snapshot taken while an
+    /// extraction is running may be slightly inconsistent.
+    pub fn snapshot(&self) -> DecodeStats {
+        let frames_decoded = self.frames_decoded.load(Ordering::Relaxed);
+        let wall_time = Duration::from_nanos(self.wall_time_nanos.load(Ordering::Relaxed));
+        let average_decode_fps = if wall_time.is_zero() {
+            0.0
+        } else {
+            frames_decoded as f64 / wall_time.as_secs_f64()
+        };
+        DecodeStats {
+            frames_decoded,
+            frames_scaled: self.frames_scaled.load(Ordering::Relaxed),
+            wall_time,
+            extractions: self.extractions.load(Ordering::Relaxed),
+            average_decode_fps,
+        }
+    }
+
+    /// Reset every counter to zero.
+    pub fn reset(&self) {
+        self.frames_decoded.store(0, Ordering::Relaxed);
+        self.frames_scaled.store(0, Ordering::Relaxed);
+        self.wall_time_nanos.store(0, Ordering::Relaxed);
+        self.extractions.store(0, Ordering::Relaxed);
+    }
+
+    // Only called from inside a timed extraction, so every counted frame
+    // falls within the measured wall time.
+    pub(crate) fn record_decoded(&self) {
+        self.frames_decoded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_scaled(&self) {
+        self.frames_scaled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_extraction(&self, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.wall_time_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.extractions.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Adds the time until it is dropped to a collector's wall time.
+pub(crate) struct ExtractionTimer<'a> {
+    collector: &'a StatsCollector,
+    started: Instant,
+}
+
+impl<'a> ExtractionTimer<'a> {
+    pub(crate) fn start(collector: &'a StatsCollector) -> Self {
+        Self {
+            collector,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for ExtractionTimer<'_> {
+    fn drop(&mut self) {
+        self.collector.record_extraction(self.started.elapsed());
+    }
+}
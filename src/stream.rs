@@ -1,10 +1,11 @@
 //! Async streaming for video and audio extraction.
 //!
 //! This module provides [`FrameStream`] for asynchronously iterating over
-//! decoded video frames, and [`AudioFuture`] for extracting audio data
-//! in the background without blocking the async runtime.
+//! decoded video frames, [`AudioFuture`] for extracting audio data
+//! in the background without blocking the async runtime, and
+//! [`AudioStream`] for receiving encoded audio chunk by chunk.
 //!
-//! Both types use `tokio::task::spawn_blocking` internally — decoding happens
+//! All three types use `tokio::task::spawn_blocking` internally — decoding happens
 //! on a dedicated blocking thread while results are streamed back through a
 //! bounded channel. This avoids tying up the Tokio runtime's cooperative
 //! task budget with CPU-heavy FFmpeg work.
@@ -47,9 +48,11 @@ use crate::error::UnbundleError;
 use crate::unbundle::MediaFile;
 use crate::video::FrameRange;
 
-/// Default bounded-channel capacity for [`FrameStream`].
+/// Default bounded-channel capacity for [`FrameStream`] and
+/// [`AudioStream`].
 ///
 /// Kept small to avoid buffering too many large decoded frames in memory.
+/// Overridden by [`ExtractOptions::with_stream_buffer_size`].
 const DEFAULT_CHANNEL_CAPACITY: usize = 8;
 
 /// A stream of decoded video frames produced by a background decode thread.
@@ -108,6 +111,7 @@ impl Stream for FrameStream {
 /// * `range` — Which frames to decode.
 /// * `config` — Extraction settings (progress, cancellation, output format).
 /// * `channel_capacity` — Bounded channel size. `None` uses the default (8).
+///   The decode thread blocks in `blocking_send` while the channel is full.
 pub(crate) fn create_frame_stream(
     source: String,
     range: FrameRange,
@@ -210,3 +214,101 @@ pub(crate) fn create_audio_future(
 
     AudioFuture { handle }
 }
+
+/// A stream of encoded audio chunks produced by a background transcode.
+///
+/// Created via [`AudioHandle::extract_stream`](crate::AudioHandle::extract_stream).
+/// Each item is the next piece of the output container, in order;
+/// concatenating them gives the same bytes a non-seekable file would hold.
+/// Chunks are plain `Vec<u8>`, which converts into `bytes::Bytes` without
+/// copying, so the stream can back an HTTP response body directly.
+///
+/// The transcode runs via `tokio::task::spawn_blocking` and sends chunks
+/// through a bounded `mpsc` channel. Dropping the stream closes the
+/// channel, which stops the transcode at the next chunk.
+///
+/// # Example
+///
+/// ```no_run
+/// use tokio_stream::StreamExt;
+///
+/// use unbundle::{AudioFormat, ExtractOptions, MediaFile, UnbundleError};
+///
+/// # async fn example() -> Result<(), UnbundleError> {
+/// let mut unbundler = MediaFile::open("input.mp4")?;
+/// let mut stream = unbundler
+///     .audio()
+///     .extract_stream(AudioFormat::Aac, ExtractOptions::new())?;
+///
+/// let mut total = 0;
+/// while let Some(chunk) = stream.next().await {
+///     total += chunk?.len();
+/// }
+/// println!("Streamed {total} bytes");
+/// # Ok(())
+/// # }
+/// ```
+pub struct AudioStream {
+    receiver: Receiver<Result<Vec<u8>, UnbundleError>>,
+    #[allow(dead_code)]
+    handle: JoinHandle<()>,
+}
+
+impl Stream for AudioStream {
+    type Item = Result<Vec<u8>, UnbundleError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Create an [`AudioStream`] that transcodes audio on a blocking thread.
+///
+/// Opens a fresh demuxer for `source` and sends each block the muxer
+/// writes through a bounded channel. `channel_capacity` of `None` uses the
+/// default (8).
+pub(crate) fn create_audio_stream(
+    source: String,
+    format: AudioFormat,
+    track_index: Option<usize>,
+    config: ExtractOptions,
+    channel_capacity: Option<usize>,
+) -> AudioStream {
+    let capacity = channel_capacity.unwrap_or(DEFAULT_CHANNEL_CAPACITY).max(1);
+    let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+
+    let handle = tokio::task::spawn_blocking(move || {
+        let result = encode_audio_blocking(&source, format, track_index, &config, &sender);
+        if let Err(e) = result {
+            // Try to send the error; the receiver may have been dropped.
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+
+    AudioStream { receiver, handle }
+}
+
+/// Background transcode — runs on a blocking thread.
+fn encode_audio_blocking(
+    source: &str,
+    format: AudioFormat,
+    track_index: Option<usize>,
+    config: &ExtractOptions,
+    sender: &Sender<Result<Vec<u8>, UnbundleError>>,
+) -> Result<(), UnbundleError> {
+    let mut unbundler = MediaFile::open_source(source)?;
+    let mut extractor = if let Some(index) = track_index {
+        unbundler.audio_track(index)?
+    } else {
+        unbundler.audio()
+    };
+
+    // A failed send means the stream was dropped; refusing the chunk makes
+    // the muxer report a write error and the transcode stop.
+    let chunk_sender = sender.clone();
+    extractor.extract_to_callback(
+        format,
+        config,
+        Box::new(move |chunk: &[u8]| chunk_sender.blocking_send(Ok(chunk.to_vec())).is_ok()),
+    )
+}
@@ -20,12 +20,13 @@
 use std::ffi::CString;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use ffmpeg_next::{
     Rational, Subtitle,
     codec::{Id, context::Context as CodecContext},
+    media::Type,
     packet::Mut as PacketMut,
     subtitle::{Bitmap as SubtitleBitmap, Rect},
 };
@@ -72,6 +73,129 @@ impl Display for SubtitleFormat {
     }
 }
 
+/// Encoding and layout choices for written subtitle text.
+///
+/// The default writes UTF-8 without a byte order mark and with `\n` line
+/// endings. Some Windows players and media centers only recognise SRT
+/// files as UTF-8 when they start with a BOM, and expect `\r\n` line
+/// endings; [`SubtitleWriteOptions::windows`] turns both on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubtitleWriteOptions {
+    /// Start the output with a UTF-8 byte order mark (`U+FEFF`).
+    pub bom: bool,
+    /// End every line, including line breaks inside cue text, with `\r\n`.
+    pub crlf: bool,
+    /// Wrap SRT and WebVTT cue lines longer than this many characters at
+    /// word boundaries. `None` leaves lines as extracted. Default: `None`.
+    pub max_line_length: Option<usize>,
+}
+
+impl SubtitleWriteOptions {
+    /// Create options that match the default output: no BOM, `\n` endings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options for Windows players: a UTF-8 BOM and `\r\n` line endings.
+    pub fn windows() -> Self {
+        Self {
+            bom: true,
+            crlf: true,
+            max_line_length: None,
+        }
+    }
+
+    /// Set whether to write a UTF-8 byte order mark.
+    #[must_use]
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Set whether to use `\r\n` line endings.
+    #[must_use]
+    pub fn with_crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+
+    /// Wrap cue lines to at most `chars` characters when writing SRT or
+    /// WebVTT.
+    ///
+    /// Subtitling guidelines commonly ask for 37–42 characters per line.
+    /// See [`wrap_text`](SubtitleWriteOptions::wrap_text) for how lines are
+    /// broken.
+    #[must_use]
+    pub fn with_max_line_length(mut self, chars: usize) -> Self {
+        self.max_line_length = Some(chars.max(1));
+        self
+    }
+
+    /// Word-wrap `text` to [`max_line_length`](SubtitleWriteOptions::max_line_length).
+    ///
+    /// Existing line breaks are kept, and each line that is too long is
+    /// broken at the spaces before the limit; runs of spaces at a break are
+    /// dropped. A single word longer than the limit gets a line of its own
+    /// rather than being split. Lengths count characters, including any
+    /// markup such as `<i>` tags. Returns `text` unchanged when no limit is
+    /// set.
+    pub fn wrap_text(&self, text: &str) -> String {
+        let Some(limit) = self.max_line_length else {
+            return text.to_string();
+        };
+        let mut lines: Vec<String> = Vec::new();
+        for line in text.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.chars().count() <= limit {
+                lines.push(line.to_string());
+                continue;
+            }
+            let mut current = String::new();
+            let mut current_length = 0;
+            for word in line.split_whitespace() {
+                let word_length = word.chars().count();
+                if current_length > 0 && current_length + 1 + word_length > limit {
+                    lines.push(std::mem::take(&mut current));
+                    current_length = 0;
+                }
+                if current_length > 0 {
+                    current.push(' ');
+                    current_length += 1;
+                }
+                current.push_str(word);
+                current_length += word_length;
+            }
+            lines.push(current);
+        }
+        lines.join("\n")
+    }
+
+    /// Apply the BOM and line-ending choices to formatted subtitle text.
+    fn apply(&self, content: String) -> String {
+        let content = if self.crlf {
+            // Normalise first so cue text that already uses CRLF does not
+            // end up with doubled carriage returns.
+            content.replace("\r\n", "\n").replace('\n', "\r\n")
+        } else {
+            content
+        };
+        if self.bom {
+            format!("\u{FEFF}{content}")
+        } else {
+            content
+        }
+    }
+}
+
+/// Files written by [`SubtitleHandle::export_ass_bundle`].
+#[derive(Debug, Clone)]
+pub struct AssBundle {
+    /// Path of the exported `.ass` script.
+    pub subtitle_path: PathBuf,
+    /// Paths of the font attachments written next to the script.
+    pub font_paths: Vec<PathBuf>,
+}
+
 /// Subtitle extraction operations.
 ///
 /// Obtained via [`MediaFile::subtitle`] or
@@ -98,6 +222,9 @@ impl<'a> SubtitleHandle<'a> {
     /// # Errors
     ///
     /// - [`UnbundleError::NoSubtitleStream`] if no subtitle stream exists.
+    /// - [`UnbundleError::BitmapSubtitleNotText`] if the stream stores
+    ///   images (PGS, DVD, DVB); see
+    ///   [`extract_bitmaps`](SubtitleHandle::extract_bitmaps).
     /// - [`UnbundleError::SubtitleDecodeError`] if decoding fails.
     ///
     /// # Example
@@ -122,6 +249,13 @@ impl<'a> SubtitleHandle<'a> {
 
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
+        let codec_id = codec_parameters.id();
+        if is_bitmap_codec(codec_id) {
+            return Err(UnbundleError::BitmapSubtitleNotText {
+                stream_index: subtitle_stream_index,
+                codec: codec_id.name().to_string(),
+            });
+        }
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
         let mut decoder = decoder_context.decoder().subtitle().map_err(|e| {
             UnbundleError::SubtitleDecodeError(format!("Failed to create subtitle decoder: {e}"))
@@ -131,6 +265,8 @@ impl<'a> SubtitleHandle<'a> {
         let mut entry_index: usize = 0;
         let mut subtitle = Subtitle::new();
 
+        // Earlier reads leave the demuxer mid-file.
+        crate::unbundle::rewind_input(&mut self.unbundler.input_context)?;
         for (stream, packet) in self.unbundler.input_context.packets() {
             if stream.index() != subtitle_stream_index {
                 continue;
@@ -226,8 +362,39 @@ impl<'a> SubtitleHandle<'a> {
         path: P,
         format: SubtitleFormat,
     ) -> Result<(), UnbundleError> {
-        let entries = self.extract()?;
-        let content = format_subtitles(&entries, format);
+        self.save_with_options(path, format, &SubtitleWriteOptions::default())
+    }
+
+    /// Extract subtitles and save them with a chosen BOM and line ending.
+    ///
+    /// Like [`save`](SubtitleHandle::save), but the written bytes follow
+    /// `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`extract`](SubtitleHandle::extract) or
+    /// I/O errors when writing the file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, SubtitleFormat, SubtitleWriteOptions, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mkv")?;
+    /// unbundler.subtitle().save_with_options(
+    ///     "subtitles.srt",
+    ///     SubtitleFormat::Srt,
+    ///     &SubtitleWriteOptions::windows(),
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn save_with_options<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: SubtitleFormat,
+        options: &SubtitleWriteOptions,
+    ) -> Result<(), UnbundleError> {
+        let content = self.extract_text_with_options(format, options)?;
         std::fs::write(path, content)?;
         Ok(())
     }
@@ -241,8 +408,27 @@ impl<'a> SubtitleHandle<'a> {
     ///
     /// Returns errors from [`extract`](SubtitleHandle::extract).
     pub fn extract_text(&mut self, format: SubtitleFormat) -> Result<String, UnbundleError> {
-        let entries = self.extract()?;
-        Ok(format_subtitles(&entries, format))
+        self.extract_text_with_options(format, &SubtitleWriteOptions::default())
+    }
+
+    /// Extract subtitles and format them as a string with a chosen BOM,
+    /// line ending and line length.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`extract`](SubtitleHandle::extract).
+    pub fn extract_text_with_options(
+        &mut self,
+        format: SubtitleFormat,
+        options: &SubtitleWriteOptions,
+    ) -> Result<String, UnbundleError> {
+        let mut entries = self.extract()?;
+        if options.max_line_length.is_some() && format != SubtitleFormat::Raw {
+            for entry in &mut entries {
+                entry.text = options.wrap_text(&entry.text);
+            }
+        }
+        Ok(options.apply(format_subtitles(&entries, format)))
     }
 
     /// Extract subtitle entries within a time range.
@@ -400,6 +586,104 @@ impl<'a> SubtitleHandle<'a> {
             .collect())
     }
 
+    /// Extract subtitle entries with every timestamp multiplied by `factor`.
+    ///
+    /// Both start and end times are scaled, so a factor below 1.0 makes
+    /// subtitles appear earlier and shorter. To match subtitles timed for
+    /// one frame rate to a sped-up or slowed-down video, use
+    /// [`rescale_timing_fps`](SubtitleHandle::rescale_timing_fps), which
+    /// derives the factor for you.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRescaleFactor`] if `factor` is not finite
+    ///   and positive, or scales a timestamp past what a `Duration` holds.
+    /// - Plus any errors from [`extract`](SubtitleHandle::extract).
+    pub fn rescale_timing(&mut self, factor: f64) -> Result<Vec<SubtitleEvent>, UnbundleError> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(UnbundleError::InvalidRescaleFactor(factor));
+        }
+
+        let scale = |time: Duration| {
+            Duration::try_from_secs_f64(time.as_secs_f64() * factor)
+                .map_err(|_| UnbundleError::InvalidRescaleFactor(factor))
+        };
+        let mut entries = self.extract()?;
+        for entry in &mut entries {
+            entry.start_time = scale(entry.start_time)?;
+            entry.end_time = scale(entry.end_time)?;
+        }
+        Ok(entries)
+    }
+
+    /// Extract subtitle entries retimed from `source_fps` to `target_fps`.
+    ///
+    /// Timestamps are scaled by `source_fps / target_fps`. For example,
+    /// NTSC-film subtitles (`24000.0 / 1001.0`) played against a PAL
+    /// speed-up of the same film (`25.0`) all move about 4% earlier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::InvalidRescaleFactor`] with the offending
+    /// rate if either rate is zero, negative or not finite, or any error from
+    /// [`rescale_timing`](SubtitleHandle::rescale_timing).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mkv")?;
+    /// let pal = unbundler.subtitle().rescale_timing_fps(24000.0 / 1001.0, 25.0)?;
+    /// println!("First line now at {:?}", pal[0].start_time);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn rescale_timing_fps(
+        &mut self,
+        source_fps: f64,
+        target_fps: f64,
+    ) -> Result<Vec<SubtitleEvent>, UnbundleError> {
+        for fps in [source_fps, target_fps] {
+            if !fps.is_finite() || fps <= 0.0 {
+                return Err(UnbundleError::InvalidRescaleFactor(fps));
+            }
+        }
+        self.rescale_timing(source_fps / target_fps)
+    }
+
+    /// Extract subtitles, rescale their timing, and save them to a file.
+    ///
+    /// Combines [`rescale_timing`](SubtitleHandle::rescale_timing) with
+    /// file output in the specified format.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`rescale_timing`](SubtitleHandle::rescale_timing)
+    /// or I/O errors when writing the file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, SubtitleFormat, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mkv")?;
+    /// unbundler
+    ///     .subtitle()
+    ///     .save_rescaled("pal.srt", SubtitleFormat::Srt, 24000.0 / 1001.0 / 25.0)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn save_rescaled<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: SubtitleFormat,
+        factor: f64,
+    ) -> Result<(), UnbundleError> {
+        let entries = self.rescale_timing(factor)?;
+        let content = format_subtitles(&entries, format);
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
     /// Extract bitmap subtitle events as images.
     ///
     /// DVD, PGS, and DVB subtitle tracks use images rather than text.
@@ -446,10 +730,19 @@ impl<'a> SubtitleHandle<'a> {
             UnbundleError::SubtitleDecodeError(format!("Failed to create subtitle decoder: {e}"))
         })?;
 
+        let video_dimensions = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .map_or((0, 0), |video| (video.width, video.height));
+
         let mut events = Vec::new();
         let mut event_index: usize = 0;
         let mut subtitle = Subtitle::new();
 
+        // Earlier reads leave the demuxer mid-file.
+        crate::unbundle::rewind_input(&mut self.unbundler.input_context)?;
         for (stream, packet) in self.unbundler.input_context.packets() {
             if stream.index() != subtitle_stream_index {
                 continue;
@@ -472,6 +765,18 @@ impl<'a> SubtitleHandle<'a> {
                 packet_pts * numerator * 1_000_000 / denominator
             };
 
+            // Rect positions refer to the subtitle's own canvas. PGS sets
+            // it per presentation and DVD tracks carry it in their
+            // parameters; otherwise the video frame is the canvas.
+            // SAFETY: the codec context is valid while `decoder` lives.
+            let (decoder_width, decoder_height) =
+                unsafe { ((*decoder.as_ptr()).width, (*decoder.as_ptr()).height) };
+            let (reference_width, reference_height) = if decoder_width > 0 && decoder_height > 0 {
+                (decoder_width as u32, decoder_height as u32)
+            } else {
+                video_dimensions
+            };
+
             let start_offset_ms = subtitle.start() as u64;
             let end_offset_ms = subtitle.end() as u64;
 
@@ -488,6 +793,8 @@ impl<'a> SubtitleHandle<'a> {
                             end_time,
                             x: bitmap.x() as u32,
                             y: bitmap.y() as u32,
+                            reference_width,
+                            reference_height,
                             image,
                             index: event_index,
                         });
@@ -501,6 +808,90 @@ impl<'a> SubtitleHandle<'a> {
         Ok(events)
     }
 
+    /// Convert bitmap subtitles to timed text with a user-supplied OCR engine.
+    ///
+    /// Decodes every bitmap event via
+    /// [`extract_bitmaps`](SubtitleHandle::extract_bitmaps) and passes each
+    /// image to [`BitmapOcr::recognize`]. Rects that share the same display
+    /// interval (e.g. the two lines of a PGS caption) are joined top to
+    /// bottom with a newline into a single [`SubtitleEvent`]. Events whose
+    /// recognised text is blank are dropped, and the survivors are
+    /// re-indexed from zero so they can be written out directly with
+    /// [`SubtitleFormat::Srt`] or [`SubtitleFormat::WebVtt`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`extract_bitmaps`](SubtitleHandle::extract_bitmaps).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use image::DynamicImage;
+    /// use unbundle::{BitmapOcr, MediaFile, UnbundleError};
+    ///
+    /// struct MyOcr;
+    ///
+    /// impl BitmapOcr for MyOcr {
+    ///     fn recognize(&self, image: &DynamicImage) -> String {
+    ///         // Call tesseract, a cloud API, etc.
+    ///         format!("{}x{} caption", image.width(), image.height())
+    ///     }
+    /// }
+    ///
+    /// let mut unbundler = MediaFile::open("bluray.mkv")?;
+    /// let events = unbundler.subtitle().ocr_bitmaps(&MyOcr)?;
+    /// println!("Recognised {} lines", events.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn ocr_bitmaps(
+        &mut self,
+        ocr: &dyn BitmapOcr,
+    ) -> Result<Vec<SubtitleEvent>, UnbundleError> {
+        let bitmaps = self.extract_bitmaps()?;
+        log::debug!("Running OCR on {} bitmap subtitle rects", bitmaps.len());
+
+        let mut events: Vec<SubtitleEvent> = Vec::new();
+        let mut group: Vec<&BitmapSubtitleEvent> = Vec::new();
+
+        let flush = |group: &mut Vec<&BitmapSubtitleEvent>, events: &mut Vec<SubtitleEvent>| {
+            let Some(first) = group.first() else {
+                return;
+            };
+            let (start_time, end_time) = (first.start_time, first.end_time);
+            group.sort_by_key(|bitmap| bitmap.y);
+            let text = group
+                .iter()
+                .map(|bitmap| ocr.recognize(&bitmap.image).trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            group.clear();
+
+            if !text.is_empty() {
+                let index = events.len();
+                events.push(SubtitleEvent {
+                    start_time,
+                    end_time,
+                    text,
+                    index,
+                });
+            }
+        };
+
+        for bitmap in &bitmaps {
+            let same_interval = group.first().is_some_and(|first| {
+                first.start_time == bitmap.start_time && first.end_time == bitmap.end_time
+            });
+            if !same_interval {
+                flush(&mut group, &mut events);
+            }
+            group.push(bitmap);
+        }
+        flush(&mut group, &mut events);
+
+        Ok(events)
+    }
+
     /// Render active bitmap subtitle events at a timestamp into one image.
     ///
     /// This is intended for image-based subtitle codecs (for example PGS,
@@ -704,6 +1095,99 @@ impl<'a> SubtitleHandle<'a> {
         self.copy_stream_to_memory(container_format, Some(start), Some(end), None)
     }
 
+    /// Export an ASS/SSA track together with the fonts it needs.
+    ///
+    /// Writes the track verbatim as `subtitles.ass` (styles, positioning
+    /// and override tags intact) and every font attachment in the container
+    /// under its embedded file name, so a libass-based renderer pointed at
+    /// `directory` has everything it needs. The directory is created if it
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoSubtitleStream`] if no subtitle stream exists.
+    /// - [`UnbundleError::StreamCopyError`] if the track is not ASS/SSA.
+    /// - [`UnbundleError::IoError`] if the directory or a font cannot be
+    ///   written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("anime.mkv")?;
+    /// let bundle = unbundler.subtitle().export_ass_bundle("render")?;
+    /// println!("{} fonts", bundle.font_paths.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn export_ass_bundle<P: AsRef<Path>>(
+        &mut self,
+        directory: P,
+    ) -> Result<AssBundle, UnbundleError> {
+        let subtitle_stream_index = self.resolve_stream_index()?;
+        let codec_id = self
+            .unbundler
+            .input_context
+            .stream(subtitle_stream_index)
+            .ok_or(UnbundleError::NoSubtitleStream)?
+            .parameters()
+            .id();
+        if !matches!(codec_id, Id::ASS | Id::SSA) {
+            return Err(UnbundleError::StreamCopyError(format!(
+                "Subtitle stream {subtitle_stream_index} is {codec_id:?}, not ASS/SSA"
+            )));
+        }
+
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+
+        let subtitle_path = directory.join("subtitles.ass");
+        self.copy_stream_to_file(&subtitle_path, None, None, None)?;
+
+        let mut font_paths = Vec::new();
+        for stream in self.unbundler.input_context.streams() {
+            let parameters = stream.parameters();
+            if parameters.medium() != Type::Attachment {
+                continue;
+            }
+            let metadata = stream.metadata();
+            let mime_type = metadata.get("mimetype").unwrap_or_default();
+            let file_name = metadata
+                .get("filename")
+                .and_then(|name| Path::new(name).file_name())
+                .map(PathBuf::from);
+            if !is_font_attachment(mime_type, file_name.as_deref()) {
+                continue;
+            }
+
+            // SAFETY: `extradata` holds `extradata_size` bytes owned by the
+            // stream parameters, which outlive this borrow.
+            let data = unsafe {
+                let raw = parameters.as_ptr();
+                if (*raw).extradata.is_null() || (*raw).extradata_size <= 0 {
+                    continue;
+                }
+                std::slice::from_raw_parts((*raw).extradata, (*raw).extradata_size as usize)
+            };
+
+            let file_name = file_name
+                .unwrap_or_else(|| PathBuf::from(format!("attachment_{}.ttf", stream.index())));
+            let font_path = directory.join(file_name);
+            std::fs::write(&font_path, data)?;
+            font_paths.push(font_path);
+        }
+
+        log::debug!(
+            "Exported ASS bundle to {:?} with {} fonts",
+            directory,
+            font_paths.len()
+        );
+        Ok(AssBundle {
+            subtitle_path,
+            font_paths,
+        })
+    }
+
     // ── Stream copy (lossless) helpers ──────────────────────────────
 
     /// Copy the subtitle stream verbatim to a file without decoding or
@@ -714,6 +1198,19 @@ impl<'a> SubtitleHandle<'a> {
         start: Option<Duration>,
         end: Option<Duration>,
         config: Option<&ExtractOptions>,
+    ) -> Result<(), UnbundleError> {
+        crate::atomic_write::write_output(path, config, |path| {
+            self.write_stream_copy(path, start, end, config)
+        })
+    }
+
+    /// Write the stream copy straight to `path`.
+    fn write_stream_copy(
+        &mut self,
+        path: &Path,
+        start: Option<Duration>,
+        end: Option<Duration>,
+        config: Option<&ExtractOptions>,
     ) -> Result<(), UnbundleError> {
         let subtitle_stream_index = self.resolve_stream_index()?;
         log::debug!(
@@ -755,9 +1252,7 @@ impl<'a> SubtitleHandle<'a> {
         // Seek to start position if specified.
         if let Some(start_time) = start {
             let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+            crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -845,9 +1340,7 @@ impl<'a> SubtitleHandle<'a> {
         // Seek to start position if specified.
         if let Some(start_time) = start {
             let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+            crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -1028,6 +1521,24 @@ impl<'a> SubtitleHandle<'a> {
     }
 }
 
+/// Whether an attachment stream carries a font, judged by MIME type or,
+/// failing that, by file extension.
+fn is_font_attachment(mime_type: &str, file_name: Option<&Path>) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    if mime_type.contains("font") || mime_type.contains("truetype") {
+        return true;
+    }
+    file_name
+        .and_then(|name| name.extension())
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            matches!(
+                extension.to_ascii_lowercase().as_str(),
+                "ttf" | "otf" | "ttc" | "otc" | "woff" | "woff2"
+            )
+        })
+}
+
 /// A bitmap subtitle event containing an image and timing.
 #[derive(Debug, Clone)]
 pub struct BitmapSubtitleEvent {
@@ -1035,10 +1546,18 @@ pub struct BitmapSubtitleEvent {
     pub start_time: Duration,
     /// When this subtitle stops displaying.
     pub end_time: Duration,
-    /// Horizontal position on the video frame.
+    /// Horizontal position on the reference canvas.
     pub x: u32,
-    /// Vertical position on the video frame.
+    /// Vertical position on the reference canvas.
     pub y: u32,
+    /// Width of the canvas [`x`](BitmapSubtitleEvent::x) and
+    /// [`y`](BitmapSubtitleEvent::y) refer to: the subtitle track's own
+    /// display size when it declares one (PGS, DVD), otherwise the stored
+    /// video width. Zero for a file without either.
+    pub reference_width: u32,
+    /// Height of the reference canvas; see
+    /// [`reference_width`](BitmapSubtitleEvent::reference_width).
+    pub reference_height: u32,
     /// The decoded subtitle image (RGBA).
     pub image: DynamicImage,
     /// Zero-based index of this event.
@@ -1050,6 +1569,127 @@ impl BitmapSubtitleEvent {
     pub fn as_image(&self) -> &DynamicImage {
         &self.image
     }
+
+    /// Place the bitmap on a frame turned by `rotation` and scaled to
+    /// `output_width`×`output_height`.
+    ///
+    /// Bitmap positions are given on the stored, unrotated canvas, so on a
+    /// frame from [`VideoHandle::display_frame`](crate::VideoHandle::display_frame)
+    /// (pass [`VideoMetadata::rotation`](crate::VideoMetadata::rotation) and
+    /// the frame's size) they would land in the wrong place. Returns the
+    /// `(x, y, width, height)` rectangle the bitmap covers on that frame;
+    /// turn the [`image`](BitmapSubtitleEvent::image) by the same rotation
+    /// and resize it to `width`×`height` before compositing.
+    ///
+    /// Without reference dimensions the rectangle is only rotated around
+    /// the bitmap's own extent and not scaled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use image::{GenericImage, imageops::FilterType};
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("phone.mkv")?;
+    /// let rotation = unbundler.metadata().video.as_ref().map_or(0, |video| video.rotation);
+    /// let mut frame = unbundler.video().display_frame(0)?;
+    /// for event in unbundler.subtitle().extract_bitmaps()? {
+    ///     let (x, y, width, height) =
+    ///         event.display_rect(rotation, frame.width(), frame.height());
+    ///     let bitmap = match rotation {
+    ///         90 => event.image.rotate90(),
+    ///         180 => event.image.rotate180(),
+    ///         270 => event.image.rotate270(),
+    ///         _ => event.image.clone(),
+    ///     };
+    ///     let bitmap = bitmap.resize_exact(width, height, FilterType::Triangle);
+    ///     frame.copy_from(&bitmap, x, y).ok();
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn display_rect(
+        &self,
+        rotation: u32,
+        output_width: u32,
+        output_height: u32,
+    ) -> (u32, u32, u32, u32) {
+        let (width, height) = (self.image.width(), self.image.height());
+        let (canvas_width, canvas_height) = if self.reference_width > 0 && self.reference_height > 0
+        {
+            (self.reference_width, self.reference_height)
+        } else {
+            (self.x + width, self.y + height)
+        };
+
+        // Turn the rectangle clockwise with its canvas.
+        let right_gap = canvas_width.saturating_sub(self.x + width);
+        let bottom_gap = canvas_height.saturating_sub(self.y + height);
+        let (x, y, width, height, canvas_width, canvas_height) = match rotation % 360 {
+            90 => (
+                bottom_gap,
+                self.x,
+                height,
+                width,
+                canvas_height,
+                canvas_width,
+            ),
+            180 => (
+                right_gap,
+                bottom_gap,
+                width,
+                height,
+                canvas_width,
+                canvas_height,
+            ),
+            270 => (
+                self.y,
+                right_gap,
+                height,
+                width,
+                canvas_height,
+                canvas_width,
+            ),
+            _ => (self.x, self.y, width, height, canvas_width, canvas_height),
+        };
+
+        if self.reference_width == 0 || self.reference_height == 0 {
+            return (x, y, width, height);
+        }
+        let scale_x = f64::from(output_width) / f64::from(canvas_width);
+        let scale_y = f64::from(output_height) / f64::from(canvas_height);
+        let scale = |value: u32, factor: f64| (f64::from(value) * factor).round() as u32;
+        (
+            scale(x, scale_x),
+            scale(y, scale_y),
+            scale(width, scale_x).max(1),
+            scale(height, scale_y).max(1),
+        )
+    }
+}
+
+/// Optical character recognition for bitmap subtitles.
+///
+/// Implement this trait to plug an OCR engine (tesseract, a cloud API, a
+/// custom model, …) into [`SubtitleHandle::ocr_bitmaps`]. The crate does
+/// not ship an OCR engine of its own.
+pub trait BitmapOcr {
+    /// Recognise the text in a single subtitle bitmap.
+    ///
+    /// `image` is the RGBA bitmap as decoded from the stream, with a
+    /// transparent background. Return an empty string if nothing was
+    /// recognised.
+    fn recognize(&self, image: &DynamicImage) -> String;
+}
+
+/// Whether `codec` stores subtitles as images rather than text.
+fn is_bitmap_codec(codec: Id) -> bool {
+    // SAFETY: descriptors are static tables owned by FFmpeg; unknown ids
+    // give a null pointer.
+    unsafe {
+        let descriptor = ffmpeg_sys_next::avcodec_descriptor_get(codec.into());
+        !descriptor.is_null()
+            && (*descriptor).props & ffmpeg_sys_next::AV_CODEC_PROP_BITMAP_SUB != 0
+    }
 }
 
 /// Decode a PAL8 bitmap subtitle rect into an RGBA [`DynamicImage`].
@@ -1217,3 +1857,18 @@ fn strip_ass_tags(input: &str) -> String {
         .trim()
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use ffmpeg_next::codec::Id;
+
+    use super::is_bitmap_codec;
+
+    #[test]
+    fn bitmap_codecs_are_detected_from_descriptors() {
+        assert!(is_bitmap_codec(Id::HDMV_PGS_SUBTITLE));
+        assert!(is_bitmap_codec(Id::DVD_SUBTITLE));
+        assert!(!is_bitmap_codec(Id::SUBRIP));
+        assert!(!is_bitmap_codec(Id::None));
+    }
+}
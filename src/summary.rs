@@ -0,0 +1,117 @@
+//! Statistics about a written output.
+//!
+//! The `*_with_summary` variants of the save and stream copy methods return
+//! an [`ExtractSummary`] describing what actually reached the output. Its
+//! duration can differ from the requested range: stream copies start at a
+//! packet or keyframe boundary, and encoders round the end up to a whole
+//! frame. Clips that are stitched together need the real figure.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use unbundle::{AudioFormat, ExtractOptions, MediaFile, UnbundleError};
+//!
+//! let mut unbundler = MediaFile::open("input.mp4")?;
+//! let summary = unbundler.audio().save_range_with_summary(
+//!     "clip.flac",
+//!     Duration::from_secs(10),
+//!     Duration::from_secs(20),
+//!     AudioFormat::Flac,
+//!     &ExtractOptions::new(),
+//! )?;
+//! println!(
+//!     "{:?} of audio in {} bytes",
+//!     summary.output_duration, summary.bytes_written
+//! );
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::time::Duration;
+
+use ffmpeg_next::format::context::Output;
+use ffmpeg_next::{Packet, Rational};
+
+/// What an extraction wrote to its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractSummary {
+    /// Presentation length of the written stream: from the first packet's
+    /// timestamp to the end of the last packet. Encoder priming, which is
+    /// written before time zero, is not counted.
+    pub output_duration: Duration,
+    /// Size of the output in bytes, headers and index included.
+    pub bytes_written: u64,
+    /// Packets written; for a video stream copy, the number of frames.
+    pub packets_written: u64,
+    /// Audio samples per channel passed to the encoder, including any
+    /// lead-in silence. `None` for stream copies, which do not decode.
+    pub samples_written: Option<u64>,
+}
+
+/// Running span of the packets written to a single-stream output.
+#[derive(Debug, Default)]
+pub(crate) struct PacketSpan {
+    packets: u64,
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl PacketSpan {
+    /// Record `packet`, whose timestamps are in the output time base. Call
+    /// before writing it, since the muxer takes the packet's contents.
+    pub(crate) fn record(&mut self, packet: &Packet) {
+        self.packets += 1;
+        let Some(timestamp) = packet.pts().or(packet.dts()) else {
+            return;
+        };
+        let end = timestamp + packet.duration().max(0);
+        self.start = Some(self.start.map_or(timestamp, |start| start.min(timestamp)));
+        self.end = Some(self.end.map_or(end, |current| current.max(end)));
+    }
+
+    /// Summarise `output` once its trailer has been written.
+    pub(crate) fn summary(
+        &self,
+        output: &mut Output,
+        time_base: Rational,
+        samples_written: Option<u64>,
+    ) -> ExtractSummary {
+        let output_duration = match (self.start, self.end) {
+            (Some(start), Some(end)) => {
+                let ticks = (end - start.max(0)).max(0);
+                Duration::from_secs_f64(crate::conversion::pts_to_seconds(ticks, time_base))
+            }
+            _ => Duration::ZERO,
+        };
+        ExtractSummary {
+            output_duration,
+            bytes_written: output_size(output),
+            packets_written: self.packets,
+            samples_written,
+        }
+    }
+}
+
+/// Bytes in `output`, asked of its I/O context rather than the file system
+/// so that pipes and other non-file outputs are measured too.
+fn output_size(output: &mut Output) -> u64 {
+    // SAFETY: the I/O context stays open until `output` is dropped, and
+    // the trailer has flushed everything written.
+    unsafe {
+        let io_context = (*output.as_mut_ptr()).pb;
+        if io_context.is_null() {
+            return 0;
+        }
+        // A seekable output may have been rewritten in place, e.g. to patch
+        // a header, so its size is its length. Anything else was written
+        // strictly in order.
+        let size = ffmpeg_sys_next::avio_size(io_context);
+        let size = if size >= 0 {
+            size
+        } else {
+            (*io_context).bytes_written
+        };
+        size.max(0) as u64
+    }
+}
@@ -145,6 +145,55 @@ impl ThumbnailHandle {
         Ok(image.resize_exact(thumb_width, thumb_height, FilterType::Triangle))
     }
 
+    /// Extract the frame at a timestamp once and scale it to each of
+    /// `widths`, preserving its aspect ratio.
+    ///
+    /// Returns `(width, image)` pairs in the order of `widths`, ready for a
+    /// responsive-image `srcset`. Every size is scaled from the full
+    /// decoded frame, so small sizes are as sharp as when extracted alone.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`at_timestamp`](ThumbnailHandle::at_timestamp).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, ThumbnailHandle, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let posters =
+    ///     ThumbnailHandle::multi_size(&mut unbundler, Duration::from_secs(5), &[1280, 640, 320])?;
+    /// for (width, image) in &posters {
+    ///     image.save(format!("poster_{width}w.jpg"))?;
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn multi_size(
+        unbundler: &mut MediaFile,
+        timestamp: Duration,
+        widths: &[u32],
+    ) -> Result<Vec<(u32, DynamicImage)>, UnbundleError> {
+        log::debug!(
+            "Generating {} thumbnail sizes at {timestamp:?}",
+            widths.len()
+        );
+        let image = unbundler.video().frame_at(timestamp)?;
+        let aspect_ratio = f64::from(image.height()) / f64::from(image.width().max(1));
+        Ok(widths
+            .iter()
+            .map(|&target_width| {
+                let target_width = target_width.max(1);
+                let target_height =
+                    ((f64::from(target_width) * aspect_ratio).round() as u32).max(1);
+                let scaled = image.resize_exact(target_width, target_height, FilterType::Triangle);
+                (target_width, scaled)
+            })
+            .collect())
+    }
+
     /// Generate a thumbnail contact-sheet grid.
     ///
     /// Extracts `columns × rows` frames at evenly-spaced intervals across
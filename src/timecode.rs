@@ -0,0 +1,174 @@
+//! SMPTE timecodes.
+//!
+//! Editors and broadcast systems address frames as `HH:MM:SS:FF` rather
+//! than as times or frame numbers. [`Timecode`] parses that notation,
+//! including drop-frame timecode for 29.97 and 59.94 fps material (written
+//! with a `;` before the frames, as in `01:23:45;12`), and converts it to
+//! and from frame counts.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use unbundle::{MediaFile, UnbundleError};
+//!
+//! let mut unbundler = MediaFile::open("broadcast.mov")?;
+//! if let Some(start) = unbundler.metadata().video.as_ref().and_then(|v| v.start_timecode) {
+//!     println!("Source starts at {start}");
+//! }
+//! let frame = unbundler.video().frame_at_timecode("01:00:10;00")?;
+//! frame.save("shot.png")?;
+//! # Ok::<(), UnbundleError>(())
+//! ```
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::error::UnbundleError;
+
+/// A SMPTE timecode.
+///
+/// Frames are counted at the nominal rate — the frame rate rounded to a
+/// whole number, so 30 for 29.97 fps. Drop-frame timecode skips frame
+/// numbers 0 and 1 (0 to 3 at 59.94 fps) at the start of every minute
+/// except each tenth, which keeps it in step with the clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    /// Hours, `0..=23` in practice but not limited.
+    pub hours: u32,
+    /// Minutes, `0..60`.
+    pub minutes: u32,
+    /// Seconds, `0..60`.
+    pub seconds: u32,
+    /// Frames within the second, below the nominal frame rate.
+    pub frames: u32,
+    /// Whether this is drop-frame timecode.
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Parse `HH:MM:SS:FF`, or `HH:MM:SS;FF` for drop-frame timecode.
+    ///
+    /// Any separator may be a `;` (some tools write `HH;MM;SS;FF`); the
+    /// timecode is drop-frame if the last one is. Field ranges other than
+    /// the frame count, which depends on the frame rate, are checked here.
+    ///
+    /// # Errors
+    ///
+    /// [`UnbundleError::InvalidTimecode`] if `text` is not four numeric
+    /// fields or minutes or seconds are 60 or more.
+    pub fn parse(text: &str) -> Result<Self, UnbundleError> {
+        let invalid = || UnbundleError::InvalidTimecode(text.to_string());
+        let text = text.trim();
+        let fields: Vec<&str> = text.split([':', ';']).collect();
+        let [hours, minutes, seconds, frames] = fields[..] else {
+            return Err(invalid());
+        };
+        let number = |field: &str| {
+            if field.is_empty() || !field.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            field.parse::<u32>().map_err(|_| invalid())
+        };
+        let timecode = Self {
+            hours: number(hours)?,
+            minutes: number(minutes)?,
+            seconds: number(seconds)?,
+            frames: number(frames)?,
+            drop_frame: text.len() > frames.len()
+                && text[..text.len() - frames.len()].ends_with(';'),
+        };
+        if timecode.minutes >= 60 || timecode.seconds >= 60 {
+            return Err(invalid());
+        }
+        Ok(timecode)
+    }
+
+    /// Frames elapsed since `00:00:00:00` at `frames_per_second`.
+    ///
+    /// # Errors
+    ///
+    /// [`UnbundleError::InvalidTimecode`] if the frame field is not below
+    /// the nominal rate, if drop-frame timecode is used at a rate that is
+    /// not a multiple of 30, or if it names a dropped frame number.
+    pub fn to_frame_number(&self, frames_per_second: f64) -> Result<u64, UnbundleError> {
+        let invalid = || UnbundleError::InvalidTimecode(self.to_string());
+        let rate = nominal_rate(frames_per_second);
+        if u64::from(self.frames) >= rate {
+            return Err(invalid());
+        }
+        let total_minutes = u64::from(self.hours) * 60 + u64::from(self.minutes);
+        let total_seconds = total_minutes * 60 + u64::from(self.seconds);
+        let frame_number = total_seconds * rate + u64::from(self.frames);
+        if !self.drop_frame {
+            return Ok(frame_number);
+        }
+
+        let dropped = dropped_per_minute(rate).ok_or_else(invalid)?;
+        if self.seconds == 0 && self.minutes % 10 != 0 && u64::from(self.frames) < dropped {
+            return Err(invalid());
+        }
+        Ok(frame_number - dropped * (total_minutes - total_minutes / 10))
+    }
+
+    /// The timecode of the `frame_number`-th frame at `frames_per_second`.
+    ///
+    /// Drop-frame is only honoured at rates whose nominal rate is a
+    /// multiple of 30; at other rates the result is non-drop-frame.
+    pub fn from_frame_number(frame_number: u64, frames_per_second: f64, drop_frame: bool) -> Self {
+        let rate = nominal_rate(frames_per_second);
+        let dropped = dropped_per_minute(rate).filter(|_| drop_frame);
+        let mut frame_number = frame_number;
+        if let Some(dropped) = dropped {
+            // Add back the numbers skipped so far, then count as non-drop.
+            let per_ten_minutes = rate * 600 - dropped * 9;
+            let per_minute = rate * 60 - dropped;
+            let tens = frame_number / per_ten_minutes;
+            let remainder = frame_number % per_ten_minutes;
+            let skipped_minutes = if remainder < dropped {
+                0
+            } else {
+                (remainder - dropped) / per_minute
+            };
+            frame_number += dropped * (tens * 9 + skipped_minutes);
+        }
+
+        let total_seconds = frame_number / rate;
+        Self {
+            hours: (total_seconds / 3600) as u32,
+            minutes: (total_seconds / 60 % 60) as u32,
+            seconds: (total_seconds % 60) as u32,
+            frames: (frame_number % rate) as u32,
+            drop_frame: dropped.is_some(),
+        }
+    }
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let separator = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{separator}{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+impl FromStr for Timecode {
+    type Err = UnbundleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// Whole frames per timecode second.
+fn nominal_rate(frames_per_second: f64) -> u64 {
+    (frames_per_second.round() as u64).max(1)
+}
+
+/// Frame numbers drop-frame timecode skips each minute, or `None` when the
+/// rate has no drop-frame form.
+fn dropped_per_minute(rate: u64) -> Option<u64> {
+    (rate % 30 == 0).then_some(rate / 15)
+}
@@ -9,22 +9,35 @@
 use std::{
     collections::HashMap,
     fmt::{Debug, Formatter, Result as FmtResult},
+    io::{Read, Seek},
     path::{Path, PathBuf},
     time::Duration,
 };
 
 use ffmpeg_next::{
-    codec::context::Context as CodecContext,
-    format::{context::Input, stream::Disposition},
+    DictionaryRef,
+    codec::{context::Context as CodecContext, packet::side_data},
+    format::{context::Input, stream::Disposition, stream::Stream},
     media::Type,
 };
+use ffmpeg_sys_next::{AVDiscard, AVFieldOrder};
+use image::DynamicImage;
 
 use crate::{
-    audio::AudioHandle,
+    audio::{AudioFormat, AudioHandle},
+    checksum::StreamChecksum,
+    compare::{CompareReport, MetricOptions},
     error::UnbundleError,
-    metadata::{AudioMetadata, ChapterMetadata, MediaMetadata, SubtitleMetadata, VideoMetadata},
+    metadata::{
+        AudioMetadata, ChapterMetadata, FieldOrder, MediaMetadata, ProgramMetadata, StreamKind,
+        StreamMetadata, SubtitleMetadata, VideoMetadata,
+    },
     packet_iterator::PacketIterator,
-    subtitle::SubtitleHandle,
+    reader_io::{READER_SOURCE, ReadSeek, ReaderInput},
+    remux::SegmentInfo,
+    subtitle::{SubtitleEvent, SubtitleHandle},
+    timecode::Timecode,
+    validation::ConcatReport,
     video::VideoHandle,
 };
 
@@ -72,6 +85,19 @@ pub struct MediaFile {
     /// error payload compatibility.
     #[allow(dead_code)]
     pub(crate) file_path: PathBuf,
+    /// Whether the input can seek, as found when it was opened.
+    pub(crate) seekable: bool,
+    /// Streams the container carried a seek index for, recorded before
+    /// any packet read could add entries of its own.
+    pub(crate) indexed_streams: Vec<usize>,
+    /// Metadata of every stream, saved by the first
+    /// [`select_program`](MediaFile::select_program) call so later calls
+    /// can choose from the whole file again.
+    pub(crate) unfiltered_metadata: Option<Box<MediaMetadata>>,
+    /// Custom I/O behind an input opened with
+    /// [`open_reader`](MediaFile::open_reader). Declared after
+    /// `input_context` so the demuxer is closed first.
+    pub(crate) reader: Option<ReaderInput>,
 }
 
 impl Debug for MediaFile {
@@ -114,6 +140,13 @@ impl MediaFile {
 
         log::debug!("Opening media source: {source}");
 
+        if source == READER_SOURCE {
+            return Err(UnbundleError::SourceOpen {
+                input_source: source.to_string(),
+                reason: "an input opened from a reader cannot be opened again".to_string(),
+            });
+        }
+
         // Initialise ffmpeg (safe to call multiple times).
         ffmpeg_next::init().map_err(|error| {
             Self::open_error(
@@ -127,16 +160,25 @@ impl MediaFile {
         let input_context = ffmpeg_next::format::input(source)
             .map_err(|error| Self::open_error(source, &source_path, error.to_string()))?;
 
-        // Locate best video and audio streams.
-        let video_stream_index = input_context
-            .streams()
-            .best(Type::Video)
-            .map(|stream| stream.index());
+        Self::from_input(input_context, source_string, source_path)
+    }
 
-        let audio_stream_index = input_context
-            .streams()
-            .best(Type::Audio)
-            .map(|stream| stream.index());
+    /// Read the metadata of an opened input and wrap it.
+    ///
+    /// `source_path` is only consulted when it names a regular file.
+    fn from_input(
+        mut input_context: Input,
+        source_string: String,
+        source_path: PathBuf,
+    ) -> Result<Self, UnbundleError> {
+        let seekable = probe_seekable(&mut input_context);
+        let indexed_streams = (0..input_context.nb_streams() as usize)
+            .filter(|&index| crate::keyframe::has_seek_index(&input_context, index))
+            .collect();
+
+        // Locate the default video and audio streams.
+        let video_stream_index = select_default_stream(&input_context, Type::Video);
+        let audio_stream_index = select_default_stream(&input_context, Type::Audio);
 
         // Extract container-level duration.
         let duration_microseconds = input_context.duration();
@@ -149,6 +191,13 @@ impl MediaFile {
         // Extract container format name.
         let format = input_context.format().name().to_string();
 
+        // Edit lists are only visible in the MP4/MOV boxes themselves.
+        let edit_lists = if format.contains("mov") && source_path.is_file() {
+            crate::edit_list::read_edit_lists(&source_path)
+        } else {
+            HashMap::new()
+        };
+
         // Extract container-level metadata tags.
         let tags = {
             let mut map = HashMap::new();
@@ -208,6 +257,26 @@ impl MediaFile {
 
             let width = video_decoder.width();
             let height = video_decoder.height();
+            let (coded_width, coded_height) = {
+                let par = stream.parameters();
+                // SAFETY: `extradata` holds `extradata_size` bytes when set.
+                let extradata = unsafe {
+                    let raw_par = *par.as_ptr();
+                    if raw_par.extradata.is_null() || raw_par.extradata_size <= 0 {
+                        &[][..]
+                    } else {
+                        std::slice::from_raw_parts(
+                            raw_par.extradata,
+                            raw_par.extradata_size as usize,
+                        )
+                    }
+                };
+                crate::coded_size::coded_dimensions(par.id(), extradata)
+                    .filter(|&(coded_width, coded_height)| {
+                        coded_width >= width && coded_height >= height
+                    })
+                    .unwrap_or((width, height))
+            };
 
             // Compute frames per second from the stream's average frame rate.
             let frame_rate = stream.avg_frame_rate();
@@ -267,9 +336,57 @@ impl MediaFile {
                 if name == "None" { None } else { Some(name) }
             };
 
+            // The coded order (TB/BT) matters only to the decoder; report
+            // which field is displayed first.
+            let field_order = {
+                let par = stream.parameters();
+                match unsafe { (*par.as_ptr()).field_order } {
+                    AVFieldOrder::AV_FIELD_PROGRESSIVE => Some(FieldOrder::Progressive),
+                    AVFieldOrder::AV_FIELD_TT | AVFieldOrder::AV_FIELD_BT => {
+                        Some(FieldOrder::TopFieldFirst)
+                    }
+                    AVFieldOrder::AV_FIELD_BB | AVFieldOrder::AV_FIELD_TB => {
+                        Some(FieldOrder::BottomFieldFirst)
+                    }
+                    _ => None,
+                }
+            };
+
+            let rotation = stream
+                .side_data()
+                .find(|data| data.kind() == side_data::Type::DisplayMatrix)
+                .map_or(0, |data| display_matrix_rotation(data.data()));
+
+            // The container's value wins over the codec's, as in
+            // `av_guess_sample_aspect_ratio`.
+            let sample_aspect_ratio = {
+                let par = stream.parameters();
+                let (container, codec) = unsafe {
+                    (
+                        (*stream.as_ptr()).sample_aspect_ratio,
+                        (*par.as_ptr()).sample_aspect_ratio,
+                    )
+                };
+                [container, codec]
+                    .into_iter()
+                    .find(|ratio| ratio.num > 0 && ratio.den > 0)
+                    .map(|ratio| (ratio.num as u32, ratio.den as u32))
+            };
+
+            // MOV copies its `tmcd` track's start onto the video stream;
+            // MXF and MPEG-TS tag the container instead.
+            let stream_tags = stream.metadata();
+            let container_tags = input_context.metadata();
+            let start_timecode = stream_tags
+                .get("timecode")
+                .or_else(|| container_tags.get("timecode"))
+                .and_then(|text| Timecode::parse(text).ok());
+
             all_video_metadata.push(VideoMetadata {
                 width,
                 height,
+                coded_width,
+                coded_height,
                 frames_per_second,
                 frame_count,
                 codec: codec_name,
@@ -279,6 +396,15 @@ impl MediaFile {
                 color_transfer,
                 bits_per_raw_sample,
                 pixel_format_name,
+                field_order,
+                rotation,
+                sample_aspect_ratio,
+                duration: stream_duration(&stream),
+                has_edit_list: edit_lists.contains_key(&stream.id()),
+                edit_list_offset: edit_lists
+                    .get(&stream.id())
+                    .map_or(0.0, |edit_list| edit_list.offset_seconds),
+                start_timecode,
                 track_index,
                 stream_index: index,
             });
@@ -351,6 +477,11 @@ impl MediaFile {
                 channels,
                 codec: codec_name,
                 bit_rate,
+                duration: stream_duration(&stream),
+                has_edit_list: edit_lists.contains_key(&stream.id()),
+                edit_list_offset: edit_lists
+                    .get(&stream.id())
+                    .map_or(0.0, |edit_list| edit_list.offset_seconds),
                 track_index,
                 stream_index: index,
             });
@@ -410,6 +541,7 @@ impl MediaFile {
             all_subtitle_metadata.push(SubtitleMetadata {
                 codec: codec_name,
                 language,
+                duration: stream_duration(&stream),
                 track_index,
                 stream_index: index,
             });
@@ -431,26 +563,7 @@ impl MediaFile {
         };
 
         // Extract chapter metadata.
-        let chapters = if input_context.nb_chapters() > 0 {
-            let mut chapter_list = Vec::with_capacity(input_context.nb_chapters() as usize);
-            for (index, chapter) in input_context.chapters().enumerate() {
-                let time_base = chapter.time_base();
-                let start_seconds = crate::conversion::pts_to_seconds(chapter.start(), time_base);
-                let end_seconds = crate::conversion::pts_to_seconds(chapter.end(), time_base);
-                let title = chapter.metadata().get("title").map(|s| s.to_string());
-
-                chapter_list.push(ChapterMetadata {
-                    title,
-                    start: Duration::from_secs_f64(start_seconds),
-                    end: Duration::from_secs_f64(end_seconds),
-                    index,
-                    id: chapter.id(),
-                });
-            }
-            Some(chapter_list)
-        } else {
-            None
-        };
+        let chapters = read_chapters(&input_context, duration);
 
         let metadata = MediaMetadata {
             video: video_metadata,
@@ -463,11 +576,12 @@ impl MediaFile {
             duration,
             format,
             tags,
+            is_live: input_is_live(&input_context, duration_microseconds),
         };
 
         log::info!(
             "Opened media file: {} (format={}, duration={:.2}s, video_streams={}, audio_streams={}, subtitle_streams={})",
-            source,
+            source_string,
             metadata.format,
             metadata.duration.as_secs_f64(),
             video_stream_indices.len(),
@@ -508,6 +622,10 @@ impl MediaFile {
             subtitle_stream_indices,
             source: source_string,
             file_path: source_path,
+            seekable,
+            indexed_streams,
+            unfiltered_metadata: None,
+            reader: None,
         })
     }
 
@@ -556,6 +674,53 @@ impl MediaFile {
         Self::open_source(url)
     }
 
+    /// Open media from any [`Read`] + [`Seek`] value, such as a
+    /// [`File`](std::fs::File) or a wrapper that decrypts or decompresses
+    /// on the fly.
+    ///
+    /// FFmpeg pulls data from `reader` as it demuxes, so the input is never
+    /// buffered whole in memory. The returned `MediaFile` owns the reader
+    /// and drops it when it is dropped itself.
+    ///
+    /// Such a file has no name to open again, so features that reopen the
+    /// input — [`MediaFilePool`](crate::MediaFilePool), parallel and async
+    /// extraction — fail with [`UnbundleError::SourceOpen`]. FFmpeg still
+    /// applies MP4 edit lists while demuxing, but the scan that reports them
+    /// needs a path, so [`VideoMetadata::has_edit_list`](crate::VideoMetadata::has_edit_list)
+    /// is always `false` for such files.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::SourceOpen`] if FFmpeg cannot recognise the
+    /// data or the reader fails while it is probed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let file = File::open("input.mp4")?;
+    /// let mut unbundler = MediaFile::open_reader(file)?;
+    /// println!("{:?}", unbundler.metadata().duration);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn open_reader<R: Read + Seek + Send + 'static>(reader: R) -> Result<Self, UnbundleError> {
+        log::debug!("Opening media from a reader");
+
+        ffmpeg_next::init().map_err(|error| UnbundleError::SourceOpen {
+            input_source: READER_SOURCE.to_string(),
+            reason: format!("FFmpeg initialisation failed: {error}"),
+        })?;
+
+        let reader: Box<dyn ReadSeek> = Box::new(reader);
+        let (input_context, reader) = ReaderInput::open(reader)?;
+        let mut file = Self::from_input(input_context, READER_SOURCE.to_string(), PathBuf::new())?;
+        file.reader = Some(reader);
+        Ok(file)
+    }
+
     /// Probe a media file and return metadata without retaining an open demuxer.
     ///
     /// This is a convenience wrapper around [`MediaProbe`](crate::MediaProbe)
@@ -586,6 +751,348 @@ impl MediaFile {
         &self.metadata
     }
 
+    /// Returns `true` if the file contains at least one video stream.
+    ///
+    /// Use this before calling [`video`](MediaFile::video) on inputs that
+    /// may be audio-only (e.g. MP3 or FLAC), since the handle itself is
+    /// always returned and [`UnbundleError::NoVideoStream`] only surfaces
+    /// once an extraction method runs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp3")?;
+    /// if unbundler.has_video() {
+    ///     unbundler.video().save_frame(0, "cover.png")?;
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn has_video(&self) -> bool {
+        self.video_stream_index.is_some()
+    }
+
+    /// Returns `true` if the file contains at least one audio stream.
+    ///
+    /// The counterpart of [`has_video`](MediaFile::has_video) for
+    /// [`audio`](MediaFile::audio).
+    pub fn has_audio(&self) -> bool {
+        self.audio_stream_index.is_some()
+    }
+
+    /// Returns `true` if the file contains at least one subtitle stream.
+    ///
+    /// The counterpart of [`has_video`](MediaFile::has_video) for
+    /// [`subtitle`](MediaFile::subtitle).
+    pub fn has_subtitles(&self) -> bool {
+        self.subtitle_stream_index.is_some()
+    }
+
+    /// Returns `true` if the input supports random access.
+    ///
+    /// This reflects the I/O layer's seekable flag: regular files and most
+    /// HTTP sources are seekable, while pipes, stdin and live streams are
+    /// not. Formats that handle their own I/O, such as RTSP, have no such
+    /// flag; their demuxer is asked instead, by seeking to the start when
+    /// the input is opened.
+    ///
+    /// Frame range extraction ([`VideoHandle::frames`],
+    /// [`VideoHandle::for_each_frame`] and their `_with_options` variants)
+    /// does not need it: on an input that is not seekable it decodes
+    /// forward from the current read position, as with
+    /// [`ExtractOptions::with_no_seek`](crate::ExtractOptions::with_no_seek).
+    ///
+    /// Other operations that start anywhere other than the current read
+    /// position still need to seek: single-frame extraction
+    /// ([`VideoHandle::frame`], [`VideoHandle::frame_at`]), frame
+    /// iterators, audio and subtitle extraction with a start time, range
+    /// stream copies, and [`split_segments`](MediaFile::split_segments). On
+    /// an input that is not seekable these fail with
+    /// [`UnbundleError::NotSeekable`] instead of decoding from the wrong
+    /// position. Metadata access and
+    /// [`packet_iter`](MediaFile::packet_iter) never seek.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open_url("pipe:0")?;
+    /// if !unbundler.is_seekable() {
+    ///     eprintln!("Input is a stream; only sequential reads are possible");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn is_seekable(&self) -> bool {
+        self.seekable
+    }
+
+    /// Whether the input looks like a live stream with no end.
+    ///
+    /// Same as [`MediaMetadata::is_live`]. Operations planned against the
+    /// media duration, such as
+    /// [`FrameRange::TimeInterval`](crate::FrameRange::TimeInterval) and
+    /// [`FrameRange::KeyframesOnly`](crate::FrameRange::KeyframesOnly),
+    /// fail with [`UnbundleError::LiveStream`] on live inputs. Read those
+    /// with [`packet_iter`](MediaFile::packet_iter), or with
+    /// [`FrameRange::Interval`](crate::FrameRange::Interval) and
+    /// [`ExtractOptions::with_no_seek`](crate::ExtractOptions::with_no_seek),
+    /// using a [`CancellationToken`](crate::CancellationToken) to stop.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let unbundler = MediaFile::open_url("https://example.com/live/index.m3u8")?;
+    /// if unbundler.is_live() {
+    ///     eprintln!("Live stream: sampling until cancelled");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn is_live(&self) -> bool {
+        self.metadata.is_live
+    }
+
+    /// List the programs (channels) of a multi-program container.
+    ///
+    /// Broadcast MPEG-TS captures often multiplex several services, each
+    /// with its own video and audio. Most other containers have no
+    /// programs, and the list is empty.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let unbundler = MediaFile::open("multiplex.ts")?;
+    /// for program in unbundler.programs() {
+    ///     println!("{}: {:?}", program.id, program.name);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn programs(&self) -> Vec<ProgramMetadata> {
+        // SAFETY: the program array and each program's stream list belong
+        // to the open input context and are only read here.
+        unsafe {
+            let context = self.input_context.as_ptr();
+            (0..(*context).nb_programs as usize)
+                .map(|program_index| {
+                    let program = *(*context).programs.add(program_index);
+                    let stream_indices = (0..(*program).nb_stream_indexes as usize)
+                        .map(|position| *(*program).stream_index.add(position) as usize)
+                        .collect();
+                    let tags = DictionaryRef::wrap((*program).metadata);
+                    ProgramMetadata {
+                        id: (*program).id,
+                        name: tags.get("service_name").map(str::to_string),
+                        provider: tags.get("service_provider").map(str::to_string),
+                        stream_indices,
+                    }
+                })
+                .collect()
+        }
+    }
+
+    /// List every stream in the container, in container order.
+    ///
+    /// Each entry carries the stream's kind, codec, language and
+    /// disposition flags, plus the track number that the `*_track` methods
+    /// accept, so callers can pick a track by language or codec without
+    /// going through FFmpeg directly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let unbundler = MediaFile::open("movie.mkv")?;
+    /// for stream in unbundler.streams() {
+    ///     println!(
+    ///         "#{} {} {} {:?}",
+    ///         stream.index, stream.kind, stream.codec, stream.language
+    ///     );
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn streams(&self) -> Vec<StreamMetadata> {
+        let video_tracks = self.metadata.video_tracks.as_deref().unwrap_or_default();
+        let audio_tracks = self.metadata.audio_tracks.as_deref().unwrap_or_default();
+        let subtitle_tracks = self.metadata.subtitle_tracks.as_deref().unwrap_or_default();
+
+        self.input_context
+            .streams()
+            .map(|stream| {
+                let index = stream.index();
+                let parameters = stream.parameters();
+                let disposition = stream.disposition();
+                let kind = match parameters.medium() {
+                    Type::Video => StreamKind::Video,
+                    Type::Audio => StreamKind::Audio,
+                    Type::Subtitle => StreamKind::Subtitle,
+                    Type::Data => StreamKind::Data,
+                    Type::Attachment => StreamKind::Attachment,
+                    Type::Unknown => StreamKind::Unknown,
+                };
+                let video = video_tracks
+                    .iter()
+                    .find(|track| track.stream_index == index);
+                let audio = audio_tracks
+                    .iter()
+                    .find(|track| track.stream_index == index);
+                let subtitle = subtitle_tracks
+                    .iter()
+                    .find(|track| track.stream_index == index);
+
+                StreamMetadata {
+                    index,
+                    kind,
+                    codec: parameters.id().name().to_string(),
+                    language: stream.metadata().get("language").map(str::to_string),
+                    track_index: video
+                        .map(|track| track.track_index)
+                        .or(audio.map(|track| track.track_index))
+                        .or(subtitle.map(|track| track.track_index)),
+                    dimensions: video.map(|track| (track.width, track.height)),
+                    sample_rate: audio.map(|track| track.sample_rate),
+                    channels: audio.map(|track| track.channels),
+                    default: disposition.contains(Disposition::DEFAULT),
+                    forced: disposition.contains(Disposition::FORCED),
+                    attached_picture: disposition.contains(Disposition::ATTACHED_PIC),
+                }
+            })
+            .collect()
+    }
+
+    /// Checksum the packet payloads of every stream.
+    ///
+    /// Returns one [`StreamChecksum`] per stream, in container order, with
+    /// a CRC-32 of the stream's packet data and its packet and byte counts.
+    /// Timestamps and container framing are not hashed, so a stream copied
+    /// into another file without re-encoding keeps its checksum. The whole
+    /// file is read from the start.
+    ///
+    /// Streams discarded by [`select_program`](MediaFile::select_program)
+    /// are not read and report zero packets.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from seeking to the start or reading packets.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// for checksum in unbundler.stream_checksums()? {
+    ///     println!(
+    ///         "#{} {}: {:08x} ({} packets)",
+    ///         checksum.stream_index, checksum.kind, checksum.crc32, checksum.packet_count
+    ///     );
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn stream_checksums(&mut self) -> Result<Vec<StreamChecksum>, UnbundleError> {
+        crate::checksum::stream_checksums_impl(self)
+    }
+
+    /// Restrict stream selection to one program of a multi-program
+    /// container.
+    ///
+    /// Afterwards [`video`](MediaFile::video), [`audio`](MediaFile::audio),
+    /// [`subtitle`](MediaFile::subtitle), the `*_track` methods, and
+    /// [`metadata`](MediaFile::metadata) only consider streams listed in
+    /// the program's map table, so the default video and audio come from
+    /// the same channel. Track indices are renumbered within the program.
+    /// The demuxer also discards packets of other programs, which speeds
+    /// up reading a busy multiplex.
+    ///
+    /// Calling it again switches to another program.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::ProgramNotFound`] if no program has the ID
+    /// `program_id`; the current selection is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{AudioFormat, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("multiplex.ts")?;
+    /// unbundler.select_program(4165)?;
+    /// unbundler.audio().save("channel.wav", AudioFormat::Wav)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn select_program(&mut self, program_id: i32) -> Result<(), UnbundleError> {
+        let members = self
+            .programs()
+            .into_iter()
+            .find(|program| program.id == program_id)
+            .ok_or(UnbundleError::ProgramNotFound { program_id })?
+            .stream_indices;
+        log::info!("Selecting program {program_id} (streams {members:?})");
+        let is_member = |stream_index: usize| members.contains(&stream_index);
+
+        let unfiltered = self
+            .unfiltered_metadata
+            .get_or_insert_with(|| Box::new(self.metadata.clone()));
+        let mut metadata = MediaMetadata::clone(unfiltered);
+
+        self.video_stream_index =
+            select_default_stream_among(&self.input_context, Type::Video, is_member);
+        self.audio_stream_index =
+            select_default_stream_among(&self.input_context, Type::Audio, is_member);
+        self.subtitle_stream_index =
+            select_default_stream_among(&self.input_context, Type::Subtitle, is_member);
+
+        (
+            metadata.video,
+            metadata.video_tracks,
+            self.video_stream_indices,
+        ) = restrict_tracks(
+            metadata.video_tracks.take(),
+            self.video_stream_index,
+            is_member,
+        );
+        (
+            metadata.audio,
+            metadata.audio_tracks,
+            self.audio_stream_indices,
+        ) = restrict_tracks(
+            metadata.audio_tracks.take(),
+            self.audio_stream_index,
+            is_member,
+        );
+        (
+            metadata.subtitle,
+            metadata.subtitle_tracks,
+            self.subtitle_stream_indices,
+        ) = restrict_tracks(
+            metadata.subtitle_tracks.take(),
+            self.subtitle_stream_index,
+            is_member,
+        );
+
+        self.metadata = metadata;
+
+        // SAFETY: the streams belong to the open input context; the demuxer
+        // reads `discard` when it next reads a packet.
+        unsafe {
+            let context = self.input_context.as_mut_ptr();
+            for index in 0..(*context).nb_streams as usize {
+                (*(*(*context).streams.add(index))).discard = if is_member(index) {
+                    AVDiscard::AVDISCARD_DEFAULT
+                } else {
+                    AVDiscard::AVDISCARD_ALL
+                };
+            }
+        }
+        Ok(())
+    }
+
     /// Create a lazy iterator over all demuxed packets.
     ///
     /// The iterator yields [`PacketInfo`](crate::PacketInfo) structs
@@ -610,6 +1117,11 @@ impl MediaFile {
 
     /// Obtain a [`VideoHandle`] for extracting video frames.
     ///
+    /// Uses the default video track: the one flagged `default` in the
+    /// container if any, otherwise the one FFmpeg ranks best. Cover art is
+    /// never selected. Use [`video_track`](MediaFile::video_track) to pick
+    /// another.
+    ///
     /// The returned extractor borrows this unbundler mutably, so you cannot
     /// hold extractors for both video and audio simultaneously.
     pub fn video(&mut self) -> VideoHandle<'_> {
@@ -657,6 +1169,9 @@ impl MediaFile {
 
     /// Obtain an [`AudioHandle`] for extracting audio data.
     ///
+    /// Uses the track reported by
+    /// [`best_audio_track`](MediaFile::best_audio_track).
+    ///
     /// The returned extractor borrows this unbundler mutably, so you cannot
     /// hold extractors for both video and audio simultaneously.
     pub fn audio(&mut self) -> AudioHandle<'_> {
@@ -667,6 +1182,38 @@ impl MediaFile {
         }
     }
 
+    /// The track index of the audio track used by
+    /// [`audio`](MediaFile::audio), or `None` if the file has no audio.
+    ///
+    /// Tracks are ranked in this order:
+    ///
+    /// 1. Main audio over commentary, audio description, and karaoke
+    ///    tracks, as marked by their stream dispositions.
+    /// 2. Tracks flagged `default` in the container.
+    /// 3. The track FFmpeg ranks best.
+    /// 4. The first track.
+    ///
+    /// So a file whose first track is a director's commentary still
+    /// defaults to the main mix, provided the commentary is tagged as such.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let unbundler = MediaFile::open("movie.mkv")?;
+    /// if let Some(track) = unbundler.best_audio_track() {
+    ///     println!("Default audio is track {track}");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn best_audio_track(&self) -> Option<usize> {
+        let stream_index = self.audio_stream_index?;
+        self.audio_stream_indices
+            .iter()
+            .position(|&index| index == stream_index)
+    }
+
     /// Obtain an [`AudioHandle`] for a specific audio track.
     ///
     /// `track_index` is the zero-based index into
@@ -700,6 +1247,84 @@ impl MediaFile {
         })
     }
 
+    /// Save every audio track to its own file in `directory`.
+    ///
+    /// Files are named `audio_00.<extension>`, `audio_01.<extension>`, …
+    /// by track index, with the track's language tag appended when it has
+    /// one (`audio_01_fre.wav`). The directory is created if it does not
+    /// exist. Each track is padded with leading silence so that every file
+    /// starts at the container's zero: a commentary track that begins two
+    /// seconds into the file gets two seconds of silence, and all outputs
+    /// line up when dropped onto a timeline at the same position. With
+    /// [`AudioFormat::Aac`], [`AudioFormat::Mp3`] and [`AudioFormat::Flac`]
+    /// the padding is rounded down to a whole encoder frame; WAV is exact.
+    ///
+    /// Returns the written paths in track order.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoAudioStream`] if the file has no audio track.
+    /// - [`UnbundleError::IoError`] if `directory` cannot be created.
+    /// - Any error from transcoding a track.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{AudioFormat, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("multi_audio.mkv")?;
+    /// for path in unbundler.extract_all_audio_tracks("stems", AudioFormat::Wav)? {
+    ///     println!("{}", path.display());
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn extract_all_audio_tracks<P: AsRef<Path>>(
+        &mut self,
+        directory: P,
+        format: AudioFormat,
+    ) -> Result<Vec<PathBuf>, UnbundleError> {
+        if self.audio_stream_indices.is_empty() {
+            return Err(UnbundleError::NoAudioStream);
+        }
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+
+        let file_names: Vec<String> = self
+            .audio_stream_indices
+            .iter()
+            .enumerate()
+            .map(|(track_index, &stream_index)| {
+                let language = self.input_context.stream(stream_index).and_then(|stream| {
+                    let language: String = stream
+                        .metadata()
+                        .get("language")?
+                        .chars()
+                        .filter(char::is_ascii_alphanumeric)
+                        .collect();
+                    (!language.is_empty() && language != "und").then_some(language)
+                });
+                match language {
+                    Some(language) => {
+                        format!("audio_{track_index:02}_{language}.{}", format.extension())
+                    }
+                    None => format!("audio_{track_index:02}.{}", format.extension()),
+                }
+            })
+            .collect();
+
+        let mut paths = Vec::with_capacity(file_names.len());
+        for (track_index, file_name) in file_names.into_iter().enumerate() {
+            let path = directory.join(file_name);
+            let lead_in = self.audio_track(track_index)?.save_aligned(&path, format)?;
+            log::debug!(
+                "Saved audio track {track_index} to {} ({lead_in:?} lead-in)",
+                path.display()
+            );
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
     /// Validate the media file and return a report.
     ///
     /// Inspects cached metadata for potential issues such as missing streams,
@@ -719,6 +1344,280 @@ impl MediaFile {
         crate::validation::validate_metadata(&self.metadata)
     }
 
+    /// Check whether this file and `other` can be concatenated without
+    /// re-encoding.
+    ///
+    /// Compares the number of video and audio streams and, track by track,
+    /// the video codec, resolution, and pixel format and the audio codec,
+    /// sample rate, and channel layout. Any difference is listed in the
+    /// returned [`ConcatReport`]; an empty list means the streams can be
+    /// copied back to back. Only cached metadata and codec parameters are
+    /// read, so nothing is decoded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let intro = MediaFile::open("intro.mp4")?;
+    /// let episode = MediaFile::open("episode.mp4")?;
+    /// let report = intro.concat_compatibility(&episode);
+    /// if !report.can_stream_copy() {
+    ///     print!("{report}");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn concat_compatibility(&self, other: &MediaFile) -> ConcatReport {
+        crate::validation::concat_compatibility_impl(self, other)
+    }
+
+    /// Measure how closely `other` reproduces this file's video.
+    ///
+    /// Both files are decoded from the start in lockstep and their frames
+    /// paired by timestamp; a frame with no counterpart within the
+    /// tolerance is counted as unmatched rather than compared. PSNR and
+    /// SSIM are computed on the luma plane, with `other` scaled to this
+    /// file's resolution first. This file is treated as the reference.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if either file has no video.
+    /// - Errors from decoding either file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, MetricOptions, UnbundleError};
+    ///
+    /// let mut original = MediaFile::open("master.mov")?;
+    /// let mut encode = MediaFile::open("encode.mp4")?;
+    /// let report = original.compare(&mut encode, MetricOptions::new().with_ssim(false))?;
+    /// let worst = report
+    ///     .frames
+    ///     .iter()
+    ///     .min_by(|a, b| a.psnr.partial_cmp(&b.psnr).unwrap());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn compare(
+        &mut self,
+        other: &mut MediaFile,
+        options: MetricOptions,
+    ) -> Result<CompareReport, UnbundleError> {
+        crate::compare::compare_impl(self, other, options)
+    }
+
+    /// Check that the first `duration` of the file decodes without errors.
+    ///
+    /// Decodes the best video and audio streams from the start of the file
+    /// up to `duration` and fails on the first demux or decode error, with
+    /// decoder error concealment disabled so corrupt data is reported
+    /// rather than papered over. This is much cheaper than a full decode
+    /// and catches the common truncated-upload and broken-header cases
+    /// that [`validate`](MediaFile::validate) cannot see from metadata
+    /// alone. A file shorter than `duration` is checked to its end.
+    /// `duration` counts from each stream's start time, so an MPEG-TS file
+    /// whose timestamps begin past zero is still checked for the full span.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::VideoDecodeError`] or
+    /// [`UnbundleError::AudioDecodeError`] describing the first failure,
+    /// [`UnbundleError::FfmpegError`] if demuxing fails, or
+    /// [`UnbundleError::NoVideoStream`] if the file has neither video nor
+    /// audio.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("upload.mp4")?;
+    /// if let Err(error) = unbundler.quick_check(Duration::from_secs(10)) {
+    ///     eprintln!("Rejecting upload: {error}");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn quick_check(&mut self, duration: Duration) -> Result<(), UnbundleError> {
+        crate::validation::quick_check_impl(self, duration)
+    }
+
+    /// Split the file into consecutive pieces of roughly
+    /// `segment_duration` without re-encoding.
+    ///
+    /// Every audio, video, and subtitle stream is copied into files named
+    /// `segment_000.<extension>`, `segment_001.<extension>`, … in
+    /// `directory`, which is created if needed. The container is inferred
+    /// from `extension` (e.g. `"mp4"`, `"mkv"`, `"ts"`). Since copied
+    /// video can only be cut on a keyframe, each segment starts at the
+    /// first video keyframe at or after its nominal boundary and so may
+    /// run longer than requested; the returned [`SegmentInfo`] list
+    /// records the actual source time span of each file. Each segment's
+    /// timestamps are shifted to start at zero so it plays on its own.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidInterval`] if `segment_duration` is zero.
+    /// - [`UnbundleError::IoError`] if `directory` cannot be created.
+    /// - [`UnbundleError::StreamCopyError`] if a segment cannot be written,
+    ///   for example because the container does not support a codec.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("recording.mkv")?;
+    /// let segments =
+    ///     unbundler.split_segments("chunks", Duration::from_secs(600), "mkv")?;
+    /// for segment in &segments {
+    ///     println!("{}: {:?}–{:?}", segment.path.display(), segment.start, segment.end);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn split_segments<P: AsRef<Path>>(
+        &mut self,
+        directory: P,
+        segment_duration: Duration,
+        extension: &str,
+    ) -> Result<Vec<SegmentInfo>, UnbundleError> {
+        crate::remux::split_segments_impl(self, directory.as_ref(), segment_duration, extension)
+    }
+
+    /// Locate the bytes of the source file that hold a time window of one
+    /// stream, for serving a clip with HTTP range requests instead of
+    /// remuxing it.
+    ///
+    /// `stream_index` is the container's stream index (as reported by
+    /// [`packet_iter`](MediaFile::packet_iter)). The window is widened to
+    /// begin at the last keyframe at or before `start`, so the returned
+    /// bytes are decodable on their own, and ends with the last packet
+    /// whose presentation timestamp is at or before `end`, including
+    /// B-frames stored after a later frame. The result is a half-open
+    /// `(first, end)` pair of file offsets; an HTTP `Range` header wants
+    /// `bytes=first-(end - 1)`.
+    ///
+    /// Containers interleave their streams, so the span also contains
+    /// other streams' packets for the same period. It does not include
+    /// the container header (an MP4 `moov` box, for instance), so it is
+    /// playable as is only for formats that need none, such as MPEG-TS.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRange`] if `start` is not before `end`.
+    /// - [`UnbundleError::StreamCopyError`] if the stream does not exist,
+    ///   or if no packet in the window has a known file position.
+    /// - [`UnbundleError::NotSeekable`] if the input cannot seek.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("broadcast.ts")?;
+    /// let (first, end) =
+    ///     unbundler.byte_range_for(0, Duration::from_secs(60), Duration::from_secs(90))?;
+    /// println!("Range: bytes={first}-{}", end - 1);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn byte_range_for(
+        &mut self,
+        stream_index: usize,
+        start: Duration,
+        end: Duration,
+    ) -> Result<(u64, u64), UnbundleError> {
+        crate::remux::byte_range_impl(self, stream_index, start, end)
+    }
+
+    /// Copy the audio and video between `start` and `end` into a new file
+    /// without re-encoding, keeping the streams in sync.
+    ///
+    /// Copying each stream separately trims them at different packet
+    /// boundaries, so the audio of such a clip can start early or late
+    /// relative to its picture. Here every stream is cut against the same
+    /// point instead: the clip begins at the last video keyframe at or
+    /// before `start` (since copied video can only start on a keyframe),
+    /// audio before that keyframe is dropped, and all timestamps are
+    /// shifted by the same amount so the clip starts at zero. Each stream
+    /// stops at its last packet decoded before `end`. Files without video
+    /// start at the first audio packet after the seek instead. Subtitle,
+    /// data and attached cover art streams are not copied; the container
+    /// is inferred from the extension of `output`.
+    ///
+    /// The returned [`SegmentInfo`] records the source time span the clip
+    /// actually covers.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRange`] if `start` is not before `end`.
+    /// - [`UnbundleError::NotSeekable`] if the input cannot seek.
+    /// - [`UnbundleError::StreamCopyError`] if the file has no audio or
+    ///   video, no keyframe is found before `end`, or the output cannot be
+    ///   written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("match.mp4")?;
+    /// let clip = unbundler.extract_clip(
+    ///     Duration::from_secs(754),
+    ///     Duration::from_secs(772),
+    ///     "goal.mp4",
+    /// )?;
+    /// println!("Saved {:?}–{:?}", clip.start, clip.end);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn extract_clip<P: AsRef<Path>>(
+        &mut self,
+        start: Duration,
+        end: Duration,
+        output: P,
+    ) -> Result<SegmentInfo, UnbundleError> {
+        crate::remux::extract_clip_impl(self, start, end, output.as_ref())
+    }
+
+    /// Join files end to end into `output` without re-encoding.
+    ///
+    /// Every input must pass [`concat_compatibility`](MediaFile::concat_compatibility)
+    /// against the first; all files are checked before anything is written.
+    /// Audio and video packets are copied as is, with each file's
+    /// timestamps shifted so it starts where the previous file ended.
+    /// Other streams (subtitles, data, attached cover art) are not copied.
+    /// The output container is inferred from the extension of `output`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::FileOpen`] if an input cannot be opened.
+    /// - [`UnbundleError::ConcatIncompatible`] naming the first input whose
+    ///   streams differ from the first file's.
+    /// - [`UnbundleError::StreamCopyError`] if `files` is empty, has no
+    ///   audio or video, or the output cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// MediaFile::concat(&["part1.mp4", "part2.mp4", "part3.mp4"], "recording.mp4")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn concat<P: AsRef<Path>, Q: AsRef<Path>>(
+        files: &[P],
+        output: Q,
+    ) -> Result<(), UnbundleError> {
+        let inputs: Vec<&Path> = files.iter().map(AsRef::as_ref).collect();
+        crate::remux::concat_impl(&inputs, output.as_ref())
+    }
+
     /// Obtain a [`SubtitleHandle`] for the best subtitle track.
     ///
     /// The returned extractor borrows this unbundler mutably, so you cannot
@@ -765,4 +1664,352 @@ impl MediaFile {
             stream_index: Some(stream_index),
         })
     }
+
+    /// Extract the frame shown at `timestamp` together with the subtitle
+    /// lines around it.
+    ///
+    /// The returned events are the lines on screen at `timestamp` plus up
+    /// to `context` lines before and after them, in start-time order. When
+    /// no line is showing, the `context` lines on either side of the gap
+    /// are returned instead. Subtitles come from the default subtitle
+    /// track and the frame from the default video track.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoSubtitleStream`] if the file has no subtitles.
+    /// - Any error from [`VideoHandle::frame_at`] or
+    ///   [`SubtitleHandle::extract`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("episode.mkv")?;
+    /// let (image, lines) = unbundler.frame_with_context(Duration::from_secs(312), 1)?;
+    /// image.save("card.jpg")?;
+    /// for line in &lines {
+    ///     println!("{:?}: {}", line.start_time, line.text);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frame_with_context(
+        &mut self,
+        timestamp: Duration,
+        context: usize,
+    ) -> Result<(DynamicImage, Vec<SubtitleEvent>), UnbundleError> {
+        let mut events = self.subtitle().extract()?;
+        events.sort_by_key(|event| event.start_time);
+
+        // Lines that start after `timestamp` begin at `first_after`; the
+        // earliest line that started before it and is still showing marks
+        // the start of the active block.
+        let first_after = events.partition_point(|event| event.start_time <= timestamp);
+        let first_active = events[..first_after]
+            .iter()
+            .position(|event| event.end_time > timestamp)
+            .unwrap_or(first_after);
+        let window_end = first_after.saturating_add(context).min(events.len());
+        events.truncate(window_end);
+        events.drain(..first_active.saturating_sub(context));
+
+        let image = self.video().frame_at(timestamp)?;
+        Ok((image, events))
+    }
+}
+
+/// Clockwise rotation, rounded to a quarter turn, that displays a picture
+/// stored with the given 3×3 display matrix upright.
+///
+/// Mirrors FFmpeg's `av_display_rotation_get` as the `ffmpeg` tool's
+/// autorotation uses it. Matrix entries are native-endian 16.16 fixed
+/// point; only their ratios matter here.
+fn display_matrix_rotation(matrix: &[u8]) -> u32 {
+    let entry = |index: usize| {
+        matrix
+            .get(index * 4..index * 4 + 4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map_or(0.0, |bytes| f64::from(i32::from_ne_bytes(bytes)))
+    };
+    let scale_x = entry(0).hypot(entry(3));
+    let scale_y = entry(1).hypot(entry(4));
+    if scale_x == 0.0 || scale_y == 0.0 {
+        return 0;
+    }
+
+    let degrees = (entry(1) / scale_y).atan2(entry(0) / scale_x).to_degrees();
+    ((degrees / 90.0).round() as i64).rem_euclid(4) as u32 * 90
+}
+
+/// Whether the input's I/O context supports seeking.
+fn input_is_seekable(input_context: &Input) -> bool {
+    // SAFETY: the context is open; `pb` is either null (formats that do
+    // their own I/O) or an AVIOContext it owns.
+    unsafe {
+        let io_context = (*input_context.as_ptr()).pb;
+        !io_context.is_null()
+            && (*io_context).seekable & ffmpeg_sys_next::AVIO_SEEKABLE_NORMAL as i32 != 0
+    }
+}
+
+/// Whether `input_context` can seek.
+///
+/// Demuxers that do their own I/O, such as RTSP, have no I/O context to
+/// report it, and FFmpeg does not expose whether they implement seeking,
+/// so they are asked by seeking to the start. Packets buffered while the
+/// streams were probed are dropped and read again.
+fn probe_seekable(input_context: &mut Input) -> bool {
+    // SAFETY: the context is open; `pb` is a plain pointer field.
+    if unsafe { !(*input_context.as_ptr()).pb.is_null() } {
+        return input_is_seekable(input_context);
+    }
+    match input_context.seek(0, ..0) {
+        Ok(()) => true,
+        Err(error) => {
+            log::debug!("Demuxer cannot seek: {error}");
+            false
+        }
+    }
+}
+
+/// Guess whether the input is a live stream: its duration is unknown and
+/// it is either not seekable or read by a demuxer that does its own I/O or
+/// expects timestamp discontinuities (RTSP, HLS, MPEG-TS and the like).
+///
+/// A seekable local file without a recorded duration, such as a raw
+/// elementary stream, is not live.
+fn input_is_live(input_context: &Input, duration_microseconds: i64) -> bool {
+    if duration_microseconds > 0 {
+        return false;
+    }
+    // SAFETY: an open input context always has its `iformat` set.
+    let format_flags = unsafe { (*(*input_context.as_ptr()).iformat).flags };
+    let streaming_flags = ffmpeg_sys_next::AVFMT_NOFILE | ffmpeg_sys_next::AVFMT_TS_DISCONT;
+    format_flags & streaming_flags != 0 || !input_is_seekable(input_context)
+}
+
+/// Seek the demuxer to `timestamp` (in `AV_TIME_BASE` units), reporting
+/// [`UnbundleError::NotSeekable`] when the seek fails because the input
+/// does not support random access.
+pub(crate) fn seek_input(input_context: &mut Input, timestamp: i64) -> Result<(), UnbundleError> {
+    input_context.seek(timestamp, ..timestamp).map_err(|error| {
+        if input_is_seekable(input_context) {
+            error.into()
+        } else {
+            UnbundleError::NotSeekable
+        }
+    })
+}
+
+/// Go back to the start of a seekable input before reading a whole stream,
+/// so earlier reads on the same [`MediaFile`] do not cut it short. Inputs
+/// that cannot seek are read on from where they are.
+pub(crate) fn rewind_input(input_context: &mut Input) -> Result<(), UnbundleError> {
+    if !input_is_seekable(input_context) {
+        return Ok(());
+    }
+    seek_input(input_context, 0)
+}
+
+/// Where a stream's timeline begins: its own start time, else the
+/// container's, else zero. MPEG-TS and some MP4 edits start well past zero.
+pub(crate) fn stream_start(input_context: &Input, stream: &Stream) -> Duration {
+    let seconds = if stream.start_time() != ffmpeg_sys_next::AV_NOPTS_VALUE {
+        crate::conversion::pts_to_seconds(stream.start_time(), stream.time_base())
+    } else {
+        // SAFETY: the context is open; `start_time` is a plain integer.
+        let file_start = unsafe { (*input_context.as_ptr()).start_time };
+        if file_start == ffmpeg_sys_next::AV_NOPTS_VALUE {
+            return Duration::ZERO;
+        }
+        file_start as f64 / f64::from(ffmpeg_sys_next::AV_TIME_BASE)
+    };
+    if seconds.is_finite() && seconds > 0.0 {
+        Duration::from_secs_f64(seconds)
+    } else {
+        Duration::ZERO
+    }
+}
+
+/// A stream's own duration, or `None` when the container does not record
+/// one (common for Matroska and MPEG-TS).
+fn stream_duration(stream: &Stream) -> Option<Duration> {
+    // Unknown durations are reported as `AV_NOPTS_VALUE` (`i64::MIN`).
+    let duration = stream.duration();
+    if duration <= 0 {
+        return None;
+    }
+    let seconds = crate::conversion::pts_to_seconds(duration, stream.time_base());
+    (seconds.is_finite() && seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+/// Read the container's chapters, sorted by start time.
+///
+/// FFmpeg merges Matroska chapters, MP4/M4B chapter tracks and Nero `chpl`
+/// atoms, and ID3v2 `CHAP` frames into one list, but leaves their gaps:
+/// Nero chapters and some ID3 taggers store no end time (or one before the
+/// start), and ID3 frames may come in any order. Such chapters end where
+/// the next one starts, and the last one at the end of the file.
+fn read_chapters(input_context: &Input, duration: Duration) -> Option<Vec<ChapterMetadata>> {
+    if input_context.nb_chapters() == 0 {
+        return None;
+    }
+
+    // Chapters paired with their stored end time, if it is usable.
+    let mut chapters: Vec<(ChapterMetadata, Option<Duration>)> = input_context
+        .chapters()
+        .enumerate()
+        .map(|(index, chapter)| {
+            let time_base = chapter.time_base();
+            let to_duration = |timestamp: i64| {
+                if timestamp == ffmpeg_sys_next::AV_NOPTS_VALUE {
+                    return None;
+                }
+                let seconds = crate::conversion::pts_to_seconds(timestamp, time_base);
+                seconds
+                    .is_finite()
+                    .then(|| Duration::from_secs_f64(seconds.max(0.0)))
+            };
+            let start = to_duration(chapter.start()).unwrap_or_default();
+            let end = to_duration(chapter.end()).filter(|&end| end > start);
+            let chapter_tags = chapter.metadata();
+            let title = chapter_tags
+                .get("title")
+                .map(str::trim)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string);
+            let metadata = ChapterMetadata {
+                title,
+                start,
+                end: start,
+                index,
+                id: chapter.id(),
+            };
+            (metadata, end)
+        })
+        .collect();
+    chapters.sort_by_key(|(chapter, _)| chapter.start);
+
+    let starts: Vec<Duration> = chapters.iter().map(|(chapter, _)| chapter.start).collect();
+    let chapters = chapters
+        .into_iter()
+        .map(|(mut chapter, end)| {
+            chapter.end = end.unwrap_or_else(|| {
+                let next_start = starts.iter().copied().find(|&start| start > chapter.start);
+                next_start.unwrap_or(duration).max(chapter.start)
+            });
+            chapter
+        })
+        .collect();
+    Some(chapters)
+}
+
+/// Audio dispositions marking a track as an alternative to the main mix.
+const SECONDARY_AUDIO: Disposition = Disposition::COMMENT
+    .union(Disposition::VISUAL_IMPAIRED)
+    .union(Disposition::DESCRIPTIONS)
+    .union(Disposition::KARAOKE);
+
+/// Pick the stream of `medium` that [`MediaFile::video`] or
+/// [`MediaFile::audio`] should use when no track is requested.
+///
+/// Attached pictures are never chosen. The ranking is documented on
+/// [`MediaFile::best_audio_track`]; video skips the first rule.
+fn select_default_stream(input_context: &Input, medium: Type) -> Option<usize> {
+    select_default_stream_among(input_context, medium, |_| true)
+}
+
+/// [`select_default_stream`] limited to streams whose index `allowed`
+/// accepts.
+fn select_default_stream_among(
+    input_context: &Input,
+    medium: Type,
+    allowed: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let ffmpeg_best = input_context
+        .streams()
+        .best(medium)
+        .map(|stream| stream.index());
+
+    input_context
+        .streams()
+        .filter(|stream| allowed(stream.index()))
+        .filter(|stream| stream.parameters().medium() == medium)
+        .filter(|stream| !stream.disposition().contains(Disposition::ATTACHED_PIC))
+        .min_by_key(|stream| {
+            let disposition = stream.disposition();
+            let secondary = medium == Type::Audio && disposition.intersects(SECONDARY_AUDIO);
+            (
+                secondary,
+                !disposition.contains(Disposition::DEFAULT),
+                ffmpeg_best != Some(stream.index()),
+                stream.index(),
+            )
+        })
+        .map(|stream| stream.index())
+}
+
+/// Track metadata that [`restrict_tracks`] can filter and renumber.
+trait TrackMetadata: Clone {
+    fn stream_index(&self) -> usize;
+    fn set_track_index(&mut self, track_index: usize);
+}
+
+impl TrackMetadata for VideoMetadata {
+    fn stream_index(&self) -> usize {
+        self.stream_index
+    }
+
+    fn set_track_index(&mut self, track_index: usize) {
+        self.track_index = track_index;
+    }
+}
+
+impl TrackMetadata for AudioMetadata {
+    fn stream_index(&self) -> usize {
+        self.stream_index
+    }
+
+    fn set_track_index(&mut self, track_index: usize) {
+        self.track_index = track_index;
+    }
+}
+
+impl TrackMetadata for SubtitleMetadata {
+    fn stream_index(&self) -> usize {
+        self.stream_index
+    }
+
+    fn set_track_index(&mut self, track_index: usize) {
+        self.track_index = track_index;
+    }
+}
+
+/// Keep the tracks whose stream index `is_member` accepts, renumbered from
+/// zero.
+///
+/// Returns the track on `default_stream`, the remaining tracks (`None` when
+/// there are none), and their stream indices.
+fn restrict_tracks<T: TrackMetadata>(
+    tracks: Option<Vec<T>>,
+    default_stream: Option<usize>,
+    is_member: impl Fn(usize) -> bool,
+) -> (Option<T>, Option<Vec<T>>, Vec<usize>) {
+    let mut tracks = tracks.unwrap_or_default();
+    tracks.retain(|track| is_member(track.stream_index()));
+    for (track_index, track) in tracks.iter_mut().enumerate() {
+        track.set_track_index(track_index);
+    }
+    let stream_indices = tracks.iter().map(TrackMetadata::stream_index).collect();
+    let default = tracks
+        .iter()
+        .find(|track| Some(track.stream_index()) == default_stream)
+        .cloned();
+    (
+        default,
+        (!tracks.is_empty()).then_some(tracks),
+        stream_indices,
+    )
 }
@@ -2,7 +2,8 @@
 //!
 //! Provides [`crate::MediaFile::validate`] which inspects a media file and
 //! returns a [`ValidationReport`] describing its structure and any potential
-//! issues.
+//! issues, and [`crate::MediaFile::concat_compatibility`] which compares two
+//! files' streams in a [`ConcatReport`].
 //!
 //! # Example
 //!
@@ -24,7 +25,18 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::time::Duration;
 
-use crate::metadata::MediaMetadata;
+use ffmpeg_next::{
+    Error as FfmpegError, Frame, Packet, Rational,
+    codec::{
+        context::Context as CodecContext,
+        decoder::{Check, Opened},
+    },
+    media::Type,
+};
+
+use crate::error::UnbundleError;
+use crate::metadata::{AudioMetadata, MediaMetadata, VideoMetadata};
+use crate::unbundle::MediaFile;
 
 /// Summary of media file validation.
 ///
@@ -74,6 +86,43 @@ impl Display for ValidationReport {
     }
 }
 
+/// Whether two files can be joined end to end by stream copy.
+///
+/// Produced by [`MediaFile::concat_compatibility`](crate::MediaFile::concat_compatibility).
+/// Each entry in [`mismatches`](ConcatReport::mismatches) describes one
+/// difference between the files' streams that a stream-copy concatenation
+/// cannot carry across, so at least one side would have to be re-encoded.
+#[derive(Debug, Clone, Default)]
+pub struct ConcatReport {
+    /// Differences in stream counts, codecs, resolution, pixel format,
+    /// sample rate, or channel layout, one per entry.
+    pub mismatches: Vec<String>,
+}
+
+impl ConcatReport {
+    /// Returns `true` if the files can be concatenated without
+    /// re-encoding.
+    pub fn can_stream_copy(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl Display for ConcatReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.can_stream_copy() {
+            return writeln!(
+                f,
+                "Compatible: lossless concatenation (stream copy) is possible."
+            );
+        }
+        writeln!(f, "Incompatible: re-encoding is required.")?;
+        for item in &self.mismatches {
+            writeln!(f, "[MISMATCH] {item}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Run validation checks on the cached metadata.
 ///
 /// This function is called by [`MediaFile::validate`].
@@ -162,6 +211,41 @@ pub(crate) fn validate_metadata(metadata: &MediaMetadata) -> ValidationReport {
         }
     }
 
+    // ── Edit lists ─────────────────────────────────────────────────
+    let video_offsets = metadata.video_tracks.iter().flatten().map(|track| {
+        (
+            "Video",
+            track.track_index,
+            track.has_edit_list,
+            track.edit_list_offset,
+        )
+    });
+    let audio_offsets = metadata.audio_tracks.iter().flatten().map(|track| {
+        (
+            "Audio",
+            track.track_index,
+            track.has_edit_list,
+            track.edit_list_offset,
+        )
+    });
+    for (kind, track_index, has_edit_list, offset) in video_offsets.chain(audio_offsets) {
+        if !has_edit_list {
+            continue;
+        }
+        // Sub-millisecond shifts are rounding in the edit list itself.
+        if offset.abs() >= 0.001 {
+            report.warnings.push(format!(
+                "{kind} track {track_index} has an edit list shifting its start by {:+.3}s — \
+                 timings from tools that ignore edit lists will be offset",
+                offset,
+            ));
+        } else {
+            report
+                .info
+                .push(format!("{kind} track {track_index} has an edit list"));
+        }
+    }
+
     // ── Subtitle info ──────────────────────────────────────────────
     if let Some(subtitle) = &metadata.subtitle {
         let language = subtitle.language.as_deref().unwrap_or("unknown language");
@@ -180,3 +264,256 @@ pub(crate) fn validate_metadata(metadata: &MediaMetadata) -> ValidationReport {
 
     report
 }
+
+/// Compare the video and audio streams of two files, pairing tracks by
+/// their position in [`MediaMetadata::video_tracks`] and
+/// [`MediaMetadata::audio_tracks`].
+///
+/// This function is called by [`MediaFile::concat_compatibility`].
+pub(crate) fn concat_compatibility_impl(first: &MediaFile, second: &MediaFile) -> ConcatReport {
+    let mut report = ConcatReport::default();
+    let no_tracks = Vec::new();
+
+    let first_video = first.metadata.video_tracks.as_ref().unwrap_or(&no_tracks);
+    let second_video = second.metadata.video_tracks.as_ref().unwrap_or(&no_tracks);
+    if first_video.len() != second_video.len() {
+        report.mismatches.push(format!(
+            "Video stream count differs: {} vs {}",
+            first_video.len(),
+            second_video.len(),
+        ));
+    }
+    for (first_track, second_track) in first_video.iter().zip(second_video) {
+        compare_video_tracks(first_track, second_track, &mut report);
+    }
+
+    let no_tracks = Vec::new();
+    let first_audio = first.metadata.audio_tracks.as_ref().unwrap_or(&no_tracks);
+    let second_audio = second.metadata.audio_tracks.as_ref().unwrap_or(&no_tracks);
+    if first_audio.len() != second_audio.len() {
+        report.mismatches.push(format!(
+            "Audio stream count differs: {} vs {}",
+            first_audio.len(),
+            second_audio.len(),
+        ));
+    }
+    for (first_track, second_track) in first_audio.iter().zip(second_audio) {
+        compare_audio_tracks(first, first_track, second, second_track, &mut report);
+    }
+
+    report
+}
+
+/// Record the differences between two video tracks that prevent stream
+/// copy.
+fn compare_video_tracks(first: &VideoMetadata, second: &VideoMetadata, report: &mut ConcatReport) {
+    let track = first.track_index;
+    if first.codec != second.codec {
+        report.mismatches.push(format!(
+            "Video track {track} codec differs: {} vs {}",
+            first.codec, second.codec,
+        ));
+    }
+    if (first.width, first.height) != (second.width, second.height) {
+        report.mismatches.push(format!(
+            "Video track {track} resolution differs: {}×{} vs {}×{}",
+            first.width, first.height, second.width, second.height,
+        ));
+    }
+    if first.pixel_format_name != second.pixel_format_name {
+        report.mismatches.push(format!(
+            "Video track {track} pixel format differs: {} vs {}",
+            first.pixel_format_name.as_deref().unwrap_or("unknown"),
+            second.pixel_format_name.as_deref().unwrap_or("unknown"),
+        ));
+    }
+}
+
+/// Record the differences between two audio tracks that prevent stream
+/// copy.
+fn compare_audio_tracks(
+    first_file: &MediaFile,
+    first: &AudioMetadata,
+    second_file: &MediaFile,
+    second: &AudioMetadata,
+    report: &mut ConcatReport,
+) {
+    let track = first.track_index;
+    if first.codec != second.codec {
+        report.mismatches.push(format!(
+            "Audio track {track} codec differs: {} vs {}",
+            first.codec, second.codec,
+        ));
+    }
+    if first.sample_rate != second.sample_rate {
+        report.mismatches.push(format!(
+            "Audio track {track} sample rate differs: {} Hz vs {} Hz",
+            first.sample_rate, second.sample_rate,
+        ));
+    }
+    let first_layout = stream_channel_layout(first_file, first.stream_index);
+    let second_layout = stream_channel_layout(second_file, second.stream_index);
+    if first_layout != second_layout {
+        report.mismatches.push(format!(
+            "Audio track {track} channel layout differs: {} vs {}",
+            first_layout.as_deref().unwrap_or("unknown"),
+            second_layout.as_deref().unwrap_or("unknown"),
+        ));
+    }
+}
+
+/// Name of an audio stream's channel layout as recorded in its codec
+/// parameters.
+fn stream_channel_layout(unbundler: &MediaFile, stream_index: usize) -> Option<String> {
+    let stream = unbundler.input_context.stream(stream_index)?;
+    let parameters = stream.parameters();
+    // SAFETY: the parameters belong to an open stream and are only read.
+    let layout = unsafe { &(*parameters.as_ptr()).ch_layout };
+    Some(crate::conversion::channel_layout_name(layout))
+}
+
+/// A stream being decoded by [`quick_check_impl`].
+struct CheckedStream {
+    index: usize,
+    medium: Type,
+    decoder: Opened,
+    time_base: Rational,
+    /// Last timestamp (in stream time base) that must decode cleanly.
+    end_timestamp: i64,
+    finished: bool,
+}
+
+impl CheckedStream {
+    fn open(
+        unbundler: &MediaFile,
+        index: usize,
+        medium: Type,
+        limit: Duration,
+    ) -> Result<Self, UnbundleError> {
+        let stream = unbundler.input_context.stream(index).ok_or(match medium {
+            Type::Video => UnbundleError::NoVideoStream,
+            _ => UnbundleError::NoAudioStream,
+        })?;
+        let time_base = stream.time_base();
+        // The limit counts from where the stream starts, not from zero.
+        let end =
+            crate::unbundle::stream_start(&unbundler.input_context, &stream).saturating_add(limit);
+
+        let mut decoder = CodecContext::from_parameters(stream.parameters())?.decoder();
+        // Surface bitstream damage as errors instead of silently
+        // concealing it, which is the point of the check.
+        decoder.check(Check::EXPLODE);
+        let decoder = decoder
+            .open()
+            .map_err(|error| Self::error(medium, None, error))?;
+
+        Ok(Self {
+            index,
+            medium,
+            decoder,
+            time_base,
+            end_timestamp: crate::conversion::duration_to_stream_timestamp(end, time_base),
+            finished: false,
+        })
+    }
+
+    /// Pull every frame the decoder has ready, failing on the first error.
+    fn drain(&mut self, frame: &mut Frame, pts: Option<i64>) -> Result<(), UnbundleError> {
+        loop {
+            match self.decoder.receive_frame(frame) {
+                Ok(()) => {}
+                Err(FfmpegError::Eof) => return Ok(()),
+                Err(FfmpegError::Other { errno }) if errno == ffmpeg_next::error::EAGAIN => {
+                    return Ok(());
+                }
+                Err(error) => return Err(self.decode_error(pts, error)),
+            }
+        }
+    }
+
+    fn decode_error(&self, pts: Option<i64>, error: FfmpegError) -> UnbundleError {
+        let seconds = pts.map(|pts| crate::conversion::pts_to_seconds(pts, self.time_base));
+        Self::error(self.medium, seconds, error)
+    }
+
+    fn error(medium: Type, seconds: Option<f64>, error: FfmpegError) -> UnbundleError {
+        let message = match seconds {
+            Some(seconds) => format!("at {seconds:.3}s: {error}"),
+            None => error.to_string(),
+        };
+        match medium {
+            Type::Video => UnbundleError::VideoDecodeError(message),
+            _ => UnbundleError::AudioDecodeError(message),
+        }
+    }
+}
+
+/// Decode the best video and audio streams from the start of the file up
+/// to `limit`, returning the first demux or decode error.
+///
+/// This function is called by [`MediaFile::quick_check`].
+pub(crate) fn quick_check_impl(
+    unbundler: &mut MediaFile,
+    limit: Duration,
+) -> Result<(), UnbundleError> {
+    log::debug!("Quick-checking first {limit:?}");
+
+    let mut streams = Vec::new();
+    if let Some(index) = unbundler.video_stream_index {
+        streams.push(CheckedStream::open(unbundler, index, Type::Video, limit)?);
+    }
+    if let Some(index) = unbundler.audio_stream_index {
+        streams.push(CheckedStream::open(unbundler, index, Type::Audio, limit)?);
+    }
+    if streams.is_empty() {
+        return Err(UnbundleError::NoVideoStream);
+    }
+
+    // Earlier extraction may have left the demuxer mid-file.
+    if let Err(error) = unbundler.input_context.seek(0, ..0) {
+        log::debug!("Could not rewind before quick check: {error}");
+    }
+
+    // SAFETY: `Frame::empty` allocates a blank AVFrame; it is only ever
+    // filled in by the decoders below.
+    let mut frame = unsafe { Frame::empty() };
+    let mut packet = Packet::empty();
+
+    while streams.iter().any(|stream| !stream.finished) {
+        match packet.read(&mut unbundler.input_context) {
+            Ok(()) => {}
+            Err(FfmpegError::Eof) => break,
+            Err(error) => return Err(UnbundleError::from(error)),
+        }
+
+        let Some(stream) = streams
+            .iter_mut()
+            .find(|stream| stream.index == packet.stream() && !stream.finished)
+        else {
+            continue;
+        };
+
+        let pts = packet.pts().or(packet.dts());
+        if pts.is_some_and(|pts| pts > stream.end_timestamp) {
+            stream.finished = true;
+            continue;
+        }
+
+        stream
+            .decoder
+            .send_packet(&packet)
+            .map_err(|error| stream.decode_error(pts, error))?;
+        stream.drain(&mut frame, pts)?;
+    }
+
+    // Flush any frames still buffered inside the decoders.
+    for stream in &mut streams {
+        stream
+            .decoder
+            .send_eof()
+            .map_err(|error| stream.decode_error(None, error))?;
+        stream.drain(&mut frame, None)?;
+    }
+
+    Ok(())
+}
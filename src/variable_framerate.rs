@@ -18,6 +18,7 @@
 //! # Ok::<(), UnbundleError>(())
 //! ```
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use ffmpeg_next::{Error as FfmpegError, Packet, Rational};
@@ -43,12 +44,36 @@ pub struct VariableFrameRateAnalysis {
     pub max_frames_per_second: f64,
     /// Mean FPS (1 / mean_frame_duration).
     pub mean_frames_per_second: f64,
+    /// Rate of the most common bucket in
+    /// [`frame_rate_histogram`](VariableFrameRateAnalysis::frame_rate_histogram),
+    /// the natural target for a constant frame rate conversion. Unlike the
+    /// mean it is not pulled between real rates by dropped frames or
+    /// bursts. `0.0` when fewer than two frames were analyzed.
+    pub predominant_frames_per_second: f64,
+    /// Frame intervals grouped by instantaneous FPS rounded to a whole
+    /// number, in ascending order of rate.
+    pub frame_rate_histogram: Vec<FrameRateBucket>,
     /// Number of frames analyzed.
     pub frames_analyzed: u64,
     /// Per-frame PTS values converted to [`Duration`], in decode order.
     pub pts_list: Vec<Duration>,
 }
 
+/// Frame intervals whose instantaneous FPS rounds to the same whole number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRateBucket {
+    /// Instantaneous FPS rounded to the nearest whole number, e.g. 30 for
+    /// both 29.97 and 30 fps.
+    pub nominal_frames_per_second: u32,
+    /// Exact rate of the intervals in this bucket (1 / their mean
+    /// duration), e.g. 29.97.
+    pub frames_per_second: f64,
+    /// Number of frame intervals in this bucket.
+    pub interval_count: u64,
+    /// Share of all frame intervals, from 0.0 to 1.0.
+    pub fraction: f64,
+}
+
 /// Analyze the PTS distribution of a video stream to detect VFR.
 ///
 /// Reads all video-stream packets and collects their PTS values.
@@ -102,6 +127,8 @@ pub(crate) fn analyze_variable_framerate_impl(
             min_frames_per_second: 0.0,
             max_frames_per_second: 0.0,
             mean_frames_per_second: 0.0,
+            predominant_frames_per_second: 0.0,
+            frame_rate_histogram: Vec::new(),
             frames_analyzed: pts_values.len() as u64,
             pts_list,
         });
@@ -122,6 +149,8 @@ pub(crate) fn analyze_variable_framerate_impl(
             min_frames_per_second: 0.0,
             max_frames_per_second: 0.0,
             mean_frames_per_second: 0.0,
+            predominant_frames_per_second: 0.0,
+            frame_rate_histogram: Vec::new(),
             frames_analyzed: pts_values.len() as u64,
             pts_list,
         });
@@ -155,6 +184,14 @@ pub(crate) fn analyze_variable_framerate_impl(
     // VFR if stddev > 10% of mean frame duration.
     let is_variable_frame_rate = mean > 0.0 && (stddev / mean) > 0.10;
 
+    let frame_rate_histogram = frame_rate_histogram(&durations);
+    // Ties go to the lower rate.
+    let predominant_frames_per_second = frame_rate_histogram
+        .iter()
+        .rev()
+        .max_by_key(|bucket| bucket.interval_count)
+        .map_or(0.0, |bucket| bucket.frames_per_second);
+
     Ok(VariableFrameRateAnalysis {
         is_variable_frame_rate,
         mean_frame_duration: mean,
@@ -162,7 +199,33 @@ pub(crate) fn analyze_variable_framerate_impl(
         min_frames_per_second,
         max_frames_per_second,
         mean_frames_per_second,
+        predominant_frames_per_second,
+        frame_rate_histogram,
         frames_analyzed: pts_values.len() as u64,
         pts_list,
     })
 }
+
+/// Group frame `durations` (in seconds, all positive) by rounded
+/// instantaneous FPS.
+fn frame_rate_histogram(durations: &[f64]) -> Vec<FrameRateBucket> {
+    // Nominal rate -> (interval count, total duration).
+    let mut buckets: BTreeMap<u32, (u64, f64)> = BTreeMap::new();
+    for &duration in durations {
+        let nominal = (1.0 / duration).round() as u32;
+        let (count, total) = buckets.entry(nominal).or_default();
+        *count += 1;
+        *total += duration;
+    }
+
+    let interval_total = durations.len() as f64;
+    buckets
+        .into_iter()
+        .map(|(nominal, (count, total))| FrameRateBucket {
+            nominal_frames_per_second: nominal,
+            frames_per_second: count as f64 / total,
+            interval_count: count,
+            fraction: count as f64 / interval_total,
+        })
+        .collect()
+}
@@ -5,40 +5,55 @@
 //! Extracted frames are returned as [`image::DynamicImage`] values that can be
 //! saved, manipulated, or converted to other formats.
 
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::CString;
-use std::path::Path;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use ffmpeg_next::{
-    Rational,
+    Codec, Packet, Rational,
     codec::Id,
     codec::context::Context as CodecContext,
     decoder::Video as VideoDecoder,
     filter::Graph as FilterGraph,
-    format::Pixel,
+    format::{
+        Pixel,
+        stream::{Disposition, Stream},
+    },
     frame::Video as VideoFrame,
     packet::Mut as PacketMut,
     software::scaling::{Context as ScalingContext, Flags as ScalingFlags},
     util::picture::Type as PictureType,
 };
-use ffmpeg_sys_next::{AVFormatContext, AVPixelFormat, AVRational};
+use ffmpeg_sys_next::{AVDiscard, AVFormatContext, AVPixelFormat, AVRational};
 use image::{DynamicImage, GrayImage, RgbImage, RgbaImage};
 
 #[cfg(feature = "gif")]
 use crate::gif::GifOptions;
+#[cfg(feature = "hardware")]
+use crate::hardware_acceleration::HardwareAccelerationMode;
 #[cfg(feature = "scene")]
 use crate::scene::{SceneChange, SceneDetectionOptions};
 #[cfg(feature = "async")]
 use crate::stream::FrameStream;
 use crate::{
-    configuration::{ExtractOptions, FrameOutputOptions, PixelFormat},
+    configuration::{
+        ColorMatrix, ExtractOptions, FrameDiscard, FrameOutputOptions, PixelFormat, ThreadType,
+    },
     error::UnbundleError,
-    keyframe::{GroupOfPicturesInfo, KeyFrameMetadata},
+    histogram::LumaHistogram,
+    keyframe::{
+        GopFrames, GroupOfPicturesInfo, KeyFrameMetadata, KeyframeEntry, SeekabilityReport,
+    },
     metadata::VideoMetadata,
     progress::{OperationType, ProgressTracker},
+    subtitle::SubtitleEvent,
+    summary::{ExtractSummary, PacketSpan},
+    thumbnail::ThumbnailOptions,
+    timecode::Timecode,
     unbundle::MediaFile,
     variable_framerate::VariableFrameRateAnalysis,
-    video_iterator::FrameIterator,
+    video_iterator::{FrameIterator, FrameMetadataIterator},
 };
 
 /// The type of a decoded video frame (I, P, B, etc.).
@@ -126,6 +141,22 @@ pub struct RawFrameView<'a> {
     pub data: &'a [u8],
 }
 
+/// Layout of the pixels written by [`VideoHandle::decode_into`].
+///
+/// Rows are tightly packed, so `stride` is always
+/// `width * pixel_format.bytes_per_pixel()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameBufferLayout {
+    /// Width of the decoded frame in pixels.
+    pub width: u32,
+    /// Height of the decoded frame in pixels.
+    pub height: u32,
+    /// Bytes per row in the buffer.
+    pub stride: usize,
+    /// Pixel format of the buffer contents.
+    pub pixel_format: PixelFormat,
+}
+
 /// Specifies which frames to extract from a video.
 ///
 /// Used with [`VideoHandle::frames`] to extract multiple frames in a single
@@ -153,6 +184,9 @@ pub enum FrameRange {
     /// Extract all frames between two timestamps.
     TimeRange(Duration, Duration),
     /// Extract frames at regular time intervals (e.g. every 2 seconds).
+    ///
+    /// The intervals span the media duration, so live inputs are rejected
+    /// with [`UnbundleError::LiveStream`].
     TimeInterval(Duration),
     /// Extract frames at specific frame numbers.
     Specific(Vec<u64>),
@@ -253,6 +287,59 @@ impl<'a> FilterChainHandle<'a> {
         self
     }
 
+    /// Report the output width, height, and pixel format of the chain.
+    ///
+    /// Builds the filter graph for the source stream's dimensions and
+    /// decoded pixel format and reads back what the graph negotiates,
+    /// without decoding any video. Use it to show the effect of `scale`,
+    /// `crop`, `pad` and similar filters before extracting, or to reject
+    /// a malformed filter specification early. An empty chain reports the
+    /// source format unchanged.
+    ///
+    /// The reported size is before any resolution set in
+    /// [`ExtractOptions`] is applied.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if the file has no video.
+    /// - [`UnbundleError::FilterGraphError`] if the filter specification
+    ///   cannot be parsed or configured.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let (width, height, pixel) = unbundler
+    ///     .video()
+    ///     .filter("crop=iw/2:ih/2")
+    ///     .probe_output()?;
+    /// println!("Output: {width}x{height} {pixel:?}");
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn probe_output(&mut self) -> Result<(u32, u32, Pixel), UnbundleError> {
+        let video_stream_index = self.video_handle.resolve_video_stream_index()?;
+        let stream = self
+            .video_handle
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let time_base = stream.time_base();
+        let decoder = CodecContext::from_parameters(stream.parameters())?
+            .decoder()
+            .video()?;
+        let (width, height, pixel) = (decoder.width(), decoder.height(), decoder.format());
+
+        match self.combined_filter_spec() {
+            Some(filter_spec) => {
+                probe_filter_graph_output(width, height, pixel, time_base, &filter_spec)
+            }
+            None => Ok((width, height, pixel)),
+        }
+    }
+
     /// Extract a single frame using the chained filters.
     pub fn frame(mut self, frame_number: u64) -> Result<DynamicImage, UnbundleError> {
         self.frame_with_options(frame_number, &ExtractOptions::default())
@@ -333,19 +420,103 @@ impl<'a> FilterChainHandle<'a> {
 /// when the output configuration (dimensions, pixel format) changes.
 pub(crate) struct CachedDecoderState {
     decoder: VideoDecoder,
-    scaler: ScalingContext,
+    /// Built from the first frame, whose format differs from the
+    /// decoder's when hardware decoding is active.
+    scaler: Option<ScalingContext>,
+    /// Whether decoded frames live in GPU memory.
+    hardware_active: bool,
     time_base: Rational,
     output_pixel: Pixel,
     target_width: u32,
     target_height: u32,
+    /// Colour matrix the scaler was told to read the source with.
+    input_color_matrix: Option<ColorMatrix>,
+    deinterlacer: Deinterlacer,
+    /// Decoder settings the state was built with.
+    decoder_settings: DecoderSettings,
+    /// Most recently decoded frame. Only valid while `last_pts` is `Some`.
     decoded_frame: VideoFrame,
+    /// Scratch frame for `receive_frame`, which clears its destination
+    /// even when no frame is ready.
+    receive_buffer: VideoFrame,
     scaled_frame: VideoFrame,
-    /// PTS of the last frame handed back to the caller.
+    /// PTS of the last frame decoded since the previous seek.
     last_pts: Option<i64>,
     /// `true` after `send_eof()` has been called on the decoder.
     eof_sent: bool,
 }
 
+/// The [`ExtractOptions`] that shape a cached decoder; changing any of them
+/// rebuilds it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DecoderSettings {
+    lowres: u8,
+    decoder_threads: usize,
+    decoder_thread_type: ThreadType,
+    frame_discard: FrameDiscard,
+    #[cfg(feature = "hardware")]
+    hardware_acceleration: HardwareAccelerationMode,
+}
+
+impl DecoderSettings {
+    fn new(config: &ExtractOptions) -> Self {
+        Self {
+            lowres: config.lowres,
+            decoder_threads: config.decoder_threads,
+            decoder_thread_type: config.decoder_thread_type,
+            frame_discard: config.frame_discard,
+            #[cfg(feature = "hardware")]
+            hardware_acceleration: config.hardware_acceleration,
+        }
+    }
+}
+
+impl CachedDecoderState {
+    /// Receive the next decoded frame into `decoded_frame`.
+    ///
+    /// Returns `false` when the decoder has nothing ready, leaving the
+    /// previous frame in `decoded_frame` intact.
+    fn receive_next(&mut self, config: &ExtractOptions) -> bool {
+        if self
+            .decoder
+            .receive_frame(&mut self.receive_buffer)
+            .is_err()
+        {
+            return false;
+        }
+        config.record_decoded();
+        std::mem::swap(&mut self.decoded_frame, &mut self.receive_buffer);
+        self.last_pts = Some(self.decoded_frame.pts().unwrap_or(0));
+        true
+    }
+
+    /// Scale the current `decoded_frame` into `scaled_frame`,
+    /// deinterlacing it first if the frame output settings ask for it.
+    fn scale_decoded(&mut self, config: &ExtractOptions) -> Result<(), UnbundleError> {
+        let prepared = prepare_source_frame(
+            &self.decoded_frame,
+            self.hardware_active,
+            &mut self.deinterlacer,
+            &config.frame_output,
+        )?;
+        let source = prepared.as_ref().unwrap_or(&self.decoded_frame);
+        ensure_scaler(
+            &mut self.scaler,
+            source,
+            self.output_pixel,
+            &config.frame_output,
+            self.target_width,
+            self.target_height,
+        )?;
+        self.scaler
+            .as_mut()
+            .unwrap()
+            .run(source, &mut self.scaled_frame)?;
+        config.record_scaled();
+        Ok(())
+    }
+}
+
 impl<'a> VideoHandle<'a> {
     /// Resolve the video stream index to use.
     fn resolve_video_stream_index(&self) -> Result<usize, UnbundleError> {
@@ -354,6 +525,39 @@ impl<'a> VideoHandle<'a> {
             .ok_or(UnbundleError::NoVideoStream)
     }
 
+    /// Whether range extraction must decode forward from the current
+    /// position instead of seeking, either because it was requested or
+    /// because the input cannot seek. Live streams are read forward too:
+    /// their demuxer may accept a seek, but there is no fixed timeline to
+    /// seek in.
+    fn decodes_linearly(&self, config: &ExtractOptions) -> bool {
+        config.no_seek || !self.unbundler.is_seekable() || self.unbundler.metadata.is_live
+    }
+
+    /// Fail with [`UnbundleError::LiveStream`] when the input is live, for
+    /// ranges that are planned against the media duration or scan the
+    /// whole stream up front.
+    fn ensure_finite(&self) -> Result<(), UnbundleError> {
+        if self.unbundler.metadata.is_live {
+            return Err(UnbundleError::LiveStream);
+        }
+        Ok(())
+    }
+
+    /// Seek to `seek_timestamp` ahead of a range extraction, or stay at the
+    /// current position when [`decodes_linearly`](Self::decodes_linearly).
+    fn seek_unless_linear(
+        &mut self,
+        seek_timestamp: i64,
+        config: &ExtractOptions,
+    ) -> Result<(), UnbundleError> {
+        if self.decodes_linearly(config) {
+            log::debug!("Decoding linearly from the current position");
+            return Ok(());
+        }
+        crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)
+    }
+
     /// Start a chainable FFmpeg filter pipeline.
     ///
     /// This is a convenience wrapper around
@@ -418,6 +622,146 @@ impl<'a> VideoHandle<'a> {
         frame_number: u64,
         config: &ExtractOptions,
     ) -> Result<DynamicImage, UnbundleError> {
+        #[cfg(feature = "cache")]
+        let cache_entry = match &config.frame_cache {
+            // Frame numbers of a live input do not name fixed pictures.
+            Some(cache) if !self.unbundler.metadata.is_live => {
+                let stream_index = self.resolve_video_stream_index()?;
+                // A hit must fail the same limits as a decode would.
+                let video_metadata = self
+                    .unbundler
+                    .metadata
+                    .video
+                    .as_ref()
+                    .ok_or(UnbundleError::NoVideoStream)?;
+                resolve_target_dimensions(config, video_metadata)?;
+                crate::frame_cache::FrameKey::new(
+                    &self.unbundler.source,
+                    stream_index,
+                    frame_number,
+                    config,
+                )
+                .map(|key| (cache, key))
+            }
+            _ => None,
+        };
+        #[cfg(feature = "cache")]
+        if let Some(image) = cache_entry.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            log::debug!("Frame {frame_number} served from cache");
+            return Ok(image);
+        }
+
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.decode_cached_frame(frame_number, config)
+            .map_err(|error| log_capture.attach(error))?;
+        let state = self.cached.as_ref().unwrap();
+        let mut image = convert_frame_to_image(
+            &state.scaled_frame,
+            state.target_width,
+            state.target_height,
+            &config.frame_output,
+        )?;
+        if let Some(video_metadata) = &self.unbundler.metadata.video {
+            config.frame_output.annotate(
+                &mut image,
+                frame_number,
+                video_metadata.frames_per_second,
+            );
+        }
+        #[cfg(feature = "cache")]
+        if let Some((cache, key)) = cache_entry {
+            cache.insert(key, &image);
+        }
+        Ok(image)
+    }
+
+    /// Extract a frame as a player would show it.
+    ///
+    /// Applies the stream's [`rotation`](crate::VideoMetadata::rotation) and
+    /// [`sample_aspect_ratio`](crate::VideoMetadata::sample_aspect_ratio),
+    /// so portrait phone footage comes out upright and anamorphic video
+    /// (DVDs, HDV) is not squashed. Uses default output settings (RGB8, at
+    /// the [`display_dimensions`](crate::VideoMetadata::display_dimensions)).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame`](VideoHandle::frame).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("phone.mp4")?;
+    /// let frame = unbundler.video().display_frame(0)?;
+    /// frame.save("upright.png")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn display_frame(&mut self, frame_number: u64) -> Result<DynamicImage, UnbundleError> {
+        self.display_frame_with_options(frame_number, &ExtractOptions::default())
+    }
+
+    /// Extract a frame as a player would show it, with custom
+    /// configuration.
+    ///
+    /// Like [`display_frame`](VideoHandle::display_frame), but the requested
+    /// resolution refers to the upright, aspect-corrected picture: with
+    /// `with_resolution(Some(320), None)` a portrait 1080×1920 recording
+    /// comes back 320 pixels wide and 569 tall. The pixel stretch and the
+    /// scale are done by one scaler pass on the stored frame, which is then
+    /// turned by whole quarter turns, so no resampling happens twice.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_with_options`](VideoHandle::frame_with_options).
+    pub fn display_frame_with_options(
+        &mut self,
+        frame_number: u64,
+        config: &ExtractOptions,
+    ) -> Result<DynamicImage, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let rotation = video_metadata.rotation;
+        let (display_width, display_height) = video_metadata.display_dimensions();
+        let (target_width, target_height) = config
+            .frame_output
+            .resolve_dimensions(display_width, display_height);
+
+        // Scale to the target size as it lies before rotation.
+        let (stored_width, stored_height) = if rotation % 180 == 90 {
+            (target_height, target_width)
+        } else {
+            (target_width, target_height)
+        };
+        let stored_config = config
+            .clone()
+            .with_resolution(Some(stored_width), Some(stored_height));
+        let image = self.frame_with_options(frame_number, &stored_config)?;
+
+        Ok(match rotation {
+            90 => image.rotate90(),
+            180 => image.rotate180(),
+            270 => image.rotate270(),
+            _ => image,
+        })
+    }
+
+    /// Decode `frame_number` with the cached decoder and scale it into
+    /// the cached state's `scaled_frame`.
+    ///
+    /// Shared by [`frame_with_options`](VideoHandle::frame_with_options)
+    /// and [`decode_into_with_options`](VideoHandle::decode_into_with_options),
+    /// which differ only in where the scaled pixels end up.
+    fn decode_cached_frame(
+        &mut self,
+        frame_number: u64,
+        config: &ExtractOptions,
+    ) -> Result<(), UnbundleError> {
+        let _timer = config.start_timer();
         let video_stream_index = self.resolve_video_stream_index()?;
 
         let video_metadata = self
@@ -429,9 +773,7 @@ impl<'a> VideoHandle<'a> {
 
         let total_frames = video_metadata.frame_count;
         let frames_per_second = video_metadata.frames_per_second;
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
 
         if total_frames > 0 && frame_number >= total_frames {
@@ -458,6 +800,8 @@ impl<'a> VideoHandle<'a> {
                 c.target_width != target_width
                     || c.target_height != target_height
                     || c.output_pixel != output_pixel
+                    || c.input_color_matrix != config.frame_output.input_color_matrix
+                    || c.decoder_settings != DecoderSettings::new(config)
             }
             None => true,
         };
@@ -471,26 +815,22 @@ impl<'a> VideoHandle<'a> {
             let time_base = stream.time_base();
             let codec_parameters = stream.parameters();
             let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-            let decoder = decoder_context.decoder().video()?;
-
-            let scaler = ScalingContext::get(
-                decoder.format(),
-                decoder.width(),
-                decoder.height(),
-                output_pixel,
-                target_width,
-                target_height,
-                ScalingFlags::BILINEAR,
-            )?;
+            let (decoder, hardware_active) =
+                create_video_decoder(decoder_context, &stream, config)?;
 
             self.cached = Some(CachedDecoderState {
                 decoder,
-                scaler,
+                scaler: None,
+                hardware_active,
                 time_base,
                 output_pixel,
                 target_width,
                 target_height,
+                input_color_matrix: config.frame_output.input_color_matrix,
+                deinterlacer: Deinterlacer::default(),
+                decoder_settings: DecoderSettings::new(config),
                 decoded_frame: VideoFrame::empty(),
+                receive_buffer: VideoFrame::empty(),
                 scaled_frame: VideoFrame::empty(),
                 last_pts: None,
                 eof_sent: false,
@@ -526,34 +866,20 @@ impl<'a> VideoHandle<'a> {
         if should_seek {
             state.decoder.flush();
             state.last_pts = None;
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+            crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
         }
 
         // ── Try buffered frames first ───────────────────────────────
         {
             let state = self.cached.as_mut().unwrap();
-            while state
-                .decoder
-                .receive_frame(&mut state.decoded_frame)
-                .is_ok()
-            {
+            while state.receive_next(config) {
                 let pts = state.decoded_frame.pts().unwrap_or(0);
-                state.last_pts = Some(pts);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, state.time_base, frames_per_second);
 
                 if current_frame_number >= frame_number {
-                    state
-                        .scaler
-                        .run(&state.decoded_frame, &mut state.scaled_frame)?;
-                    return convert_frame_to_image(
-                        &state.scaled_frame,
-                        state.target_width,
-                        state.target_height,
-                        &config.frame_output,
-                    );
+                    state.scale_decoded(config)?;
+                    return Ok(());
                 }
             }
         }
@@ -567,26 +893,14 @@ impl<'a> VideoHandle<'a> {
             let state = self.cached.as_mut().unwrap();
             state.decoder.send_packet(&packet)?;
 
-            while state
-                .decoder
-                .receive_frame(&mut state.decoded_frame)
-                .is_ok()
-            {
+            while state.receive_next(config) {
                 let pts = state.decoded_frame.pts().unwrap_or(0);
-                state.last_pts = Some(pts);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, state.time_base, frames_per_second);
 
                 if current_frame_number >= frame_number {
-                    state
-                        .scaler
-                        .run(&state.decoded_frame, &mut state.scaled_frame)?;
-                    return convert_frame_to_image(
-                        &state.scaled_frame,
-                        state.target_width,
-                        state.target_height,
-                        &config.frame_output,
-                    );
+                    state.scale_decoded(config)?;
+                    return Ok(());
                 }
             }
         }
@@ -596,34 +910,112 @@ impl<'a> VideoHandle<'a> {
         state.decoder.send_eof()?;
         state.eof_sent = true;
 
-        while state
-            .decoder
-            .receive_frame(&mut state.decoded_frame)
-            .is_ok()
-        {
+        while state.receive_next(config) {
             let pts = state.decoded_frame.pts().unwrap_or(0);
-            state.last_pts = Some(pts);
             let current_frame_number =
                 crate::conversion::pts_to_frame_number(pts, state.time_base, frames_per_second);
 
             if current_frame_number >= frame_number {
-                state
-                    .scaler
-                    .run(&state.decoded_frame, &mut state.scaled_frame)?;
-                return convert_frame_to_image(
-                    &state.scaled_frame,
-                    state.target_width,
-                    state.target_height,
-                    &config.frame_output,
-                );
+                state.scale_decoded(config)?;
+                return Ok(());
             }
         }
 
+        // The stream ended before any frame mapped to `frame_number`. This
+        // happens for the final frame(s) when PTS-to-frame rounding or a
+        // non-zero start time leaves the last decodable frame one number
+        // short; since the request already passed the range check, hand
+        // back the last frame the decoder produced.
+        if let Some(pts) = state.last_pts {
+            log::debug!(
+                "Frame {frame_number} not reached before end of stream; \
+                 returning last decoded frame (pts={pts})"
+            );
+            state.scale_decoded(config)?;
+            return Ok(());
+        }
+
         Err(UnbundleError::VideoDecodeError(format!(
             "Could not locate frame {frame_number} in the video stream"
         )))
     }
 
+    /// Decode a single frame into a caller-provided buffer.
+    ///
+    /// Scales the frame according to `output` and writes tightly packed
+    /// pixels into `buffer`, replacing its contents. The buffer's allocation
+    /// is reused across calls and only grows when a frame needs more room,
+    /// which keeps real-time loops free of per-frame allocations. The
+    /// decoder and scaler are cached on the handle just like
+    /// [`frame`](VideoHandle::frame).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame`](VideoHandle::frame).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameOutputOptions, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let mut video = unbundler.video();
+    /// let output = FrameOutputOptions::default();
+    /// let mut buffer = Vec::new();
+    /// for frame_number in 0..100 {
+    ///     let layout = video.decode_into(frame_number, &mut buffer, &output)?;
+    ///     println!("{}x{} ({} bytes)", layout.width, layout.height, buffer.len());
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn decode_into(
+        &mut self,
+        frame_number: u64,
+        buffer: &mut Vec<u8>,
+        output: &FrameOutputOptions,
+    ) -> Result<FrameBufferLayout, UnbundleError> {
+        let config = ExtractOptions::new().with_frame_output(output.clone());
+        self.decode_into_with_options(frame_number, buffer, &config)
+    }
+
+    /// Decode a single frame into a caller-provided buffer with custom
+    /// configuration.
+    ///
+    /// Like [`decode_into`](VideoHandle::decode_into) but takes the frame
+    /// output settings, cancellation and the other extraction settings from
+    /// an [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_with_options`](VideoHandle::frame_with_options).
+    pub fn decode_into_with_options(
+        &mut self,
+        frame_number: u64,
+        buffer: &mut Vec<u8>,
+        config: &ExtractOptions,
+    ) -> Result<FrameBufferLayout, UnbundleError> {
+        self.decode_cached_frame(frame_number, config)?;
+        let state = self.cached.as_ref().unwrap();
+        let pixel_format = config.frame_output.pixel_format;
+        let bytes_per_pixel = pixel_format.bytes_per_pixel();
+        crate::conversion::frame_into_buffer(
+            &state.scaled_frame,
+            state.target_width,
+            state.target_height,
+            bytes_per_pixel,
+            buffer,
+        );
+        if config.frame_output.auto_levels {
+            stretch_levels(buffer, bytes_per_pixel);
+        }
+        Ok(FrameBufferLayout {
+            width: state.target_width,
+            height: state.target_height,
+            stride: state.target_width as usize * bytes_per_pixel,
+            pixel_format,
+        })
+    }
+
     /// Extract a single frame, process it through a custom FFmpeg filter graph,
     /// and return the filtered image.
     ///
@@ -673,6 +1065,79 @@ impl<'a> VideoHandle<'a> {
         filter_spec: &str,
         config: &ExtractOptions,
     ) -> Result<DynamicImage, UnbundleError> {
+        let mut images = self.frame_with_filter_outputs_with_options(
+            frame_number,
+            filter_spec,
+            &["out"],
+            config,
+        )?;
+        Ok(images.remove(0))
+    }
+
+    /// Extract a single frame through a filter graph with several named
+    /// outputs, returning one image per output.
+    ///
+    /// Each name in `outputs` is wired to its own buffersink and must appear
+    /// as an output label in `filter_spec`. This allows filters such as
+    /// `split` to produce several variants of a frame from one decode, for
+    /// example the full frame alongside a cropped region. Images are
+    /// returned in the order of `outputs`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_with_filter`](VideoHandle::frame_with_filter). A
+    /// [`UnbundleError::FilterGraphError`] is also returned when `outputs`
+    /// is empty, names `in` (reserved for the source), or does not match the
+    /// graph's open output labels.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let images = unbundler.video().frame_with_filter_outputs(
+    ///     0,
+    ///     "split=2[full][tmp];[tmp]crop=320:240:0:0[crop]",
+    ///     &["full", "crop"],
+    /// )?;
+    /// images[0].save("full.png")?;
+    /// images[1].save("crop.png")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frame_with_filter_outputs(
+        &mut self,
+        frame_number: u64,
+        filter_spec: &str,
+        outputs: &[&str],
+    ) -> Result<Vec<DynamicImage>, UnbundleError> {
+        self.frame_with_filter_outputs_with_options(
+            frame_number,
+            filter_spec,
+            outputs,
+            &ExtractOptions::default(),
+        )
+    }
+
+    /// Extract a single frame through a filter graph with several named
+    /// outputs and extraction options.
+    ///
+    /// Like [`frame_with_filter_outputs`](VideoHandle::frame_with_filter_outputs),
+    /// with every output scaled and converted according to `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested, or
+    /// any error from
+    /// [`frame_with_filter_outputs`](VideoHandle::frame_with_filter_outputs).
+    pub fn frame_with_filter_outputs_with_options(
+        &mut self,
+        frame_number: u64,
+        filter_spec: &str,
+        outputs: &[&str],
+        config: &ExtractOptions,
+    ) -> Result<Vec<DynamicImage>, UnbundleError> {
+        let _timer = config.start_timer();
         if filter_spec.trim().is_empty() {
             return Err(UnbundleError::FilterGraphError(
                 "Filter specification cannot be empty".to_string(),
@@ -690,7 +1155,7 @@ impl<'a> VideoHandle<'a> {
 
         let total_frames = video_metadata.frame_count;
         let frames_per_second = video_metadata.frames_per_second;
-        let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
+        config.check_dimensions(video_metadata.width, video_metadata.height)?;
 
         if total_frames > 0 && frame_number >= total_frames {
             return Err(UnbundleError::FrameOutOfRange {
@@ -718,15 +1183,15 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(frame_number, frames_per_second);
 
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
 
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
 
         for (stream, packet) in self.unbundler.input_context.packets() {
@@ -741,44 +1206,32 @@ impl<'a> VideoHandle<'a> {
             decoder.send_packet(&packet)?;
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
                 if current_frame_number >= frame_number {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    let filtered = apply_filter_graph_to_frame(source, time_base, filter_spec)?;
-
-                    let (target_width, target_height) = config
-                        .frame_output
-                        .resolve_dimensions(filtered.width(), filtered.height());
-
-                    let mut scaler = ScalingContext::get(
-                        filtered.format(),
-                        filtered.width(),
-                        filtered.height(),
-                        output_pixel,
-                        target_width,
-                        target_height,
-                        ScalingFlags::BILINEAR,
-                    )?;
-
-                    let mut scaled_frame = VideoFrame::empty();
-                    scaler.run(&filtered, &mut scaled_frame)?;
-                    return convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
+                    let transferred = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
                         &config.frame_output,
-                    );
+                    )?;
+                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
+                    let filtered =
+                        apply_filter_graph_outputs(source, time_base, filter_spec, outputs)?;
+                    return filtered
+                        .iter()
+                        .map(|frame| convert_filtered_frame(frame, config))
+                        .collect();
                 }
             }
         }
 
         decoder.send_eof()?;
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
@@ -788,32 +1241,18 @@ impl<'a> VideoHandle<'a> {
                 crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
             if current_frame_number >= frame_number {
-                let transferred = maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                let filtered = apply_filter_graph_to_frame(source, time_base, filter_spec)?;
-
-                let (target_width, target_height) = config
-                    .frame_output
-                    .resolve_dimensions(filtered.width(), filtered.height());
-
-                let mut scaler = ScalingContext::get(
-                    filtered.format(),
-                    filtered.width(),
-                    filtered.height(),
-                    output_pixel,
-                    target_width,
-                    target_height,
-                    ScalingFlags::BILINEAR,
-                )?;
-
-                let mut scaled_frame = VideoFrame::empty();
-                scaler.run(&filtered, &mut scaled_frame)?;
-                return convert_frame_to_image(
-                    &scaled_frame,
-                    target_width,
-                    target_height,
+                let transferred = prepare_source_frame(
+                    &decoded_frame,
+                    hardware_active,
+                    &mut deinterlacer,
                     &config.frame_output,
-                );
+                )?;
+                let source = transferred.as_ref().unwrap_or(&decoded_frame);
+                let filtered = apply_filter_graph_outputs(source, time_base, filter_spec, outputs)?;
+                return filtered
+                    .iter()
+                    .map(|frame| convert_filtered_frame(frame, config))
+                    .collect();
             }
         }
 
@@ -897,11 +1336,77 @@ impl<'a> VideoHandle<'a> {
         self.frame_with_options(frame_number, config)
     }
 
-    /// Extract a single frame by number, returning both the image and its
-    /// [`FrameMetadata`] metadata.
+    /// Extract the frame at a SMPTE timecode such as `"01:23:45:12"`.
     ///
-    /// This combines frame extraction with metadata collection (PTS,
-    /// keyframe flag, picture type) in a single decode pass.
+    /// Write drop-frame timecode with a `;` before the frames
+    /// (`"01:23:45;12"`); it is honoured at 29.97 and 59.94 fps. Frames are
+    /// counted at the stream's frame rate rounded to a whole number.
+    ///
+    /// When the stream carries a
+    /// [`start_timecode`](crate::VideoMetadata::start_timecode), `timecode`
+    /// is read on the source's timeline, so with a start of `01:00:00:00`
+    /// the first frame is `"01:00:00:00"`. Otherwise the first frame is
+    /// `"00:00:00:00"`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidTimecode`] if `timecode` cannot be parsed,
+    ///   names a frame that does not exist at the stream's frame rate, or
+    ///   is before the start timecode.
+    /// - [`UnbundleError::NoVideoStream`] if the file has no video.
+    /// - Any error from [`frame`](VideoHandle::frame).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("program.mxf")?;
+    /// let frame = unbundler.video().frame_at_timecode("10:00:05;12")?;
+    /// frame.save("cue.png")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frame_at_timecode(&mut self, timecode: &str) -> Result<DynamicImage, UnbundleError> {
+        self.frame_at_timecode_with_options(timecode, &ExtractOptions::default())
+    }
+
+    /// Extract the frame at a SMPTE timecode with custom configuration.
+    ///
+    /// Like [`frame_at_timecode`](VideoHandle::frame_at_timecode) but
+    /// accepts an [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_at_timecode`](VideoHandle::frame_at_timecode), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn frame_at_timecode_with_options(
+        &mut self,
+        timecode: &str,
+        config: &ExtractOptions,
+    ) -> Result<DynamicImage, UnbundleError> {
+        let timecode = Timecode::parse(timecode)?;
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let frames_per_second = video_metadata.frames_per_second;
+
+        let mut frame_number = timecode.to_frame_number(frames_per_second)?;
+        if let Some(start) = video_metadata.start_timecode {
+            frame_number = frame_number
+                .checked_sub(start.to_frame_number(frames_per_second)?)
+                .ok_or_else(|| UnbundleError::InvalidTimecode(timecode.to_string()))?;
+        }
+        self.frame_with_options(frame_number, config)
+    }
+
+    /// Extract a single frame by number, returning both the image and its
+    /// [`FrameMetadata`] metadata.
+    ///
+    /// This combines frame extraction with metadata collection (PTS,
+    /// keyframe flag, picture type) in a single decode pass.
     ///
     /// # Errors
     ///
@@ -951,6 +1456,7 @@ impl<'a> VideoHandle<'a> {
         frame_number: u64,
         config: &ExtractOptions,
     ) -> Result<(DynamicImage, FrameMetadata), UnbundleError> {
+        let _timer = config.start_timer();
         let video_stream_index = self.resolve_video_stream_index()?;
 
         let video_metadata = self
@@ -962,9 +1468,7 @@ impl<'a> VideoHandle<'a> {
 
         let total_frames = video_metadata.frame_count;
         let frames_per_second = video_metadata.frames_per_second;
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
 
         if total_frames > 0 && frame_number >= total_frames {
@@ -986,25 +1490,16 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let mut decoder = decoder_context.decoder().video()?;
-
-        let mut scaler = ScalingContext::get(
-            decoder.format(),
-            decoder.width(),
-            decoder.height(),
-            output_pixel,
-            target_width,
-            target_height,
-            ScalingFlags::BILINEAR,
-        )?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
+        let mut scaler: Option<ScalingContext> = None;
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(frame_number, frames_per_second);
 
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
 
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
         let mut rgb_frame = VideoFrame::empty();
 
@@ -1016,19 +1511,41 @@ impl<'a> VideoHandle<'a> {
             decoder.send_packet(&packet)?;
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
                 if current_frame_number >= frame_number {
                     let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                    scaler.run(&decoded_frame, &mut rgb_frame)?;
-                    let image = convert_frame_to_image(
+                    let prepared = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
+                        &config.frame_output,
+                    )?;
+                    let source = prepared.as_ref().unwrap_or(&decoded_frame);
+                    ensure_scaler(
+                        &mut scaler,
+                        source,
+                        output_pixel,
+                        &config.frame_output,
+                        target_width,
+                        target_height,
+                    )?;
+                    scaler.as_mut().unwrap().run(source, &mut rgb_frame)?;
+                    config.record_scaled();
+                    let mut image = convert_frame_to_image(
                         &rgb_frame,
                         target_width,
                         target_height,
                         &config.frame_output,
                     )?;
+                    config.frame_output.annotate(
+                        &mut image,
+                        current_frame_number,
+                        frames_per_second,
+                    );
                     return Ok((image, info));
                 }
             }
@@ -1036,19 +1553,39 @@ impl<'a> VideoHandle<'a> {
 
         decoder.send_eof()?;
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
             let pts = decoded_frame.pts().unwrap_or(0);
             let current_frame_number =
                 crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
             if current_frame_number >= frame_number {
                 let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                scaler.run(&decoded_frame, &mut rgb_frame)?;
-                let image = convert_frame_to_image(
+                let prepared = prepare_source_frame(
+                    &decoded_frame,
+                    hardware_active,
+                    &mut deinterlacer,
+                    &config.frame_output,
+                )?;
+                let source = prepared.as_ref().unwrap_or(&decoded_frame);
+                ensure_scaler(
+                    &mut scaler,
+                    source,
+                    output_pixel,
+                    &config.frame_output,
+                    target_width,
+                    target_height,
+                )?;
+                scaler.as_mut().unwrap().run(source, &mut rgb_frame)?;
+                config.record_scaled();
+                let mut image = convert_frame_to_image(
                     &rgb_frame,
                     target_width,
                     target_height,
                     &config.frame_output,
                 )?;
+                config
+                    .frame_output
+                    .annotate(&mut image, current_frame_number, frames_per_second);
                 return Ok((image, info));
             }
         }
@@ -1120,6 +1657,7 @@ impl<'a> VideoHandle<'a> {
 
         let mut results = Vec::with_capacity(total.unwrap_or(0) as usize);
 
+        let log_capture = crate::ffmpeg::LogCapture::start();
         self.dispatch_range_with_info(
             range,
             &video_metadata,
@@ -1129,89 +1667,133 @@ impl<'a> VideoHandle<'a> {
                 tracker.advance(Some(_frame_number), None);
                 Ok(())
             },
-        )?;
+        )
+        .map_err(|error| log_capture.attach(error))?;
 
         tracker.finish();
         Ok(results)
     }
 
-    /// Extract a frame and save it directly to a file.
+    /// Extract the frame shown at the start of each subtitle event.
     ///
-    /// Convenience method that combines [`frame`](VideoHandle::frame) with
-    /// [`DynamicImage::save`]. The output format is inferred from the file
-    /// extension.
+    /// Each event's [`start_time`](SubtitleEvent::start_time) is mapped to
+    /// the nearest frame number and all frames are decoded in one sorted
+    /// pass, so a full subtitle track costs a single forward decode rather
+    /// than one seek per line. The returned pairs keep the order of
+    /// `events`. Events that start after the last frame use the final frame;
+    /// events whose frame could not be decoded are omitted.
     ///
     /// # Errors
     ///
-    /// Returns errors from [`frame`](VideoHandle::frame), or
-    /// [`UnbundleError::ImageError`] if the image cannot be written.
+    /// Returns [`UnbundleError::NoVideoStream`] if the file has no video,
+    /// or decoding errors.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use unbundle::{MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.video().save_frame(0, "first_frame.png")?;
+    /// let mut unbundler = MediaFile::open("input.mkv")?;
+    /// let events = unbundler.subtitle().extract()?;
+    /// let cards = unbundler.video().frames_for_subtitles(events)?;
+    /// for (event, image) in &cards {
+    ///     image.save(format!("card_{}.jpg", event.index))?;
+    /// }
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn save_frame<P: AsRef<Path>>(
+    pub fn frames_for_subtitles(
         &mut self,
-        frame_number: u64,
-        path: P,
-    ) -> Result<(), UnbundleError> {
-        let image = self.frame(frame_number)?;
-        image.save(path)?;
-        Ok(())
+        events: Vec<SubtitleEvent>,
+    ) -> Result<Vec<(SubtitleEvent, DynamicImage)>, UnbundleError> {
+        self.frames_for_subtitles_with_options(events, &ExtractOptions::default())
     }
 
-    /// Extract a frame at a timestamp and save it directly to a file.
+    /// Extract one frame per subtitle event with progress/cancellation.
     ///
-    /// Convenience method that combines [`frame_at`](VideoHandle::frame_at)
-    /// with [`DynamicImage::save`]. The output format is inferred from the file
-    /// extension.
+    /// Like [`frames_for_subtitles`](VideoHandle::frames_for_subtitles) but
+    /// accepts an [`ExtractOptions`].
     ///
     /// # Errors
     ///
-    /// Returns errors from [`frame_at`](VideoHandle::frame_at), or
-    /// [`UnbundleError::ImageError`] if the image cannot be written.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use std::time::Duration;
-    ///
-    /// use unbundle::{MediaFile, UnbundleError};
-    ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.video().save_frame_at(Duration::from_secs(5), "frame_5s.png")?;
-    /// # Ok::<(), UnbundleError>(())
-    /// ```
-    pub fn save_frame_at<P: AsRef<Path>>(
+    /// Same as [`frames_for_subtitles`](VideoHandle::frames_for_subtitles),
+    /// plus [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn frames_for_subtitles_with_options(
         &mut self,
-        timestamp: Duration,
-        path: P,
-    ) -> Result<(), UnbundleError> {
-        let image = self.frame_at(timestamp)?;
-        image.save(path)?;
-        Ok(())
-    }
+        events: Vec<SubtitleEvent>,
+        config: &ExtractOptions,
+    ) -> Result<Vec<(SubtitleEvent, DynamicImage)>, UnbundleError> {
+        let _timer = config.start_timer();
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
 
-    // ── Stream copy (lossless) ─────────────────────────────────────────
+        let last_frame = video_metadata.frame_count.saturating_sub(1);
+        let frame_numbers: Vec<u64> = events
+            .iter()
+            .map(|event| {
+                crate::conversion::timestamp_to_frame_number(
+                    event.start_time,
+                    video_metadata.frames_per_second,
+                )
+                .min(last_frame)
+            })
+            .collect();
 
-    /// Copy the video stream verbatim to a file without re-encoding.
-    ///
-    /// Unlike frame extraction methods, this copies packets directly from the
-    /// input stream, preserving the original codec and quality. The output
-    /// container format is inferred from the file extension.
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            Some(frame_numbers.len() as u64),
+            config.batch_size,
+        );
+
+        let mut decoded: HashMap<u64, DynamicImage> = HashMap::with_capacity(frame_numbers.len());
+        self.process_specific_frames(
+            &frame_numbers,
+            &video_metadata,
+            config,
+            &mut |frame_number, mut frame_image| {
+                config.record_scaled();
+                config.frame_output.annotate(
+                    &mut frame_image,
+                    frame_number,
+                    video_metadata.frames_per_second,
+                );
+                decoded.insert(frame_number, frame_image);
+                tracker.advance(Some(frame_number), None);
+                Ok(())
+            },
+        )?;
+        tracker.finish();
+
+        Ok(events
+            .into_iter()
+            .zip(frame_numbers)
+            .filter_map(|(event, frame_number)| {
+                let image = decoded.get(&frame_number)?.clone();
+                Some((event, image))
+            })
+            .collect())
+    }
+
+    /// Extract `count` frames spread evenly across the video.
     ///
-    /// This is equivalent to `ffmpeg -i input.mp4 -an -sn -c:v copy output.mp4`.
+    /// The duration is split into `count` equal slots and the frame at the
+    /// middle of each slot is decoded, so the first and last frames (often
+    /// black) are skipped and the spacing does not depend on the video's
+    /// length. All frames are decoded in one sorted pass. Each image comes
+    /// with its frame's timestamp. Short videos with fewer frames than
+    /// slots return each frame once, so the result can hold fewer than
+    /// `count` entries; a `count` of 0 returns nothing.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
-    /// - [`UnbundleError::StreamCopyError`] if the output container does
-    ///   not support the source codec.
+    /// Returns [`UnbundleError::NoVideoStream`] if the file has no video,
+    /// [`UnbundleError::LiveStream`] if the input is live, or decoding
+    /// errors.
     ///
     /// # Example
     ///
@@ -1219,149 +1801,197 @@ impl<'a> VideoHandle<'a> {
     /// use unbundle::{MediaFile, UnbundleError};
     ///
     /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.video().stream_copy("output.mp4")?;
+    /// for (timestamp, image) in unbundler.video().storyboard(12)? {
+    ///     image.save(format!("board_{:06}.jpg", timestamp.as_millis()))?;
+    /// }
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn stream_copy<P: AsRef<Path>>(&mut self, path: P) -> Result<(), UnbundleError> {
-        self.copy_stream_to_file(path.as_ref(), None, None, None)
-    }
-
-    /// Copy a video segment verbatim to a file without re-encoding.
-    ///
-    /// Like [`stream_copy`](VideoHandle::stream_copy) but copies only
-    /// packets between `start` and `end`. Because there is no re-encoding,
-    /// the actual boundaries are aligned to packet/keyframe boundaries.
-    ///
-    /// # Errors
-    ///
-    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
-    /// - Plus any errors from [`stream_copy`](VideoHandle::stream_copy).
-    pub fn stream_copy_range<P: AsRef<Path>>(
+    pub fn storyboard(
         &mut self,
-        path: P,
-        start: Duration,
-        end: Duration,
-    ) -> Result<(), UnbundleError> {
-        if start >= end {
-            return Err(UnbundleError::InvalidRange {
-                start: format!("{start:?}"),
-                end: format!("{end:?}"),
-            });
-        }
-        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), None)
+        count: usize,
+    ) -> Result<Vec<(Duration, DynamicImage)>, UnbundleError> {
+        self.storyboard_with_options(count, &ExtractOptions::default())
     }
 
-    /// Copy the video stream verbatim to a file with cancellation support.
+    /// Extract an evenly spaced storyboard with progress/cancellation.
     ///
-    /// Like [`stream_copy`](VideoHandle::stream_copy) but accepts an
-    /// [`ExtractOptions`] for cancellation.
+    /// Like [`storyboard`](VideoHandle::storyboard) but accepts an
+    /// [`ExtractOptions`].
     ///
     /// # Errors
     ///
-    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
-    /// or any error from [`stream_copy`](VideoHandle::stream_copy).
-    pub fn stream_copy_with_options<P: AsRef<Path>>(
+    /// Same as [`storyboard`](VideoHandle::storyboard), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn storyboard_with_options(
         &mut self,
-        path: P,
+        count: usize,
         config: &ExtractOptions,
-    ) -> Result<(), UnbundleError> {
-        self.copy_stream_to_file(path.as_ref(), None, None, Some(config))
-    }
+    ) -> Result<Vec<(Duration, DynamicImage)>, UnbundleError> {
+        self.ensure_finite()?;
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
 
-    /// Copy a video segment verbatim to a file with cancellation support.
-    ///
-    /// Like [`stream_copy_range`](VideoHandle::stream_copy_range) but
-    /// accepts an [`ExtractOptions`].
-    pub fn stream_copy_range_with_options<P: AsRef<Path>>(
-        &mut self,
-        path: P,
-        start: Duration,
-        end: Duration,
-        config: &ExtractOptions,
-    ) -> Result<(), UnbundleError> {
-        if start >= end {
-            return Err(UnbundleError::InvalidRange {
-                start: format!("{start:?}"),
-                end: format!("{end:?}"),
-            });
-        }
-        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), Some(config))
+        let last_frame = video_metadata.frame_count.saturating_sub(1);
+        let frame_numbers: Vec<u64> = self
+            .storyboard_timestamps(count)
+            .into_iter()
+            .map(|timestamp| {
+                crate::conversion::timestamp_to_frame_number(
+                    timestamp,
+                    video_metadata.frames_per_second,
+                )
+                .min(last_frame)
+            })
+            .collect();
+        self.storyboard_frames(&frame_numbers, &video_metadata, config)
     }
 
-    /// Copy the video stream verbatim to memory without re-encoding.
+    /// Extract a storyboard of up to `count` keyframes, one nearest to each
+    /// evenly spaced slot.
     ///
-    /// `container_format` is the FFmpeg short name for the output container
-    /// (for example: `"matroska"`, `"mp4"`, `"mpegts"`).
+    /// A faster variant of [`storyboard`](VideoHandle::storyboard): every
+    /// image is a keyframe, which decodes without the frames before it, so
+    /// long files with long GOPs are much quicker to sample. The spacing is
+    /// only as even as the keyframes allow, and slots that share a nearest
+    /// keyframe produce a single entry.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
-    /// - [`UnbundleError::StreamCopyError`] if the container format is
-    ///   invalid or does not support the source codec.
-    pub fn stream_copy_to_memory(
+    /// Returns [`UnbundleError::NoVideoStream`] if the file has no video,
+    /// [`UnbundleError::LiveStream`] if the input is live,
+    /// [`UnbundleError::NoTimestampedKeyframes`] if no keyframe has a
+    /// timestamp, or errors from [`keyframes`](VideoHandle::keyframes) and
+    /// decoding.
+    pub fn keyframe_storyboard(
         &mut self,
-        container_format: &str,
-    ) -> Result<Vec<u8>, UnbundleError> {
-        self.copy_stream_to_memory(container_format, None, None, None)
+        count: usize,
+    ) -> Result<Vec<(Duration, DynamicImage)>, UnbundleError> {
+        self.keyframe_storyboard_with_options(count, &ExtractOptions::default())
     }
 
-    /// Copy a video segment verbatim to memory without re-encoding.
+    /// Extract a keyframe storyboard with progress/cancellation.
     ///
-    /// Like [`stream_copy_to_memory`](VideoHandle::stream_copy_to_memory) but
-    /// copies only packets between `start` and `end`.
+    /// Like [`keyframe_storyboard`](VideoHandle::keyframe_storyboard) but
+    /// accepts an [`ExtractOptions`].
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
-    /// - Plus any errors from [`stream_copy_to_memory`](VideoHandle::stream_copy_to_memory).
-    pub fn stream_copy_range_to_memory(
+    /// Same as [`keyframe_storyboard`](VideoHandle::keyframe_storyboard),
+    /// plus [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn keyframe_storyboard_with_options(
         &mut self,
-        container_format: &str,
-        start: Duration,
-        end: Duration,
-    ) -> Result<Vec<u8>, UnbundleError> {
-        if start >= end {
-            return Err(UnbundleError::InvalidRange {
-                start: format!("{start:?}"),
-                end: format!("{end:?}"),
-            });
+        count: usize,
+        config: &ExtractOptions,
+    ) -> Result<Vec<(Duration, DynamicImage)>, UnbundleError> {
+        self.ensure_finite()?;
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let keyframe_times: Vec<Duration> = self
+            .keyframes()?
+            .into_iter()
+            .filter_map(|keyframe| keyframe.timestamp)
+            .collect();
+        if keyframe_times.is_empty() {
+            return Err(UnbundleError::NoTimestampedKeyframes);
         }
-        self.copy_stream_to_memory(container_format, Some(start), Some(end), None)
+
+        let frame_numbers: Vec<u64> = self
+            .storyboard_timestamps(count)
+            .into_iter()
+            .filter_map(|timestamp| {
+                keyframe_times
+                    .iter()
+                    .min_by_key(|keyframe_time| keyframe_time.abs_diff(timestamp))
+            })
+            .map(|keyframe_time| {
+                crate::conversion::timestamp_to_frame_number(
+                    *keyframe_time,
+                    video_metadata.frames_per_second,
+                )
+            })
+            .collect();
+        self.storyboard_frames(&frame_numbers, &video_metadata, config)
     }
 
-    /// Extract multiple frames according to the specified range.
-    ///
-    /// See [`FrameRange`] for the available selection modes.
-    ///
-    /// # Errors
-    ///
-    /// Returns errors from individual frame extraction, or
-    /// [`UnbundleError::NoVideoStream`] if the file has no video.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
-    ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let frames = unbundler.video().frames(FrameRange::Range(0, 9))?;
-    /// assert_eq!(frames.len(), 10);
-    /// # Ok::<(), UnbundleError>(())
-    /// ```
-    pub fn frames(&mut self, range: FrameRange) -> Result<Vec<DynamicImage>, UnbundleError> {
-        self.frames_with_options(range, &ExtractOptions::default())
+    /// Midpoints of `count` equal slots spanning the media duration.
+    fn storyboard_timestamps(&self, count: usize) -> Vec<Duration> {
+        let slot = self.unbundler.metadata.duration.as_secs_f64() / count.max(1) as f64;
+        (0..count)
+            .map(|index| Duration::from_secs_f64(slot * (index as f64 + 0.5)))
+            .collect()
     }
 
-    /// Process frames one at a time without collecting them into a `Vec`.
+    /// Decode `frame_numbers` in one pass and pair each distinct frame with
+    /// its timestamp, in frame order.
+    fn storyboard_frames(
+        &mut self,
+        frame_numbers: &[u64],
+        video_metadata: &VideoMetadata,
+        config: &ExtractOptions,
+    ) -> Result<Vec<(Duration, DynamicImage)>, UnbundleError> {
+        let _timer = config.start_timer();
+        let mut unique_numbers = frame_numbers.to_vec();
+        unique_numbers.sort_unstable();
+        unique_numbers.dedup();
+
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            Some(unique_numbers.len() as u64),
+            config.batch_size,
+        );
+
+        let frames_per_second = video_metadata.frames_per_second;
+        let mut storyboard = Vec::with_capacity(unique_numbers.len());
+        self.process_specific_frames(
+            &unique_numbers,
+            video_metadata,
+            config,
+            &mut |frame_number, mut frame_image| {
+                config.record_scaled();
+                config
+                    .frame_output
+                    .annotate(&mut frame_image, frame_number, frames_per_second);
+                let timestamp = if frames_per_second > 0.0 {
+                    Duration::from_secs_f64(frame_number as f64 / frames_per_second)
+                } else {
+                    Duration::ZERO
+                };
+                storyboard.push((timestamp, frame_image));
+                tracker.advance(Some(frame_number), Some(timestamp));
+                Ok(())
+            },
+        )?;
+        tracker.finish();
+        Ok(storyboard)
+    }
+
+    /// Save a range of frames as PNG files, each with a JSON sidecar holding
+    /// its [`FrameMetadata`].
     ///
-    /// This is a streaming alternative to [`frames`](VideoHandle::frames)
-    /// that calls `callback` for each decoded frame. The callback receives the
-    /// frame number and the decoded image. Processing stops if the callback
-    /// returns an error.
+    /// Frame `N` is written to `directory/frame_NNNNNN.png`, and its frame
+    /// number, PTS, timestamp, keyframe flag and picture type to
+    /// `directory/frame_NNNNNN.json` alongside it, so an archived frame
+    /// dump keeps the timing it was decoded with. The directory is created
+    /// if needed. Returns the paths of the saved images in extraction
+    /// order.
     ///
     /// # Errors
     ///
-    /// Returns the first error from decoding or from the callback.
+    /// Returns errors from [`frames`](VideoHandle::frames), or
+    /// [`UnbundleError::IoError`] / [`UnbundleError::ImageError`] if a file
+    /// cannot be written.
     ///
     /// # Example
     ///
@@ -1369,60 +1999,40 @@ impl<'a> VideoHandle<'a> {
     /// use unbundle::{FrameRange, MediaFile, UnbundleError};
     ///
     /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.video().for_each_frame(
-    ///     FrameRange::Range(0, 9),
-    ///     |frame_number, image| {
-    ///         image.save(format!("frame_{frame_number}.png"))?;
-    ///         Ok(())
-    ///     },
-    /// )?;
+    /// let saved = unbundler
+    ///     .video()
+    ///     .save_frames_with_sidecar(FrameRange::KeyframesOnly, "evidence")?;
+    /// println!("Saved {} frames", saved.len());
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn for_each_frame<F>(&mut self, range: FrameRange, callback: F) -> Result<(), UnbundleError>
-    where
-        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
-    {
-        self.for_each_frame_with_options(range, &ExtractOptions::default(), callback)
+    pub fn save_frames_with_sidecar<P: AsRef<Path>>(
+        &mut self,
+        range: FrameRange,
+        directory: P,
+    ) -> Result<Vec<PathBuf>, UnbundleError> {
+        self.save_frames_with_sidecar_with_options(range, directory, &ExtractOptions::default())
     }
 
-    /// Extract multiple frames with progress reporting and cancellation.
+    /// Save a range of frames with JSON sidecars, using custom extraction
+    /// options.
     ///
-    /// Like [`frames`](VideoHandle::frames) but accepts an
-    /// [`ExtractOptions`] for progress callbacks and cancellation support.
+    /// Like [`save_frames_with_sidecar`](VideoHandle::save_frames_with_sidecar)
+    /// but with progress, cancellation and frame output settings from
+    /// `config`. Images are always written as PNG.
     ///
     /// # Errors
     ///
-    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
-    /// or any error from [`frames`](VideoHandle::frames).
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use std::sync::Arc;
-    ///
-    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, ProgressCallback, ProgressInfo, UnbundleError};
-    ///
-    /// struct PrintProgress;
-    /// impl ProgressCallback for PrintProgress {
-    ///     fn on_progress(&self, info: &ProgressInfo) {
-    ///         println!("Frame {}/{}", info.current, info.total.unwrap_or(0));
-    ///     }
-    /// }
-    ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let config = ExtractOptions::new()
-    ///     .with_progress(Arc::new(PrintProgress));
-    /// let frames = unbundler.video().frames_with_options(
-    ///     FrameRange::Range(0, 9),
-    ///     &config,
-    /// )?;
-    /// # Ok::<(), UnbundleError>(())
-    /// ```
-    pub fn frames_with_options(
+    /// Same as [`save_frames_with_sidecar`](VideoHandle::save_frames_with_sidecar),
+    /// plus [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn save_frames_with_sidecar_with_options<P: AsRef<Path>>(
         &mut self,
         range: FrameRange,
+        directory: P,
         config: &ExtractOptions,
-    ) -> Result<Vec<DynamicImage>, UnbundleError> {
+    ) -> Result<Vec<PathBuf>, UnbundleError> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+
         let video_metadata = self
             .unbundler
             .metadata
@@ -1441,195 +2051,1595 @@ impl<'a> VideoHandle<'a> {
             config.batch_size,
         );
 
-        let mut frames = Vec::with_capacity(total.unwrap_or(0) as usize);
+        let mut saved = Vec::with_capacity(total.unwrap_or(0) as usize);
 
-        self.dispatch_range(
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range_with_info(
             range,
             &video_metadata,
             config,
-            &mut |frame_number, frame_image| {
-                frames.push(frame_image);
-                tracker.advance(Some(frame_number), None);
+            &mut |frame_number, frame_image, info| {
+                let image_path = directory.join(format!("frame_{frame_number:06}.png"));
+                frame_image.save_with_format(&image_path, image::ImageFormat::Png)?;
+                std::fs::write(image_path.with_extension("json"), frame_sidecar_json(&info))?;
+                saved.push(image_path);
+                tracker.advance(Some(frame_number), Some(info.timestamp));
                 Ok(())
             },
-        )?;
+        )
+        .map_err(|error| log_capture.attach(error))?;
 
         tracker.finish();
-        Ok(frames)
+        Ok(saved)
     }
 
-    /// Process frames one at a time with progress reporting and cancellation.
+    /// Extract a frame and save it directly to a file.
     ///
-    /// Like [`for_each_frame`](VideoHandle::for_each_frame) but accepts an
-    /// [`ExtractOptions`].
+    /// Convenience method that combines [`frame`](VideoHandle::frame) with
+    /// [`DynamicImage::save`]. The output format is inferred from the file
+    /// extension.
     ///
     /// # Errors
     ///
-    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
-    /// or any error from decoding or the callback.
+    /// Returns errors from [`frame`](VideoHandle::frame), or
+    /// [`UnbundleError::ImageError`] if the image cannot be written.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use unbundle::{CancellationToken, ExtractOptions, FrameRange, MediaFile, UnbundleError};
-    ///
-    /// let token = CancellationToken::new();
-    /// let config = ExtractOptions::new()
-    ///     .with_cancellation(token.clone());
+    /// use unbundle::{MediaFile, UnbundleError};
     ///
     /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// unbundler.video().for_each_frame_with_options(
-    ///     FrameRange::Range(0, 99),
-    ///     &config,
-    ///     |frame_number, image| {
-    ///         image.save(format!("frame_{frame_number}.png"))?;
-    ///         Ok(())
-    ///     },
-    /// )?;
+    /// unbundler.video().save_frame(0, "first_frame.png")?;
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn for_each_frame_with_options<F>(
+    pub fn save_frame<P: AsRef<Path>>(
         &mut self,
-        range: FrameRange,
-        config: &ExtractOptions,
-        mut callback: F,
-    ) -> Result<(), UnbundleError>
-    where
-        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
-    {
-        let video_metadata = self
-            .unbundler
-            .metadata
-            .video
-            .as_ref()
-            .ok_or(UnbundleError::NoVideoStream)?
-            .clone();
-
-        let total =
-            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
-
-        let mut tracker = ProgressTracker::new(
-            config.progress.clone(),
-            OperationType::FrameExtraction,
-            total,
-            config.batch_size,
-        );
-
-        self.dispatch_range(
-            range,
-            &video_metadata,
-            config,
-            &mut |frame_number, frame_image| {
-                callback(frame_number, frame_image)?;
-                tracker.advance(Some(frame_number), None);
-                Ok(())
-            },
-        )?;
-
-        tracker.finish();
+        frame_number: u64,
+        path: P,
+    ) -> Result<(), UnbundleError> {
+        let image = self.frame(frame_number)?;
+        image.save(path)?;
         Ok(())
     }
 
-    /// Process decoded frames as zero-copy byte slices plus metadata.
+    /// Extract a frame and save it to a file with custom configuration.
     ///
-    /// Unlike [`for_each_frame`](VideoHandle::for_each_frame), this avoids
-    /// conversion to [`DynamicImage`]. The callback receives a borrowed
-    /// [`RawFrameView`] valid for the duration of that callback call.
-    pub fn for_each_raw_frame<F>(
-        &mut self,
-        range: FrameRange,
-        callback: F,
-    ) -> Result<(), UnbundleError>
-    where
-        F: FnMut(RawFrameView<'_>) -> Result<(), UnbundleError>,
-    {
-        self.for_each_raw_frame_with_options(range, &ExtractOptions::default(), callback)
-    }
-
-    /// Process decoded frames as zero-copy byte slices plus metadata,
-    /// with progress/cancellation support.
-    pub fn for_each_raw_frame_with_options<F>(
+    /// Like [`save_frame`](VideoHandle::save_frame) but applies the output
+    /// settings from `config`, and writes through a temporary file when
+    /// [`with_atomic_write`](ExtractOptions::with_atomic_write) is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`frame_with_options`](VideoHandle::frame_with_options),
+    /// or [`UnbundleError::ImageError`] or [`UnbundleError::IoError`] if
+    /// the image cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{ExtractOptions, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = ExtractOptions::new().with_atomic_write(true);
+    /// unbundler
+    ///     .video()
+    ///     .save_frame_with_options(0, "watched/poster.jpg", &config)?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn save_frame_with_options<P: AsRef<Path>>(
         &mut self,
-        range: FrameRange,
+        frame_number: u64,
+        path: P,
         config: &ExtractOptions,
-        mut callback: F,
-    ) -> Result<(), UnbundleError>
-    where
-        F: FnMut(RawFrameView<'_>) -> Result<(), UnbundleError>,
-    {
-        let video_metadata = self
-            .unbundler
-            .metadata
-            .video
-            .as_ref()
-            .ok_or(UnbundleError::NoVideoStream)?
-            .clone();
+    ) -> Result<(), UnbundleError> {
+        let image = self.frame_with_options(frame_number, config)?;
+        crate::atomic_write::write_output(path.as_ref(), Some(config), |path| {
+            image.save(path)?;
+            Ok(())
+        })
+    }
 
-        let video_stream_index = self.resolve_video_stream_index()?;
-        let time_base = self
-            .unbundler
-            .input_context
-            .stream(video_stream_index)
-            .ok_or(UnbundleError::NoVideoStream)?
+    /// Extract a frame at a timestamp and save it directly to a file.
+    ///
+    /// Convenience method that combines [`frame_at`](VideoHandle::frame_at)
+    /// with [`DynamicImage::save`]. The output format is inferred from the file
+    /// extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`frame_at`](VideoHandle::frame_at), or
+    /// [`UnbundleError::ImageError`] if the image cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.video().save_frame_at(Duration::from_secs(5), "frame_5s.png")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn save_frame_at<P: AsRef<Path>>(
+        &mut self,
+        timestamp: Duration,
+        path: P,
+    ) -> Result<(), UnbundleError> {
+        let image = self.frame_at(timestamp)?;
+        image.save(path)?;
+        Ok(())
+    }
+
+    // ── Stream copy (lossless) ─────────────────────────────────────────
+
+    /// Copy the video stream verbatim to a file without re-encoding.
+    ///
+    /// Unlike frame extraction methods, this copies packets directly from the
+    /// input stream, preserving the original codec and quality. The output
+    /// container format is inferred from the file extension.
+    ///
+    /// This is equivalent to `ffmpeg -i input.mp4 -an -sn -c:v copy output.mp4`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - [`UnbundleError::StreamCopyError`] if the output container does
+    ///   not support the source codec.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.video().stream_copy("output.mp4")?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn stream_copy<P: AsRef<Path>>(&mut self, path: P) -> Result<(), UnbundleError> {
+        self.copy_stream_to_file(path.as_ref(), None, None, None)?;
+        Ok(())
+    }
+
+    /// Copy a video segment verbatim to a file without re-encoding.
+    ///
+    /// Like [`stream_copy`](VideoHandle::stream_copy) but copies only
+    /// packets between `start` and `end`. Because there is no re-encoding,
+    /// the actual boundaries are aligned to packet/keyframe boundaries.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
+    /// - Plus any errors from [`stream_copy`](VideoHandle::stream_copy).
+    pub fn stream_copy_range<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        start: Duration,
+        end: Duration,
+    ) -> Result<(), UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), None)?;
+        Ok(())
+    }
+
+    /// Copy the video stream verbatim to a file with cancellation support.
+    ///
+    /// Like [`stream_copy`](VideoHandle::stream_copy) but accepts an
+    /// [`ExtractOptions`] for cancellation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from [`stream_copy`](VideoHandle::stream_copy).
+    pub fn stream_copy_with_options<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        config: &ExtractOptions,
+    ) -> Result<(), UnbundleError> {
+        self.copy_stream_to_file(path.as_ref(), None, None, Some(config))?;
+        Ok(())
+    }
+
+    /// Copy a video segment verbatim to a file with cancellation support.
+    ///
+    /// Like [`stream_copy_range`](VideoHandle::stream_copy_range) but
+    /// accepts an [`ExtractOptions`].
+    pub fn stream_copy_range_with_options<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        start: Duration,
+        end: Duration,
+        config: &ExtractOptions,
+    ) -> Result<(), UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), Some(config))?;
+        Ok(())
+    }
+
+    /// Copy a video segment verbatim and report what was written.
+    ///
+    /// Like [`stream_copy_range_with_options`](VideoHandle::stream_copy_range_with_options)
+    /// but returns an [`ExtractSummary`]. The copy begins at the keyframe
+    /// before `start`, so the summary's duration is usually longer than
+    /// `end - start`; it is the exact length to use when stitching clips.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`stream_copy_range`](VideoHandle::stream_copy_range).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{ExtractOptions, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let summary = unbundler.video().stream_copy_range_with_summary(
+    ///     "clip.mp4",
+    ///     Duration::from_secs(30),
+    ///     Duration::from_secs(40),
+    ///     &ExtractOptions::new(),
+    /// )?;
+    /// println!("{} frames, {:?}", summary.packets_written, summary.output_duration);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn stream_copy_range_with_summary<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        start: Duration,
+        end: Duration,
+        config: &ExtractOptions,
+    ) -> Result<ExtractSummary, UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.copy_stream_to_file(path.as_ref(), Some(start), Some(end), Some(config))
+    }
+
+    /// Copy the video stream verbatim to memory without re-encoding.
+    ///
+    /// `container_format` is the FFmpeg short name for the output container
+    /// (for example: `"matroska"`, `"mp4"`, `"mpegts"`).
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - [`UnbundleError::StreamCopyError`] if the container format is
+    ///   invalid or does not support the source codec.
+    pub fn stream_copy_to_memory(
+        &mut self,
+        container_format: &str,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        self.copy_stream_to_memory(container_format, None, None, None)
+    }
+
+    /// Copy a video segment verbatim to memory without re-encoding.
+    ///
+    /// Like [`stream_copy_to_memory`](VideoHandle::stream_copy_to_memory) but
+    /// copies only packets between `start` and `end`.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidRange`] if `start >= end`.
+    /// - Plus any errors from [`stream_copy_to_memory`](VideoHandle::stream_copy_to_memory).
+    pub fn stream_copy_range_to_memory(
+        &mut self,
+        container_format: &str,
+        start: Duration,
+        end: Duration,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        if start >= end {
+            return Err(UnbundleError::InvalidRange {
+                start: format!("{start:?}"),
+                end: format!("{end:?}"),
+            });
+        }
+        self.copy_stream_to_memory(container_format, Some(start), Some(end), None)
+    }
+
+    /// Extract multiple frames according to the specified range.
+    ///
+    /// See [`FrameRange`] for the available selection modes.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from individual frame extraction, or
+    /// [`UnbundleError::NoVideoStream`] if the file has no video.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let frames = unbundler.video().frames(FrameRange::Range(0, 9))?;
+    /// assert_eq!(frames.len(), 10);
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frames(&mut self, range: FrameRange) -> Result<Vec<DynamicImage>, UnbundleError> {
+        self.frames_with_options(range, &ExtractOptions::default())
+    }
+
+    /// Process frames one at a time without collecting them into a `Vec`.
+    ///
+    /// This is a streaming alternative to [`frames`](VideoHandle::frames)
+    /// that calls `callback` for each decoded frame. The callback receives the
+    /// frame number and the decoded image. Processing stops if the callback
+    /// returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from decoding or from the callback.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.video().for_each_frame(
+    ///     FrameRange::Range(0, 9),
+    ///     |frame_number, image| {
+    ///         image.save(format!("frame_{frame_number}.png"))?;
+    ///         Ok(())
+    ///     },
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn for_each_frame<F>(&mut self, range: FrameRange, callback: F) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
+    {
+        self.for_each_frame_with_options(range, &ExtractOptions::default(), callback)
+    }
+
+    /// Extract multiple frames with progress reporting and cancellation.
+    ///
+    /// Like [`frames`](VideoHandle::frames) but accepts an
+    /// [`ExtractOptions`] for progress callbacks and cancellation support.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from [`frames`](VideoHandle::frames).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    ///
+    /// use unbundle::{ExtractOptions, FrameRange, MediaFile, ProgressCallback, ProgressInfo, UnbundleError};
+    ///
+    /// struct PrintProgress;
+    /// impl ProgressCallback for PrintProgress {
+    ///     fn on_progress(&self, info: &ProgressInfo) {
+    ///         println!("Frame {}/{}", info.current, info.total.unwrap_or(0));
+    ///     }
+    /// }
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = ExtractOptions::new()
+    ///     .with_progress(Arc::new(PrintProgress));
+    /// let frames = unbundler.video().frames_with_options(
+    ///     FrameRange::Range(0, 9),
+    ///     &config,
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frames_with_options(
+        &mut self,
+        range: FrameRange,
+        config: &ExtractOptions,
+    ) -> Result<Vec<DynamicImage>, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let total =
+            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
+
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            total,
+            config.batch_size,
+        );
+
+        let mut frames = Vec::with_capacity(total.unwrap_or(0) as usize);
+
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range(
+            range,
+            &video_metadata,
+            config,
+            &mut |frame_number, frame_image| {
+                frames.push(frame_image);
+                tracker.advance(Some(frame_number), None);
+                Ok(())
+            },
+        )
+        .map_err(|error| log_capture.attach(error))?;
+
+        tracker.finish();
+        Ok(frames)
+    }
+
+    /// Extract the frames whose presentation timestamps exactly match
+    /// `pts_values`.
+    ///
+    /// Values are in the video stream's time base, as reported by
+    /// [`PacketInfo::pts`](crate::PacketInfo::pts) or
+    /// [`FrameMetadata::pts`]. Decoded frames are matched on their PTS
+    /// directly rather than through a frame number derived from the frame
+    /// rate, so the result lines up with the source even on
+    /// variable-frame-rate content where frame-number rounding drifts.
+    ///
+    /// Results are returned in ascending PTS order as `(pts, image)`
+    /// pairs, with duplicates removed. Values that no decoded frame
+    /// carries are skipped, so compare the returned PTS against the input
+    /// to detect misses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::NoVideoStream`] if the file has no video,
+    /// or decoding errors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// // PTS values recorded earlier, e.g. from `PacketInfo::pts`.
+    /// let pts_values = [0, 512, 1536];
+    /// for (pts, image) in unbundler.video().frames_at_pts(&pts_values)? {
+    ///     image.save(format!("frame_{pts}.png"))?;
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frames_at_pts(
+        &mut self,
+        pts_values: &[i64],
+    ) -> Result<Vec<(i64, DynamicImage)>, UnbundleError> {
+        self.frames_at_pts_with_options(pts_values, &ExtractOptions::default())
+    }
+
+    /// Extract frames at exact PTS values with progress, cancellation, and
+    /// output settings.
+    ///
+    /// Like [`frames_at_pts`](VideoHandle::frames_at_pts) but accepts an
+    /// [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from [`frames_at_pts`](VideoHandle::frames_at_pts).
+    pub fn frames_at_pts_with_options(
+        &mut self,
+        pts_values: &[i64],
+        config: &ExtractOptions,
+    ) -> Result<Vec<(i64, DynamicImage)>, UnbundleError> {
+        let _timer = config.start_timer();
+        let mut targets = pts_values.to_vec();
+        targets.sort_unstable();
+        targets.dedup();
+        if targets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+        log::debug!(
+            "Extracting {} frames by PTS (stream={})",
+            targets.len(),
+            video_stream_index
+        );
+
+        let (target_width, target_height) = resolve_target_dimensions(config, &video_metadata)?;
+        let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
+
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let time_base = stream.time_base();
+        let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
+
+        let first_seconds = crate::conversion::pts_to_seconds(targets[0], time_base).max(0.0);
+        let seek_timestamp =
+            crate::conversion::duration_to_seek_timestamp(Duration::from_secs_f64(first_seconds));
+        crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            Some(targets.len() as u64),
+            config.batch_size,
+        );
+
+        let mut results = Vec::with_capacity(targets.len());
+        let mut scaler: Option<ScalingContext> = None;
+        let mut target_index = 0;
+        let mut deinterlacer = Deinterlacer::default();
+        let mut decoded_frame = VideoFrame::empty();
+        let mut scaled_frame = VideoFrame::empty();
+        let mut packets = self.unbundler.input_context.packets();
+        let mut eof_sent = false;
+
+        while target_index < targets.len() {
+            if config.is_cancelled() {
+                return Err(UnbundleError::Cancelled);
+            }
+
+            if decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
+                let Some(pts) = decoded_frame.pts() else {
+                    continue;
+                };
+
+                // Targets below the current PTS have no matching frame.
+                while target_index < targets.len() && targets[target_index] < pts {
+                    log::debug!("No frame with PTS {}", targets[target_index]);
+                    target_index += 1;
+                }
+
+                if target_index < targets.len() && targets[target_index] == pts {
+                    let transferred = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
+                        &config.frame_output,
+                    )?;
+                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
+                    ensure_scaler(
+                        &mut scaler,
+                        source,
+                        output_pixel,
+                        &config.frame_output,
+                        target_width,
+                        target_height,
+                    )?;
+                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
+                    config.record_scaled();
+                    let image = convert_frame_to_image(
+                        &scaled_frame,
+                        target_width,
+                        target_height,
+                        &config.frame_output,
+                    )?;
+                    results.push((pts, image));
+
+                    let seconds = crate::conversion::pts_to_seconds(pts, time_base).max(0.0);
+                    tracker.advance(None, Some(Duration::from_secs_f64(seconds)));
+                    target_index += 1;
+                }
+                continue;
+            }
+
+            if eof_sent {
+                break;
+            }
+            match packets.next() {
+                Some((stream, packet)) => {
+                    if stream.index() == video_stream_index {
+                        decoder.send_packet(&packet)?;
+                    }
+                }
+                None => {
+                    decoder.send_eof()?;
+                    eof_sent = true;
+                }
+            }
+        }
+
+        tracker.finish();
+        Ok(results)
+    }
+
+    /// Decode the keyframe that encloses `frame_number`, for fast previews.
+    ///
+    /// Seeks to the last keyframe at or before `frame_number` and returns
+    /// the first frame decoded there, without decoding forward to the
+    /// exact frame. Returns the keyframe's own frame number with its image;
+    /// it can be well before `frame_number` on files with long keyframe
+    /// intervals. Use [`frame`](VideoHandle::frame) when the exact frame
+    /// matters.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::FrameOutOfRange`] if `frame_number` is past the
+    ///   last frame.
+    /// - [`UnbundleError::NotSeekable`] if the input cannot seek.
+    /// - [`UnbundleError::VideoDecodeError`] if no frame decodes after the
+    ///   seek, or other decoding errors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("huge.mkv")?;
+    /// let (keyframe, image) = unbundler.video().nearest_keyframe_image(90_000)?;
+    /// image.save(format!("preview_{keyframe}.jpg"))?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn nearest_keyframe_image(
+        &mut self,
+        frame_number: u64,
+    ) -> Result<(u64, DynamicImage), UnbundleError> {
+        self.nearest_keyframe_image_with_options(frame_number, &ExtractOptions::default())
+    }
+
+    /// Decode the keyframe that encloses `frame_number` with cancellation
+    /// and output settings.
+    ///
+    /// Like [`nearest_keyframe_image`](VideoHandle::nearest_keyframe_image)
+    /// but accepts an [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from
+    /// [`nearest_keyframe_image`](VideoHandle::nearest_keyframe_image).
+    pub fn nearest_keyframe_image_with_options(
+        &mut self,
+        frame_number: u64,
+        config: &ExtractOptions,
+    ) -> Result<(u64, DynamicImage), UnbundleError> {
+        let _timer = config.start_timer();
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let total_frames = video_metadata.frame_count;
+        if total_frames > 0 && frame_number >= total_frames {
+            return Err(UnbundleError::FrameOutOfRange {
+                frame_number,
+                total_frames,
+            });
+        }
+
+        let (target_width, target_height) = resolve_target_dimensions(config, &video_metadata)?;
+        let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
+        let frames_per_second = video_metadata.frames_per_second;
+
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let time_base = stream.time_base();
+        let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
+
+        // Seeking lands on the keyframe at or before the target, which is
+        // the first frame the decoder produces afterwards.
+        let seek_timestamp =
+            crate::conversion::frame_number_to_seek_timestamp(frame_number, frames_per_second);
+        crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
+
+        let mut deinterlacer = Deinterlacer::default();
+        let mut convert =
+            |decoded_frame: &VideoFrame| -> Result<(u64, DynamicImage), UnbundleError> {
+                let transferred = prepare_source_frame(
+                    decoded_frame,
+                    hardware_active,
+                    &mut deinterlacer,
+                    &config.frame_output,
+                )?;
+                let source = transferred.as_ref().unwrap_or(decoded_frame);
+                let mut scaler = None;
+                ensure_scaler(
+                    &mut scaler,
+                    source,
+                    output_pixel,
+                    &config.frame_output,
+                    target_width,
+                    target_height,
+                )?;
+                let mut scaled_frame = VideoFrame::empty();
+                scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
+                config.record_scaled();
+                let image = convert_frame_to_image(
+                    &scaled_frame,
+                    target_width,
+                    target_height,
+                    &config.frame_output,
+                )?;
+                let keyframe_number = crate::conversion::pts_to_frame_number(
+                    decoded_frame.pts().unwrap_or(0),
+                    time_base,
+                    frames_per_second,
+                );
+                Ok((keyframe_number, image))
+            };
+
+        let mut decoded_frame = VideoFrame::empty();
+        for (stream, packet) in self.unbundler.input_context.packets() {
+            if config.is_cancelled() {
+                return Err(UnbundleError::Cancelled);
+            }
+            if stream.index() != video_stream_index {
+                continue;
+            }
+            decoder.send_packet(&packet)?;
+            if decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
+                return convert(&decoded_frame);
+            }
+        }
+
+        decoder.send_eof()?;
+        if decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
+            return convert(&decoded_frame);
+        }
+        Err(UnbundleError::VideoDecodeError(format!(
+            "No frame decoded at the keyframe before frame {frame_number}"
+        )))
+    }
+
+    /// Process frames one at a time with progress reporting and cancellation.
+    ///
+    /// Like [`for_each_frame`](VideoHandle::for_each_frame) but accepts an
+    /// [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnbundleError::Cancelled`] if cancellation is requested,
+    /// or any error from decoding or the callback.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{CancellationToken, ExtractOptions, FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let token = CancellationToken::new();
+    /// let config = ExtractOptions::new()
+    ///     .with_cancellation(token.clone());
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// unbundler.video().for_each_frame_with_options(
+    ///     FrameRange::Range(0, 99),
+    ///     &config,
+    ///     |frame_number, image| {
+    ///         image.save(format!("frame_{frame_number}.png"))?;
+    ///         Ok(())
+    ///     },
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn for_each_frame_with_options<F>(
+        &mut self,
+        range: FrameRange,
+        config: &ExtractOptions,
+        mut callback: F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
+    {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let total =
+            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
+
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            total,
+            config.batch_size,
+        );
+
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range(
+            range,
+            &video_metadata,
+            config,
+            &mut |frame_number, frame_image| {
+                callback(frame_number, frame_image)?;
+                tracker.advance(Some(frame_number), None);
+                Ok(())
+            },
+        )
+        .map_err(|error| log_capture.attach(error))?;
+
+        tracker.finish();
+        Ok(())
+    }
+
+    /// Process decoded frames as zero-copy byte slices plus metadata.
+    ///
+    /// Unlike [`for_each_frame`](VideoHandle::for_each_frame), this avoids
+    /// conversion to [`DynamicImage`]. The callback receives a borrowed
+    /// [`RawFrameView`] valid for the duration of that callback call.
+    pub fn for_each_raw_frame<F>(
+        &mut self,
+        range: FrameRange,
+        callback: F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(RawFrameView<'_>) -> Result<(), UnbundleError>,
+    {
+        self.for_each_raw_frame_with_options(range, &ExtractOptions::default(), callback)
+    }
+
+    /// Process decoded frames as zero-copy byte slices plus metadata,
+    /// with progress/cancellation support.
+    pub fn for_each_raw_frame_with_options<F>(
+        &mut self,
+        range: FrameRange,
+        config: &ExtractOptions,
+        mut callback: F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(RawFrameView<'_>) -> Result<(), UnbundleError>,
+    {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let time_base = self
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?
             .time_base();
 
-        let total =
-            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
+        let total =
+            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
+
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            total,
+            config.batch_size,
+        );
+
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range_raw(
+            range,
+            &video_metadata,
+            config,
+            &mut |frame_number, frame| {
+                let pts = frame.pts();
+                let timestamp = Duration::from_secs_f64(
+                    crate::conversion::pts_to_seconds(pts.unwrap_or(0), time_base).max(0.0),
+                );
+
+                let view = RawFrameView {
+                    frame_number,
+                    pts,
+                    timestamp,
+                    width: frame.width(),
+                    height: frame.height(),
+                    stride: frame.stride(0),
+                    pixel_format: frame.format(),
+                    is_keyframe: frame.is_key(),
+                    frame_type: picture_type_to_frame_type(frame.kind()),
+                    data: frame.data(0),
+                };
+
+                callback(view)?;
+                tracker.advance(Some(frame_number), Some(timestamp));
+                Ok(())
+            },
+        )
+        .map_err(|error| log_capture.attach(error))?;
+
+        tracker.finish();
+        Ok(())
+    }
+
+    /// Detect scene changes (shot boundaries) in the video.
+    ///
+    /// Uses FFmpeg's `scdet` filter to analyse every frame and return a list
+    /// of [`SceneChange`](crate::scene::SceneChange) entries.
+    ///
+    /// An optional [`SceneDetectionOptions`](crate::scene::SceneDetectionOptions)
+    /// controls the detection threshold. Pass `None` for defaults (threshold
+    /// 10.0).
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if the file has no video.
+    /// - [`UnbundleError::VideoDecodeError`] if the `scdet` filter is not
+    ///   available in your FFmpeg build.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let scenes = unbundler.video().detect_scenes(None)?;
+    /// println!("Found {} scene changes", scenes.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "scene")]
+    pub fn detect_scenes(
+        &mut self,
+        config: Option<SceneDetectionOptions>,
+    ) -> Result<Vec<SceneChange>, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let scene_config = config.unwrap_or_default();
+        crate::scene::detect_scenes_impl(
+            self.unbundler,
+            &video_metadata,
+            &scene_config,
+            None,
+            self.stream_index,
+        )
+    }
+
+    /// Detect scene changes with cancellation support.
+    ///
+    /// Like [`detect_scenes`](VideoHandle::detect_scenes) but accepts an
+    /// [`ExtractOptions`] for cancellation.
+    #[cfg(feature = "scene")]
+    pub fn detect_scenes_with_options(
+        &mut self,
+        scene_config: Option<SceneDetectionOptions>,
+        config: &ExtractOptions,
+    ) -> Result<Vec<SceneChange>, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let scene_config = scene_config.unwrap_or_default();
+        let cancel_check: Box<dyn Fn() -> bool> = Box::new(|| config.is_cancelled());
+        crate::scene::detect_scenes_impl(
+            self.unbundler,
+            &video_metadata,
+            &scene_config,
+            Some(&*cancel_check),
+            self.stream_index,
+        )
+    }
+
+    /// Return the time span of the `index`-th scene.
+    ///
+    /// Runs [`detect_scenes`](VideoHandle::detect_scenes) and treats the
+    /// detected changes as boundaries: scene 0 starts at the beginning of
+    /// the video and the last scene ends at the media duration, so a video
+    /// with N changes has N + 1 scenes. The returned `(start, end)` pair can
+    /// be passed straight to [`FrameRange::TimeRange`] or the audio range
+    /// methods.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::SceneOutOfRange`] if `index` is past the last scene.
+    /// - Any error from [`detect_scenes`](VideoHandle::detect_scenes).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let (start, end) = unbundler.video().scene_range(2, None)?;
+    /// let frames = unbundler
+    ///     .video()
+    ///     .frames(FrameRange::TimeRange(start, end))?;
+    /// println!("Scene 2 has {} frames", frames.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "scene")]
+    pub fn scene_range(
+        &mut self,
+        index: usize,
+        config: Option<SceneDetectionOptions>,
+    ) -> Result<(Duration, Duration), UnbundleError> {
+        let changes = self.detect_scenes(config)?;
+        crate::scene::scene_bounds(&changes, self.unbundler.metadata.duration, index).map_err(
+            |scene_count| UnbundleError::SceneOutOfRange {
+                scene_index: index,
+                scene_count,
+            },
+        )
+    }
+
+    /// Export frames as an animated GIF to a file.
+    ///
+    /// Extracts frames matching the given [`FrameRange`], scales them
+    /// according to [`GifOptions`], and writes the result as an animated
+    /// GIF.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - [`UnbundleError::GifEncodeError`] if encoding fails.
+    /// - Any errors from frame extraction.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use unbundle::{FrameRange, GifOptions, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = GifOptions::new().width(320).frame_delay(10);
+    /// unbundler.video().export_gif(
+    ///     "output.gif",
+    ///     FrameRange::TimeRange(Duration::from_secs(0), Duration::from_secs(5)),
+    ///     &config,
+    /// )?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    #[cfg(feature = "gif")]
+    pub fn export_gif<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        range: FrameRange,
+        gif_config: &GifOptions,
+    ) -> Result<(), UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+
+        let frame_output_config =
+            gif_config.to_frame_output_config(video_metadata.width, video_metadata.height);
+        let extraction_config = ExtractOptions::default().with_frame_output(frame_output_config);
+        let frames = if gif_config.motion_interpolation {
+            self.interpolated_gif_frames(
+                &range,
+                &video_metadata,
+                gif_config.frame_delay,
+                &extraction_config,
+            )?
+        } else {
+            self.frames_with_options(range, &extraction_config)?
+        };
+        crate::gif::encode_gif(path, &frames, gif_config)
+    }
 
-        let mut tracker = ProgressTracker::new(
-            config.progress.clone(),
-            OperationType::FrameExtraction,
-            total,
-            config.batch_size,
-        );
+    /// Export frames as an animated GIF into memory.
+    ///
+    /// Like [`export_gif`](VideoHandle::export_gif) but returns the
+    /// raw GIF bytes instead of writing to a file.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`export_gif`](VideoHandle::export_gif).
+    #[cfg(feature = "gif")]
+    pub fn export_gif_to_memory(
+        &mut self,
+        range: FrameRange,
+        gif_config: &GifOptions,
+    ) -> Result<Vec<u8>, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
 
-        self.dispatch_range_raw(
-            range,
-            &video_metadata,
-            config,
-            &mut |frame_number, frame| {
-                let pts = frame.pts();
-                let timestamp = Duration::from_secs_f64(
-                    crate::conversion::pts_to_seconds(pts.unwrap_or(0), time_base).max(0.0),
-                );
+        let frame_output_config =
+            gif_config.to_frame_output_config(video_metadata.width, video_metadata.height);
+        let extraction_config = ExtractOptions::default().with_frame_output(frame_output_config);
+        let frames = if gif_config.motion_interpolation {
+            self.interpolated_gif_frames(
+                &range,
+                &video_metadata,
+                gif_config.frame_delay,
+                &extraction_config,
+            )?
+        } else {
+            self.frames_with_options(range, &extraction_config)?
+        };
+        crate::gif::encode_gif_to_memory(&frames, gif_config)
+    }
 
-                let view = RawFrameView {
-                    frame_number,
-                    pts,
-                    timestamp,
-                    width: frame.width(),
-                    height: frame.height(),
-                    stride: frame.stride(0),
-                    pixel_format: frame.format(),
-                    is_keyframe: frame.is_key(),
-                    frame_type: picture_type_to_frame_type(frame.kind()),
-                    data: frame.data(0),
-                };
+    /// Decode the picture embedded in the container, if there is one.
+    ///
+    /// MP4/M4A cover art, MP3 `APIC` frames, and Matroska cover attachments
+    /// are exposed by FFmpeg as a single-image stream flagged as an attached
+    /// picture. This decodes that image directly, which is instant compared
+    /// with sampling frames and returns the poster the file's author chose.
+    /// The image is returned as RGB8 at its stored size.
+    ///
+    /// Returns `Ok(None)` when the file carries no embedded picture. This
+    /// works on audio-only files too, since cover art is not treated as the
+    /// file's video stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::VideoDecodeError`] if the embedded picture cannot
+    ///   be decoded.
+    /// - [`UnbundleError::DimensionsTooLarge`] if it exceeds the default
+    ///   dimension limit.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("album_track.m4a")?;
+    /// if let Some(cover) = unbundler.video().embedded_thumbnail()? {
+    ///     cover.save("cover.png")?;
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn embedded_thumbnail(&mut self) -> Result<Option<DynamicImage>, UnbundleError> {
+        let Some(stream) = self
+            .unbundler
+            .input_context
+            .streams()
+            .find(|stream| stream.disposition().contains(Disposition::ATTACHED_PIC))
+        else {
+            return Ok(None);
+        };
+        log::debug!("Decoding embedded picture from stream {}", stream.index());
+
+        // SAFETY: `attached_pic` is owned by the stream and lives as long as
+        // the input context; its payload is copied out before decoding.
+        let picture_data = unsafe {
+            let attached = &(*stream.as_ptr()).attached_pic;
+            if attached.data.is_null() || attached.size <= 0 {
+                return Ok(None);
+            }
+            std::slice::from_raw_parts(attached.data, attached.size as usize).to_vec()
+        };
 
-                callback(view)?;
-                tracker.advance(Some(frame_number), Some(timestamp));
-                Ok(())
-            },
+        let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+        let mut decoder = decoder_context.decoder().video()?;
+        decoder.send_packet(&Packet::copy(&picture_data))?;
+        decoder.send_eof()?;
+
+        let mut decoded_frame = VideoFrame::empty();
+        decoder.receive_frame(&mut decoded_frame).map_err(|error| {
+            UnbundleError::VideoDecodeError(format!("Failed to decode embedded picture: {error}"))
+        })?;
+
+        let (width, height) = (decoded_frame.width(), decoded_frame.height());
+        ExtractOptions::default().check_dimensions(width, height)?;
+
+        let mut scaler = ScalingContext::get(
+            decoded_frame.format(),
+            width,
+            height,
+            Pixel::RGB24,
+            width,
+            height,
+            ScalingFlags::BILINEAR,
+        )?;
+        let mut rgb_frame = VideoFrame::empty();
+        scaler.run(&decoded_frame, &mut rgb_frame)?;
+
+        convert_frame_to_image(&rgb_frame, width, height, &FrameOutputOptions::default()).map(Some)
+    }
+
+    /// Analyze the Group of Pictures structure of the video stream.
+    ///
+    /// Scans all video packets (without decoding) to identify keyframes and
+    /// compute Group of Pictures statistics such as average, minimum and maximum sequence size.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - [`UnbundleError::LiveStream`] if the input is live, since the scan
+    ///   would never reach the end.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let group_of_pictures = unbundler.video().analyze_group_of_pictures()?;
+    /// println!(
+    ///     "Keyframes: {}, Average Group of Pictures: {:.1}",
+    ///     group_of_pictures.keyframes.len(),
+    ///     group_of_pictures.average_group_of_pictures_size
+    /// );
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn analyze_group_of_pictures(&mut self) -> Result<GroupOfPicturesInfo, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        self.ensure_finite()?;
+        crate::keyframe::analyze_group_of_pictures_impl(self.unbundler, video_stream_index)
+    }
+
+    /// Summarise how well the video stream supports seeking.
+    ///
+    /// Combines the keyframe analysis from
+    /// [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures)
+    /// with whether the container has a seek index into a single
+    /// [`SeekabilityReport`]. Check it before building a scrubbing UI: a
+    /// low [`score`](SeekabilityReport::score) means sparse keyframes or a
+    /// missing index, and the file is worth re-encoding or remuxing first.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let report = unbundler.video().seekability_report()?;
+    /// if report.score < 0.5 {
+    ///     println!(
+    ///         "Scrubbing will be slow: a keyframe every {:?}, index: {}",
+    ///         report.average_keyframe_interval, report.has_index
+    ///     );
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn seekability_report(&mut self) -> Result<SeekabilityReport, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        // The index was recorded at open time; demuxing (including this
+        // scan, which rewinds to the start) can add entries to it.
+        let has_index = self.unbundler.indexed_streams.contains(&video_stream_index);
+        let group_of_pictures = self.analyze_group_of_pictures()?;
+        Ok(SeekabilityReport::new(
+            &group_of_pictures,
+            has_index,
+            self.unbundler.metadata.duration,
+        ))
+    }
+
+    /// Measure how many bytes the video stream spends in each time window.
+    ///
+    /// Walks the video packets without decoding and sums their sizes into
+    /// consecutive windows of `window` length. Each entry is the window's
+    /// start time and its total size in bytes; divide by the window length
+    /// for a bitrate. Plotted, the peaks show complex or high-motion scenes
+    /// and the spikes at keyframes. Windows with no packets are included
+    /// with zero bytes, so the list covers the stream without gaps.
+    ///
+    /// Window times count from the start of the stream, so the first window
+    /// holds the first packets even in an MPEG-TS file whose timestamps
+    /// begin seconds or hours past zero. They are therefore offset from
+    /// [`KeyFrameMetadata::timestamp`] by the stream's start time.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidInterval`] if `window` is zero or so
+    ///   short that the timeline would have more than a million windows.
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - [`UnbundleError::LiveStream`] if the input is live.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// for (start, bytes) in unbundler.video().bitrate_timeline(Duration::from_secs(1))? {
+    ///     println!("{:>6.1}s  {:>8.1} kbit/s", start.as_secs_f64(), bytes as f64 * 8.0 / 1000.0);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn bitrate_timeline(
+        &mut self,
+        window: Duration,
+    ) -> Result<Vec<(Duration, u64)>, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        self.ensure_finite()?;
+        crate::keyframe::bitrate_timeline_impl(self.unbundler, video_stream_index, window)
+    }
+
+    /// Return a list of all keyframes in the video stream.
+    ///
+    /// This is a convenience wrapper around
+    /// [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures)
+    /// that returns only the keyframe list.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures).
+    pub fn keyframes(&mut self) -> Result<Vec<KeyFrameMetadata>, UnbundleError> {
+        Ok(self.analyze_group_of_pictures()?.keyframes)
+    }
+
+    /// Decode the `keyframe_index`-th keyframe of the video stream.
+    ///
+    /// Looks the keyframe up in [`keyframes`](VideoHandle::keyframes) and
+    /// decodes the frame it starts, returning the image with its
+    /// [`FrameMetadata`]. The keyframe list costs one packet scan per call;
+    /// when browsing many keyframes, fetch the list once and pass frame
+    /// numbers to [`frame_and_metadata`](VideoHandle::frame_and_metadata).
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::KeyframeOutOfRange`] if `keyframe_index` is not
+    ///   less than the number of keyframes.
+    /// - Any error from [`frame_and_metadata`](VideoHandle::frame_and_metadata).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let (image, info) = unbundler.video().frame_at_keyframe(3)?;
+    /// image.save(format!("keyframe_{}.png", info.frame_number))?;
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frame_at_keyframe(
+        &mut self,
+        keyframe_index: usize,
+    ) -> Result<(DynamicImage, FrameMetadata), UnbundleError> {
+        self.frame_at_keyframe_with_options(keyframe_index, &ExtractOptions::default())
+    }
+
+    /// Decode the `keyframe_index`-th keyframe with custom configuration.
+    ///
+    /// Like [`frame_at_keyframe`](VideoHandle::frame_at_keyframe) but
+    /// accepts an [`ExtractOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_at_keyframe`](VideoHandle::frame_at_keyframe), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn frame_at_keyframe_with_options(
+        &mut self,
+        keyframe_index: usize,
+        config: &ExtractOptions,
+    ) -> Result<(DynamicImage, FrameMetadata), UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let keyframes = self.keyframes()?;
+        let frame_numbers = crate::keyframe::keyframe_frame_numbers(
+            self.unbundler,
+            video_stream_index,
+            &keyframes,
+        )?;
+        let frame_number =
+            *frame_numbers
+                .get(keyframe_index)
+                .ok_or(UnbundleError::KeyframeOutOfRange {
+                    keyframe_index,
+                    keyframe_count: keyframes.len(),
+                })?;
+        self.frame_and_metadata_with_options(frame_number, config)
+    }
+
+    /// Build an index of every keyframe with a scaled image, its timestamp,
+    /// frame number and byte offset.
+    ///
+    /// Each image is `config.thumbnail_width` pixels wide with the height
+    /// following the aspect ratio; the grid settings of `config` are not
+    /// used. The byte offsets let a player or CMS map seek targets straight
+    /// to file positions. Keyframes that land on the same frame number are
+    /// listed once.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// - Any decoding error from
+    ///   [`frames_with_options`](VideoHandle::frames_with_options).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, ThumbnailOptions, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let config = ThumbnailOptions::new(1, 1).with_thumbnail_width(160);
+    /// for entry in unbundler.video().keyframe_index(&config)? {
+    ///     entry.image.save(format!("key_{}.jpg", entry.frame_number))?;
+    ///     println!("{:?} at byte {:?}", entry.timestamp, entry.position);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn keyframe_index(
+        &mut self,
+        config: &ThumbnailOptions,
+    ) -> Result<Vec<KeyframeEntry>, UnbundleError> {
+        self.keyframe_index_with_options(config, &ExtractOptions::default())
+    }
+
+    /// Build a keyframe index with progress, cancellation and decoder
+    /// settings.
+    ///
+    /// Like [`keyframe_index`](VideoHandle::keyframe_index) but accepts an
+    /// [`ExtractOptions`]. Its resolution is replaced by the thumbnail
+    /// width.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`keyframe_index`](VideoHandle::keyframe_index), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn keyframe_index_with_options(
+        &mut self,
+        config: &ThumbnailOptions,
+        extraction_config: &ExtractOptions,
+    ) -> Result<Vec<KeyframeEntry>, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let keyframes = self.keyframes()?;
+        let frame_numbers = crate::keyframe::keyframe_frame_numbers(
+            self.unbundler,
+            video_stream_index,
+            &keyframes,
         )?;
 
-        tracker.finish();
-        Ok(())
+        let mut by_frame_number: BTreeMap<u64, KeyFrameMetadata> = BTreeMap::new();
+        for (frame_number, keyframe) in frame_numbers.into_iter().zip(keyframes) {
+            by_frame_number.entry(frame_number).or_insert(keyframe);
+        }
+
+        let extraction_config = extraction_config
+            .clone()
+            .with_resolution(Some(config.thumbnail_width), None)
+            .with_maintain_aspect_ratio(true);
+        let numbers = by_frame_number.keys().copied().collect();
+        let frames = self
+            .frames_and_metadata_with_options(FrameRange::Specific(numbers), &extraction_config)?;
+
+        Ok(frames
+            .into_iter()
+            .filter_map(|(image, info)| {
+                let keyframe = by_frame_number.get(&info.frame_number)?;
+                Some(KeyframeEntry {
+                    image,
+                    timestamp: keyframe.timestamp.unwrap_or(info.timestamp),
+                    frame_number: info.frame_number,
+                    position: keyframe.position,
+                })
+            })
+            .collect())
+    }
+
+    /// Extract frames grouped by the Group of Pictures they belong to.
+    ///
+    /// Decodes `range` like
+    /// [`frames_and_metadata`](VideoHandle::frames_and_metadata) and puts
+    /// each frame in the group of the last keyframe at or before it, taken
+    /// from [`keyframes`](VideoHandle::keyframes). A group whose keyframe
+    /// is outside the range, such as a leading group when the range starts
+    /// mid-group, does not contain that keyframe. Sparse ranges such as
+    /// [`FrameRange::Interval`] keep only the selected frames in each
+    /// group, but never merge two groups.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frames_and_metadata`](VideoHandle::frames_and_metadata),
+    /// plus any error from [`keyframes`](VideoHandle::keyframes).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// for group in unbundler.video().frames_by_gop(FrameRange::Range(0, 299))? {
+    ///     let types: String = group
+    ///         .frames
+    ///         .iter()
+    ///         .map(|(_, info)| format!("{:?}", info.frame_type))
+    ///         .collect();
+    ///     println!("GOP at {}: {types}", group.keyframe_number);
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frames_by_gop(&mut self, range: FrameRange) -> Result<Vec<GopFrames>, UnbundleError> {
+        self.frames_by_gop_with_options(range, &ExtractOptions::default())
     }
 
-    /// Detect scene changes (shot boundaries) in the video.
+    /// Extract frames grouped by Group of Pictures with custom
+    /// configuration.
     ///
-    /// Uses FFmpeg's `scdet` filter to analyse every frame and return a list
-    /// of [`SceneChange`](crate::scene::SceneChange) entries.
+    /// Like [`frames_by_gop`](VideoHandle::frames_by_gop) but accepts an
+    /// [`ExtractOptions`].
     ///
-    /// An optional [`SceneDetectionOptions`](crate::scene::SceneDetectionOptions)
-    /// controls the detection threshold. Pass `None` for defaults (threshold
-    /// 10.0).
+    /// # Errors
+    ///
+    /// Same as [`frames_by_gop`](VideoHandle::frames_by_gop), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn frames_by_gop_with_options(
+        &mut self,
+        range: FrameRange,
+        config: &ExtractOptions,
+    ) -> Result<Vec<GopFrames>, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        // Group boundaries come from every keyframe in the stream, not only
+        // the selected ones, so the scan runs before decoding.
+        let keyframes = self.keyframes()?;
+        let mut keyframe_numbers = crate::keyframe::keyframe_frame_numbers(
+            self.unbundler,
+            video_stream_index,
+            &keyframes,
+        )?;
+        keyframe_numbers.sort_unstable();
+        keyframe_numbers.dedup();
+
+        let frames = self.frames_and_metadata_with_options(range, config)?;
+        let mut groups: Vec<GopFrames> = Vec::new();
+        for (image, info) in frames {
+            // The last keyframe at or before the frame; frames ahead of the
+            // first keyframe are grouped under frame 0.
+            let keyframe_number =
+                match keyframe_numbers.partition_point(|&number| number <= info.frame_number) {
+                    0 => 0,
+                    position => keyframe_numbers[position - 1],
+                };
+            match groups.last_mut() {
+                Some(group) if group.keyframe_number == keyframe_number => {
+                    group.frames.push((image, info));
+                }
+                _ => groups.push(GopFrames {
+                    keyframe_number,
+                    frames: vec![(image, info)],
+                }),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Analyze the video stream for variable frame rate (VFR).
+    ///
+    /// Scans all video packet PTS values and computes timing statistics.
+    /// The result indicates whether the stream is VFR, plus min/max/mean
+    /// FPS and per-frame PTS values.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if the file has no video.
-    /// - [`UnbundleError::VideoDecodeError`] if the `scdet` filter is not
-    ///   available in your FFmpeg build.
+    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
     ///
     /// # Example
     ///
@@ -1637,125 +3647,168 @@ impl<'a> VideoHandle<'a> {
     /// use unbundle::{MediaFile, UnbundleError};
     ///
     /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let scenes = unbundler.video().detect_scenes(None)?;
-    /// println!("Found {} scene changes", scenes.len());
+    /// let analysis = unbundler.video().analyze_variable_framerate()?;
+    /// println!("VFR: {}, mean FPS: {:.2}", analysis.is_variable_frame_rate, analysis.mean_frames_per_second);
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    #[cfg(feature = "scene")]
-    pub fn detect_scenes(
+    pub fn analyze_variable_framerate(
         &mut self,
-        config: Option<SceneDetectionOptions>,
-    ) -> Result<Vec<SceneChange>, UnbundleError> {
-        let video_metadata = self
-            .unbundler
-            .metadata
-            .video
-            .as_ref()
-            .ok_or(UnbundleError::NoVideoStream)?
-            .clone();
-
-        let scene_config = config.unwrap_or_default();
-        crate::scene::detect_scenes_impl(
+    ) -> Result<VariableFrameRateAnalysis, UnbundleError> {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        crate::variable_framerate::analyze_variable_framerate_impl(
             self.unbundler,
-            &video_metadata,
-            &scene_config,
-            None,
-            self.stream_index,
+            video_stream_index,
         )
     }
 
-    /// Detect scene changes with cancellation support.
+    /// Compute the luma histogram of a single frame.
     ///
-    /// Like [`detect_scenes`](VideoHandle::detect_scenes) but accepts an
-    /// [`ExtractOptions`] for cancellation.
-    #[cfg(feature = "scene")]
-    pub fn detect_scenes_with_options(
+    /// The frame is decoded and converted to GRAY8, and each of its pixels
+    /// is counted in one of 256 bins. The returned [`LumaHistogram`] also
+    /// provides the frame's mean and median luma.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame`](VideoHandle::frame).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let histogram = unbundler.video().luma_histogram(0)?;
+    /// println!("mean luma: {:.1}", histogram.mean());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn luma_histogram(&mut self, frame_number: u64) -> Result<LumaHistogram, UnbundleError> {
+        self.luma_histogram_with_options(frame_number, &ExtractOptions::default())
+    }
+
+    /// Compute the luma histogram of a single frame with custom
+    /// configuration.
+    ///
+    /// The configured pixel format is ignored, since the histogram is always
+    /// taken from GRAY8 pixels; resolution settings still apply and can be
+    /// used to sample a smaller image.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frame_with_options`](VideoHandle::frame_with_options).
+    pub fn luma_histogram_with_options(
         &mut self,
-        scene_config: Option<SceneDetectionOptions>,
+        frame_number: u64,
         config: &ExtractOptions,
-    ) -> Result<Vec<SceneChange>, UnbundleError> {
-        let video_metadata = self
-            .unbundler
-            .metadata
-            .video
-            .as_ref()
-            .ok_or(UnbundleError::NoVideoStream)?
-            .clone();
-
-        let scene_config = scene_config.unwrap_or_default();
-        let cancel_check: Box<dyn Fn() -> bool> = Box::new(|| config.is_cancelled());
-        crate::scene::detect_scenes_impl(
-            self.unbundler,
-            &video_metadata,
-            &scene_config,
-            Some(&*cancel_check),
-            self.stream_index,
-        )
+    ) -> Result<LumaHistogram, UnbundleError> {
+        let config = config.clone().with_pixel_format(PixelFormat::Gray8);
+        let image = self.frame_with_options(frame_number, &config)?;
+        Ok(LumaHistogram::from_gray_image(
+            frame_number,
+            &image.into_luma8(),
+        ))
     }
 
-    /// Export frames as an animated GIF to a file.
+    /// Compute luma histograms for every frame in a range.
     ///
-    /// Extracts frames matching the given [`FrameRange`], scales them
-    /// according to [`GifOptions`], and writes the result as an animated
-    /// GIF.
+    /// Frames are processed one at a time, so only the histograms are kept
+    /// in memory.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
-    /// - [`UnbundleError::GifEncodeError`] if encoding fails.
-    /// - Any errors from frame extraction.
+    /// Same as [`frames`](VideoHandle::frames).
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use std::time::Duration;
-    /// use unbundle::{FrameRange, GifOptions, MediaFile, UnbundleError};
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
     ///
     /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let config = GifOptions::new().width(320).frame_delay(10);
-    /// unbundler.video().export_gif(
-    ///     "output.gif",
-    ///     FrameRange::TimeRange(Duration::from_secs(0), Duration::from_secs(5)),
-    ///     &config,
-    /// )?;
+    /// let histograms = unbundler.video().luma_histograms(FrameRange::Interval(30))?;
+    /// let darkest = histograms.iter().min_by_key(|histogram| histogram.median());
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    #[cfg(feature = "gif")]
-    pub fn export_gif<P: AsRef<Path>>(
+    pub fn luma_histograms(
         &mut self,
-        path: P,
         range: FrameRange,
-        gif_config: &GifOptions,
-    ) -> Result<(), UnbundleError> {
-        let video_metadata = self
-            .unbundler
-            .metadata
-            .video
-            .as_ref()
-            .ok_or(UnbundleError::NoVideoStream)?
-            .clone();
-
-        let frame_output_config =
-            gif_config.to_frame_output_config(video_metadata.width, video_metadata.height);
-        let extraction_config = ExtractOptions::default().with_frame_output(frame_output_config);
-        let frames = self.frames_with_options(range, &extraction_config)?;
-        crate::gif::encode_gif(path, &frames, gif_config)
+    ) -> Result<Vec<LumaHistogram>, UnbundleError> {
+        self.luma_histograms_with_options(range, &ExtractOptions::default())
     }
 
-    /// Export frames as an animated GIF into memory.
+    /// Compute luma histograms for a range of frames with progress,
+    /// cancellation and resolution settings from `config`.
     ///
-    /// Like [`export_gif`](VideoHandle::export_gif) but returns the
-    /// raw GIF bytes instead of writing to a file.
+    /// As with
+    /// [`luma_histogram_with_options`](VideoHandle::luma_histogram_with_options),
+    /// the configured pixel format is replaced with GRAY8.
     ///
     /// # Errors
     ///
-    /// Same as [`export_gif`](VideoHandle::export_gif).
-    #[cfg(feature = "gif")]
-    pub fn export_gif_to_memory(
+    /// Same as [`frames_with_options`](VideoHandle::frames_with_options).
+    pub fn luma_histograms_with_options(
         &mut self,
         range: FrameRange,
-        gif_config: &GifOptions,
-    ) -> Result<Vec<u8>, UnbundleError> {
+        config: &ExtractOptions,
+    ) -> Result<Vec<LumaHistogram>, UnbundleError> {
+        let config = config.clone().with_pixel_format(PixelFormat::Gray8);
+        let mut histograms = Vec::new();
+        self.for_each_frame_with_options(range, &config, |frame_number, image| {
+            histograms.push(LumaHistogram::from_gray_image(
+                frame_number,
+                &image.into_luma8(),
+            ));
+            Ok(())
+        })?;
+        Ok(histograms)
+    }
+
+    /// Sample the average colour of the video every `interval`.
+    ///
+    /// Returns one `(timestamp, [r, g, b])` entry per sampled frame, the
+    /// data behind a "movie barcode" or a colour-grading reference. Frames
+    /// are scaled down to 64 pixels wide before averaging, which changes
+    /// the result very little and saves most of the conversion work.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::InvalidInterval`] if `interval` is zero.
+    /// - Same as [`frames`](VideoHandle::frames).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use unbundle::{MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("film.mkv")?;
+    /// let timeline = unbundler.video().color_timeline(Duration::from_secs(1))?;
+    /// for (timestamp, [r, g, b]) in &timeline {
+    ///     println!("{timestamp:?}: #{r:02x}{g:02x}{b:02x}");
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn color_timeline(
+        &mut self,
+        interval: Duration,
+    ) -> Result<Vec<(Duration, [u8; 3])>, UnbundleError> {
+        self.color_timeline_with_options(interval, &ExtractOptions::default())
+    }
+
+    /// Sample the average colour every `interval` with progress,
+    /// cancellation and resolution settings from `config`.
+    ///
+    /// The configured pixel format is replaced with RGB8. An explicit
+    /// resolution replaces the default 64-pixel sampling width.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`color_timeline`](VideoHandle::color_timeline), plus
+    /// [`UnbundleError::Cancelled`] if cancellation is requested.
+    pub fn color_timeline_with_options(
+        &mut self,
+        interval: Duration,
+        config: &ExtractOptions,
+    ) -> Result<Vec<(Duration, [u8; 3])>, UnbundleError> {
         let video_metadata = self
             .unbundler
             .metadata
@@ -1764,82 +3817,234 @@ impl<'a> VideoHandle<'a> {
             .ok_or(UnbundleError::NoVideoStream)?
             .clone();
 
-        let frame_output_config =
-            gif_config.to_frame_output_config(video_metadata.width, video_metadata.height);
-        let extraction_config = ExtractOptions::default().with_frame_output(frame_output_config);
-        let frames = self.frames_with_options(range, &extraction_config)?;
-        crate::gif::encode_gif_to_memory(&frames, gif_config)
+        let mut config = config.clone().with_pixel_format(PixelFormat::Rgb8);
+        if config.frame_output.width.is_none() && config.frame_output.height.is_none() {
+            config = config
+                .with_resolution(Some(COLOR_SAMPLE_WIDTH), None)
+                .with_maintain_aspect_ratio(true);
+        }
+
+        let range = FrameRange::TimeInterval(interval);
+        let total =
+            Self::estimate_frame_count(&range, &video_metadata, self.unbundler.metadata.duration);
+        let mut tracker = ProgressTracker::new(
+            config.progress.clone(),
+            OperationType::FrameExtraction,
+            total,
+            config.batch_size,
+        );
+
+        let mut timeline = Vec::with_capacity(total.unwrap_or(0) as usize);
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range_with_info(
+            range,
+            &video_metadata,
+            &config,
+            &mut |frame_number, image, info| {
+                timeline.push((info.timestamp, average_color(&image.into_rgb8())));
+                tracker.advance(Some(frame_number), Some(info.timestamp));
+                Ok(())
+            },
+        )
+        .map_err(|error| log_capture.attach(error))?;
+
+        tracker.finish();
+        Ok(timeline)
     }
 
-    /// Analyze the Group of Pictures structure of the video stream.
+    /// Average colour of each detected scene.
     ///
-    /// Scans all video packets (without decoding) to identify keyframes and
-    /// compute Group of Pictures statistics such as average, minimum and maximum sequence size.
+    /// Samples a [`color_timeline`](VideoHandle::color_timeline) at
+    /// `interval` and averages the samples between consecutive scene
+    /// changes from [`detect_scenes`](VideoHandle::detect_scenes). Returns
+    /// one `(scene_start, [r, g, b])` entry per scene; scenes shorter than
+    /// `interval` may hold no sample and are left out, so pick an interval
+    /// below the shortest shot you care about.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// Any error from [`detect_scenes`](VideoHandle::detect_scenes) or
+    /// [`color_timeline`](VideoHandle::color_timeline).
     ///
     /// # Example
     ///
     /// ```no_run
+    /// use std::time::Duration;
+    ///
     /// use unbundle::{MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let group_of_pictures = unbundler.video().analyze_group_of_pictures()?;
-    /// println!(
-    ///     "Keyframes: {}, Average Group of Pictures: {:.1}",
-    ///     group_of_pictures.keyframes.len(),
-    ///     group_of_pictures.average_group_of_pictures_size
-    /// );
+    /// let mut unbundler = MediaFile::open("film.mkv")?;
+    /// let scenes = unbundler.video().scene_colors(Duration::from_millis(500), None)?;
+    /// println!("{} scenes", scenes.len());
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn analyze_group_of_pictures(&mut self) -> Result<GroupOfPicturesInfo, UnbundleError> {
-        let video_stream_index = self.resolve_video_stream_index()?;
-        crate::keyframe::analyze_group_of_pictures_impl(self.unbundler, video_stream_index)
-    }
-
-    /// Return a list of all keyframes in the video stream.
-    ///
-    /// This is a convenience wrapper around
-    /// [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures)
-    /// that returns only the keyframe list.
-    ///
-    /// # Errors
-    ///
-    /// Same as [`analyze_group_of_pictures`](VideoHandle::analyze_group_of_pictures).
-    pub fn keyframes(&mut self) -> Result<Vec<KeyFrameMetadata>, UnbundleError> {
-        Ok(self.analyze_group_of_pictures()?.keyframes)
+    #[cfg(feature = "scene")]
+    pub fn scene_colors(
+        &mut self,
+        interval: Duration,
+        config: Option<SceneDetectionOptions>,
+    ) -> Result<Vec<(Duration, [u8; 3])>, UnbundleError> {
+        let changes = self.detect_scenes(config)?;
+        let timeline = self.color_timeline(interval)?;
+
+        let mut scene_starts = vec![Duration::ZERO];
+        scene_starts.extend(changes.iter().map(|change| change.timestamp));
+
+        let mut scenes = Vec::with_capacity(scene_starts.len());
+        let mut samples = timeline.iter().peekable();
+        for (index, &start) in scene_starts.iter().enumerate() {
+            let end = scene_starts.get(index + 1).copied();
+            let mut sums = [0u64; 3];
+            let mut count = 0u64;
+            while let Some((_, color)) =
+                samples.next_if(|(timestamp, _)| end.is_none_or(|end| *timestamp < end))
+            {
+                for (sum, channel) in sums.iter_mut().zip(color) {
+                    *sum += u64::from(*channel);
+                }
+                count += 1;
+            }
+            if count > 0 {
+                scenes.push((start, sums.map(|sum| (sum / count) as u8)));
+            }
+        }
+        Ok(scenes)
     }
 
-    /// Analyze the video stream for variable frame rate (VFR).
+    /// Find where the picture content of the video starts and ends,
+    /// ignoring leading and trailing black frames.
     ///
-    /// Scans all video packet PTS values and computes timing statistics.
-    /// The result indicates whether the stream is VFR, plus min/max/mean
-    /// FPS and per-frame PTS values.
+    /// A frame counts as black when the mean of its GRAY8 luma (0–255) is
+    /// at or below `luma_threshold`; 16–24 suits video that fades to
+    /// limited-range black. The start is found by decoding forward from
+    /// the first frame and the end by decoding backward from the last, a
+    /// second at a time, so only the black frames themselves and at most a
+    /// second of content on each side are decoded.
+    ///
+    /// Returns `(start, end)`, where `start` is the timestamp of the first
+    /// content frame and `end` is where the last content frame stops
+    /// being displayed, ready for [`MediaFile::extract_clip`] or
+    /// [`FrameRange::TimeRange`]. A video that is black throughout returns
+    /// `(Duration::ZERO, Duration::ZERO)`.
     ///
     /// # Errors
     ///
-    /// - [`UnbundleError::NoVideoStream`] if no video stream exists.
+    /// Same as [`frames`](VideoHandle::frames).
     ///
     /// # Example
     ///
     /// ```no_run
     /// use unbundle::{MediaFile, UnbundleError};
     ///
-    /// let mut unbundler = MediaFile::open("input.mp4")?;
-    /// let analysis = unbundler.video().analyze_variable_framerate()?;
-    /// println!("VFR: {}, mean FPS: {:.2}", analysis.is_variable_frame_rate, analysis.mean_frames_per_second);
+    /// let mut unbundler = MediaFile::open("recording.mp4")?;
+    /// let (start, end) = unbundler.video().content_bounds(20.0)?;
+    /// unbundler.extract_clip(start, end, "trimmed.mp4")?;
     /// # Ok::<(), UnbundleError>(())
     /// ```
-    pub fn analyze_variable_framerate(
+    pub fn content_bounds(
         &mut self,
-    ) -> Result<VariableFrameRateAnalysis, UnbundleError> {
-        let video_stream_index = self.resolve_video_stream_index()?;
-        crate::variable_framerate::analyze_variable_framerate_impl(
-            self.unbundler,
-            video_stream_index,
-        )
+        luma_threshold: f64,
+    ) -> Result<(Duration, Duration), UnbundleError> {
+        self.content_bounds_with_options(luma_threshold, &ExtractOptions::default())
+    }
+
+    /// Find the content bounds with cancellation and decoder settings from
+    /// `config`.
+    ///
+    /// The pixel format and resolution are replaced, since frames are only
+    /// inspected as small GRAY8 images. The progress callback sees each
+    /// one-second window as a separate extraction.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frames_with_options`](VideoHandle::frames_with_options).
+    pub fn content_bounds_with_options(
+        &mut self,
+        luma_threshold: f64,
+        config: &ExtractOptions,
+    ) -> Result<(Duration, Duration), UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+        let frames_per_second = video_metadata.frames_per_second;
+        let total_frames = video_metadata.frame_count;
+        let window = (frames_per_second.ceil() as u64).max(1);
+        let frame_duration = if frames_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / frames_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        let config = config
+            .clone()
+            .with_pixel_format(PixelFormat::Gray8)
+            .with_resolution(Some(COLOR_SAMPLE_WIDTH), None)
+            .with_maintain_aspect_ratio(true);
+        let is_content = |image: DynamicImage| {
+            LumaHistogram::from_gray_image(0, &image.into_luma8()).mean() > luma_threshold
+        };
+
+        let mut first: Option<FrameMetadata> = None;
+        let mut window_start = 0;
+        'forward: loop {
+            let range = FrameRange::Range(window_start, window_start + window - 1);
+            let frames = self.frames_and_metadata_with_options(range, &config)?;
+            if frames.is_empty() {
+                break;
+            }
+            for (image, info) in frames {
+                if is_content(image) {
+                    first = Some(info);
+                    break 'forward;
+                }
+            }
+            window_start += window;
+        }
+        let Some(first) = first else {
+            return Ok((Duration::ZERO, Duration::ZERO));
+        };
+
+        let mut last = first.clone();
+        if total_frames > first.frame_number + 1 {
+            let mut window_end = total_frames;
+            'backward: while window_end > first.frame_number + 1 {
+                let window_start = window_end
+                    .saturating_sub(window)
+                    .max(first.frame_number + 1);
+                let range = FrameRange::Range(window_start, window_end - 1);
+                let frames = self.frames_and_metadata_with_options(range, &config)?;
+                for (image, info) in frames.into_iter().rev() {
+                    if is_content(image) {
+                        last = info;
+                        break 'backward;
+                    }
+                }
+                window_end = window_start;
+            }
+        } else if total_frames == 0 {
+            // Without a frame count there is no end to search back from,
+            // so keep decoding forward and remember the last content frame.
+            let mut window_start = first.frame_number + 1;
+            loop {
+                let range = FrameRange::Range(window_start, window_start + window - 1);
+                let frames = self.frames_and_metadata_with_options(range, &config)?;
+                if frames.is_empty() {
+                    break;
+                }
+                for (image, info) in frames {
+                    if is_content(image) {
+                        last = info;
+                    }
+                }
+                window_start += window;
+            }
+        }
+
+        Ok((first.timestamp, last.timestamp + frame_duration))
     }
 
     /// Create an async stream of decoded video frames.
@@ -1853,6 +4058,11 @@ impl<'a> VideoHandle<'a> {
     /// A fresh demuxer is opened internally so this method returns
     /// immediately and the mutable borrow on the unbundler is released.
     ///
+    /// The decoder runs at most
+    /// [`with_stream_buffer_size`](ExtractOptions::with_stream_buffer_size)
+    /// frames ahead of the consumer (8 by default) and then waits, so a
+    /// slow consumer applies backpressure rather than growing memory.
+    ///
     /// # Errors
     ///
     /// Returns [`UnbundleError::NoVideoStream`] if the file has no video
@@ -1893,8 +4103,12 @@ impl<'a> VideoHandle<'a> {
             .ok_or(UnbundleError::NoVideoStream)?;
 
         let source = self.unbundler.source.clone();
+        let buffer_size = config.stream_buffer_size;
         Ok(crate::stream::create_frame_stream(
-            source, range, config, None,
+            source,
+            range,
+            config,
+            buffer_size,
         ))
     }
 
@@ -1978,6 +4192,86 @@ impl<'a> VideoHandle<'a> {
         )
     }
 
+    /// Create a lazy iterator that also yields each frame's [`FrameMetadata`].
+    ///
+    /// The lazy counterpart of
+    /// [`frames_and_metadata`](VideoHandle::frames_and_metadata): each
+    /// [`next()`](Iterator::next) decodes one more frame and returns it
+    /// with its timestamp, keyframe flag, and picture type. This makes it
+    /// cheap to scan forward for the next keyframe or B-frame and stop
+    /// there, without buffering every frame in between.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`frame_iter`](VideoHandle::frame_iter).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let iter = unbundler
+    ///     .video()
+    ///     .frame_iter_with_metadata(FrameRange::Range(0, 299))?;
+    ///
+    /// for result in iter {
+    ///     let (_image, info) = result?;
+    ///     if info.is_keyframe && info.frame_number > 0 {
+    ///         println!("Next keyframe: {}", info.frame_number);
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn frame_iter_with_metadata(
+        self,
+        range: FrameRange,
+    ) -> Result<FrameMetadataIterator<'a>, UnbundleError> {
+        self.frame_iter(range).map(FrameMetadataIterator::new)
+    }
+
+    /// List the frame numbers `range` selects, without decoding anything.
+    ///
+    /// Returns the same sorted, deduplicated numbers that
+    /// [`frame_iter`](VideoHandle::frame_iter) and
+    /// [`frames`](VideoHandle::frames) work through, so a caller can show
+    /// or check the plan before starting a long extraction. Numbers past
+    /// the end of the stream are listed but yield no frame, and
+    /// [`ExtractOptions::with_resume_from`] is not applied.
+    /// [`FrameRange::KeyframesOnly`] scans the stream's packets to find
+    /// the keyframes.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnbundleError::NoVideoStream`] if the file has no video.
+    /// - [`UnbundleError::InvalidRange`] or
+    ///   [`UnbundleError::InvalidInterval`] for a malformed `range`.
+    /// - [`UnbundleError::LiveStream`] for [`FrameRange::TimeInterval`] and
+    ///   [`FrameRange::KeyframesOnly`] on a live input.
+    /// - Errors from reading packets for [`FrameRange::KeyframesOnly`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use unbundle::{FrameRange, MediaFile, UnbundleError};
+    ///
+    /// let mut unbundler = MediaFile::open("input.mp4")?;
+    /// let plan = unbundler.video().resolve_frames(FrameRange::Interval(24))?;
+    /// println!("This will extract {} frames: {plan:?}", plan.len());
+    /// # Ok::<(), UnbundleError>(())
+    /// ```
+    pub fn resolve_frames(&mut self, range: FrameRange) -> Result<Vec<u64>, UnbundleError> {
+        let video_metadata = self
+            .unbundler
+            .metadata
+            .video
+            .as_ref()
+            .ok_or(UnbundleError::NoVideoStream)?
+            .clone();
+        self.resolve_frame_numbers_for_iter(range, &video_metadata)
+    }
+
     /// Resolve a [`FrameRange`] into sorted, deduplicated frame numbers.
     ///
     /// Shared helper for [`frame_iter`](VideoHandle::frame_iter) and
@@ -2026,6 +4320,7 @@ impl<'a> VideoHandle<'a> {
                 if interval.is_zero() {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                self.ensure_finite()?;
                 let total_duration = self.unbundler.metadata.duration;
                 let mut nums = Vec::new();
                 let mut current = Duration::ZERO;
@@ -2052,6 +4347,7 @@ impl<'a> VideoHandle<'a> {
         &mut self,
         video_metadata: &VideoMetadata,
     ) -> Result<Vec<u64>, UnbundleError> {
+        self.ensure_finite()?;
         let video_stream_index = self.resolve_video_stream_index()?;
         let keyframes =
             crate::keyframe::analyze_group_of_pictures_impl(self.unbundler, video_stream_index)?
@@ -2078,6 +4374,109 @@ impl<'a> VideoHandle<'a> {
         Ok(numbers)
     }
 
+    /// First and last frame numbers selected by `range`, for operations
+    /// that need every frame in between. `None` when nothing is selected.
+    #[cfg(feature = "gif")]
+    fn resolve_range_span(
+        &mut self,
+        range: &FrameRange,
+        video_metadata: &VideoMetadata,
+    ) -> Result<Option<(u64, u64)>, UnbundleError> {
+        let frames_per_second = video_metadata.frames_per_second;
+        let numbers = match range {
+            FrameRange::Range(start, end) => return Ok(Some((*start, *end))),
+            FrameRange::TimeRange(start, end) => {
+                return Ok(Some((
+                    crate::conversion::timestamp_to_frame_number(*start, frames_per_second),
+                    crate::conversion::timestamp_to_frame_number(*end, frames_per_second),
+                )));
+            }
+            FrameRange::Interval(_) | FrameRange::TimeInterval(_) => {
+                // An unknown frame count means decoding to the end.
+                let last = video_metadata
+                    .frame_count
+                    .checked_sub(1)
+                    .unwrap_or(u64::MAX);
+                return Ok(Some((0, last)));
+            }
+            FrameRange::Specific(numbers) => numbers.clone(),
+            FrameRange::KeyframesOnly => self.resolve_keyframe_numbers(video_metadata)?,
+            FrameRange::Segments(segments) => Self::resolve_segments(segments, video_metadata)?,
+        };
+        Ok(numbers
+            .iter()
+            .min()
+            .copied()
+            .zip(numbers.iter().max().copied()))
+    }
+
+    /// Decode every frame spanned by `range` and resample the sequence to
+    /// `100 / frame_delay` fps with FFmpeg's `minterpolate` filter.
+    ///
+    /// Backs [`GifOptions::motion_interpolation`].
+    #[cfg(feature = "gif")]
+    fn interpolated_gif_frames(
+        &mut self,
+        range: &FrameRange,
+        video_metadata: &VideoMetadata,
+        frame_delay: u16,
+        config: &ExtractOptions,
+    ) -> Result<Vec<DynamicImage>, UnbundleError> {
+        let Some((first, last)) = self.resolve_range_span(range, video_metadata)? else {
+            return Ok(Vec::new());
+        };
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let time_base = self
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?
+            .time_base();
+        let (source_width, source_height) = (video_metadata.width, video_metadata.height);
+        config.check_dimensions(source_width, source_height)?;
+        let (target_width, target_height) = config
+            .frame_output
+            .resolve_dimensions(source_width, source_height);
+        config.check_dimensions(target_width, target_height)?;
+        // Scale first: motion estimation costs grow with the pixel count.
+        let filter_spec = format!(
+            "scale={target_width}:{target_height}:flags=bilinear,minterpolate=fps=100/{}",
+            frame_delay.max(1)
+        );
+        log::debug!("Interpolating frames {first}..={last} with '{filter_spec}'");
+
+        let mut graph: Option<FilterGraph> = None;
+        let mut images = Vec::new();
+        let log_capture = crate::ffmpeg::LogCapture::start();
+        self.dispatch_range_raw(
+            FrameRange::Range(first, last),
+            video_metadata,
+            config,
+            &mut |_frame_number, frame| {
+                if graph.is_none() {
+                    graph = Some(build_filter_graph(
+                        frame.width(),
+                        frame.height(),
+                        frame.format(),
+                        time_base,
+                        &filter_spec,
+                        &["out"],
+                    )?);
+                }
+                let active = graph.as_mut().unwrap();
+                feed_filter_graph(active, Some(frame))?;
+                drain_filter_graph(active, config, &mut images)
+            },
+        )
+        .map_err(|error| log_capture.attach(error))?;
+
+        if let Some(active) = graph.as_mut() {
+            feed_filter_graph(active, None)?;
+            drain_filter_graph(active, config, &mut images)?;
+        }
+        Ok(images)
+    }
+
     /// Resolve a list of `(start, end)` time segments into sorted,
     /// deduplicated frame numbers.
     fn resolve_segments(
@@ -2117,10 +4516,15 @@ impl<'a> VideoHandle<'a> {
     /// with widely spaced numbers). For small ranges, sequential extraction is
     /// often faster due to per-thread file-open overhead.
     ///
+    /// Work runs on rayon's global pool unless
+    /// [`ExtractOptions::with_parallel_threads`] bounds it to a dedicated
+    /// pool for this call.
+    ///
     /// # Errors
     ///
     /// Returns [`UnbundleError::NoVideoStream`] if the file has no video
-    /// stream, or errors from individual worker threads.
+    /// stream, [`UnbundleError::ThreadPoolError`] if a dedicated pool cannot
+    /// be built, or errors from individual worker threads.
     ///
     /// # Example
     ///
@@ -2141,6 +4545,30 @@ impl<'a> VideoHandle<'a> {
         range: FrameRange,
         config: &ExtractOptions,
     ) -> Result<Vec<DynamicImage>, UnbundleError> {
+        Ok(self
+            .numbered_frames_parallel(range, config)?
+            .into_iter()
+            .map(|(_, frame_image)| frame_image)
+            .collect())
+    }
+
+    /// Extract frames in parallel, each paired with its frame number.
+    ///
+    /// Like [`frames_parallel`](VideoHandle::frames_parallel), but keeps the
+    /// number of every returned frame. Frames can be missing from the
+    /// result, for instance when [`ExtractOptions::with_frame_discard`]
+    /// drops them, so the position of a frame in the list does not identify
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`frames_parallel`](VideoHandle::frames_parallel).
+    #[cfg(feature = "rayon")]
+    pub fn numbered_frames_parallel(
+        &mut self,
+        range: FrameRange,
+        config: &ExtractOptions,
+    ) -> Result<Vec<(u64, DynamicImage)>, UnbundleError> {
         let video_metadata = self
             .unbundler
             .metadata
@@ -2149,20 +4577,17 @@ impl<'a> VideoHandle<'a> {
             .ok_or(UnbundleError::NoVideoStream)?
             .clone();
 
-        // Resolve the range into concrete frame numbers.
-        let frame_numbers = self.resolve_frame_numbers_for_iter(range, &video_metadata)?;
+        // Resolve the range into concrete frame numbers. Frames before the
+        // resume point are dropped here so no worker is started for them.
+        let mut frame_numbers = self.resolve_frame_numbers_for_iter(range, &video_metadata)?;
+        frame_numbers.retain(|&number| number >= config.resume_from);
 
-        let results = crate::rayon::parallel_extract_frames(
+        crate::rayon::parallel_extract_frames(
             &self.unbundler.source,
             &frame_numbers,
             &video_metadata,
             config,
-        )?;
-
-        Ok(results
-            .into_iter()
-            .map(|(_, frame_image)| frame_image)
-            .collect())
+        )
     }
 
     /// Estimate the total number of frames a [`FrameRange`] will produce.
@@ -2221,7 +4646,9 @@ impl<'a> VideoHandle<'a> {
     }
 
     /// Validate and dispatch a [`FrameRange`] to the appropriate processing
-    /// method, with cancellation support.
+    /// method, with cancellation support. Images get the configured
+    /// timecode overlay and are counted in the configured stats before
+    /// they reach `handler`.
     fn dispatch_range<F>(
         &mut self,
         range: FrameRange,
@@ -2229,6 +4656,37 @@ impl<'a> VideoHandle<'a> {
         config: &ExtractOptions,
         handler: &mut F,
     ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
+    {
+        let _timer = config.start_timer();
+        if config.frame_output.timecode_overlay.is_none() && config.stats.is_none() {
+            return self.dispatch_unannotated_range(range, video_metadata, config, handler);
+        }
+        let frames_per_second = video_metadata.frames_per_second;
+        self.dispatch_unannotated_range(
+            range,
+            video_metadata,
+            config,
+            &mut |frame_number, mut image| {
+                config.record_scaled();
+                config
+                    .frame_output
+                    .annotate(&mut image, frame_number, frames_per_second);
+                handler(frame_number, image)
+            },
+        )
+    }
+
+    /// [`dispatch_range`](Self::dispatch_range) without the timecode
+    /// overlay.
+    fn dispatch_unannotated_range<F>(
+        &mut self,
+        range: FrameRange,
+        video_metadata: &VideoMetadata,
+        config: &ExtractOptions,
+        handler: &mut F,
+    ) -> Result<(), UnbundleError>
     where
         F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
     {
@@ -2240,12 +4698,33 @@ impl<'a> VideoHandle<'a> {
                         end: format!("frame {end}"),
                     });
                 }
-                self.process_frame_range(start, end, video_metadata, config, handler)
+                self.process_frame_range(start, end, 1, video_metadata, config, handler)
             }
             FrameRange::Interval(step) => {
                 if step == 0 {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                if let Some(stride) = config.packet_stride {
+                    return self.process_keyframe_stride(
+                        stride,
+                        step,
+                        video_metadata,
+                        config,
+                        handler,
+                    );
+                }
+                if self.decodes_linearly(config) {
+                    // The frame count of a pipe or live stream is often
+                    // unknown, so decode to the end and keep every nth frame.
+                    return self.process_frame_range(
+                        0,
+                        u64::MAX,
+                        step,
+                        video_metadata,
+                        config,
+                        handler,
+                    );
+                }
                 let total = video_metadata.frame_count;
                 let numbers: Vec<u64> = (0..total).step_by(step as usize).collect();
                 self.process_specific_frames(&numbers, video_metadata, config, handler)
@@ -2265,12 +4744,13 @@ impl<'a> VideoHandle<'a> {
                     end_time,
                     video_metadata.frames_per_second,
                 );
-                self.process_frame_range(start_frame, end_frame, video_metadata, config, handler)
+                self.process_frame_range(start_frame, end_frame, 1, video_metadata, config, handler)
             }
             FrameRange::TimeInterval(interval) => {
                 if interval.is_zero() {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                self.ensure_finite()?;
                 let total_duration = self.unbundler.metadata.duration;
                 let mut numbers = Vec::new();
                 let mut current = Duration::ZERO;
@@ -2306,6 +4786,42 @@ impl<'a> VideoHandle<'a> {
         config: &ExtractOptions,
         handler: &mut F,
     ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage, FrameMetadata) -> Result<(), UnbundleError>,
+    {
+        let _timer = config.start_timer();
+        if config.frame_output.timecode_overlay.is_none() && config.stats.is_none() {
+            return self.dispatch_unannotated_range_with_info(
+                range,
+                video_metadata,
+                config,
+                handler,
+            );
+        }
+        let frames_per_second = video_metadata.frames_per_second;
+        self.dispatch_unannotated_range_with_info(
+            range,
+            video_metadata,
+            config,
+            &mut |frame_number, mut image, info| {
+                config.record_scaled();
+                config
+                    .frame_output
+                    .annotate(&mut image, frame_number, frames_per_second);
+                handler(frame_number, image, info)
+            },
+        )
+    }
+
+    /// [`dispatch_range_with_info`](Self::dispatch_range_with_info) without
+    /// the timecode overlay.
+    fn dispatch_unannotated_range_with_info<F>(
+        &mut self,
+        range: FrameRange,
+        video_metadata: &VideoMetadata,
+        config: &ExtractOptions,
+        handler: &mut F,
+    ) -> Result<(), UnbundleError>
     where
         F: FnMut(u64, DynamicImage, FrameMetadata) -> Result<(), UnbundleError>,
     {
@@ -2317,12 +4833,24 @@ impl<'a> VideoHandle<'a> {
                         end: format!("frame {end}"),
                     });
                 }
-                self.process_frame_range_with_info(start, end, video_metadata, config, handler)
+                self.process_frame_range_with_info(start, end, 1, video_metadata, config, handler)
             }
             FrameRange::Interval(step) => {
                 if step == 0 {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                if self.decodes_linearly(config) {
+                    // The frame count of a pipe or live stream is often
+                    // unknown, so decode to the end and keep every nth frame.
+                    return self.process_frame_range_with_info(
+                        0,
+                        u64::MAX,
+                        step,
+                        video_metadata,
+                        config,
+                        handler,
+                    );
+                }
                 let total = video_metadata.frame_count;
                 let numbers: Vec<u64> = (0..total).step_by(step as usize).collect();
                 self.process_specific_frames_and_metadata(&numbers, video_metadata, config, handler)
@@ -2345,6 +4873,7 @@ impl<'a> VideoHandle<'a> {
                 self.process_frame_range_with_info(
                     start_frame,
                     end_frame,
+                    1,
                     video_metadata,
                     config,
                     handler,
@@ -2354,6 +4883,7 @@ impl<'a> VideoHandle<'a> {
                 if interval.is_zero() {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                self.ensure_finite()?;
                 let total_duration = self.unbundler.metadata.duration;
                 let mut numbers = Vec::new();
                 let mut current = Duration::ZERO;
@@ -2391,6 +4921,11 @@ impl<'a> VideoHandle<'a> {
     where
         F: FnMut(u64, &VideoFrame) -> Result<(), UnbundleError>,
     {
+        let _timer = config.start_timer();
+        let handler = &mut |frame_number, frame: &VideoFrame| {
+            config.record_scaled();
+            handler(frame_number, frame)
+        };
         match range {
             FrameRange::Range(start, end) => {
                 if start > end {
@@ -2399,12 +4934,24 @@ impl<'a> VideoHandle<'a> {
                         end: format!("frame {end}"),
                     });
                 }
-                self.process_frame_range_raw(start, end, video_metadata, config, handler)
+                self.process_frame_range_raw(start, end, 1, video_metadata, config, handler)
             }
             FrameRange::Interval(step) => {
                 if step == 0 {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                if self.decodes_linearly(config) {
+                    // The frame count of a pipe or live stream is often
+                    // unknown, so decode to the end and keep every nth frame.
+                    return self.process_frame_range_raw(
+                        0,
+                        u64::MAX,
+                        step,
+                        video_metadata,
+                        config,
+                        handler,
+                    );
+                }
                 let total = video_metadata.frame_count;
                 let numbers: Vec<u64> = (0..total).step_by(step as usize).collect();
                 self.process_specific_frames_raw(&numbers, video_metadata, config, handler)
@@ -2427,6 +4974,7 @@ impl<'a> VideoHandle<'a> {
                 self.process_frame_range_raw(
                     start_frame,
                     end_frame,
+                    1,
                     video_metadata,
                     config,
                     handler,
@@ -2436,6 +4984,7 @@ impl<'a> VideoHandle<'a> {
                 if interval.is_zero() {
                     return Err(UnbundleError::InvalidInterval);
                 }
+                self.ensure_finite()?;
                 let total_duration = self.unbundler.metadata.duration;
                 let mut numbers = Vec::new();
                 let mut current = Duration::ZERO;
@@ -2468,6 +5017,7 @@ impl<'a> VideoHandle<'a> {
         &mut self,
         start: u64,
         end: u64,
+        step: u64,
         video_metadata: &VideoMetadata,
         config: &ExtractOptions,
         handler: &mut F,
@@ -2475,11 +5025,18 @@ impl<'a> VideoHandle<'a> {
     where
         F: FnMut(u64, DynamicImage, FrameMetadata) -> Result<(), UnbundleError>,
     {
+        // Frames are kept every `step` frames counted from the requested
+        // start, so a resume point does not shift the selection.
+        let origin = start;
+        let start = start.max(config.resume_from);
+        if start > end {
+            return Ok(());
+        }
+
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
 
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
         let frames_per_second = video_metadata.frames_per_second;
 
@@ -2491,7 +5048,8 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let mut scaler: Option<ScalingContext> = if hardware_active {
             None
@@ -2506,87 +5064,116 @@ impl<'a> VideoHandle<'a> {
                 ScalingFlags::BILINEAR,
             )?)
         };
+        if let Some(scaler) = scaler.as_mut() {
+            config.frame_output.apply_color_matrix(scaler);
+        }
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(start, frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
         let mut scaled_frame = VideoFrame::empty();
+        let mut presentation_order = PresentationOrder::new(config.reorder_frames);
+        let mut emit = |frame_number: u64, (image, info): (DynamicImage, FrameMetadata)| {
+            handler(frame_number, image, info)
+        };
 
         for (stream, packet) in self.unbundler.input_context.packets() {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return presentation_order.finish(&mut emit);
+            }
             if stream.index() != video_stream_index {
                 continue;
             }
 
-            decoder.send_packet(&packet)?;
+            let sent = decoder.send_packet(&packet).map_err(UnbundleError::from);
+            if config.skip_corrupt_frame(None, sent)?.is_none() {
+                continue;
+            }
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
-                if current_frame_number >= start && current_frame_number <= end {
+                if current_frame_number >= start
+                    && current_frame_number <= end
+                    && (current_frame_number - origin).is_multiple_of(step)
+                {
                     let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
+                    presentation_order.push(
+                        current_frame_number,
+                        (image, info),
+                        reorder_depth(&decoder),
+                        &mut emit,
                     )?;
-                    handler(current_frame_number, image, info)?;
                 }
 
                 if current_frame_number > end {
-                    return Ok(());
+                    return presentation_order.finish(&mut emit);
                 }
             }
         }
 
         decoder.send_eof()?;
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return presentation_order.finish(&mut emit);
+            }
             let pts = decoded_frame.pts().unwrap_or(0);
-            let current_frame_number =
-                crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
-
-            if current_frame_number >= start && current_frame_number <= end {
-                let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                let transferred = maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                ensure_scaler(
-                    &mut scaler,
-                    source,
-                    output_pixel,
-                    target_width,
-                    target_height,
-                )?;
-                scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                let image = convert_frame_to_image(
-                    &scaled_frame,
-                    target_width,
-                    target_height,
+            let current_frame_number =
+                crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
+
+            if current_frame_number >= start
+                && current_frame_number <= end
+                && (current_frame_number - origin).is_multiple_of(step)
+            {
+                let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
+                let image = decoded_frame_to_image(
+                    &decoded_frame,
+                    hardware_active,
+                    &mut scaler,
+                    &mut deinterlacer,
+                    &mut scaled_frame,
+                    output_pixel,
                     &config.frame_output,
+                    (target_width, target_height),
+                );
+                let Some(image) = config.skip_corrupt_frame(Some(current_frame_number), image)?
+                else {
+                    continue;
+                };
+                presentation_order.push(
+                    current_frame_number,
+                    (image, info),
+                    reorder_depth(&decoder),
+                    &mut emit,
                 )?;
-                handler(current_frame_number, image, info)?;
             }
 
             if current_frame_number > end {
@@ -2594,7 +5181,7 @@ impl<'a> VideoHandle<'a> {
             }
         }
 
-        Ok(())
+        presentation_order.finish(&mut emit)
     }
 
     /// Process frames at specific (possibly non-contiguous) frame numbers,
@@ -2614,16 +5201,19 @@ impl<'a> VideoHandle<'a> {
         }
 
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
 
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
         let frames_per_second = video_metadata.frames_per_second;
 
         let mut sorted_numbers = frame_numbers.to_vec();
         sorted_numbers.sort_unstable();
         sorted_numbers.dedup();
+        sorted_numbers.retain(|&number| number >= config.resume_from);
+        if sorted_numbers.is_empty() {
+            return Ok(());
+        }
 
         let stream = self
             .unbundler
@@ -2633,7 +5223,8 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let mut scaler: Option<ScalingContext> = if hardware_active {
             None
@@ -2648,14 +5239,16 @@ impl<'a> VideoHandle<'a> {
                 ScalingFlags::BILINEAR,
             )?)
         };
+        if let Some(scaler) = scaler.as_mut() {
+            config.frame_output.apply_color_matrix(scaler);
+        }
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(sorted_numbers[0], frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
         let mut target_index = 0;
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
         let mut scaled_frame = VideoFrame::empty();
 
@@ -2666,13 +5259,20 @@ impl<'a> VideoHandle<'a> {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
             if stream.index() != video_stream_index {
                 continue;
             }
 
-            decoder.send_packet(&packet)?;
+            let sent = decoder.send_packet(&packet).map_err(UnbundleError::from);
+            if config.skip_corrupt_frame(None, sent)?.is_none() {
+                continue;
+            }
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
@@ -2691,23 +5291,21 @@ impl<'a> VideoHandle<'a> {
                     && current_frame_number == sorted_numbers[target_index]
                 {
                     let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
-                    )?;
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
                     handler(current_frame_number, image, info)?;
                     target_index += 1;
                 }
@@ -2717,12 +5315,16 @@ impl<'a> VideoHandle<'a> {
         if target_index < sorted_numbers.len() {
             decoder.send_eof()?;
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
                 if config.is_cancelled() {
                     return Err(UnbundleError::Cancelled);
                 }
+                if budget_exhausted(deadline) {
+                    return Ok(());
+                }
 
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
@@ -2738,23 +5340,21 @@ impl<'a> VideoHandle<'a> {
                     && current_frame_number == sorted_numbers[target_index]
                 {
                     let info = build_frame_info(&decoded_frame, current_frame_number, time_base);
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
-                    )?;
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
                     handler(current_frame_number, image, info)?;
                     target_index += 1;
                 }
@@ -2769,6 +5369,7 @@ impl<'a> VideoHandle<'a> {
         &mut self,
         start: u64,
         end: u64,
+        step: u64,
         video_metadata: &VideoMetadata,
         config: &ExtractOptions,
         handler: &mut F,
@@ -2776,7 +5377,16 @@ impl<'a> VideoHandle<'a> {
     where
         F: FnMut(u64, &VideoFrame) -> Result<(), UnbundleError>,
     {
+        // Frames are kept every `step` frames counted from the requested
+        // start, so a resume point does not shift the selection.
+        let origin = start;
+        let start = start.max(config.resume_from);
+        if start > end {
+            return Ok(());
+        }
+
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
         let frames_per_second = video_metadata.frames_per_second;
 
         let stream = self
@@ -2787,20 +5397,23 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(start, frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
 
         for (stream, packet) in self.unbundler.input_context.packets() {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
             if stream.index() != video_stream_index {
                 continue;
             }
@@ -2808,13 +5421,21 @@ impl<'a> VideoHandle<'a> {
             decoder.send_packet(&packet)?;
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
-                if current_frame_number >= start && current_frame_number <= end {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
+                if current_frame_number >= start
+                    && current_frame_number <= end
+                    && (current_frame_number - origin).is_multiple_of(step)
+                {
+                    let transferred = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
+                        &config.frame_output,
+                    )?;
                     if let Some(raw_frame) = transferred.as_ref() {
                         handler(current_frame_number, raw_frame)?;
                     } else {
@@ -2830,16 +5451,28 @@ impl<'a> VideoHandle<'a> {
 
         decoder.send_eof()?;
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
 
             let pts = decoded_frame.pts().unwrap_or(0);
             let current_frame_number =
                 crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
-            if current_frame_number >= start && current_frame_number <= end {
-                let transferred = maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
+            if current_frame_number >= start
+                && current_frame_number <= end
+                && (current_frame_number - origin).is_multiple_of(step)
+            {
+                let transferred = prepare_source_frame(
+                    &decoded_frame,
+                    hardware_active,
+                    &mut deinterlacer,
+                    &config.frame_output,
+                )?;
                 if let Some(raw_frame) = transferred.as_ref() {
                     handler(current_frame_number, raw_frame)?;
                 } else {
@@ -2871,11 +5504,16 @@ impl<'a> VideoHandle<'a> {
         }
 
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
         let frames_per_second = video_metadata.frames_per_second;
 
         let mut sorted_numbers = frame_numbers.to_vec();
         sorted_numbers.sort_unstable();
         sorted_numbers.dedup();
+        sorted_numbers.retain(|&number| number >= config.resume_from);
+        if sorted_numbers.is_empty() {
+            return Ok(());
+        }
 
         let stream = self
             .unbundler
@@ -2885,15 +5523,15 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(sorted_numbers[0], frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
         let mut target_index = 0;
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
 
         for (stream, packet) in self.unbundler.input_context.packets() {
@@ -2903,6 +5541,9 @@ impl<'a> VideoHandle<'a> {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
             if stream.index() != video_stream_index {
                 continue;
             }
@@ -2910,6 +5551,7 @@ impl<'a> VideoHandle<'a> {
             decoder.send_packet(&packet)?;
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
@@ -2927,8 +5569,12 @@ impl<'a> VideoHandle<'a> {
                 if target_index < sorted_numbers.len()
                     && current_frame_number == sorted_numbers[target_index]
                 {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
+                    let transferred = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
+                        &config.frame_output,
+                    )?;
                     if let Some(raw_frame) = transferred.as_ref() {
                         handler(current_frame_number, raw_frame)?;
                     } else {
@@ -2942,12 +5588,16 @@ impl<'a> VideoHandle<'a> {
         if target_index < sorted_numbers.len() {
             decoder.send_eof()?;
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
                 if config.is_cancelled() {
                     return Err(UnbundleError::Cancelled);
                 }
+                if budget_exhausted(deadline) {
+                    return Ok(());
+                }
 
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
@@ -2962,8 +5612,12 @@ impl<'a> VideoHandle<'a> {
                 if target_index < sorted_numbers.len()
                     && current_frame_number == sorted_numbers[target_index]
                 {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
+                    let transferred = prepare_source_frame(
+                        &decoded_frame,
+                        hardware_active,
+                        &mut deinterlacer,
+                        &config.frame_output,
+                    )?;
                     if let Some(raw_frame) = transferred.as_ref() {
                         handler(current_frame_number, raw_frame)?;
                     } else {
@@ -2982,6 +5636,7 @@ impl<'a> VideoHandle<'a> {
         &mut self,
         start: u64,
         end: u64,
+        step: u64,
         video_metadata: &VideoMetadata,
         config: &ExtractOptions,
         handler: &mut F,
@@ -2989,7 +5644,16 @@ impl<'a> VideoHandle<'a> {
     where
         F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
     {
+        // Frames are kept every `step` frames counted from the requested
+        // start, so a resume point does not shift the selection.
+        let origin = start;
+        let start = start.max(config.resume_from);
+        if start > end {
+            return Ok(());
+        }
+
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
         log::debug!(
             "Processing frame range {}..={} (stream={})",
             start,
@@ -2997,9 +5661,7 @@ impl<'a> VideoHandle<'a> {
             video_stream_index
         );
 
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
         let frames_per_second = video_metadata.frames_per_second;
 
@@ -3011,7 +5673,8 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         // Defer scaler creation when hardware accel is active — the software pixel
         // format is only known after the first frame transfer.
@@ -3028,56 +5691,72 @@ impl<'a> VideoHandle<'a> {
                 ScalingFlags::BILINEAR,
             )?)
         };
+        if let Some(scaler) = scaler.as_mut() {
+            config.frame_output.apply_color_matrix(scaler);
+        }
 
         // Seek to start frame.
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(start, frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
         let mut scaled_frame = VideoFrame::empty();
+        let mut presentation_order = PresentationOrder::new(config.reorder_frames);
 
         for (stream, packet) in self.unbundler.input_context.packets() {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return presentation_order.finish(handler);
+            }
 
             if stream.index() != video_stream_index {
                 continue;
             }
 
-            decoder.send_packet(&packet)?;
+            let sent = decoder.send_packet(&packet).map_err(UnbundleError::from);
+            if config.skip_corrupt_frame(None, sent)?.is_none() {
+                continue;
+            }
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
                     crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
-                if current_frame_number >= start && current_frame_number <= end {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                if current_frame_number >= start
+                    && current_frame_number <= end
+                    && (current_frame_number - origin).is_multiple_of(step)
+                {
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
+                    presentation_order.push(
+                        current_frame_number,
+                        image,
+                        reorder_depth(&decoder),
+                        handler,
                     )?;
-                    handler(current_frame_number, image)?;
                 }
 
                 if current_frame_number > end {
-                    return Ok(());
+                    return presentation_order.finish(handler);
                 }
             }
         }
@@ -3085,32 +5764,42 @@ impl<'a> VideoHandle<'a> {
         // Flush the decoder.
         decoder.send_eof()?;
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
+            config.record_decoded();
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return presentation_order.finish(handler);
+            }
 
             let pts = decoded_frame.pts().unwrap_or(0);
             let current_frame_number =
                 crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
 
-            if current_frame_number >= start && current_frame_number <= end {
-                let transferred = maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                ensure_scaler(
+            if current_frame_number >= start
+                && current_frame_number <= end
+                && (current_frame_number - origin).is_multiple_of(step)
+            {
+                let image = decoded_frame_to_image(
+                    &decoded_frame,
+                    hardware_active,
                     &mut scaler,
-                    source,
+                    &mut deinterlacer,
+                    &mut scaled_frame,
                     output_pixel,
-                    target_width,
-                    target_height,
-                )?;
-                scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                let image = convert_frame_to_image(
-                    &scaled_frame,
-                    target_width,
-                    target_height,
                     &config.frame_output,
+                    (target_width, target_height),
+                );
+                let Some(image) = config.skip_corrupt_frame(Some(current_frame_number), image)?
+                else {
+                    continue;
+                };
+                presentation_order.push(
+                    current_frame_number,
+                    image,
+                    reorder_depth(&decoder),
+                    handler,
                 )?;
-                handler(current_frame_number, image)?;
             }
 
             if current_frame_number > end {
@@ -3118,6 +5807,182 @@ impl<'a> VideoHandle<'a> {
             }
         }
 
+        presentation_order.finish(handler)
+    }
+
+    /// Interval extraction from every `stride`th keyframe packet, backing
+    /// [`ExtractOptions::with_packet_stride`].
+    ///
+    /// Keyframes closer than `step` frames to the previously decoded one
+    /// are skipped as well, judged from the packet PTS before decoding.
+    fn process_keyframe_stride<F>(
+        &mut self,
+        stride: u64,
+        step: u64,
+        video_metadata: &VideoMetadata,
+        config: &ExtractOptions,
+        handler: &mut F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
+    {
+        let video_stream_index = self.resolve_video_stream_index()?;
+        let frames_per_second = video_metadata.frames_per_second;
+        let seek_timestamp = crate::conversion::frame_number_to_seek_timestamp(
+            config.resume_from,
+            frames_per_second,
+        );
+        self.seek_unless_linear(seek_timestamp, config)?;
+
+        // SAFETY: the stream belongs to the open input context and the
+        // demuxer reads `discard` when it next reads a packet. Not every
+        // demuxer honours NONKEY, so packets are filtered below as well.
+        let previous_discard = unsafe {
+            let stream = *(*self.unbundler.input_context.as_mut_ptr())
+                .streams
+                .add(video_stream_index);
+            std::mem::replace(&mut (*stream).discard, AVDiscard::AVDISCARD_NONKEY)
+        };
+        log::debug!(
+            "Sampling every {} keyframe(s) at least {} frames apart (stream={})",
+            stride,
+            step,
+            video_stream_index
+        );
+
+        let result = self.decode_keyframe_stride(
+            stride,
+            step,
+            video_stream_index,
+            video_metadata,
+            config,
+            handler,
+        );
+
+        // SAFETY: as above.
+        unsafe {
+            let stream = *(*self.unbundler.input_context.as_mut_ptr())
+                .streams
+                .add(video_stream_index);
+            (*stream).discard = previous_discard;
+        }
+        result
+    }
+
+    /// Demux and decode loop for
+    /// [`process_keyframe_stride`](Self::process_keyframe_stride).
+    fn decode_keyframe_stride<F>(
+        &mut self,
+        stride: u64,
+        step: u64,
+        video_stream_index: usize,
+        video_metadata: &VideoMetadata,
+        config: &ExtractOptions,
+        handler: &mut F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, DynamicImage) -> Result<(), UnbundleError>,
+    {
+        let deadline = config.budget_deadline();
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
+        let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
+        let frames_per_second = video_metadata.frames_per_second;
+
+        let stream = self
+            .unbundler
+            .input_context
+            .stream(video_stream_index)
+            .ok_or(UnbundleError::NoVideoStream)?;
+        let time_base = stream.time_base();
+        let decoder_context = CodecContext::from_parameters(stream.parameters())?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
+
+        let mut scaler: Option<ScalingContext> = if hardware_active {
+            None
+        } else {
+            Some(ScalingContext::get(
+                decoder.format(),
+                decoder.width(),
+                decoder.height(),
+                output_pixel,
+                target_width,
+                target_height,
+                ScalingFlags::BILINEAR,
+            )?)
+        };
+        if let Some(scaler) = scaler.as_mut() {
+            config.frame_output.apply_color_matrix(scaler);
+        }
+
+        let mut deinterlacer = Deinterlacer::default();
+        let mut decoded_frame = VideoFrame::empty();
+        let mut scaled_frame = VideoFrame::empty();
+        let mut keyframes_seen: u64 = 0;
+        let mut next_frame_number = config.resume_from;
+        let mut packets = self.unbundler.input_context.packets();
+        let mut at_end = false;
+
+        while !at_end {
+            if config.is_cancelled() {
+                return Err(UnbundleError::Cancelled);
+            }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
+
+            match packets.next() {
+                Some((stream, packet)) => {
+                    if stream.index() != video_stream_index || !packet.is_key() {
+                        continue;
+                    }
+                    keyframes_seen += 1;
+                    if !(keyframes_seen - 1).is_multiple_of(stride) {
+                        continue;
+                    }
+                    let frame_number = packet.pts().map(|pts| {
+                        crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second)
+                    });
+                    if frame_number.is_some_and(|number| number < next_frame_number) {
+                        continue;
+                    }
+                    let sent = decoder.send_packet(&packet).map_err(UnbundleError::from);
+                    if config.skip_corrupt_frame(None, sent)?.is_none() {
+                        continue;
+                    }
+                }
+                None => {
+                    decoder.send_eof()?;
+                    at_end = true;
+                }
+            }
+
+            while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
+                let pts = decoded_frame.pts().unwrap_or(0);
+                let frame_number =
+                    crate::conversion::pts_to_frame_number(pts, time_base, frames_per_second);
+                if frame_number < next_frame_number {
+                    continue;
+                }
+                let image = decoded_frame_to_image(
+                    &decoded_frame,
+                    hardware_active,
+                    &mut scaler,
+                    &mut deinterlacer,
+                    &mut scaled_frame,
+                    output_pixel,
+                    &config.frame_output,
+                    (target_width, target_height),
+                );
+                let Some(image) = config.skip_corrupt_frame(Some(frame_number), image)? else {
+                    continue;
+                };
+                next_frame_number = frame_number.saturating_add(step);
+                handler(frame_number, image)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -3140,15 +6005,14 @@ impl<'a> VideoHandle<'a> {
         }
 
         let video_stream_index = self.resolve_video_stream_index()?;
+        let deadline = config.budget_deadline();
         log::debug!(
             "Processing {} specific frames (stream={})",
             frame_numbers.len(),
             video_stream_index
         );
 
-        let (target_width, target_height) = config
-            .frame_output
-            .resolve_dimensions(video_metadata.width, video_metadata.height);
+        let (target_width, target_height) = resolve_target_dimensions(config, video_metadata)?;
         let output_pixel = config.frame_output.pixel_format.to_ffmpeg_pixel();
         let frames_per_second = video_metadata.frames_per_second;
 
@@ -3156,6 +6020,10 @@ impl<'a> VideoHandle<'a> {
         let mut sorted_numbers = frame_numbers.to_vec();
         sorted_numbers.sort_unstable();
         sorted_numbers.dedup();
+        sorted_numbers.retain(|&number| number >= config.resume_from);
+        if sorted_numbers.is_empty() {
+            return Ok(());
+        }
 
         let stream = self
             .unbundler
@@ -3165,7 +6033,8 @@ impl<'a> VideoHandle<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let (mut decoder, hardware_active) = create_video_decoder(decoder_context, config)?;
+        let (mut decoder, hardware_active) =
+            create_video_decoder(decoder_context, &stream, config)?;
 
         let mut scaler: Option<ScalingContext> = if hardware_active {
             None
@@ -3180,15 +6049,17 @@ impl<'a> VideoHandle<'a> {
                 ScalingFlags::BILINEAR,
             )?)
         };
+        if let Some(scaler) = scaler.as_mut() {
+            config.frame_output.apply_color_matrix(scaler);
+        }
 
         // Seek to the first requested frame.
         let seek_timestamp =
             crate::conversion::frame_number_to_seek_timestamp(sorted_numbers[0], frames_per_second);
-        self.unbundler
-            .input_context
-            .seek(seek_timestamp, ..seek_timestamp)?;
+        self.seek_unless_linear(seek_timestamp, config)?;
 
         let mut target_index = 0;
+        let mut deinterlacer = Deinterlacer::default();
         let mut decoded_frame = VideoFrame::empty();
         let mut scaled_frame = VideoFrame::empty();
 
@@ -3199,13 +6070,20 @@ impl<'a> VideoHandle<'a> {
             if config.is_cancelled() {
                 return Err(UnbundleError::Cancelled);
             }
+            if budget_exhausted(deadline) {
+                return Ok(());
+            }
             if stream.index() != video_stream_index {
                 continue;
             }
 
-            decoder.send_packet(&packet)?;
+            let sent = decoder.send_packet(&packet).map_err(UnbundleError::from);
+            if config.skip_corrupt_frame(None, sent)?.is_none() {
+                continue;
+            }
 
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
@@ -3225,23 +6103,21 @@ impl<'a> VideoHandle<'a> {
                 if target_index < sorted_numbers.len()
                     && current_frame_number == sorted_numbers[target_index]
                 {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
-                    )?;
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
                     handler(current_frame_number, image)?;
                     target_index += 1;
                 }
@@ -3252,6 +6128,7 @@ impl<'a> VideoHandle<'a> {
         if target_index < sorted_numbers.len() {
             decoder.send_eof()?;
             while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                config.record_decoded();
                 if target_index >= sorted_numbers.len() {
                     break;
                 }
@@ -3259,6 +6136,9 @@ impl<'a> VideoHandle<'a> {
                 if config.is_cancelled() {
                     return Err(UnbundleError::Cancelled);
                 }
+                if budget_exhausted(deadline) {
+                    return Ok(());
+                }
 
                 let pts = decoded_frame.pts().unwrap_or(0);
                 let current_frame_number =
@@ -3273,23 +6153,21 @@ impl<'a> VideoHandle<'a> {
                 if target_index < sorted_numbers.len()
                     && current_frame_number == sorted_numbers[target_index]
                 {
-                    let transferred =
-                        maybe_transfer_hardware_frame(&decoded_frame, hardware_active)?;
-                    let source = transferred.as_ref().unwrap_or(&decoded_frame);
-                    ensure_scaler(
+                    let image = decoded_frame_to_image(
+                        &decoded_frame,
+                        hardware_active,
                         &mut scaler,
-                        source,
+                        &mut deinterlacer,
+                        &mut scaled_frame,
                         output_pixel,
-                        target_width,
-                        target_height,
-                    )?;
-                    scaler.as_mut().unwrap().run(source, &mut scaled_frame)?;
-                    let image = convert_frame_to_image(
-                        &scaled_frame,
-                        target_width,
-                        target_height,
                         &config.frame_output,
-                    )?;
+                        (target_width, target_height),
+                    );
+                    let Some(image) =
+                        config.skip_corrupt_frame(Some(current_frame_number), image)?
+                    else {
+                        continue;
+                    };
                     handler(current_frame_number, image)?;
                     target_index += 1;
                 }
@@ -3299,17 +6177,30 @@ impl<'a> VideoHandle<'a> {
         Ok(())
     }
 
-    // ── Stream copy (lossless) helpers ──────────────────────────────
-
-    /// Copy the video stream verbatim to a file without decoding or
-    /// re-encoding. Container format is inferred from the file extension.
-    fn copy_stream_to_file(
+    // ── Stream copy (lossless) helpers ──────────────────────────────
+
+    /// Copy the video stream verbatim to a file without decoding or
+    /// re-encoding. Container format is inferred from the file extension.
+    fn copy_stream_to_file(
+        &mut self,
+        path: &Path,
+        start: Option<Duration>,
+        end: Option<Duration>,
+        config: Option<&ExtractOptions>,
+    ) -> Result<ExtractSummary, UnbundleError> {
+        crate::atomic_write::write_output(path, config, |path| {
+            self.write_stream_copy(path, start, end, config)
+        })
+    }
+
+    /// Write the stream copy straight to `path`.
+    fn write_stream_copy(
         &mut self,
         path: &Path,
         start: Option<Duration>,
         end: Option<Duration>,
         config: Option<&ExtractOptions>,
-    ) -> Result<(), UnbundleError> {
+    ) -> Result<ExtractSummary, UnbundleError> {
         let video_stream_index = self.resolve_video_stream_index()?;
         log::debug!(
             "Stream-copying video to file {:?} (stream={})",
@@ -3346,9 +6237,7 @@ impl<'a> VideoHandle<'a> {
 
         if let Some(start_time) = start {
             let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+            crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -3365,6 +6254,7 @@ impl<'a> VideoHandle<'a> {
         });
 
         let output_time_base = output_context.stream(0).unwrap().time_base();
+        let mut span = PacketSpan::default();
 
         for (stream, mut packet) in self.unbundler.input_context.packets() {
             if let Some(active_config) = config
@@ -3386,6 +6276,7 @@ impl<'a> VideoHandle<'a> {
             packet.set_stream(0);
             packet.rescale_ts(input_time_base, output_time_base);
             packet.set_position(-1);
+            span.record(&packet);
             packet
                 .write_interleaved(&mut output_context)
                 .map_err(|error| {
@@ -3405,7 +6296,7 @@ impl<'a> VideoHandle<'a> {
             UnbundleError::StreamCopyError(format!("Failed to write trailer: {error}"))
         })?;
 
-        Ok(())
+        Ok(span.summary(&mut output_context, output_time_base, None))
     }
 
     /// Copy the video stream verbatim to memory without decoding or
@@ -3434,9 +6325,7 @@ impl<'a> VideoHandle<'a> {
 
         if let Some(start_time) = start {
             let seek_timestamp = crate::conversion::duration_to_seek_timestamp(start_time);
-            self.unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp)?;
+            crate::unbundle::seek_input(&mut self.unbundler.input_context, seek_timestamp)?;
         }
 
         let end_stream_timestamp = end.map(|end_time| {
@@ -3602,14 +6491,89 @@ impl<'a> VideoHandle<'a> {
     }
 }
 
-/// Create a video decoder, optionally with hardware acceleration.
+/// Clamp a requested `lowres` level to what a decoder supports.
+///
+/// Most codecs report a `max_lowres` of zero, in which case decode-time
+/// downscaling is silently disabled.
+fn effective_lowres(requested: u8, decoder: Option<Codec>) -> u8 {
+    if requested == 0 {
+        return 0;
+    }
+    let supported = decoder
+        .map(|codec| codec.max_lowres().clamp(0, u8::MAX as i32) as u8)
+        .unwrap_or(0);
+    if supported < requested {
+        log::debug!("Decoder supports lowres up to {supported}, requested {requested}");
+    }
+    requested.min(supported)
+}
+
+/// Resolve output dimensions for a video stream.
+///
+/// When [`ExtractOptions::with_lowres`] is in effect and no explicit
+/// resolution was requested, frames are produced at the reduced decode
+/// size rather than being scaled back up to the stream's full size.
+///
+/// Both the stream's dimensions and the resolved output size are checked
+/// against [`ExtractOptions::with_max_dimensions`] so oversized frames are
+/// rejected before a scaler or frame buffer is allocated.
+fn resolve_target_dimensions(
+    config: &ExtractOptions,
+    video_metadata: &VideoMetadata,
+) -> Result<(u32, u32), UnbundleError> {
+    config.check_dimensions(video_metadata.width, video_metadata.height)?;
+
+    let lowres = effective_lowres(
+        config.lowres,
+        ffmpeg_next::decoder::find_by_name(&video_metadata.codec),
+    );
+    let width = video_metadata.width.div_ceil(1 << lowres);
+    let height = video_metadata.height.div_ceil(1 << lowres);
+    let (target_width, target_height) = config.frame_output.resolve_dimensions(width, height);
+
+    config.check_dimensions(target_width, target_height)?;
+    Ok((target_width, target_height))
+}
+
+/// Create a video decoder for `stream`, optionally with hardware
+/// acceleration.
 ///
 /// Returns `(decoder, hardware_active)` where `hardware_active` indicates
-/// whether hardware decoding was successfully initialised.
+/// whether hardware decoding was successfully initialised. Hardware
+/// decoding is skipped when the stream's alpha plane is wanted, since
+/// hardware decoders output formats without one.
 fn create_video_decoder(
-    codec_context: CodecContext,
-    #[allow(unused_variables)] config: &ExtractOptions,
+    mut codec_context: CodecContext,
+    stream: &Stream,
+    config: &ExtractOptions,
 ) -> Result<(VideoDecoder, bool), UnbundleError> {
+    let lowres = effective_lowres(
+        config.lowres,
+        ffmpeg_next::decoder::find(codec_context.id()),
+    );
+    if lowres > 0 {
+        // SAFETY: the context has not been opened yet; `lowres` is read by
+        // avcodec_open2 and the decoder.
+        unsafe {
+            (*codec_context.as_mut_ptr()).lowres = lowres as i32;
+        }
+    }
+
+    // SAFETY: as above, these fields are read when the decoder is opened.
+    unsafe {
+        let context = codec_context.as_mut_ptr();
+        (*context).thread_count = config.decoder_threads.min(i32::MAX as usize) as i32;
+        (*context).thread_type = config.decoder_thread_type.to_ffmpeg_flags();
+        (*context).skip_frame = config.frame_discard.to_ffmpeg();
+    }
+
+    let pixel_format = config.frame_output.pixel_format;
+    if pixel_format.has_alpha() && alpha_source(stream, &codec_context).is_some() {
+        log::debug!("Decoding in software to keep the alpha plane");
+        let decoder = open_software_decoder(codec_context, stream, pixel_format)?;
+        return Ok((decoder, false));
+    }
+
     #[cfg(feature = "hardware")]
     {
         let setup = crate::hardware_acceleration::try_create_hardware_decoder(
@@ -3625,6 +6589,70 @@ fn create_video_decoder(
     }
 }
 
+/// Where a stream keeps its alpha plane.
+enum AlphaSource {
+    /// In the pixel format, as ProRes 4444, PNG and QuickTime Animation
+    /// do. Every software decoder outputs it.
+    PixelFormat,
+    /// In a separate channel beside the colour planes, as VP8 and VP9 in
+    /// WebM do. FFmpeg's native decoders ignore it; the libvpx decoder
+    /// given here merges it into a YUVA frame.
+    SideChannel(Codec),
+}
+
+/// Find the alpha plane of `stream`, or `None` when it has none that can
+/// be decoded.
+fn alpha_source(stream: &Stream, codec_context: &CodecContext) -> Option<AlphaSource> {
+    // SAFETY: the context was filled from the stream parameters, so
+    // `pix_fmt` is a valid pixel format or `AV_PIX_FMT_NONE`.
+    let pixel = Pixel::from(unsafe { (*codec_context.as_ptr()).pix_fmt });
+    let format_has_alpha = pixel.descriptor().is_some_and(|descriptor| {
+        // SAFETY: descriptors are static tables owned by FFmpeg.
+        let flags = unsafe { (*descriptor.as_ptr()).flags };
+        flags & ffmpeg_sys_next::AV_PIX_FMT_FLAG_ALPHA as u64 != 0
+    });
+    if format_has_alpha {
+        return Some(AlphaSource::PixelFormat);
+    }
+
+    // The Matroska demuxer sets this tag for tracks with BlockAdditional
+    // alpha data.
+    if stream.metadata().get("alpha_mode") != Some("1") {
+        return None;
+    }
+    let decoder_name = match codec_context.id() {
+        Id::VP8 => "libvpx",
+        Id::VP9 => "libvpx-vp9",
+        _ => return None,
+    };
+    let codec = ffmpeg_next::decoder::find_by_name(decoder_name);
+    if codec.is_none() {
+        log::warn!("Stream has alpha, but FFmpeg was built without {decoder_name} to decode it");
+    }
+    codec.map(AlphaSource::SideChannel)
+}
+
+/// Open a software decoder for `stream`, choosing one that decodes the
+/// alpha plane when the stream has one and `pixel_format` can hold it.
+pub(crate) fn open_software_decoder(
+    codec_context: CodecContext,
+    stream: &Stream,
+    pixel_format: PixelFormat,
+) -> Result<VideoDecoder, UnbundleError> {
+    if !pixel_format.has_alpha() {
+        return Ok(codec_context.decoder().video()?);
+    }
+    match alpha_source(stream, &codec_context) {
+        Some(AlphaSource::SideChannel(codec)) => {
+            log::debug!("Decoding side-channel alpha with {}", codec.name());
+            // libvpx only reports YUVA once frames arrive; scalers are
+            // rebuilt from each frame's own format by `ensure_scaler`.
+            Ok(codec_context.decoder().open_as(codec)?.video()?)
+        }
+        Some(AlphaSource::PixelFormat) | None => Ok(codec_context.decoder().video()?),
+    }
+}
+
 /// If hardware decoding is active, transfer a decoded frame from GPU to
 /// system memory.  Returns `Some(software_frame)` on successful transfer,
 /// `None` when the frame is already in system memory or when hardware
@@ -3643,20 +6671,271 @@ fn maybe_transfer_hardware_frame(
     Ok(None)
 }
 
+/// Resolve the frame to scale or hand out for a decoded `frame`: hardware
+/// frames are downloaded to system memory, then deinterlaced if requested.
+///
+/// Returns `None` when `frame` can be used as is.
+fn prepare_source_frame(
+    frame: &VideoFrame,
+    hardware_active: bool,
+    deinterlacer: &mut Deinterlacer,
+    frame_output: &FrameOutputOptions,
+) -> Result<Option<VideoFrame>, UnbundleError> {
+    let transferred = maybe_transfer_hardware_frame(frame, hardware_active)?;
+    let deinterlaced = deinterlacer.run(transferred.as_ref().unwrap_or(frame), frame_output)?;
+    Ok(deinterlaced.or(transferred))
+}
+
+/// `bwdif` deinterlacer used by the frames of one extraction.
+///
+/// Each frame is sent once into a fresh graph, which is then closed so
+/// `bwdif` rebuilds the frame from its own two fields straight away
+/// instead of holding it back for a future neighbour. No state carries
+/// over between frames, so a frame reached by a seek comes out the same
+/// as one reached by decoding forward, and frames from before a seek or
+/// timestamp jump never leak into it. The pixel format is pinned to the
+/// input's so an existing scaler stays valid.
+#[derive(Default)]
+pub(crate) struct Deinterlacer {
+    /// The frame size and pixel format the last graph was built for, and
+    /// its filter description, reused while frames keep that shape.
+    filter_spec: Option<((u32, u32, Pixel), String)>,
+}
+
+impl Deinterlacer {
+    /// Deinterlace `frame` when [`FrameOutputOptions::deinterlace`] is set
+    /// and the frame is flagged as interlaced.
+    pub(crate) fn run(
+        &mut self,
+        frame: &VideoFrame,
+        frame_output: &FrameOutputOptions,
+    ) -> Result<Option<VideoFrame>, UnbundleError> {
+        if !frame_output.deinterlace || !frame.is_interlaced() {
+            return Ok(None);
+        }
+
+        let input = (frame.width(), frame.height(), frame.format());
+        if self
+            .filter_spec
+            .as_ref()
+            .is_none_or(|(built_for, _)| *built_for != input)
+        {
+            self.filter_spec = Some((input, deinterlace_filter_spec(frame)?));
+        }
+        let (_, filter_spec) = self.filter_spec.as_ref().unwrap();
+        let mut graph = build_filter_graph(
+            frame.width(),
+            frame.height(),
+            frame.format(),
+            Rational::new(1, 1),
+            filter_spec,
+            &["out"],
+        )?;
+
+        // At the end of input `bwdif` extrapolates a timestamp from the
+        // frame's own, so give an unstamped frame one.
+        let stamped;
+        let frame = if frame.pts().is_some() {
+            frame
+        } else {
+            let mut copy = frame.clone();
+            copy.set_pts(Some(0));
+            stamped = copy;
+            &stamped
+        };
+
+        let mut context = graph
+            .get("in")
+            .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'in' not found".to_string()))?;
+        // SAFETY: `context` is the graph's buffer source and `frame` is a
+        // valid frame; `av_buffersrc_write_frame` adds its own reference,
+        // leaving the caller's frame intact. The null frame that follows
+        // marks the end of input, which makes `bwdif` emit the frame.
+        let result = unsafe {
+            let result =
+                ffmpeg_sys_next::av_buffersrc_write_frame(context.as_mut_ptr(), frame.as_ptr());
+            if result < 0 {
+                result
+            } else {
+                ffmpeg_sys_next::av_buffersrc_write_frame(context.as_mut_ptr(), std::ptr::null())
+            }
+        };
+        if result < 0 {
+            return Err(UnbundleError::FilterGraphError(format!(
+                "Failed to feed deinterlacer: {}",
+                ffmpeg_next::Error::from(result)
+            )));
+        }
+
+        let mut sink = graph
+            .get("out")
+            .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'out' not found".to_string()))?;
+        let mut output = VideoFrame::empty();
+        match sink.sink().frame(&mut output) {
+            Ok(()) => Ok(Some(output)),
+            Err(error) => Err(UnbundleError::FilterGraphError(format!(
+                "Deinterlacer produced no frame: {error}"
+            ))),
+        }
+    }
+}
+
+/// The `bwdif` filter description for frames shaped like `frame`.
+fn deinterlace_filter_spec(frame: &VideoFrame) -> Result<String, UnbundleError> {
+    let pixel_name = frame
+        .format()
+        .descriptor()
+        .map(|descriptor| descriptor.name())
+        .ok_or_else(|| {
+            UnbundleError::FilterGraphError(format!(
+                "Cannot deinterlace frames in pixel format {:?}",
+                frame.format()
+            ))
+        })?;
+    Ok(format!(
+        "bwdif=mode=send_frame,format=pix_fmts={pixel_name}"
+    ))
+}
+
+/// Transfer a decoded frame off the GPU if needed, scale it to the target
+/// size and convert it to an image.
+fn decoded_frame_to_image(
+    decoded_frame: &VideoFrame,
+    hardware_active: bool,
+    scaler: &mut Option<ScalingContext>,
+    deinterlacer: &mut Deinterlacer,
+    scaled_frame: &mut VideoFrame,
+    output_pixel: Pixel,
+    frame_output: &FrameOutputOptions,
+    (target_width, target_height): (u32, u32),
+) -> Result<DynamicImage, UnbundleError> {
+    let transferred =
+        prepare_source_frame(decoded_frame, hardware_active, deinterlacer, frame_output)?;
+    let source = transferred.as_ref().unwrap_or(decoded_frame);
+    ensure_scaler(
+        scaler,
+        source,
+        output_pixel,
+        frame_output,
+        target_width,
+        target_height,
+    )?;
+    scaler.as_mut().unwrap().run(source, scaled_frame)?;
+    convert_frame_to_image(scaled_frame, target_width, target_height, frame_output)
+}
+
+/// Whether an extraction's time budget has run out.
+///
+/// Extraction loops stop and return what they have produced so far when
+/// this turns `true`, unlike cancellation, which is reported as an error.
+fn budget_exhausted(deadline: Option<Instant>) -> bool {
+    let exhausted = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    if exhausted {
+        log::debug!("Time budget exhausted; returning the frames decoded so far");
+    }
+    exhausted
+}
+
+/// Frames the decoder may still reorder: its B-frame delay as reported
+/// by FFmpeg, which can grow once decoding has started.
+fn reorder_depth(decoder: &VideoDecoder) -> usize {
+    // SAFETY: the decoder context is open; `has_b_frames` is a plain int.
+    let depth = unsafe { (*decoder.as_ptr()).has_b_frames };
+    usize::try_from(depth).unwrap_or(0)
+}
+
+/// Reorder buffer that releases converted frames to a handler in
+/// ascending frame-number order.
+///
+/// Holds at most the decoder's reorder depth of frames. When disabled
+/// (see [`ExtractOptions::with_frame_reordering`]) every frame is passed
+/// straight through.
+struct PresentationOrder<T> {
+    enabled: bool,
+    pending: BTreeMap<u64, T>,
+    last_emitted: Option<u64>,
+}
+
+impl<T> PresentationOrder<T> {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            pending: BTreeMap::new(),
+            last_emitted: None,
+        }
+    }
+
+    /// Queue a frame and release every frame beyond `depth` waiting ones.
+    fn push<F>(
+        &mut self,
+        frame_number: u64,
+        item: T,
+        depth: usize,
+        handler: &mut F,
+    ) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, T) -> Result<(), UnbundleError>,
+    {
+        if !self.enabled {
+            return handler(frame_number, item);
+        }
+        if self.last_emitted.is_some_and(|last| frame_number <= last)
+            || self.pending.contains_key(&frame_number)
+        {
+            log::debug!("Dropping frame {frame_number}: duplicate or out of presentation order");
+            return Ok(());
+        }
+        self.pending.insert(frame_number, item);
+        while self.pending.len() > depth {
+            self.emit_first(handler)?;
+        }
+        Ok(())
+    }
+
+    /// Release every frame still waiting.
+    fn finish<F>(&mut self, handler: &mut F) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, T) -> Result<(), UnbundleError>,
+    {
+        while !self.pending.is_empty() {
+            self.emit_first(handler)?;
+        }
+        Ok(())
+    }
+
+    fn emit_first<F>(&mut self, handler: &mut F) -> Result<(), UnbundleError>
+    where
+        F: FnMut(u64, T) -> Result<(), UnbundleError>,
+    {
+        if let Some((frame_number, item)) = self.pending.pop_first() {
+            self.last_emitted = Some(frame_number);
+            handler(frame_number, item)?;
+        }
+        Ok(())
+    }
+}
+
 /// Lazily initialise the software scaler on the first decoded frame.
 ///
 /// When hardware decoding is in use the decoder reports a hardware pixel
 /// format that the software scaler cannot process.  This function creates
-/// the scaler from the actual (transferred) frame dimensions and format.
-fn ensure_scaler(
+/// the scaler from the actual (transferred) frame dimensions and format,
+/// and rebuilds it when a frame differs from the one it was created for,
+/// as libvpx frames do once they carry a side-channel alpha plane.
+pub(crate) fn ensure_scaler(
     scaler: &mut Option<ScalingContext>,
     source: &VideoFrame,
     output_pixel: Pixel,
+    frame_output: &FrameOutputOptions,
     target_width: u32,
     target_height: u32,
 ) -> Result<(), UnbundleError> {
-    if scaler.is_none() {
-        *scaler = Some(ScalingContext::get(
+    let source_definition = (source.format(), source.width(), source.height());
+    if scaler.as_ref().is_none_or(|existing| {
+        let input = existing.input();
+        (input.format, input.width, input.height) != source_definition
+    }) {
+        let mut created = ScalingContext::get(
             source.format(),
             source.width(),
             source.height(),
@@ -3664,26 +6943,168 @@ fn ensure_scaler(
             target_width,
             target_height,
             ScalingFlags::BILINEAR,
-        )?);
+        )?;
+        frame_output.apply_color_matrix(&mut created);
+        *scaler = Some(created);
     }
     Ok(())
 }
 
 /// Apply a custom FFmpeg filter graph to a decoded frame.
 ///
-/// The graph is built as: `buffer -> <filter_spec> -> buffersink`.
-fn apply_filter_graph_to_frame(
+/// The graph is built as `buffer -> <filter_spec> -> buffersink`, with one
+/// buffersink per name in `outputs`. One filtered frame is returned per
+/// sink, in the same order.
+fn apply_filter_graph_outputs(
     frame: &VideoFrame,
     time_base: Rational,
     filter_spec: &str,
-) -> Result<VideoFrame, UnbundleError> {
+    outputs: &[&str],
+) -> Result<Vec<VideoFrame>, UnbundleError> {
+    let mut graph = build_filter_graph(
+        frame.width(),
+        frame.height(),
+        frame.format(),
+        time_base,
+        filter_spec,
+        outputs,
+    )?;
+
+    graph
+        .get("in")
+        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'in' not found".to_string()))?
+        .source()
+        .add(frame)
+        .map_err(|error| {
+            UnbundleError::FilterGraphError(format!("Failed to feed filter graph: {error}"))
+        })?;
+
+    let mut filtered_frames = Vec::with_capacity(outputs.len());
+    for &name in outputs {
+        let mut filtered_frame = VideoFrame::empty();
+        graph
+            .get(name)
+            .ok_or_else(|| UnbundleError::FilterGraphError(format!("Filter '{name}' not found")))?
+            .sink()
+            .frame(&mut filtered_frame)
+            .map_err(|error| {
+                UnbundleError::FilterGraphError(format!(
+                    "Filter graph did not produce a frame for output '{name}': {error}"
+                ))
+            })?;
+        filtered_frames.push(filtered_frame);
+    }
+
+    Ok(filtered_frames)
+}
+
+/// Push a frame into a filter graph's `in` source, or signal the end of
+/// input when `frame` is `None`.
+#[cfg(feature = "gif")]
+fn feed_filter_graph(
+    graph: &mut FilterGraph,
+    frame: Option<&VideoFrame>,
+) -> Result<(), UnbundleError> {
+    let mut context = graph
+        .get("in")
+        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'in' not found".to_string()))?;
+    let mut source = context.source();
+    match frame {
+        Some(frame) => source.add(frame),
+        None => source.flush(),
+    }
+    .map_err(|error| {
+        UnbundleError::FilterGraphError(format!("Failed to feed filter graph: {error}"))
+    })
+}
+
+/// Convert every frame currently available at a filter graph's `out` sink
+/// and append the images to `images`.
+#[cfg(feature = "gif")]
+fn drain_filter_graph(
+    graph: &mut FilterGraph,
+    config: &ExtractOptions,
+    images: &mut Vec<DynamicImage>,
+) -> Result<(), UnbundleError> {
+    let mut context = graph
+        .get("out")
+        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'out' not found".to_string()))?;
+    let mut sink = context.sink();
+    let mut filtered_frame = VideoFrame::empty();
+    loop {
+        match sink.frame(&mut filtered_frame) {
+            Ok(()) => images.push(convert_filtered_frame(&filtered_frame, config)?),
+            Err(ffmpeg_next::Error::Eof) => return Ok(()),
+            Err(ffmpeg_next::Error::Other { errno }) if errno == ffmpeg_next::error::EAGAIN => {
+                return Ok(());
+            }
+            Err(error) => {
+                return Err(UnbundleError::FilterGraphError(format!(
+                    "Failed to read filter graph output: {error}"
+                )));
+            }
+        }
+    }
+}
+
+/// Scale a filter graph output to the configured size and pixel format and
+/// convert it to an image.
+fn convert_filtered_frame(
+    filtered: &VideoFrame,
+    config: &ExtractOptions,
+) -> Result<DynamicImage, UnbundleError> {
+    let (target_width, target_height) = config
+        .frame_output
+        .resolve_dimensions(filtered.width(), filtered.height());
+    config.check_dimensions(target_width, target_height)?;
+
+    let mut scaler = ScalingContext::get(
+        filtered.format(),
+        filtered.width(),
+        filtered.height(),
+        config.frame_output.pixel_format.to_ffmpeg_pixel(),
+        target_width,
+        target_height,
+        ScalingFlags::BILINEAR,
+    )?;
+    config.frame_output.apply_color_matrix(&mut scaler);
+
+    let mut scaled_frame = VideoFrame::empty();
+    scaler.run(filtered, &mut scaled_frame)?;
+    convert_frame_to_image(
+        &scaled_frame,
+        target_width,
+        target_height,
+        &config.frame_output,
+    )
+}
+
+/// Build and configure a `buffer -> <filter_spec> -> buffersink` graph for
+/// input frames of the given size and pixel format.
+///
+/// One buffersink is created per entry in `outputs`, each named after the
+/// filter graph label it is linked to.
+fn build_filter_graph(
+    width: u32,
+    height: u32,
+    pixel: Pixel,
+    time_base: Rational,
+    filter_spec: &str,
+    outputs: &[&str],
+) -> Result<FilterGraph, UnbundleError> {
+    if outputs.is_empty() {
+        return Err(UnbundleError::FilterGraphError(
+            "At least one filter output is required".to_string(),
+        ));
+    }
+
     let mut graph = FilterGraph::new();
 
-    let pixel_format = AVPixelFormat::from(frame.format()) as i32;
+    let pixel_format = AVPixelFormat::from(pixel) as i32;
     let buffer_args = format!(
         "video_size={}x{}:pix_fmt={}:time_base={}/{}:pixel_aspect=1/1",
-        frame.width(),
-        frame.height(),
+        width,
+        height,
         pixel_format,
         time_base.numerator(),
         time_base.denominator(),
@@ -3701,73 +7122,192 @@ fn apply_filter_graph_to_frame(
             UnbundleError::FilterGraphError(format!("Failed to add buffer filter: {error}"))
         })?;
 
-    graph
-        .add(
-            &ffmpeg_next::filter::find("buffersink").ok_or_else(|| {
-                UnbundleError::FilterGraphError("FFmpeg 'buffersink' filter not found".to_string())
-            })?,
-            "out",
-            "",
-        )
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!("Failed to add buffersink filter: {error}"))
+    let buffersink = ffmpeg_next::filter::find("buffersink").ok_or_else(|| {
+        UnbundleError::FilterGraphError("FFmpeg 'buffersink' filter not found".to_string())
+    })?;
+    for &name in outputs {
+        if name == "in" {
+            return Err(UnbundleError::FilterGraphError(
+                "Filter output name 'in' is reserved for the source".to_string(),
+            ));
+        }
+        graph.add(&buffersink, name, "").map_err(|error| {
+            UnbundleError::FilterGraphError(format!(
+                "Failed to add buffersink filter '{name}': {error}"
+            ))
         })?;
+    }
 
-    graph
-        .output("in", 0)
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!("Filter graph output error: {error}"))
-        })?
-        .input("out", 0)
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!("Filter graph input error: {error}"))
-        })?
-        .parse(filter_spec)
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!("Filter graph parse error: {error}"))
-        })?;
+    parse_filter_spec(&mut graph, filter_spec, outputs)?;
 
     graph.validate().map_err(|error| {
         UnbundleError::FilterGraphError(format!("Filter graph validation error: {error}"))
     })?;
 
-    graph
-        .get("in")
-        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'in' not found".to_string()))?
-        .source()
-        .add(frame)
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!("Failed to feed filter graph: {error}"))
-        })?;
+    Ok(graph)
+}
 
-    let mut filtered_frame = VideoFrame::empty();
-    graph
+/// Parse `filter_spec` into `graph`, linking its open input to the `in`
+/// source and its open outputs to the buffersinks named in `outputs`.
+///
+/// The `AVFilterInOut` lists are built by hand because `ffmpeg_next`'s
+/// parser builder can only chain two entries per list.
+fn parse_filter_spec(
+    graph: &mut FilterGraph,
+    filter_spec: &str,
+    outputs: &[&str],
+) -> Result<(), UnbundleError> {
+    let nul_error =
+        |what: &str| UnbundleError::FilterGraphError(format!("{what} must not contain NUL bytes"));
+    let spec = CString::new(filter_spec).map_err(|_| nul_error("Filter specification"))?;
+
+    // Resolve every filter context before allocating, so the only failure
+    // after this point is an allocation failure.
+    let mut endpoints = Vec::with_capacity(outputs.len() + 1);
+    for &name in std::iter::once(&"in").chain(outputs) {
+        let label = CString::new(name).map_err(|_| nul_error("Filter output names"))?;
+        // SAFETY: the context pointer stays valid for as long as the graph.
+        let context = unsafe {
+            graph
+                .get(name)
+                .ok_or_else(|| {
+                    UnbundleError::FilterGraphError(format!("Filter '{name}' not found"))
+                })?
+                .as_mut_ptr()
+        };
+        endpoints.push((label, context));
+    }
+
+    // SAFETY: each entry is freshly allocated and owned by one of the two
+    // lists, which are freed with `avfilter_inout_free` on every path.
+    unsafe {
+        let mut graph_outputs: *mut ffmpeg_sys_next::AVFilterInOut = std::ptr::null_mut();
+        let mut graph_inputs: *mut ffmpeg_sys_next::AVFilterInOut = std::ptr::null_mut();
+
+        // Push in reverse so the sink list keeps the order of `outputs`.
+        for (index, (label, context)) in endpoints.iter().enumerate().rev() {
+            let entry = ffmpeg_sys_next::avfilter_inout_alloc();
+            if entry.is_null() {
+                ffmpeg_sys_next::avfilter_inout_free(&mut graph_outputs);
+                ffmpeg_sys_next::avfilter_inout_free(&mut graph_inputs);
+                return Err(UnbundleError::FilterGraphError(
+                    "Failed to allocate filter graph endpoints".to_string(),
+                ));
+            }
+            (*entry).name = ffmpeg_sys_next::av_strdup(label.as_ptr());
+            (*entry).filter_ctx = *context;
+            (*entry).pad_idx = 0;
+            let list = if index == 0 {
+                &mut graph_outputs
+            } else {
+                &mut graph_inputs
+            };
+            (*entry).next = *list;
+            *list = entry;
+        }
+
+        let result = ffmpeg_sys_next::avfilter_graph_parse_ptr(
+            graph.as_mut_ptr(),
+            spec.as_ptr(),
+            &mut graph_inputs,
+            &mut graph_outputs,
+            std::ptr::null_mut(),
+        );
+        ffmpeg_sys_next::avfilter_inout_free(&mut graph_outputs);
+        ffmpeg_sys_next::avfilter_inout_free(&mut graph_inputs);
+
+        if result < 0 {
+            return Err(UnbundleError::FilterGraphError(format!(
+                "Filter graph parse error: {}",
+                ffmpeg_next::Error::from(result)
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the frame size and pixel format a filter graph would produce for
+/// input frames of the given size and format, without feeding it a frame.
+fn probe_filter_graph_output(
+    width: u32,
+    height: u32,
+    pixel: Pixel,
+    time_base: Rational,
+    filter_spec: &str,
+) -> Result<(u32, u32, Pixel), UnbundleError> {
+    let mut graph = build_filter_graph(width, height, pixel, time_base, filter_spec, &["out"])?;
+    let sink = graph
         .get("out")
-        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'out' not found".to_string()))?
-        .sink()
-        .frame(&mut filtered_frame)
-        .map_err(|error| {
-            UnbundleError::FilterGraphError(format!(
-                "Filter graph did not produce an output frame: {error}"
-            ))
-        })?;
+        .ok_or_else(|| UnbundleError::FilterGraphError("Filter 'out' not found".to_string()))?;
+
+    // SAFETY: the graph has been configured by `validate`, so the sink's
+    // negotiated output properties are set; the context outlives the calls.
+    let (output_width, output_height, output_format) = unsafe {
+        let context = sink.as_ptr();
+        (
+            ffmpeg_sys_next::av_buffersink_get_w(context),
+            ffmpeg_sys_next::av_buffersink_get_h(context),
+            ffmpeg_sys_next::av_buffersink_get_format(context),
+        )
+    };
+    if output_width <= 0 || output_height <= 0 || output_format < 0 {
+        return Err(UnbundleError::FilterGraphError(
+            "Filter graph did not negotiate an output format".to_string(),
+        ));
+    }
+
+    let output_pixel = pixel_from_raw(output_format).ok_or_else(|| {
+        UnbundleError::FilterGraphError(format!(
+            "Filter graph negotiated unsupported pixel format {output_format}"
+        ))
+    })?;
+    Ok((output_width as u32, output_height as u32, output_pixel))
+}
 
-    Ok(filtered_frame)
+/// The [`Pixel`] for a raw `AVPixelFormat` value, or `None` if FFmpeg has
+/// no such format.
+///
+/// The value is looked up by name rather than cast, so a format newer than
+/// the bindings never becomes an invalid enum value.
+fn pixel_from_raw(format: i32) -> Option<Pixel> {
+    let index = usize::try_from(format).ok()?;
+    // SAFETY: FFmpeg's descriptor table is static and indexed by pixel
+    // format; `av_pix_fmt_desc_next` returns null after its last entry,
+    // and every descriptor has a static, NUL-terminated name.
+    let name = unsafe {
+        let mut descriptor = ffmpeg_sys_next::av_pix_fmt_desc_next(std::ptr::null());
+        for _ in 0..index {
+            if descriptor.is_null() {
+                return None;
+            }
+            descriptor = ffmpeg_sys_next::av_pix_fmt_desc_next(descriptor);
+        }
+        if descriptor.is_null() {
+            return None;
+        }
+        std::ffi::CStr::from_ptr((*descriptor).name)
+    };
+    name.to_str().ok()?.parse().ok()
 }
 
 /// Convert a scaled video frame to an [`image::DynamicImage`].
 ///
-/// Supports RGB24, RGBA, and GRAY8 output depending on the
-/// [`FrameOutputOptions`].
-fn convert_frame_to_image(
+/// Supports RGB24, RGBA, GRAY8, BGR24, and BGRA output depending on the
+/// [`FrameOutputOptions`]. BGR layouts are wrapped in the RGB image types
+/// with their bytes left in BGR order.
+pub(crate) fn convert_frame_to_image(
     frame: &VideoFrame,
     width: u32,
     height: u32,
     output_config: &FrameOutputOptions,
 ) -> Result<DynamicImage, UnbundleError> {
     match output_config.pixel_format {
-        PixelFormat::Rgb8 => {
-            let buffer = crate::conversion::frame_to_buffer(frame, width, height, 3);
+        PixelFormat::Rgb8 | PixelFormat::Bgr8 => {
+            let mut buffer = crate::conversion::frame_to_buffer(frame, width, height, 3);
+            if output_config.auto_levels {
+                stretch_levels(&mut buffer, 3);
+            }
             let rgb_image = RgbImage::from_raw(width, height, buffer).ok_or_else(|| {
                 UnbundleError::VideoDecodeError(
                     "Failed to construct RGB image from decoded frame data".to_string(),
@@ -3775,8 +7315,11 @@ fn convert_frame_to_image(
             })?;
             Ok(DynamicImage::ImageRgb8(rgb_image))
         }
-        PixelFormat::Rgba8 => {
-            let buffer = crate::conversion::frame_to_buffer(frame, width, height, 4);
+        PixelFormat::Rgba8 | PixelFormat::Bgra8 => {
+            let mut buffer = crate::conversion::frame_to_buffer(frame, width, height, 4);
+            if output_config.auto_levels {
+                stretch_levels(&mut buffer, 4);
+            }
             let rgba_image = RgbaImage::from_raw(width, height, buffer).ok_or_else(|| {
                 UnbundleError::VideoDecodeError(
                     "Failed to construct RGBA image from decoded frame data".to_string(),
@@ -3785,7 +7328,10 @@ fn convert_frame_to_image(
             Ok(DynamicImage::ImageRgba8(rgba_image))
         }
         PixelFormat::Gray8 => {
-            let buffer = crate::conversion::frame_to_buffer(frame, width, height, 1);
+            let mut buffer = crate::conversion::frame_to_buffer(frame, width, height, 1);
+            if output_config.auto_levels {
+                stretch_levels(&mut buffer, 1);
+            }
             let gray_image = GrayImage::from_raw(width, height, buffer).ok_or_else(|| {
                 UnbundleError::VideoDecodeError(
                     "Failed to construct grayscale image from decoded frame data".to_string(),
@@ -3796,8 +7342,62 @@ fn convert_frame_to_image(
     }
 }
 
+/// Width frames are scaled to for colour sampling when the caller sets no
+/// resolution.
+const COLOR_SAMPLE_WIDTH: u32 = 64;
+
+/// Mean red, green, and blue of an image, rounded down.
+fn average_color(image: &RgbImage) -> [u8; 3] {
+    let pixel_count = u64::from(image.width()) * u64::from(image.height());
+    if pixel_count == 0 {
+        return [0; 3];
+    }
+
+    let mut sums = [0u64; 3];
+    for pixel in image.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += u64::from(channel);
+        }
+    }
+    sums.map(|sum| (sum / pixel_count) as u8)
+}
+
+/// Linearly stretch packed 8-bit samples so the darkest colour sample
+/// becomes 0 and the brightest becomes 255.
+///
+/// With four channels the fourth is treated as alpha and left untouched.
+fn stretch_levels(buffer: &mut [u8], channels: usize) {
+    let colour_channels = if channels == 4 { 3 } else { channels };
+    let colour_samples = || {
+        buffer
+            .chunks_exact(channels)
+            .flat_map(|pixel| pixel[..colour_channels].iter().copied())
+    };
+    let low = colour_samples().min().unwrap_or(0);
+    let high = colour_samples().max().unwrap_or(0);
+    if high <= low || (low == 0 && high == u8::MAX) {
+        return;
+    }
+
+    let span = u32::from(high - low);
+    let mut lookup = [0u8; 256];
+    for (value, entry) in lookup.iter_mut().enumerate() {
+        let clamped = (value as u8).clamp(low, high);
+        *entry = ((u32::from(clamped - low) * 255 + span / 2) / span) as u8;
+    }
+    for pixel in buffer.chunks_exact_mut(channels) {
+        for sample in &mut pixel[..colour_channels] {
+            *sample = lookup[*sample as usize];
+        }
+    }
+}
+
 /// Build a [`FrameMetadata`] from a decoded video frame.
-fn build_frame_info(frame: &VideoFrame, frame_number: u64, time_base: Rational) -> FrameMetadata {
+pub(crate) fn build_frame_info(
+    frame: &VideoFrame,
+    frame_number: u64,
+    time_base: Rational,
+) -> FrameMetadata {
     let pts = frame.pts();
     let timestamp_seconds = crate::conversion::pts_to_seconds(pts.unwrap_or(0), time_base);
     let timestamp = Duration::from_secs_f64(timestamp_seconds.max(0.0));
@@ -3811,6 +7411,19 @@ fn build_frame_info(frame: &VideoFrame, frame_number: u64, time_base: Rational)
     }
 }
 
+/// Render a [`FrameMetadata`] as the pretty-printed JSON written next to
+/// frames saved by [`VideoHandle::save_frames_with_sidecar`].
+fn frame_sidecar_json(info: &FrameMetadata) -> String {
+    let sidecar = serde_json::json!({
+        "frame_number": info.frame_number,
+        "pts": info.pts,
+        "timestamp_seconds": info.timestamp.as_secs_f64(),
+        "is_keyframe": info.is_keyframe,
+        "frame_type": format!("{:?}", info.frame_type),
+    });
+    format!("{sidecar:#}")
+}
+
 /// Convert FFmpeg's [`PictureType`] to our public [`FrameType`] enum.
 fn picture_type_to_frame_type(ptype: PictureType) -> FrameType {
     match ptype {
@@ -23,17 +23,19 @@
 //! ```
 
 use ffmpeg_next::{
-    Error as FfmpegError, Packet, Rational,
-    codec::context::Context as CodecContext,
-    decoder::Video as VideoDecoder,
-    frame::Video as VideoFrame,
-    software::scaling::{Context as ScalingContext, Flags as ScalingFlags},
+    Error as FfmpegError, Packet, Rational, codec::context::Context as CodecContext,
+    decoder::Video as VideoDecoder, format::Pixel, frame::Video as VideoFrame,
+    software::scaling::Context as ScalingContext,
 };
-use image::{DynamicImage, GrayImage, RgbImage, RgbaImage};
+use image::DynamicImage;
 
-use crate::configuration::{FrameOutputOptions, PixelFormat};
+use crate::configuration::{DEFAULT_MAX_DIMENSION, FrameOutputOptions, check_dimensions};
 use crate::error::UnbundleError;
 use crate::unbundle::MediaFile;
+use crate::video::{
+    Deinterlacer, FrameMetadata, build_frame_info, convert_frame_to_image, ensure_scaler,
+    open_software_decoder,
+};
 
 /// A lazy iterator over decoded video frames.
 ///
@@ -46,7 +48,10 @@ use crate::unbundle::MediaFile;
 pub struct FrameIterator<'a> {
     unbundler: &'a mut MediaFile,
     decoder: VideoDecoder,
-    scaler: ScalingContext,
+    /// Built from the first decoded frame, whose format can differ from
+    /// what the decoder reports before decoding.
+    scaler: Option<ScalingContext>,
+    output_pixel: Pixel,
     video_stream_index: usize,
     /// Sorted, deduplicated frame numbers to yield.
     target_frames: Vec<u64>,
@@ -57,6 +62,7 @@ pub struct FrameIterator<'a> {
     output_config: FrameOutputOptions,
     target_width: u32,
     target_height: u32,
+    deinterlacer: Deinterlacer,
     decoded_frame: VideoFrame,
     scaled_frame: VideoFrame,
     eof_sent: bool,
@@ -91,8 +97,12 @@ impl<'a> FrameIterator<'a> {
             .ok_or(UnbundleError::NoVideoStream)?;
 
         let frames_per_second = video_metadata.frames_per_second;
+        // Iterators take no `ExtractOptions`, so apply the default cap.
+        let max_dimensions = (DEFAULT_MAX_DIMENSION, DEFAULT_MAX_DIMENSION);
+        check_dimensions(video_metadata.width, video_metadata.height, max_dimensions)?;
         let (target_width, target_height) =
             output_config.resolve_dimensions(video_metadata.width, video_metadata.height);
+        check_dimensions(target_width, target_height, max_dimensions)?;
         let output_pixel = output_config.pixel_format.to_ffmpeg_pixel();
 
         let stream = unbundler
@@ -102,31 +112,27 @@ impl<'a> FrameIterator<'a> {
         let time_base = stream.time_base();
         let codec_parameters = stream.parameters();
         let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-        let decoder = decoder_context.decoder().video()?;
-
-        let scaler = ScalingContext::get(
-            decoder.format(),
-            decoder.width(),
-            decoder.height(),
-            output_pixel,
-            target_width,
-            target_height,
-            ScalingFlags::BILINEAR,
-        )?;
+        let decoder = open_software_decoder(decoder_context, &stream, output_config.pixel_format)?;
 
         // Seek to the first requested frame.
         if let Some(&first) = frame_numbers.first() {
             let seek_timestamp =
                 crate::conversion::frame_number_to_seek_timestamp(first, frames_per_second);
-            let _ = unbundler
-                .input_context
-                .seek(seek_timestamp, ..seek_timestamp);
+            // Other seek failures are tolerated and decoding starts from the
+            // current position, but a non-seekable input would silently
+            // yield the wrong frames.
+            if let Err(UnbundleError::NotSeekable) =
+                crate::unbundle::seek_input(&mut unbundler.input_context, seek_timestamp)
+            {
+                return Err(UnbundleError::NotSeekable);
+            }
         }
 
         Ok(Self {
             unbundler,
             decoder,
-            scaler,
+            scaler: None,
+            output_pixel,
             video_stream_index,
             target_frames: frame_numbers,
             target_index: 0,
@@ -135,6 +141,7 @@ impl<'a> FrameIterator<'a> {
             output_config,
             target_width,
             target_height,
+            deinterlacer: Deinterlacer::default(),
             decoded_frame: VideoFrame::empty(),
             scaled_frame: VideoFrame::empty(),
             eof_sent: false,
@@ -144,44 +151,29 @@ impl<'a> FrameIterator<'a> {
 
     /// Scale and convert the current `decoded_frame` to a `DynamicImage`.
     fn convert_current_frame(&mut self) -> Result<DynamicImage, UnbundleError> {
+        let deinterlaced = self
+            .deinterlacer
+            .run(&self.decoded_frame, &self.output_config)?;
+        let source = deinterlaced.as_ref().unwrap_or(&self.decoded_frame);
+        ensure_scaler(
+            &mut self.scaler,
+            source,
+            self.output_pixel,
+            &self.output_config,
+            self.target_width,
+            self.target_height,
+        )?;
         self.scaler
-            .run(&self.decoded_frame, &mut self.scaled_frame)?;
-
-        let width = self.target_width;
-        let height = self.target_height;
+            .as_mut()
+            .unwrap()
+            .run(source, &mut self.scaled_frame)?;
 
-        match self.output_config.pixel_format {
-            PixelFormat::Rgb8 => {
-                let buffer =
-                    crate::conversion::frame_to_buffer(&self.scaled_frame, width, height, 3);
-                let rgb_image = RgbImage::from_raw(width, height, buffer).ok_or_else(|| {
-                    UnbundleError::VideoDecodeError(
-                        "Failed to construct RGB image from decoded frame data".to_string(),
-                    )
-                })?;
-                Ok(DynamicImage::ImageRgb8(rgb_image))
-            }
-            PixelFormat::Rgba8 => {
-                let buffer =
-                    crate::conversion::frame_to_buffer(&self.scaled_frame, width, height, 4);
-                let rgba_image = RgbaImage::from_raw(width, height, buffer).ok_or_else(|| {
-                    UnbundleError::VideoDecodeError(
-                        "Failed to construct RGBA image from decoded frame data".to_string(),
-                    )
-                })?;
-                Ok(DynamicImage::ImageRgba8(rgba_image))
-            }
-            PixelFormat::Gray8 => {
-                let buffer =
-                    crate::conversion::frame_to_buffer(&self.scaled_frame, width, height, 1);
-                let gray_image = GrayImage::from_raw(width, height, buffer).ok_or_else(|| {
-                    UnbundleError::VideoDecodeError(
-                        "Failed to construct grayscale image from decoded frame data".to_string(),
-                    )
-                })?;
-                Ok(DynamicImage::ImageLuma8(gray_image))
-            }
-        }
+        convert_frame_to_image(
+            &self.scaled_frame,
+            self.target_width,
+            self.target_height,
+            &self.output_config,
+        )
     }
 }
 
@@ -189,6 +181,17 @@ impl Iterator for FrameIterator<'_> {
     type Item = Result<(u64, DynamicImage), UnbundleError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_metadata()
+            .map(|result| result.map(|(image, info)| (info.frame_number, image)))
+    }
+}
+
+impl FrameIterator<'_> {
+    /// Decode up to the next requested frame and return it with its
+    /// [`FrameMetadata`].
+    fn next_with_metadata(
+        &mut self,
+    ) -> Option<Result<(DynamicImage, FrameMetadata), UnbundleError>> {
         if self.done || self.target_index >= self.target_frames.len() {
             return None;
         }
@@ -218,9 +221,13 @@ impl Iterator for FrameIterator<'_> {
                 if current_frame == self.target_frames[self.target_index] {
                     match self.convert_current_frame() {
                         Ok(image) => {
-                            let frame_number = current_frame;
+                            let info = build_frame_info(
+                                &self.decoded_frame,
+                                current_frame,
+                                self.time_base,
+                            );
                             self.target_index += 1;
-                            return Some(Ok((frame_number, image)));
+                            return Some(Ok((image, info)));
                         }
                         Err(e) => {
                             self.done = true;
@@ -265,3 +272,29 @@ impl Iterator for FrameIterator<'_> {
         }
     }
 }
+
+/// A lazy iterator over decoded video frames and their [`FrameMetadata`].
+///
+/// Yields the same frames as [`FrameIterator`], paired with the keyframe
+/// flag, picture type, and timestamp that
+/// [`frames_and_metadata`](crate::VideoHandle::frames_and_metadata)
+/// reports.
+///
+/// Created via [`VideoHandle::frame_iter_with_metadata`](crate::VideoHandle).
+pub struct FrameMetadataIterator<'a> {
+    inner: FrameIterator<'a>,
+}
+
+impl<'a> FrameMetadataIterator<'a> {
+    pub(crate) fn new(inner: FrameIterator<'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Iterator for FrameMetadataIterator<'_> {
+    type Item = Result<(DynamicImage, FrameMetadata), UnbundleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_with_metadata()
+    }
+}
@@ -19,15 +19,21 @@
 
 use std::time::Duration;
 
-use ffmpeg_next::codec::context::Context as CodecContext;
+use ffmpeg_next::codec::{Parameters, context::Context as CodecContext};
+use ffmpeg_next::decoder::Audio as AudioDecoder;
 use ffmpeg_next::format::{Sample, sample::Type as SampleType};
 use ffmpeg_next::frame::Audio as AudioFrame;
 use ffmpeg_next::software::resampling::Context as ResamplingContext;
 use ffmpeg_next::{ChannelLayout, Rational};
 
+use crate::configuration::ExtractOptions;
 use crate::error::UnbundleError;
+use crate::progress::{OperationType, ProgressTracker};
 use crate::unbundle::MediaFile;
 
+/// Audio decoded at each probe position when sampling a waveform.
+const PROBE_WINDOW: Duration = Duration::from_millis(250);
+
 /// Configuration for waveform generation.
 #[derive(Debug, Clone)]
 pub struct WaveformOptions {
@@ -37,6 +43,13 @@ pub struct WaveformOptions {
     pub start: Option<Duration>,
     /// Optional end time to limit the range.
     pub end: Option<Duration>,
+    /// Sample the track instead of decoding all of it: seek to every
+    /// `n`-th bin, decode a short window there and interpolate the bins in
+    /// between. `None` decodes every sample. Default: `None`.
+    ///
+    /// See [`downsample_stride`](WaveformOptions::downsample_stride) for
+    /// the accuracy this trades away.
+    pub downsample_stride: Option<usize>,
 }
 
 impl Default for WaveformOptions {
@@ -45,6 +58,7 @@ impl Default for WaveformOptions {
             bins: 800,
             start: None,
             end: None,
+            downsample_stride: None,
         }
     }
 }
@@ -93,6 +107,32 @@ impl WaveformOptions {
     pub fn with_end(self, end: Duration) -> Self {
         self.end(end)
     }
+
+    /// Build a quick, approximate waveform by sampling the track.
+    ///
+    /// Instead of decoding every sample, the generator seeks to the start
+    /// of every `stride`-th bin (and the last bin), decodes up to 250 ms
+    /// there, and fills the bins in between by linear interpolation. A
+    /// 3-hour track rendered into 800 bins then decodes a few minutes of
+    /// audio at most, and a stride of 4 cuts that to under one.
+    ///
+    /// The result is an overview, not a measurement: a probed bin's
+    /// min/max/RMS describe only its first 250 ms, so short peaks
+    /// elsewhere in the bin are missed, and interpolated bins only follow
+    /// the trend between their neighbours. When bins are so short that
+    /// probing would decode most of the range anyway, the full decode is
+    /// used instead. Zero is treated as one (probe every bin).
+    pub fn downsample_stride(mut self, stride: usize) -> Self {
+        self.downsample_stride = Some(stride.max(1));
+        self
+    }
+
+    /// Build a quick, approximate waveform by sampling the track.
+    ///
+    /// Alias for [`downsample_stride`](WaveformOptions::downsample_stride).
+    pub fn with_downsample_stride(self, stride: usize) -> Self {
+        self.downsample_stride(stride)
+    }
 }
 
 /// A single waveform bin containing amplitude statistics.
@@ -115,7 +155,9 @@ pub struct WaveformData {
     pub duration: Duration,
     /// The sample rate of the decoded audio.
     pub sample_rate: u32,
-    /// Total number of mono samples decoded.
+    /// Total number of mono samples decoded. With
+    /// [`downsample_stride`](WaveformOptions::downsample_stride) this
+    /// counts only the probed windows.
     pub total_samples: u64,
 }
 
@@ -124,53 +166,377 @@ pub(crate) fn generate_waveform_impl(
     unbundler: &mut MediaFile,
     audio_stream_index: usize,
     config: &WaveformOptions,
+    extract_config: Option<&ExtractOptions>,
 ) -> Result<WaveformData, UnbundleError> {
     log::debug!(
         "Generating waveform (stream={}, bins={})",
         audio_stream_index,
         config.bins
     );
+    if let Some(stride) = config.downsample_stride
+        && let Some(waveform) = sampled_waveform(
+            unbundler,
+            audio_stream_index,
+            config,
+            stride,
+            extract_config,
+        )?
+    {
+        return Ok(waveform);
+    }
+    let (all_samples, sample_rate) = decode_mono_samples(
+        unbundler,
+        audio_stream_index,
+        config.start,
+        config.end,
+        extract_config,
+    )?;
+
+    let total_samples = all_samples.len() as u64;
+    let duration = Duration::from_secs_f64(total_samples as f64 / sample_rate as f64);
+
+    // Bucket into bins.
+    let bin_count = config.bins.max(1);
+    let samples_per_bin = (all_samples.len() as f64 / bin_count as f64).ceil() as usize;
+
+    let mut bins: Vec<WaveformBin> = all_samples
+        .chunks(samples_per_bin.max(1))
+        .map(bin_statistics)
+        .collect();
+
+    // Pad to exactly bin_count if the last chunks were short.
+    while bins.len() < bin_count {
+        bins.push(WaveformBin {
+            min: 0.0,
+            max: 0.0,
+            rms: 0.0,
+        });
+    }
+
+    Ok(WaveformData {
+        bins,
+        duration,
+        sample_rate,
+        total_samples,
+    })
+}
+
+/// Min, max and RMS of a run of samples. Empty runs give a silent bin.
+fn bin_statistics(samples: &[f32]) -> WaveformBin {
+    if samples.is_empty() {
+        return WaveformBin {
+            min: 0.0,
+            max: 0.0,
+            rms: 0.0,
+        };
+    }
+
+    let mut min_value = f32::INFINITY;
+    let mut max_value = f32::NEG_INFINITY;
+    let mut sum_squared = 0.0_f64;
+    for &sample in samples {
+        if sample < min_value {
+            min_value = sample;
+        }
+        if sample > max_value {
+            max_value = sample;
+        }
+        sum_squared += (sample as f64) * (sample as f64);
+    }
+
+    WaveformBin {
+        min: min_value,
+        max: max_value,
+        rms: (sum_squared / samples.len() as f64).sqrt() as f32,
+    }
+}
+
+/// Build a waveform from short windows decoded at every `stride`-th bin,
+/// interpolating the rest.
+///
+/// Returns `Ok(None)` when the windows would cover at least half of the
+/// range, so the caller decodes everything instead.
+fn sampled_waveform(
+    unbundler: &mut MediaFile,
+    audio_stream_index: usize,
+    config: &WaveformOptions,
+    stride: usize,
+    extract_config: Option<&ExtractOptions>,
+) -> Result<Option<WaveformData>, UnbundleError> {
+    let range_start = config.start.unwrap_or_default();
+    let range_end = config
+        .end
+        .unwrap_or(unbundler.metadata.duration)
+        .max(range_start);
+    let range = range_end - range_start;
+    let invalid_range = || UnbundleError::InvalidRange {
+        start: format!("{range_start:?}"),
+        end: format!("{range_end:?}"),
+    };
+    let bin_count = config.bins.max(1);
+    let bin_duration = range / u32::try_from(bin_count).map_err(|_| invalid_range())?;
+    let probe_duration = PROBE_WINDOW.min(bin_duration);
+
+    let mut probed_bins: Vec<usize> = (0..bin_count).step_by(stride.max(1)).collect();
+    if probed_bins.last() != Some(&(bin_count - 1)) {
+        probed_bins.push(bin_count - 1);
+    }
+    // Probing reads about twice the window per probe; sample only when
+    // that is well short of decoding the whole range.
+    let probed_duration = u32::try_from(probed_bins.len())
+        .ok()
+        .and_then(|probe_count| probe_duration.checked_mul(probe_count))
+        .and_then(|duration| duration.checked_mul(2))
+        .ok_or_else(invalid_range)?;
+    if probe_duration.is_zero() || probed_duration >= range {
+        return Ok(None);
+    }
+    log::debug!(
+        "Sampling waveform: {} probes of {:?}",
+        probed_bins.len(),
+        probe_duration
+    );
+
     let stream = unbundler
         .input_context
         .stream(audio_stream_index)
         .ok_or(UnbundleError::NoAudioStream)?;
-
     let time_base: Rational = stream.time_base();
-    let codec_parameters = stream.parameters();
-    let decoder_context = CodecContext::from_parameters(codec_parameters)?;
-    let mut decoder = decoder_context.decoder().audio().map_err(|e| {
-        UnbundleError::WaveformDecodeError(format!("Failed to create audio decoder: {e}"))
-    })?;
-
+    let mut decoder = open_audio_decoder(stream.parameters())?;
     let sample_rate = decoder.rate();
+    let probe_samples =
+        ((probe_duration.as_secs_f64() * sample_rate as f64).ceil() as usize).max(1);
+
+    let mut tracker = extract_config.map(|active_config| {
+        ProgressTracker::new(
+            active_config.progress.clone(),
+            OperationType::WaveformGeneration,
+            Some(probed_bins.len() as u64),
+            active_config.batch_size,
+        )
+    });
 
-    // Set up resampler: convert to mono f32.
-    let mut resampler = ResamplingContext::get(
-        decoder.format(),
-        decoder.channel_layout(),
-        sample_rate,
-        Sample::F32(SampleType::Packed),
-        ChannelLayout::MONO,
+    let mut decoded_frame = AudioFrame::empty();
+    let mut resampled_frame = AudioFrame::empty();
+    let mut frame_samples: Vec<f32> = Vec::new();
+    let mut probes: Vec<(usize, WaveformBin)> = Vec::with_capacity(probed_bins.len());
+    let mut total_samples: u64 = 0;
+
+    for (probe_number, &bin_index) in probed_bins.iter().enumerate() {
+        let target = u32::try_from(bin_index)
+            .ok()
+            .and_then(|index| bin_duration.checked_mul(index))
+            .and_then(|offset| range_start.checked_add(offset))
+            .ok_or_else(invalid_range)?;
+        crate::unbundle::seek_input(
+            &mut unbundler.input_context,
+            crate::conversion::duration_to_seek_timestamp(target),
+        )?;
+        // Drop state from the previous probe so its samples cannot leak
+        // into this one.
+        decoder.flush();
+        let mut resampler = mono_resampler(&decoder)?;
+
+        let mut window: Vec<f32> = Vec::with_capacity(probe_samples);
+        for (stream, packet) in unbundler.input_context.packets() {
+            if let Some(active_config) = extract_config
+                && active_config.is_cancelled()
+            {
+                return Err(UnbundleError::Cancelled);
+            }
+            if stream.index() != audio_stream_index {
+                continue;
+            }
+
+            decoder.send_packet(&packet).map_err(|e| {
+                UnbundleError::WaveformDecodeError(format!("Audio decode error: {e}"))
+            })?;
+            while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                frame_samples.clear();
+                append_mono_samples(
+                    &mut resampler,
+                    &decoded_frame,
+                    &mut resampled_frame,
+                    &mut frame_samples,
+                )?;
+
+                // The seek lands on the packet before the target; drop the
+                // samples that precede it.
+                let frame_start = decoded_frame
+                    .pts()
+                    .map(|pts| crate::conversion::pts_to_seconds(pts, time_base))
+                    .unwrap_or_else(|| target.as_secs_f64());
+                let lead = ((target.as_secs_f64() - frame_start) * sample_rate as f64).max(0.0);
+                let skip = (lead as usize).min(frame_samples.len());
+                window.extend_from_slice(&frame_samples[skip..]);
+            }
+            if window.len() >= probe_samples {
+                break;
+            }
+        }
+        window.truncate(probe_samples);
+        total_samples += window.len() as u64;
+        probes.push((bin_index, bin_statistics(&window)));
+
+        if let Some(active_tracker) = tracker.as_mut() {
+            active_tracker.advance_to(probe_number as u64 + 1, Some(target));
+        }
+    }
+
+    if let Some(active_tracker) = tracker.as_mut() {
+        active_tracker.finish();
+    }
+
+    Ok(Some(WaveformData {
+        bins: interpolate_bins(&probes, bin_count),
+        duration: range,
         sample_rate,
-    )
-    .map_err(|e| UnbundleError::WaveformDecodeError(format!("Failed to create resampler: {e}")))?;
+        total_samples,
+    }))
+}
+
+/// Fill `bin_count` bins from probed `(index, bin)` pairs in ascending
+/// order, interpolating linearly between neighbouring probes.
+fn interpolate_bins(probes: &[(usize, WaveformBin)], bin_count: usize) -> Vec<WaveformBin> {
+    let mut bins = Vec::with_capacity(bin_count);
+    for pair in probes.windows(2) {
+        let ((left_index, left), (right_index, right)) = (pair[0], pair[1]);
+        let span = (right_index - left_index) as f32;
+        for index in left_index..right_index {
+            let weight = (index - left_index) as f32 / span;
+            let mix = |a: f32, b: f32| a + (b - a) * weight;
+            bins.push(WaveformBin {
+                min: mix(left.min, right.min),
+                max: mix(left.max, right.max),
+                rms: mix(left.rms, right.rms),
+            });
+        }
+    }
+    if let Some(&(_, last)) = probes.last() {
+        bins.push(last);
+    }
+    bins.resize(
+        bin_count,
+        WaveformBin {
+            min: 0.0,
+            max: 0.0,
+            rms: 0.0,
+        },
+    );
+    bins
+}
+
+/// Decode audio to mono f32 and compute the RMS of consecutive windows of
+/// `1 / samples_per_second` seconds.
+pub(crate) fn envelope_impl(
+    unbundler: &mut MediaFile,
+    audio_stream_index: usize,
+    samples_per_second: f64,
+) -> Result<Vec<f32>, UnbundleError> {
+    // Windows shorter than one sample would come out empty.
+    let stream_rate = unbundler
+        .metadata
+        .audio_tracks
+        .iter()
+        .flatten()
+        .find(|track| track.stream_index == audio_stream_index)
+        .map(|track| f64::from(track.sample_rate))
+        .filter(|&rate| rate > 0.0);
+    if !samples_per_second.is_finite()
+        || samples_per_second <= 0.0
+        || stream_rate.is_some_and(|rate| samples_per_second > rate)
+    {
+        return Err(UnbundleError::InvalidInterval);
+    }
+    log::debug!(
+        "Computing envelope (stream={}, rate={})",
+        audio_stream_index,
+        samples_per_second
+    );
+    let (all_samples, sample_rate) =
+        decode_mono_samples(unbundler, audio_stream_index, None, None, None)?;
+
+    // Window edges are computed from the window index rather than by
+    // accumulating a fractional step, so a rate that does not divide the
+    // sample rate (e.g. 29.97 Hz) does not drift over a long file.
+    let samples_per_window = sample_rate as f64 / samples_per_second;
+    let window_count = (all_samples.len() as f64 / samples_per_window).ceil() as usize;
+    let window_edge = |index: usize| {
+        ((index as f64 * samples_per_window).round() as usize).min(all_samples.len())
+    };
+
+    let envelope = (0..window_count)
+        .map(|index| {
+            let window = &all_samples[window_edge(index)..window_edge(index + 1)];
+            if window.is_empty() {
+                return 0.0;
+            }
+            let sum_squared: f64 = window.iter().map(|&s| (s as f64) * (s as f64)).sum();
+            (sum_squared / window.len() as f64).sqrt() as f32
+        })
+        .collect();
+
+    Ok(envelope)
+}
+
+/// Decode the audio stream to mono f32 samples at its native rate,
+/// optionally limited to `start..end`.
+///
+/// When `config` is given, progress is reported in decoded samples against
+/// the expected sample count of the range, and cancellation is checked
+/// before each packet.
+fn decode_mono_samples(
+    unbundler: &mut MediaFile,
+    audio_stream_index: usize,
+    start: Option<Duration>,
+    end: Option<Duration>,
+    config: Option<&ExtractOptions>,
+) -> Result<(Vec<f32>, u32), UnbundleError> {
+    let stream = unbundler
+        .input_context
+        .stream(audio_stream_index)
+        .ok_or(UnbundleError::NoAudioStream)?;
+
+    let time_base: Rational = stream.time_base();
+    let mut decoder = open_audio_decoder(stream.parameters())?;
+    let mut resampler = mono_resampler(&decoder)?;
+    let sample_rate = decoder.rate();
 
     // Compute time-range boundaries in stream time base.
-    let start_pts: Option<i64> = config.start.map(|duration| {
+    let start_pts: Option<i64> = start.map(|duration| {
         (duration.as_secs_f64() * time_base.denominator() as f64
             / time_base.numerator().max(1) as f64) as i64
     });
-    let end_pts: Option<i64> = config.end.map(|duration| {
+    let end_pts: Option<i64> = end.map(|duration| {
         (duration.as_secs_f64() * time_base.denominator() as f64
             / time_base.numerator().max(1) as f64) as i64
     });
 
+    let range_end = end.unwrap_or(unbundler.metadata.duration);
+    let expected_samples = (range_end
+        .saturating_sub(start.unwrap_or_default())
+        .as_secs_f64()
+        * sample_rate as f64) as u64;
+    let mut tracker = config.map(|active_config| {
+        ProgressTracker::new(
+            active_config.progress.clone(),
+            OperationType::WaveformGeneration,
+            Some(expected_samples),
+            active_config.batch_size,
+        )
+    });
+
     // Collect all mono f32 samples.
     let mut all_samples: Vec<f32> = Vec::new();
     let mut decoded_frame = AudioFrame::empty();
     let mut resampled_frame = AudioFrame::empty();
 
     for (stream, packet) in unbundler.input_context.packets() {
+        if let Some(active_config) = config
+            && active_config.is_cancelled()
+        {
+            return Err(UnbundleError::Cancelled);
+        }
         if stream.index() != audio_stream_index {
             continue;
         }
@@ -200,75 +566,77 @@ pub(crate) fn generate_waveform_impl(
             .map_err(|e| UnbundleError::WaveformDecodeError(format!("Audio decode error: {e}")))?;
 
         while decoder.receive_frame(&mut decoded_frame).is_ok() {
-            let delay = resampler
-                .run(&decoded_frame, &mut resampled_frame)
-                .map_err(|e| UnbundleError::WaveformDecodeError(format!("Resample error: {e}")))?;
-
-            let data = resampled_frame.data(0);
-            let sample_count = resampled_frame.samples();
-            let float_samples: &[f32] =
-                unsafe { std::slice::from_raw_parts(data.as_ptr() as *const f32, sample_count) };
-            all_samples.extend_from_slice(float_samples);
-
-            if delay.is_some() {
-                // Flush the remaining samples from the resampler.
-                let flush_frame = AudioFrame::empty();
-                if resampler.run(&flush_frame, &mut resampled_frame).is_ok() {
-                    let data = resampled_frame.data(0);
-                    let flush_sample_count = resampled_frame.samples();
-                    let flush_samples: &[f32] = unsafe {
-                        std::slice::from_raw_parts(data.as_ptr() as *const f32, flush_sample_count)
-                    };
-                    all_samples.extend_from_slice(flush_samples);
-                }
+            append_mono_samples(
+                &mut resampler,
+                &decoded_frame,
+                &mut resampled_frame,
+                &mut all_samples,
+            )?;
+
+            if let Some(active_tracker) = tracker.as_mut() {
+                let position = all_samples.len() as u64;
+                let offset = Duration::from_secs_f64(position as f64 / sample_rate as f64);
+                active_tracker.advance_to(position, Some(start.unwrap_or_default() + offset));
             }
         }
     }
 
-    let total_samples = all_samples.len() as u64;
-    let duration = Duration::from_secs_f64(total_samples as f64 / sample_rate as f64);
+    if let Some(active_tracker) = tracker.as_mut() {
+        active_tracker.finish();
+    }
 
-    // Bucket into bins.
-    let bin_count = config.bins.max(1);
-    let samples_per_bin = (all_samples.len() as f64 / bin_count as f64).ceil() as usize;
+    Ok((all_samples, sample_rate))
+}
 
-    let mut bins = Vec::with_capacity(bin_count);
-    for chunk in all_samples.chunks(samples_per_bin.max(1)) {
-        let mut min_value = f32::INFINITY;
-        let mut max_value = f32::NEG_INFINITY;
-        let mut sum_squared = 0.0_f64;
-
-        for &sample in chunk {
-            if sample < min_value {
-                min_value = sample;
-            }
-            if sample > max_value {
-                max_value = sample;
-            }
-            sum_squared += (sample as f64) * (sample as f64);
-        }
+/// Create a decoder for the stream.
+fn open_audio_decoder(codec_parameters: Parameters) -> Result<AudioDecoder, UnbundleError> {
+    let decoder_context = CodecContext::from_parameters(codec_parameters)?;
+    decoder_context.decoder().audio().map_err(|e| {
+        UnbundleError::WaveformDecodeError(format!("Failed to create audio decoder: {e}"))
+    })
+}
 
-        let rms = (sum_squared / chunk.len() as f64).sqrt() as f32;
-        bins.push(WaveformBin {
-            min: min_value,
-            max: max_value,
-            rms,
-        });
-    }
+/// Create a resampler from `decoder`'s output to mono f32 at the same rate.
+fn mono_resampler(decoder: &AudioDecoder) -> Result<ResamplingContext, UnbundleError> {
+    ResamplingContext::get(
+        decoder.format(),
+        decoder.channel_layout(),
+        decoder.rate(),
+        Sample::F32(SampleType::Packed),
+        ChannelLayout::MONO,
+        decoder.rate(),
+    )
+    .map_err(|e| UnbundleError::WaveformDecodeError(format!("Failed to create resampler: {e}")))
+}
 
-    // Pad to exactly bin_count if the last chunks were short.
-    while bins.len() < bin_count {
-        bins.push(WaveformBin {
-            min: 0.0,
-            max: 0.0,
-            rms: 0.0,
-        });
+/// Resample a decoded frame to mono f32 and append it to `samples`.
+fn append_mono_samples(
+    resampler: &mut ResamplingContext,
+    decoded_frame: &AudioFrame,
+    resampled_frame: &mut AudioFrame,
+    samples: &mut Vec<f32>,
+) -> Result<(), UnbundleError> {
+    let delay = resampler
+        .run(decoded_frame, resampled_frame)
+        .map_err(|e| UnbundleError::WaveformDecodeError(format!("Resample error: {e}")))?;
+
+    let data = resampled_frame.data(0);
+    let sample_count = resampled_frame.samples();
+    let float_samples: &[f32] =
+        unsafe { std::slice::from_raw_parts(data.as_ptr() as *const f32, sample_count) };
+    samples.extend_from_slice(float_samples);
+
+    if delay.is_some() {
+        // Flush the remaining samples from the resampler.
+        let flush_frame = AudioFrame::empty();
+        if resampler.run(&flush_frame, resampled_frame).is_ok() {
+            let data = resampled_frame.data(0);
+            let flush_sample_count = resampled_frame.samples();
+            let flush_samples: &[f32] = unsafe {
+                std::slice::from_raw_parts(data.as_ptr() as *const f32, flush_sample_count)
+            };
+            samples.extend_from_slice(flush_samples);
+        }
     }
-
-    Ok(WaveformData {
-        bins,
-        duration,
-        sample_rate,
-        total_samples,
-    })
+    Ok(())
 }
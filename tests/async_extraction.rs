@@ -5,11 +5,20 @@
 
 #![cfg(feature = "async")]
 
-use std::path::Path;
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use image::DynamicImage;
 use tokio_stream::StreamExt;
-use unbundle::{ExtractOptions, FrameRange, FrameStream, MediaFile};
+use unbundle::{
+    ExtractOptions, FrameRange, FrameStream, MediaFile, ProgressCallback, ProgressInfo,
+};
 
 const SAMPLE_VIDEO: &str = "tests/fixtures/sample_video.mp4";
 const AUDIO_ONLY: &str = "tests/fixtures/sample_audio_only.mp4";
@@ -127,6 +136,37 @@ async fn audio_future_extracts_wav() {
     );
 }
 
+#[tokio::test]
+async fn audio_stream_chunks_match_buffered_extraction() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let buffered = unbundler
+        .audio()
+        .extract_async(unbundle::AudioFormat::Aac, ExtractOptions::new())
+        .unwrap()
+        .await
+        .unwrap();
+
+    let mut stream = unbundler
+        .audio()
+        .extract_stream(unbundle::AudioFormat::Aac, ExtractOptions::new())
+        .unwrap();
+    let mut streamed = Vec::new();
+    let mut chunk_count = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.unwrap();
+        assert!(!chunk.is_empty() && chunk.len() <= 64 * 1024);
+        streamed.extend_from_slice(&chunk);
+        chunk_count += 1;
+    }
+
+    assert!(chunk_count >= buffered.len().div_ceil(64 * 1024));
+    assert_eq!(streamed, buffered, "ADTS output needs no header fix-up");
+}
+
 #[tokio::test]
 async fn stream_from_open_url_source_input() {
     if skip_unless(SAMPLE_VIDEO) {
@@ -167,3 +207,45 @@ async fn audio_future_from_open_url_source_input() {
     assert!(!audio_bytes.is_empty(), "expected non-empty WAV data");
     assert_eq!(&audio_bytes[..4], b"RIFF");
 }
+
+struct DecodedCounter(AtomicU64);
+
+impl ProgressCallback for DecodedCounter {
+    fn on_progress(&self, _info: &ProgressInfo) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn stream_buffer_size_bounds_decode_ahead() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let counter = Arc::new(DecodedCounter(AtomicU64::new(0)));
+    let config = ExtractOptions::new()
+        .with_progress(counter.clone())
+        .with_stream_buffer_size(2);
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let mut stream = unbundler
+        .video()
+        .frame_stream(FrameRange::Range(0, 19), config)
+        .unwrap();
+
+    // Don't consume for a while: the decoder should stall once the buffer
+    // is full instead of racing through all 20 frames.
+    std::thread::sleep(Duration::from_millis(500));
+    let decoded_ahead = counter.0.load(Ordering::SeqCst);
+    assert!(
+        decoded_ahead <= 2,
+        "decoder ran {decoded_ahead} frames ahead of a buffer of 2",
+    );
+
+    let mut count = 0u64;
+    while let Some(result) = stream.next().await {
+        result.unwrap();
+        count += 1;
+    }
+    assert_eq!(count, 20, "expected every frame once drained");
+}
@@ -3,9 +3,11 @@
 //! These tests require the fixture files generated by
 //! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
 
-use std::{path::Path, time::Duration};
+use std::{collections::HashMap, path::Path, time::Duration};
 
-use unbundle::{AudioFormat, MediaFile};
+use unbundle::{
+    AudioFormat, ExtractOptions, MediaFile, PcmSampleFormat, ResampleQuality, UnbundleError,
+};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -123,6 +125,131 @@ fn no_audio_stream_returns_error() {
     assert!(result.is_err(), "Expected NoAudioStream error");
 }
 
+#[test]
+fn extract_all_audio_tracks_writes_one_file_per_track() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let track_count = unbundler
+        .metadata()
+        .audio_tracks
+        .as_ref()
+        .map_or(1, Vec::len);
+    let paths = unbundler
+        .extract_all_audio_tracks(directory.path(), AudioFormat::Wav)
+        .expect("Failed to extract audio tracks");
+
+    assert_eq!(paths.len(), track_count);
+    for output in &paths {
+        assert!(output.starts_with(directory.path()));
+        let bytes = std::fs::read(output).expect("Failed to read track");
+        assert_eq!(&bytes[..4], b"RIFF");
+    }
+}
+
+/// A mono 8 kHz 16-bit WAV file holding `samples` samples of a square wave.
+fn wav_bytes(samples: usize) -> Vec<u8> {
+    let data_size = (samples * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + samples * 2);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&8_000u32.to_le_bytes());
+    bytes.extend_from_slice(&16_000u32.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    for index in 0..samples {
+        let sample: i16 = if index / 20 % 2 == 0 { 8_000 } else { -8_000 };
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+/// Stream-copy one WAV track per entry of `sample_counts` into a single
+/// Matroska file in `directory`.
+fn write_multitrack_clip(directory: &Path, sample_counts: &[usize]) -> std::path::PathBuf {
+    use ffmpeg_next::codec::Id;
+
+    ffmpeg_next::init().expect("Failed to initialise FFmpeg");
+    let output_path = directory.join("tracks.mkv");
+    let mut output = ffmpeg_next::format::output(&output_path).expect("Failed to create output");
+    let mut inputs = Vec::new();
+    for (index, &samples) in sample_counts.iter().enumerate() {
+        let wav_path = directory.join(format!("track_{index}.wav"));
+        std::fs::write(&wav_path, wav_bytes(samples)).expect("Failed to write WAV");
+        let input = ffmpeg_next::format::input(&wav_path).expect("Failed to open WAV");
+        let mut stream = output
+            .add_stream(ffmpeg_next::encoder::find(Id::None))
+            .expect("Failed to add stream");
+        stream.set_parameters(input.stream(0).expect("WAV has no stream").parameters());
+        inputs.push(input);
+    }
+    output.write_header().expect("Failed to write header");
+    for (index, input) in inputs.iter_mut().enumerate() {
+        let input_time_base = input.stream(0).expect("WAV has no stream").time_base();
+        let output_time_base = output.stream(index).expect("Missing stream").time_base();
+        for (_, mut packet) in input.packets() {
+            packet.rescale_ts(input_time_base, output_time_base);
+            packet.set_position(-1);
+            packet.set_stream(index);
+            packet
+                .write_interleaved(&mut output)
+                .expect("Failed to write packet");
+        }
+    }
+    output.write_trailer().expect("Failed to write trailer");
+    output_path
+}
+
+#[test]
+fn extract_all_audio_tracks_reads_every_track_in_full() {
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let sample_counts = [4_000, 8_000, 6_000];
+    let clip = write_multitrack_clip(directory.path(), &sample_counts);
+
+    let mut unbundler = MediaFile::open(&clip).expect("Failed to open clip");
+    let paths = unbundler
+        .extract_all_audio_tracks(directory.path().join("tracks"), AudioFormat::Wav)
+        .expect("Failed to extract audio tracks");
+    assert_eq!(paths.len(), sample_counts.len());
+
+    for (output, &expected) in paths.iter().zip(&sample_counts) {
+        let mut track = MediaFile::open(output).expect("Failed to open track");
+        let (_, info) = track
+            .audio()
+            .extract_raw_pcm(PcmSampleFormat::S16, 8_000, 1)
+            .expect("Failed to decode track");
+        assert_eq!(
+            info.samples_per_channel,
+            expected as u64,
+            "{} has the wrong length",
+            output.display()
+        );
+    }
+}
+
+#[test]
+fn extract_all_audio_tracks_requires_audio() {
+    let path = "tests/fixtures/sample_video_only.mp4";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open video-only file");
+    let result = unbundler.extract_all_audio_tracks(directory.path(), AudioFormat::Wav);
+    assert!(matches!(result, Err(UnbundleError::NoAudioStream)));
+}
+
 #[test]
 fn audio_only_file_works() {
     let path = "tests/fixtures/sample_audio_only.mp4";
@@ -165,3 +292,415 @@ fn audio_metadata_is_populated() {
         "Expected non-empty codec name"
     );
 }
+
+#[test]
+fn extract_with_each_resample_quality() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let qualities = [
+        ResampleQuality::Default,
+        ResampleQuality::High,
+        // Falls back to the built-in engine when FFmpeg lacks libsoxr.
+        ResampleQuality::Soxr { precision: 28 },
+    ];
+
+    for quality in qualities {
+        let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+        let audio = unbundler
+            .metadata()
+            .audio
+            .clone()
+            .expect("No audio metadata");
+        let config = ExtractOptions::new().with_resample_quality(quality);
+        let audio_bytes = unbundler
+            .audio()
+            .extract_with_options(AudioFormat::Wav, &config)
+            .expect("Failed to extract audio");
+        assert_eq!(
+            &audio_bytes[..4],
+            b"RIFF",
+            "Expected RIFF header for {quality:?}"
+        );
+        // At least a tenth of a second of 16-bit audio after the header.
+        let minimum = 44 + (audio.sample_rate as usize * usize::from(audio.channels) * 2) / 10;
+        assert!(
+            audio_bytes.len() > minimum,
+            "Only {} bytes for {quality:?}",
+            audio_bytes.len()
+        );
+    }
+}
+
+#[test]
+fn extract_raw_pcm_interleaved_f32() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let duration = unbundler.metadata().duration.as_secs_f64();
+    let (bytes, info) = unbundler
+        .audio()
+        .extract_raw_pcm(PcmSampleFormat::F32, 48_000, 2)
+        .expect("Failed to extract raw PCM");
+
+    assert_eq!(info.sample_rate, 48_000);
+    assert_eq!(info.channels, 2);
+    assert_eq!(bytes.len() as u64, info.samples_per_channel * 2 * 4);
+    // No RIFF header, just samples.
+    assert_ne!(&bytes[..4], b"RIFF");
+
+    let expected = duration * 48_000.0;
+    let actual = info.samples_per_channel as f64;
+    assert!(
+        (actual - expected).abs() < expected * 0.1,
+        "Expected ~{expected} samples per channel, got {actual}"
+    );
+}
+
+#[test]
+fn extract_raw_pcm_planar_s16() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let (bytes, info) = unbundler
+        .audio()
+        .extract_raw_pcm(PcmSampleFormat::S16Planar, 22_050, 1)
+        .expect("Failed to extract raw PCM");
+
+    assert!(info.samples_per_channel > 0);
+    assert_eq!(bytes.len() as u64, info.samples_per_channel * 2);
+}
+
+#[test]
+fn aac_extraction_keeps_encoder_delayed_tail() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let audio = unbundler
+        .metadata()
+        .audio
+        .clone()
+        .expect("No audio metadata");
+    let (_, source) = unbundler
+        .audio()
+        .extract_raw_pcm(PcmSampleFormat::F32, audio.sample_rate, audio.channels)
+        .expect("Failed to decode source audio");
+
+    // Saving after the decode above must still cover the whole track.
+    let output = tempfile::Builder::new()
+        .suffix(".aac")
+        .tempfile()
+        .expect("Failed to create temp file");
+    unbundler
+        .audio()
+        .save(output.path(), AudioFormat::Aac)
+        .expect("Failed to save AAC");
+
+    let mut encoded = MediaFile::open(output.path()).expect("Failed to open AAC output");
+    let (_, round_trip) = encoded
+        .audio()
+        .extract_raw_pcm(PcmSampleFormat::F32, audio.sample_rate, audio.channels)
+        .expect("Failed to decode AAC output");
+
+    // ADTS keeps the encoder's priming samples and pads the last frame, so
+    // the round trip may be up to two frames longer, but never shorter:
+    // a shortfall means the encoder's buffered tail was dropped.
+    let source_samples = source.samples_per_channel;
+    let output_samples = round_trip.samples_per_channel;
+    assert!(
+        output_samples >= source_samples,
+        "AAC output lost samples: {output_samples} < {source_samples}"
+    );
+    assert!(
+        output_samples <= source_samples + 2 * 1024,
+        "AAC output has {output_samples} samples, source {source_samples}"
+    );
+}
+
+#[test]
+fn extract_channel_is_mono() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let audio_bytes = unbundler
+        .audio()
+        .extract_channel(0, AudioFormat::Wav)
+        .expect("Failed to extract channel 0");
+
+    assert_eq!(&audio_bytes[..4], b"RIFF");
+    // The channel count sits at byte 22 of the canonical WAV header.
+    let channels = u16::from_le_bytes([audio_bytes[22], audio_bytes[23]]);
+    assert_eq!(channels, 1);
+}
+
+#[test]
+fn downmix_matrix_sets_output_channels() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let channels = usize::from(unbundler.metadata().audio.as_ref().unwrap().channels);
+    let config =
+        ExtractOptions::new().with_downmix_matrix(vec![vec![1.0 / channels as f64; channels]]);
+    let audio_bytes = unbundler
+        .audio()
+        .extract_with_options(AudioFormat::Wav, &config)
+        .expect("Failed to extract downmixed audio");
+
+    assert_eq!(&audio_bytes[..4], b"RIFF");
+    let output_channels = u16::from_le_bytes([audio_bytes[22], audio_bytes[23]]);
+    assert_eq!(output_channels, 1);
+}
+
+#[test]
+fn downmix_matrix_rejects_wrong_width() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let channels = usize::from(unbundler.metadata().audio.as_ref().unwrap().channels);
+    let config = ExtractOptions::new().with_downmix_matrix(vec![vec![0.5; channels + 1]; 2]);
+    let result = unbundler
+        .audio()
+        .extract_with_options(AudioFormat::Wav, &config);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::InvalidDownmixMatrix(_))
+    ));
+}
+
+#[test]
+fn downmix_matrix_applies_after_channel_selection() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // The selected channel is mono, so a one-column matrix fits it.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let config = ExtractOptions::new().with_downmix_matrix(vec![vec![1.0], vec![0.5]]);
+    let audio_bytes = unbundler
+        .audio()
+        .extract_channel_with_options(0, AudioFormat::Wav, &config)
+        .expect("Failed to extract remixed channel");
+    let output_channels = u16::from_le_bytes([audio_bytes[22], audio_bytes[23]]);
+    assert_eq!(output_channels, 2);
+
+    // A matrix sized for the source no longer fits and is reported.
+    let channels = usize::from(unbundler.metadata().audio.as_ref().unwrap().channels);
+    if channels > 1 {
+        let config = ExtractOptions::new().with_downmix_matrix(vec![vec![0.5; channels]]);
+        let result = unbundler
+            .audio()
+            .extract_channel_with_options(0, AudioFormat::Wav, &config);
+        assert!(matches!(
+            result,
+            Err(UnbundleError::InvalidDownmixMatrix(_))
+        ));
+    }
+}
+
+#[test]
+fn extract_channel_rejects_missing_channel() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let result = unbundler.audio().extract_channel(64, AudioFormat::Wav);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::ChannelOutOfRange {
+            channel_index: 64,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn range_at_end_of_short_clip() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let clip = tempfile::Builder::new()
+        .suffix(".wav")
+        .tempfile()
+        .expect("Failed to create temp file");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    unbundler
+        .audio()
+        .save_range(
+            clip.path(),
+            Duration::ZERO,
+            Duration::from_millis(500),
+            AudioFormat::Wav,
+        )
+        .expect("Failed to cut 0.5 s clip");
+
+    let mut short = MediaFile::open(clip.path()).expect("Failed to open clip");
+    let clip_duration = short.metadata().duration;
+    let tail = short
+        .audio()
+        .extract_range(Duration::from_millis(400), clip_duration, AudioFormat::Wav)
+        .expect("Failed to extract the last 0.1 s of the clip");
+    // More than the 44-byte WAV header: the seek must not skip the tail.
+    assert!(tail.len() > 44, "tail of the clip decoded to no samples");
+
+    // A start past the end of the file is rejected before anything is
+    // decoded or written.
+    let past_start = clip_duration + Duration::from_millis(100);
+    let past_end = short.audio().extract_range(
+        past_start,
+        clip_duration + Duration::from_millis(200),
+        AudioFormat::Wav,
+    );
+    assert!(
+        matches!(past_end, Err(UnbundleError::InvalidTimestamp(start)) if start == past_start),
+        "expected InvalidTimestamp for a range past the clip, got {:?}",
+        past_end.map(|bytes| bytes.len())
+    );
+
+    let output = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_path = output.path().join("past_end.wav");
+    let saved = short.audio().save_range(
+        &output_path,
+        past_start,
+        clip_duration + Duration::from_millis(200),
+        AudioFormat::Wav,
+    );
+    assert!(matches!(saved, Err(UnbundleError::InvalidTimestamp(_))));
+    assert!(!output_path.exists(), "no file should be created");
+}
+
+/// Container tags of `path`, keyed by lower-cased name.
+fn container_tags(path: &Path) -> HashMap<String, String> {
+    MediaFile::open(path)
+        .expect("Failed to open output")
+        .metadata()
+        .tags
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key.to_lowercase(), value))
+        .collect()
+}
+
+#[test]
+fn save_with_tags_writes_container_metadata() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tags = HashMap::from([
+        ("title".to_string(), "Opening Theme".to_string()),
+        ("artist".to_string(), "The Composers".to_string()),
+    ]);
+    let config = ExtractOptions::new().with_tags(tags);
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+    for (name, format) in [
+        ("tagged.flac", AudioFormat::Flac),
+        ("tagged.mp3", AudioFormat::Mp3),
+    ] {
+        let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+        let output = dir.path().join(name);
+        unbundler
+            .audio()
+            .save_with_options(&output, format, &config)
+            .expect("Failed to save audio");
+        let mut saved = MediaFile::open(&output).expect("Failed to open saved audio");
+        let (_, info) = saved
+            .audio()
+            .extract_raw_pcm(PcmSampleFormat::S16, 8_000, 1)
+            .expect("Failed to decode saved audio");
+        assert!(info.samples_per_channel > 0, "{name} has no audio");
+
+        let written = container_tags(&output);
+        assert_eq!(
+            written.get("title").map(String::as_str),
+            Some("Opening Theme")
+        );
+        assert_eq!(
+            written.get("artist").map(String::as_str),
+            Some("The Composers")
+        );
+    }
+}
+
+#[test]
+fn save_without_tags_writes_none() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = dir.path().join("untagged.flac");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    unbundler
+        .audio()
+        .save(&output, AudioFormat::Flac)
+        .expect("Failed to save audio");
+
+    assert!(!container_tags(&output).contains_key("title"));
+}
+
+#[test]
+fn save_range_with_summary_reports_written_output() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_path = directory.path().join("clip.flac");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let sample_rate = unbundler
+        .metadata()
+        .audio
+        .as_ref()
+        .expect("Fixture has audio")
+        .sample_rate;
+    let summary = unbundler
+        .audio()
+        .save_range_with_summary(
+            &output_path,
+            Duration::from_secs(1),
+            Duration::from_secs(2),
+            AudioFormat::Flac,
+            &ExtractOptions::new(),
+        )
+        .expect("Failed to save audio range");
+
+    let file_size = std::fs::metadata(&output_path)
+        .expect("Output missing")
+        .len();
+    assert_eq!(summary.bytes_written, file_size);
+    assert!(summary.packets_written > 0);
+    let samples = summary.samples_written.expect("Encoding counts samples");
+    let seconds = samples as f64 / f64::from(sample_rate);
+    assert!((seconds - 1.0).abs() < 0.1, "wrote {seconds} s of samples");
+    assert!((summary.output_duration.as_secs_f64() - seconds).abs() < 0.05);
+}
@@ -69,3 +69,32 @@ fn sample_iter_early_exit() {
     // Should not panic or error when taking only a few chunks.
     assert!(first_three.len() <= 3);
 }
+
+#[test]
+fn sample_iter_chunk_levels_match_samples() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    for chunk in unbundler
+        .audio()
+        .sample_iter()
+        .expect("sample_iter")
+        .filter_map(|r| r.ok())
+        .take(20)
+    {
+        let peak = chunk
+            .samples
+            .iter()
+            .fold(0.0f64, |peak, &sample| peak.max(f64::from(sample).abs()));
+        if peak == 0.0 {
+            assert_eq!(chunk.peak_dbfs, f64::NEG_INFINITY);
+            assert_eq!(chunk.rms_dbfs, f64::NEG_INFINITY);
+            continue;
+        }
+        assert!((chunk.peak_dbfs - 20.0 * peak.log10()).abs() < 1e-6);
+        assert!(chunk.rms_dbfs <= chunk.peak_dbfs + 1e-9);
+    }
+}
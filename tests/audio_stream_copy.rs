@@ -104,3 +104,32 @@ fn stream_copy_audio_invalid_range_returns_error() {
         "Expected InvalidRange variant"
     );
 }
+
+#[test]
+fn stream_copy_native_to_memory_picks_codec_container() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let codec = unbundler
+        .metadata()
+        .audio
+        .as_ref()
+        .expect("audio")
+        .codec
+        .clone();
+    let (bytes, container) = unbundler
+        .audio()
+        .stream_copy_native_to_memory()
+        .expect("Failed to stream-copy audio");
+
+    assert!(!bytes.is_empty());
+    if codec == "aac" {
+        assert_eq!(container, "adts");
+        // ADTS frames start with a 12-bit sync word.
+        assert_eq!(bytes[0], 0xFF);
+        assert_eq!(bytes[1] & 0xF0, 0xF0);
+    }
+}
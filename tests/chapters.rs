@@ -3,9 +3,9 @@
 //! These tests require the fixture files generated by
 //! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
-use unbundle::MediaFile;
+use unbundle::{MediaFile, Remuxer};
 
 fn sample_with_chapters_path() -> &'static str {
     "tests/fixtures/sample_with_chapters.mkv"
@@ -144,3 +144,52 @@ fn chapter_start_end_durations() {
         first.end,
     );
 }
+
+#[test]
+fn m4b_chapters_are_read() {
+    let path = sample_with_chapters_path();
+    if !Path::new(path).exists() {
+        eprintln!("Skipping: fixture '{path}' not found.");
+        return;
+    }
+
+    // The `.m4b` extension selects FFmpeg's iPod muxer, which writes the
+    // chapters as a QuickTime chapter track the way audiobook tools do.
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let book_path = dir.path().join("book.m4b");
+    Remuxer::new(path, &book_path)
+        .expect("Failed to create remuxer")
+        .exclude_subtitles()
+        .run()
+        .expect("Failed to remux");
+
+    let book = MediaFile::open(&book_path).expect("Failed to open M4B");
+    let metadata = book.metadata();
+    let chapters = metadata.chapters.as_ref().expect("Expected M4B chapters");
+    let titles: Vec<_> = chapters
+        .iter()
+        .map(|chapter| chapter.title.as_deref())
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            Some("Introduction"),
+            Some("Main Content"),
+            Some("Conclusion")
+        ]
+    );
+
+    for window in chapters.windows(2) {
+        assert!(window[0].start < window[1].start);
+        assert!(
+            window[0].end <= window[1].start + Duration::from_millis(50),
+            "{:?} overlaps {:?}",
+            window[0],
+            window[1]
+        );
+    }
+    for chapter in chapters {
+        assert!(chapter.end > chapter.start, "empty chapter: {chapter:?}");
+        assert!(chapter.end <= metadata.duration + Duration::from_millis(50));
+    }
+}
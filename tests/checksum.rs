@@ -0,0 +1,81 @@
+//! Per-stream checksum integration tests.
+//!
+//! These tests require the fixture files generated by
+//! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
+
+use std::path::Path;
+
+use unbundle::{MediaFile, Remuxer, StreamChecksum, StreamKind};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+/// Checksums of the audio and video streams, without the stream index.
+fn media_checksums(checksums: &[StreamChecksum]) -> Vec<(StreamKind, u64, u64, u32)> {
+    checksums
+        .iter()
+        .filter(|checksum| matches!(checksum.kind, StreamKind::Video | StreamKind::Audio))
+        .map(|checksum| {
+            (
+                checksum.kind,
+                checksum.packet_count,
+                checksum.byte_count,
+                checksum.crc32,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn stream_checksums_cover_every_stream() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let first = unbundler.stream_checksums().expect("Failed to checksum");
+    let streams = unbundler.streams();
+    assert_eq!(first.len(), streams.len());
+    for (checksum, stream) in first.iter().zip(&streams) {
+        assert_eq!(checksum.stream_index, stream.index);
+        assert_eq!(checksum.kind, stream.kind);
+    }
+
+    let video = first
+        .iter()
+        .find(|checksum| checksum.kind == StreamKind::Video)
+        .expect("video checksum");
+    assert!(video.packet_count > 0);
+    assert!(video.byte_count >= video.packet_count);
+
+    // A second pass starts from the beginning again.
+    let second = unbundler.stream_checksums().expect("Failed to checksum");
+    assert_eq!(first, second);
+}
+
+#[test]
+fn stream_checksums_survive_remux() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_path = dir.path().join("remuxed.mkv");
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .run()
+        .expect("Failed to remux");
+
+    let original = MediaFile::open(path)
+        .expect("Failed to open")
+        .stream_checksums()
+        .expect("Failed to checksum");
+    let remuxed = MediaFile::open(&output_path)
+        .expect("Failed to open remuxed file")
+        .stream_checksums()
+        .expect("Failed to checksum");
+    assert_eq!(media_checksums(&original), media_checksums(&remuxed));
+}
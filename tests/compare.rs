@@ -0,0 +1,75 @@
+//! Video quality comparison integration tests.
+//!
+//! These tests require the fixture files generated by
+//! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
+
+use std::{path::Path, time::Duration};
+
+use unbundle::{MediaFile, MetricOptions, UnbundleError};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+#[test]
+fn compare_with_itself_is_lossless() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut reference = MediaFile::open(path).expect("Failed to open");
+    let mut distorted = MediaFile::open(path).expect("Failed to open");
+    let report = reference
+        .compare(&mut distorted, MetricOptions::new())
+        .expect("Failed to compare");
+
+    assert!(!report.frames.is_empty());
+    assert_eq!(report.unmatched_reference, 0);
+    assert_eq!(report.unmatched_distorted, 0);
+    assert_eq!(report.average_psnr, Some(f64::INFINITY));
+    let ssim = report.average_ssim.expect("SSIM enabled");
+    assert!(
+        (ssim - 1.0).abs() < 1e-9,
+        "SSIM of identical frames: {ssim}"
+    );
+    for frame in &report.frames {
+        assert_eq!(frame.reference_frame, frame.distorted_frame);
+        assert_eq!(frame.psnr, Some(f64::INFINITY));
+    }
+}
+
+#[test]
+fn compare_skips_disabled_metrics() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut reference = MediaFile::open(path).expect("Failed to open");
+    let mut distorted = MediaFile::open(path).expect("Failed to open");
+    let options = MetricOptions::new()
+        .with_ssim(false)
+        .with_tolerance(Duration::from_millis(1));
+    let report = reference
+        .compare(&mut distorted, options)
+        .expect("Failed to compare");
+
+    assert!(report.average_psnr.is_some());
+    assert_eq!(report.average_ssim, None);
+    assert!(report.frames.iter().all(|frame| frame.ssim.is_none()));
+}
+
+#[test]
+fn compare_without_video_fails() {
+    let video_path = sample_video_path();
+    let audio_path = "tests/fixtures/sample_audio_only.mp4";
+    if !Path::new(video_path).exists() || !Path::new(audio_path).exists() {
+        return;
+    }
+
+    let mut reference = MediaFile::open(video_path).expect("Failed to open");
+    let mut distorted = MediaFile::open(audio_path).expect("Failed to open");
+    let result = reference.compare(&mut distorted, MetricOptions::new());
+    assert!(matches!(result, Err(UnbundleError::NoVideoStream)));
+}
@@ -6,7 +6,9 @@ use std::path::Path;
 use std::sync::Arc;
 
 use unbundle::{
-    ExtractOptions, FrameRange, MediaFile, PixelFormat, ProgressCallback, ProgressInfo,
+    AudioFormat, CancellationToken, ColorMatrix, ErrorPolicy, ExtractOptions, FieldOrder,
+    FrameDiscard, FrameOutputOptions, FrameRange, MediaFile, OverlayCorner, OverlayOptions,
+    PixelFormat, ProgressCallback, ProgressInfo, StatsCollector, ThreadType, UnbundleError,
 };
 
 fn sample_video_path() -> &'static str {
@@ -105,6 +107,123 @@ fn frames_gray8() {
     );
 }
 
+#[test]
+fn frames_bgr8_swaps_red_and_blue() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let rgb = unbundler
+        .video()
+        .frame_with_options(
+            0,
+            &ExtractOptions::new().with_pixel_format(PixelFormat::Rgb8),
+        )
+        .expect("Failed to extract RGB frame")
+        .to_rgb8();
+    let bgr = unbundler
+        .video()
+        .frame_with_options(
+            0,
+            &ExtractOptions::new().with_pixel_format(PixelFormat::Bgr8),
+        )
+        .expect("Failed to extract BGR frame");
+
+    let image::DynamicImage::ImageRgb8(bgr) = bgr else {
+        panic!("Expected BGR bytes wrapped in an RGB8 image");
+    };
+    assert_eq!(bgr.dimensions(), rgb.dimensions());
+    // swscale may round the two layouts slightly differently.
+    for (bgr_pixel, rgb_pixel) in bgr.pixels().zip(rgb.pixels()) {
+        let swapped = [rgb_pixel[2], rgb_pixel[1], rgb_pixel[0]];
+        for (actual, expected) in bgr_pixel.0.iter().zip(swapped) {
+            assert!(
+                actual.abs_diff(expected) <= 1,
+                "{:?} vs {swapped:?}",
+                bgr_pixel.0
+            );
+        }
+    }
+}
+
+#[test]
+fn bgr_formats_match_rgb_sizes() {
+    assert_eq!(PixelFormat::Bgr8.bytes_per_pixel(), 3);
+    assert_eq!(PixelFormat::Bgra8.bytes_per_pixel(), 4);
+}
+
+#[test]
+fn pixel_format_parses_cli_names() {
+    for (name, expected) in [
+        ("rgb8", PixelFormat::Rgb8),
+        ("RGBA", PixelFormat::Rgba8),
+        ("grayscale", PixelFormat::Gray8),
+        ("bgr24", PixelFormat::Bgr8),
+        ("bgra8", PixelFormat::Bgra8),
+    ] {
+        assert_eq!(name.parse::<PixelFormat>().unwrap(), expected, "{name}");
+    }
+    assert!(matches!(
+        "yuv420p".parse::<PixelFormat>(),
+        Err(UnbundleError::InvalidOptionValue {
+            option: "pixel format",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn timecode_overlay_is_burned_into_corner() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let overlay = OverlayOptions::new()
+        .with_corner(OverlayCorner::BottomLeft)
+        .with_scale(2);
+    let config = ExtractOptions::new().with_timecode_overlay(overlay);
+    let frame = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &config)
+        .expect("Failed to extract frame")
+        .remove(0)
+        .to_rgb8();
+
+    // At scale 2 the box is 22 pixels tall with a 4-pixel margin, and the
+    // first glyph ("0") starts with a lit pixel one font column in.
+    let box_top = frame.height() - 26;
+    assert_eq!(frame.get_pixel(4, box_top).0, [0, 0, 0]);
+    assert_eq!(frame.get_pixel(10, box_top + 4).0, [255, 255, 255]);
+}
+
+#[test]
+fn oversized_timecode_overlay_is_clamped() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let overlay = OverlayOptions::new().with_scale(u32::MAX);
+    assert_eq!(overlay.scale, Some(256));
+    let config = ExtractOptions::new().with_timecode_overlay(overlay);
+    let frame = unbundler
+        .video()
+        .frame_with_options(0, &config)
+        .expect("Failed to extract frame")
+        .to_rgb8();
+
+    // The box starts at the 512-pixel margin and runs past the frame edges.
+    assert_eq!(
+        frame.get_pixel(frame.width() - 1, frame.height() - 1).0,
+        [0, 0, 0]
+    );
+}
+
 // ── Resolution scaling ─────────────────────────────────────────────
 
 #[test]
@@ -166,6 +285,91 @@ fn frames_fixed_resolution() {
 
 // ── FrameOutputOptions resolve_dimensions ───────────────────────────
 
+#[test]
+fn frames_lowres_matches_decoder_support() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // Codecs without lowres support decode at full size; those with it
+    // (e.g. MJPEG) decode at a quarter of each dimension for level 2.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let config = ExtractOptions::new().with_lowres(2);
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &config)
+        .expect("Failed to extract");
+
+    let size = (frames[0].width(), frames[0].height());
+    assert!(
+        size == (640, 480) || size == (160, 120),
+        "Unexpected lowres output size: {size:?}"
+    );
+}
+
+#[test]
+fn frame_with_options_honours_lowres() {
+    // A still JPEG is decoded by the MJPEG decoder, which supports lowres.
+    let image = image::RgbImage::from_pixel(64, 48, image::Rgb([200, 40, 40]));
+    let file = tempfile::Builder::new()
+        .suffix(".jpg")
+        .tempfile()
+        .expect("Failed to create temp file");
+    image.save(file.path()).expect("Failed to write JPEG");
+
+    let mut unbundler = MediaFile::open(file.path()).expect("Failed to open JPEG");
+    let config = ExtractOptions::new().with_lowres(1);
+    let frame = unbundler
+        .video()
+        .frame_with_options(0, &config)
+        .expect("Failed to extract");
+    assert_eq!((frame.width(), frame.height()), (32, 24));
+
+    // Dropping lowres rebuilds the cached decoder at full size.
+    let frame = unbundler
+        .video()
+        .frame_with_options(0, &ExtractOptions::new())
+        .expect("Failed to extract");
+    assert_eq!((frame.width(), frame.height()), (64, 48));
+}
+
+#[test]
+fn decoder_threads_default_to_automatic() {
+    let config = ExtractOptions::new();
+    assert_eq!(config.decoder_threads(), (0, ThreadType::Auto));
+
+    let config = config.with_decoder_threads(2, ThreadType::Slice);
+    assert_eq!(config.decoder_threads(), (2, ThreadType::Slice));
+}
+
+#[test]
+fn frame_threading_matches_single_threaded_output() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // Frame threading delays output; every requested frame must still
+    // come back, identical to a single-threaded decode.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let single = ExtractOptions::new().with_decoder_threads(1, ThreadType::Auto);
+    let threaded = ExtractOptions::new().with_decoder_threads(4, ThreadType::Frame);
+    let expected = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &single)
+        .expect("Failed to extract single-threaded");
+    let actual = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &threaded)
+        .expect("Failed to extract with frame threads");
+
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(&expected) {
+        assert_eq!(actual.as_bytes(), expected.as_bytes());
+    }
+}
+
 #[test]
 fn frame_output_config_defaults() {
     let config = unbundle::FrameOutputOptions::default();
@@ -215,3 +419,651 @@ fn progress_callback_fires() {
         "Progress callback should have been called at least once"
     );
 }
+
+#[test]
+fn max_dimensions_rejects_oversized_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+
+    // Source (640x480) exceeds the limit even if output would be smaller.
+    let config = ExtractOptions::new()
+        .with_max_dimensions(320, 240)
+        .with_resolution(Some(160), None);
+    let result = unbundler.video().frame_with_options(0, &config);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::DimensionsTooLarge {
+            width: 640,
+            height: 480,
+            max_width: 320,
+            max_height: 240,
+        })
+    ));
+
+    // Requested output larger than the limit is rejected too.
+    let config = ExtractOptions::new()
+        .with_max_dimensions(1024, 1024)
+        .with_resolution(Some(2048), Some(1536));
+    let result = unbundler.video().frame_with_options(0, &config);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::DimensionsTooLarge { width: 2048, .. })
+    ));
+
+    let config = ExtractOptions::new().with_max_dimensions(640, 480);
+    unbundler
+        .video()
+        .frame_with_options(0, &config)
+        .expect("Frame at the limit should be accepted");
+}
+
+#[test]
+fn frame_output_color_matrix_default_is_none() {
+    let config = FrameOutputOptions::default();
+    assert!(config.input_color_matrix.is_none());
+
+    let config = config.with_input_color_matrix(ColorMatrix::Bt709);
+    assert_eq!(config.input_color_matrix, Some(ColorMatrix::Bt709));
+}
+
+#[test]
+fn auto_levels_stretches_frame_to_full_range() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let config = ExtractOptions::new()
+        .with_pixel_format(PixelFormat::Gray8)
+        .with_auto_levels(true);
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let image = unbundler
+        .video()
+        .frame_with_options(0, &config)
+        .expect("Failed to extract frame")
+        .to_luma8();
+
+    let low = image.as_raw().iter().copied().min().unwrap_or(0);
+    let high = image.as_raw().iter().copied().max().unwrap_or(0);
+    assert_eq!((low, high), (0, 255));
+}
+
+#[test]
+fn frame_iterator_applies_auto_levels() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut output = FrameOutputOptions::default().with_auto_levels(true);
+    output.pixel_format = PixelFormat::Gray8;
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let (_, image) = unbundler
+        .video()
+        .frame_iter_with_options(FrameRange::Specific(vec![0]), output)
+        .expect("Failed to create iterator")
+        .next()
+        .expect("Iterator yielded no frame")
+        .expect("Failed to extract frame");
+    let image = image.to_luma8();
+
+    let low = image.as_raw().iter().copied().min().unwrap_or(0);
+    let high = image.as_raw().iter().copied().max().unwrap_or(0);
+    assert_eq!((low, high), (0, 255));
+}
+
+/// Write a top-field-first Y4M clip of a white bar moving 8 pixels per
+/// field, so every unprocessed frame shows comb artefacts.
+fn write_interlaced_clip() -> tempfile::NamedTempFile {
+    const SIZE: usize = 64;
+    let mut clip = b"YUV4MPEG2 W64 H64 F25:1 It A1:1 C420jpeg\n".to_vec();
+    for frame in 0..10 {
+        clip.extend_from_slice(b"FRAME\n");
+        for y in 0..SIZE {
+            let bar_left = (frame * 16 + (y % 2) * 8) % 48;
+            clip.extend((0..SIZE).map(|x| {
+                if (bar_left..bar_left + 16).contains(&x) {
+                    255
+                } else {
+                    0
+                }
+            }));
+        }
+        clip.extend(std::iter::repeat_n(128u8, SIZE * SIZE / 2));
+    }
+    let file = tempfile::Builder::new()
+        .suffix(".y4m")
+        .tempfile()
+        .expect("Failed to create temp file");
+    std::fs::write(file.path(), &clip).expect("Failed to write Y4M");
+    file
+}
+
+/// Sum of luma differences between vertically adjacent pixels.
+fn comb_energy(image: &image::DynamicImage) -> u64 {
+    let gray = image.to_luma8();
+    let rows: Vec<&[u8]> = gray.as_raw().chunks(gray.width() as usize).collect();
+    rows.windows(2)
+        .flat_map(|pair| pair[0].iter().zip(pair[1]))
+        .map(|(above, below)| u64::from(above.abs_diff(*below)))
+        .sum()
+}
+
+#[test]
+fn deinterlace_removes_combing_from_interlaced_frames() {
+    let clip = write_interlaced_clip();
+    let mut unbundler = MediaFile::open(clip.path()).expect("Failed to open Y4M");
+    let field_order = unbundler.metadata().video.as_ref().unwrap().field_order;
+    assert!(field_order.is_some_and(FieldOrder::is_interlaced));
+
+    let config = ExtractOptions::new().with_deinterlace(true);
+    let plain = unbundler
+        .video()
+        .frames(FrameRange::Range(0, 9))
+        .expect("Failed to extract frames");
+    let deinterlaced = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &config)
+        .expect("Failed to extract deinterlaced frames");
+    assert_eq!(deinterlaced.len(), plain.len());
+    for (original, filtered) in plain.iter().zip(&deinterlaced) {
+        assert!(comb_energy(filtered) * 2 < comb_energy(original));
+    }
+
+    // A frame reached by seeking comes out the same as in the range.
+    let single = unbundler
+        .video()
+        .frame_with_options(5, &config)
+        .expect("Failed to extract deinterlaced frame");
+    assert!(comb_energy(&single) * 2 < comb_energy(&plain[5]));
+    assert_eq!(single.as_bytes(), deinterlaced[5].as_bytes());
+}
+
+#[test]
+fn deinterlace_leaves_progressive_frames_untouched() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let field_order = unbundler.metadata().video.as_ref().unwrap().field_order;
+    assert!(!field_order.is_some_and(FieldOrder::is_interlaced));
+
+    let plain = unbundler.video().frame(5).expect("Failed to extract frame");
+    let config = ExtractOptions::new().with_deinterlace(true);
+    let deinterlaced = unbundler
+        .video()
+        .frame_with_options(5, &config)
+        .expect("Failed to extract deinterlaced frame");
+    assert_eq!(plain.to_rgb8().as_raw(), deinterlaced.to_rgb8().as_raw());
+}
+
+#[test]
+fn input_color_matrix_changes_rgb_output() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let mut frame_with = |matrix: ColorMatrix| {
+        let config = ExtractOptions::new().with_input_color_matrix(matrix);
+        unbundler
+            .video()
+            .frame_with_options(0, &config)
+            .expect("Failed to extract frame")
+            .to_rgb8()
+    };
+    let bt601 = frame_with(ColorMatrix::Bt601);
+    let bt709 = frame_with(ColorMatrix::Bt709);
+    assert_eq!(bt601.dimensions(), bt709.dimensions());
+
+    // The test pattern's saturated colour bars convert measurably
+    // differently under the two matrices; grey areas do not.
+    let max_difference = bt601
+        .pixels()
+        .zip(bt709.pixels())
+        .flat_map(|(a, b)| a.0.iter().zip(b.0).map(|(x, y)| x.abs_diff(y)))
+        .max()
+        .unwrap_or(0);
+    assert!(
+        max_difference >= 8,
+        "Expected BT.601 and BT.709 to differ on coloured pixels, max diff {max_difference}"
+    );
+}
+
+#[test]
+fn resume_from_completes_interrupted_range() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+
+    // First run: stop after frame 5 as if the process had been interrupted.
+    let mut completed = Vec::new();
+    let result = unbundler.video().for_each_frame_with_options(
+        FrameRange::Range(0, 11),
+        &ExtractOptions::new(),
+        |number, _image| {
+            if number > 5 {
+                return Err(UnbundleError::Cancelled);
+            }
+            completed.push(number);
+            Ok(())
+        },
+    );
+    assert!(matches!(result, Err(UnbundleError::Cancelled)));
+    let checkpoint = *completed.last().expect("No frames completed");
+
+    // Second run: same range, resumed after the checkpoint.
+    let config = ExtractOptions::new().with_resume_from(checkpoint + 1);
+    let mut resumed = Vec::new();
+    unbundler
+        .video()
+        .for_each_frame_with_options(FrameRange::Range(0, 11), &config, |number, image| {
+            resumed.push((number, image.to_rgb8()));
+            Ok(())
+        })
+        .expect("Resumed run failed");
+
+    let mut all: Vec<u64> = completed.clone();
+    all.extend(resumed.iter().map(|(number, _)| *number));
+    assert_eq!(all, (0..=11).collect::<Vec<u64>>());
+
+    // Resuming must produce the same pixels as asking for the tail directly.
+    let direct = unbundler
+        .video()
+        .frames(FrameRange::Range(checkpoint + 1, 11))
+        .expect("Direct range extraction failed");
+    assert_eq!(direct.len(), resumed.len());
+    for (image, (_, resumed_image)) in direct.iter().zip(&resumed) {
+        assert_eq!(&image.to_rgb8(), resumed_image);
+    }
+}
+
+#[test]
+fn resume_from_filters_specific_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let config = ExtractOptions::new().with_resume_from(20);
+
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Specific(vec![0, 10, 20, 30]), &config)
+        .expect("Failed to extract frames");
+    assert_eq!(frames.len(), 2);
+
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 10), &config)
+        .expect("Range ending before the resume point should succeed");
+    assert!(frames.is_empty());
+}
+
+#[test]
+fn no_seek_interval_matches_seeking_extraction() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let expected = unbundler
+        .video()
+        .frames_and_metadata(FrameRange::Interval(25))
+        .expect("Seeking extraction failed");
+
+    // A freshly opened file is positioned at its start, like a pipe would be.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let config = ExtractOptions::new().with_no_seek(true);
+    let mut linear = Vec::new();
+    unbundler
+        .video()
+        .for_each_frame_with_options(FrameRange::Interval(25), &config, |number, image| {
+            linear.push((number, image.to_rgb8()));
+            Ok(())
+        })
+        .expect("Linear extraction failed");
+
+    let expected_numbers: Vec<u64> = expected.iter().map(|(_, info)| info.frame_number).collect();
+    let linear_numbers: Vec<u64> = linear.iter().map(|(number, _)| *number).collect();
+    assert_eq!(linear_numbers, expected_numbers);
+    for ((_, image), (expected_image, _)) in linear.iter().zip(&expected) {
+        assert_eq!(image, &expected_image.to_rgb8());
+    }
+}
+
+#[test]
+fn skip_corrupt_policy_keeps_every_frame_of_a_clean_file() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let config = ExtractOptions::new().with_error_policy(ErrorPolicy::SkipCorrupt);
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &config)
+        .expect("Failed to extract");
+
+    assert_eq!(frames.len(), 10);
+    assert_eq!(config.skipped_frames(), 0);
+    assert_eq!(config.clone().skipped_frames(), 0);
+}
+
+#[test]
+fn skip_corrupt_policy_counts_an_undecodable_frame() {
+    // A PNG whose zlib stream header is broken fails in the decoder.
+    let image = image::RgbImage::from_pixel(16, 16, image::Rgb([10, 120, 200]));
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .expect("Failed to encode PNG");
+    let idat = png
+        .windows(4)
+        .position(|window| window == b"IDAT")
+        .expect("PNG has no IDAT chunk");
+    png[idat + 4] = 0xff;
+    png[idat + 5] = 0xff;
+    let file = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .expect("Failed to create temp file");
+    std::fs::write(file.path(), &png).expect("Failed to write PNG");
+
+    let mut unbundler = MediaFile::open(file.path()).expect("Failed to open PNG");
+    let result = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &ExtractOptions::new());
+    assert!(result.is_err(), "Abort should report the corrupt frame");
+
+    let config = ExtractOptions::new().with_error_policy(ErrorPolicy::SkipCorrupt);
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &config)
+        .expect("SkipCorrupt should not fail");
+    assert!(frames.is_empty());
+    assert_eq!(config.skipped_frames(), 1);
+
+    // Clones share the count, and later calls add to it.
+    let clone = config.clone();
+    unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &clone)
+        .expect("SkipCorrupt should not fail");
+    assert_eq!(config.skipped_frames(), 2);
+}
+
+#[test]
+fn packet_stride_decodes_every_other_keyframe() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let keyframe_count = unbundler
+        .video()
+        .keyframes()
+        .expect("Failed to scan keyframes")
+        .len();
+    let config = ExtractOptions::new().with_packet_stride(2);
+    let mut numbers = Vec::new();
+    unbundler
+        .video()
+        .for_each_frame_with_options(FrameRange::Interval(1), &config, |number, _| {
+            numbers.push(number);
+            Ok(())
+        })
+        .expect("Strided extraction failed");
+
+    assert_eq!(numbers.len(), keyframe_count.div_ceil(2));
+    assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn stats_collector_counts_decoded_and_scaled_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let stats = Arc::new(StatsCollector::new());
+    let config = ExtractOptions::new().with_stats(stats.clone());
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &config)
+        .expect("Failed to extract frames");
+
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.frames_scaled, frames.len() as u64);
+    assert!(snapshot.frames_decoded >= snapshot.frames_scaled);
+    assert_eq!(snapshot.extractions, 1);
+    assert!(snapshot.wall_time > std::time::Duration::ZERO);
+    assert!(snapshot.average_decode_fps > 0.0);
+
+    unbundler
+        .video()
+        .frame_with_options(20, &config)
+        .expect("Failed to extract frame");
+    assert_eq!(stats.snapshot().extractions, 2);
+    assert_eq!(stats.snapshot().frames_scaled, frames.len() as u64 + 1);
+
+    stats.reset();
+    assert_eq!(stats.snapshot().frames_decoded, 0);
+
+    // Storyboards are measured like any other extraction.
+    let storyboard = unbundler
+        .video()
+        .storyboard_with_options(3, &config)
+        .expect("Failed to build storyboard");
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.extractions, 1);
+    assert_eq!(snapshot.frames_scaled, storyboard.len() as u64);
+    assert!(snapshot.frames_decoded >= snapshot.frames_scaled);
+}
+
+// ── Atomic writes ────────────────────────────────────────────────
+
+#[test]
+fn atomic_write_renames_into_place() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = directory.path().join("frame.png");
+    let config = ExtractOptions::new().with_atomic_write(true);
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    unbundler
+        .video()
+        .save_frame_with_options(0, &output, &config)
+        .expect("Failed to save frame");
+
+    let names: Vec<_> = std::fs::read_dir(directory.path())
+        .expect("Failed to list temp dir")
+        .map(|entry| entry.expect("entry").file_name())
+        .collect();
+    assert_eq!(names, vec![std::ffi::OsString::from("frame.png")]);
+    assert!(image::open(&output).is_ok());
+}
+
+#[test]
+fn atomic_write_keeps_existing_file_on_failure() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = directory.path().join("audio.wav");
+    std::fs::write(&output, b"previous").expect("Failed to seed output");
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let config = ExtractOptions::new()
+        .with_cancellation(token)
+        .with_atomic_write(true);
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let result = unbundler
+        .audio()
+        .save_with_options(&output, AudioFormat::Wav, &config);
+
+    assert!(matches!(result, Err(UnbundleError::Cancelled)));
+    assert_eq!(std::fs::read(&output).expect("read"), b"previous");
+    assert_eq!(
+        std::fs::read_dir(directory.path()).expect("list").count(),
+        1,
+        "the partial file should be removed"
+    );
+}
+
+#[test]
+fn frame_discard_non_key_yields_only_keyframes() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let all = unbundler
+        .video()
+        .frames_and_metadata(FrameRange::Range(0, 59))
+        .expect("Failed to extract frames");
+    let config = ExtractOptions::new().with_frame_discard(FrameDiscard::NonKey);
+    let keyframes = unbundler
+        .video()
+        .frames_and_metadata_with_options(FrameRange::Range(0, 59), &config)
+        .expect("Failed to extract keyframes");
+
+    assert!(!keyframes.is_empty());
+    assert!(keyframes.len() <= all.len());
+    assert!(keyframes.iter().all(|(_, info)| info.is_keyframe));
+}
+
+#[test]
+fn frame_discard_non_ref_keeps_frame_numbers() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let config = ExtractOptions::new().with_frame_discard(FrameDiscard::NonRef);
+    let frames = unbundler
+        .video()
+        .frames_and_metadata_with_options(FrameRange::Range(0, 59), &config)
+        .expect("Failed to extract frames");
+
+    assert!(!frames.is_empty());
+    assert!(frames.iter().all(|(_, info)| info.frame_number <= 59));
+    assert!(
+        frames
+            .windows(2)
+            .all(|pair| pair[0].1.frame_number < pair[1].1.frame_number)
+    );
+}
+
+#[test]
+fn frame_discard_applies_to_reused_single_frame_decoder() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let config = ExtractOptions::new().with_frame_discard(FrameDiscard::NonKey);
+    let mut fresh = MediaFile::open(path).expect("Failed to open test video");
+    let expected = fresh
+        .video()
+        .frame_with_options(5, &config)
+        .expect("Failed to extract frame");
+
+    // A decoder cached without frame discard must not be reused.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    unbundler.video().frame(5).expect("Failed to extract frame");
+    let frame = unbundler
+        .video()
+        .frame_with_options(5, &config)
+        .expect("Failed to extract frame");
+    assert_eq!(frame.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn rgba8_keeps_source_alpha() {
+    // A PNG keeps its alpha in the pixel format, like ProRes 4444.
+    let mut image = image::RgbaImage::from_pixel(16, 16, image::Rgba([30, 90, 160, 255]));
+    image.put_pixel(3, 5, image::Rgba([30, 90, 160, 0]));
+    let file = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .expect("Failed to create temp file");
+    image.save(file.path()).expect("Failed to write PNG");
+
+    let mut unbundler = MediaFile::open(file.path()).expect("Failed to open PNG");
+    let config = ExtractOptions::new().with_pixel_format(PixelFormat::Rgba8);
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 0), &config)
+        .expect("Failed to extract");
+    let rgba = frames[0].as_rgba8().expect("Expected RGBA8 image");
+    assert_eq!(rgba.get_pixel(3, 5)[3], 0, "Alpha plane was dropped");
+    assert_eq!(rgba.get_pixel(0, 0)[3], 255);
+}
+
+#[test]
+fn rgba8_keeps_vp9_side_channel_alpha() {
+    // WebM stores VP9 alpha beside the colour planes; only libvpx reads it.
+    let path = "tests/fixtures/sample_alpha.webm";
+    if !Path::new(path).exists() {
+        return;
+    }
+    ffmpeg_next::init().expect("Failed to initialise FFmpeg");
+    if ffmpeg_next::decoder::find_by_name("libvpx-vp9").is_none() {
+        return;
+    }
+
+    let config = ExtractOptions::new().with_pixel_format(PixelFormat::Rgba8);
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 2), &config)
+        .expect("Failed to extract frames");
+    let single = unbundler
+        .video()
+        .frame_with_options(1, &config)
+        .expect("Failed to extract frame");
+    let mut output = FrameOutputOptions::default();
+    output.pixel_format = PixelFormat::Rgba8;
+    let iterated: Vec<_> = unbundler
+        .video()
+        .frame_iter_with_options(FrameRange::Range(0, 2), output)
+        .expect("Failed to create iterator")
+        .collect::<Result<_, _>>()
+        .expect("Failed to iterate frames");
+
+    let images = frames
+        .iter()
+        .chain([&single])
+        .chain(iterated.iter().map(|(_, image)| image));
+    for image in images {
+        let rgba = image.as_rgba8().expect("Expected RGBA8 image");
+        assert!(
+            rgba.pixels().any(|pixel| pixel[3] < 255),
+            "Alpha plane was dropped"
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Tests require fixture files from `tests/fixtures/generate_fixtures.sh`.
 
+use std::collections::HashMap;
 use std::path::Path;
 
 use unbundle::{MediaFile, Remuxer};
@@ -14,6 +15,24 @@ fn sample_mkv_path() -> &'static str {
     "tests/fixtures/sample_video.mkv"
 }
 
+fn sample_with_chapters_path() -> &'static str {
+    "tests/fixtures/sample_with_chapters.mkv"
+}
+
+/// Container tags keyed by lower-cased name, since Matroska upper-cases
+/// tag names on write.
+fn container_tags(path: &Path) -> HashMap<String, String> {
+    let unbundler = MediaFile::open(path).expect("Failed to open");
+    unbundler
+        .metadata()
+        .tags
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key.to_lowercase(), value))
+        .collect()
+}
+
 #[test]
 fn remux_mp4_to_mkv() {
     let path = sample_video_path();
@@ -131,3 +150,140 @@ fn remux_with_aliases_compile_and_run() {
 
     let _ = std::fs::remove_file(&output_path);
 }
+
+#[test]
+fn remux_with_fix_timestamps() {
+    let path = sample_mkv_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("mp4");
+
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .with_fix_timestamps(true)
+        .run()
+        .expect("Failed to remux with timestamp fixing");
+
+    let mut unbundler = MediaFile::open(&output_path).expect("Failed to open remuxed file");
+    let mut last_dts: HashMap<usize, i64> = HashMap::new();
+    for packet in unbundler.packet_iter().expect("Failed to iterate packets") {
+        let packet = packet.expect("Packet read failed");
+        if let (Some(pts), Some(dts)) = (packet.pts, packet.dts) {
+            assert!(pts >= dts, "PTS must not precede DTS");
+            if let Some(previous) = last_dts.insert(packet.stream_index, dts) {
+                assert!(dts > previous, "DTS must be strictly increasing");
+            }
+        }
+    }
+
+    let _ = std::fs::remove_file(&output_path);
+}
+
+#[test]
+fn remux_preserves_chapters() {
+    let path = sample_with_chapters_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("mp4");
+
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .run()
+        .expect("Failed to remux");
+
+    let unbundler = MediaFile::open(&output_path).expect("Failed to open remuxed file");
+    let chapters = unbundler
+        .metadata()
+        .chapters
+        .as_ref()
+        .expect("Remuxed file should keep its chapters");
+    assert_eq!(chapters.len(), 3);
+    assert_eq!(chapters[0].title.as_deref(), Some("Introduction"));
+    assert_eq!(chapters[2].title.as_deref(), Some("Conclusion"));
+
+    let _ = std::fs::remove_file(&output_path);
+}
+
+#[test]
+fn remux_without_chapters() {
+    let path = sample_with_chapters_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("mkv");
+
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .with_chapters(false)
+        .run()
+        .expect("Failed to remux");
+
+    let unbundler = MediaFile::open(&output_path).expect("Failed to open remuxed file");
+    assert!(unbundler.metadata().chapters.is_none());
+
+    let _ = std::fs::remove_file(&output_path);
+}
+
+#[test]
+fn remux_preserves_container_tags() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("mkv");
+
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .run()
+        .expect("Failed to remux");
+
+    let input_tags = container_tags(Path::new(path));
+    let output_tags = container_tags(&output_path);
+    for (key, value) in &input_tags {
+        // The muxer always stamps its own encoder tag.
+        if key == "encoder" {
+            continue;
+        }
+        assert_eq!(output_tags.get(key), Some(value), "tag '{key}' was lost");
+    }
+
+    let _ = std::fs::remove_file(&output_path);
+}
+
+#[test]
+fn remux_strip_metadata() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("mkv");
+
+    Remuxer::new(path, &output_path)
+        .expect("Failed to create remuxer")
+        .with_strip_metadata(true)
+        .run()
+        .expect("Failed to remux");
+
+    let input_tags = container_tags(Path::new(path));
+    let output_tags = container_tags(&output_path);
+    for key in input_tags.keys().filter(|key| *key != "encoder") {
+        assert!(
+            !output_tags.contains_key(key),
+            "tag '{key}' was not stripped"
+        );
+    }
+
+    let _ = std::fs::remove_file(&output_path);
+}
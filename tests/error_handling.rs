@@ -114,6 +114,31 @@ fn no_audio_stream_error() {
     );
 }
 
+#[test]
+fn stream_presence_queries_audio_only() {
+    let path = "tests/fixtures/sample_audio_only.mp4";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open audio-only file");
+    assert!(!unbundler.has_video());
+    assert!(unbundler.has_audio());
+    assert!(!unbundler.has_subtitles());
+}
+
+#[test]
+fn stream_presence_queries_video_only() {
+    let path = "tests/fixtures/sample_video_only.mp4";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open video-only file");
+    assert!(unbundler.has_video());
+    assert!(!unbundler.has_audio());
+}
+
 #[test]
 fn invalid_audio_range_timestamps() {
     let path = "tests/fixtures/sample_video.mp4";
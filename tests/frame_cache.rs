@@ -0,0 +1,126 @@
+//! Frame cache integration tests.
+//!
+//! Requires the `cache` feature and test fixtures.
+
+#![cfg(feature = "cache")]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use unbundle::{ExtractOptions, FrameCache, FrameDiscard, MediaFile, PixelFormat, UnbundleError};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+#[test]
+fn repeated_frame_is_served_from_cache() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let cache = Arc::new(FrameCache::new(64 * 1024 * 1024));
+    let config = ExtractOptions::new().with_frame_cache(cache.clone());
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let decoded = unbundler
+        .video()
+        .frame_with_options(10, &config)
+        .expect("Failed to extract frame");
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 1, 1));
+
+    // A separately opened file shares the cache.
+    let mut reopened = MediaFile::open(path).expect("Failed to reopen fixture");
+    let cached = reopened
+        .video()
+        .frame_with_options(10, &config)
+        .expect("Failed to extract cached frame");
+    assert_eq!(cache.stats().hits, 1);
+    assert_eq!(cached.as_bytes(), decoded.as_bytes());
+
+    // Different output options are a different entry.
+    let gray = config.clone().with_pixel_format(PixelFormat::Gray8);
+    unbundler
+        .video()
+        .frame_with_options(10, &gray)
+        .expect("Failed to extract gray frame");
+    assert_eq!(cache.stats().entries, 2);
+
+    cache.clear();
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.bytes), (0, 0));
+}
+
+#[test]
+fn cache_respects_entry_limit() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let cache = Arc::new(FrameCache::new(64 * 1024 * 1024).with_max_entries(2));
+    let config = ExtractOptions::new().with_frame_cache(cache.clone());
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    for frame_number in [0, 5, 10] {
+        unbundler
+            .video()
+            .frame_with_options(frame_number, &config)
+            .expect("Failed to extract frame");
+    }
+    assert_eq!(cache.stats().entries, 2);
+
+    // Frame 0 was least recently used and has been evicted.
+    unbundler
+        .video()
+        .frame_with_options(0, &config)
+        .expect("Failed to extract frame");
+    assert_eq!(cache.stats().hits, 0);
+    unbundler
+        .video()
+        .frame_with_options(10, &config)
+        .expect("Failed to extract frame");
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn cache_hits_honour_decode_settings_and_limits() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let cache = Arc::new(FrameCache::new(64 * 1024 * 1024));
+    let config = ExtractOptions::new().with_frame_cache(cache.clone());
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    unbundler
+        .video()
+        .frame_with_options(10, &config)
+        .expect("Failed to extract frame");
+
+    // Discarding frames can land on another picture, so it is its own entry.
+    let keyframes_only = config.clone().with_frame_discard(FrameDiscard::NonKey);
+    unbundler
+        .video()
+        .frame_with_options(10, &keyframes_only)
+        .expect("Failed to extract keyframe-only frame");
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.entries), (0, 2));
+
+    // A cached frame is not served past the dimension limit.
+    let limited = config.clone().with_max_dimensions(1, 1);
+    let result = unbundler.video().frame_with_options(10, &limited);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::DimensionsTooLarge { .. })
+    ));
+    assert_eq!(cache.stats().hits, 0);
+
+    // Methods without options never consult the cache.
+    unbundler
+        .video()
+        .frame(10)
+        .expect("Failed to extract frame");
+    assert_eq!(cache.stats().hits, 0);
+}
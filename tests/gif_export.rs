@@ -5,7 +5,7 @@
 
 #![cfg(feature = "gif")]
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
 use unbundle::{FrameRange, GifOptions, MediaFile};
 
@@ -75,3 +75,40 @@ fn gif_config_with_aliases_builder() {
     assert_eq!(config.frame_delay, 12);
     assert_eq!(config.repeat, Some(2));
 }
+
+#[test]
+fn gif_motion_interpolation_resamples_to_playback_rate() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    // 10 centiseconds per frame is 10 fps, so one second of source should
+    // come out as roughly ten frames.
+    let config = GifOptions::new()
+        .width(80)
+        .frame_delay(10)
+        .motion_interpolation(true);
+    let data = unbundler
+        .video()
+        .export_gif_to_memory(
+            FrameRange::TimeRange(Duration::ZERO, Duration::from_secs(1)),
+            &config,
+        )
+        .expect("interpolated gif export");
+    assert_eq!(&data[..3], b"GIF");
+
+    let mut decoder = gif::DecodeOptions::new()
+        .read_info(data.as_slice())
+        .expect("decode gif");
+    assert_eq!(decoder.width(), 80);
+    let mut frame_count = 0;
+    while decoder.read_next_frame().expect("read gif frame").is_some() {
+        frame_count += 1;
+    }
+    assert!(
+        (8..=12).contains(&frame_count),
+        "expected about 10 frames, got {frame_count}"
+    );
+}
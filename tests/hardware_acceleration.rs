@@ -8,7 +8,10 @@
 
 use std::path::Path;
 
-use unbundle::{ExtractOptions, FrameRange, HardwareAccelerationMode, MediaFile};
+use unbundle::{
+    ExtractOptions, FrameRange, HardwareAccelerationMode, HardwareDeviceType, MediaFile,
+    UnbundleError,
+};
 
 const SAMPLE_VIDEO: &str = "tests/fixtures/sample_video.mp4";
 
@@ -66,3 +69,23 @@ fn hardware_acceleration_software_fallback() {
     assert_eq!(frame.len(), 1);
     assert!(frame[0].width() > 0);
 }
+
+#[test]
+fn hardware_mode_parses_cli_names() {
+    assert_eq!(
+        "AUTO".parse::<HardwareAccelerationMode>().unwrap(),
+        HardwareAccelerationMode::Auto
+    );
+    assert_eq!(
+        "sw".parse::<HardwareAccelerationMode>().unwrap(),
+        HardwareAccelerationMode::Software
+    );
+    assert_eq!(
+        "videotoolbox".parse::<HardwareAccelerationMode>().unwrap(),
+        HardwareAccelerationMode::Specific(HardwareDeviceType::VideoToolbox)
+    );
+    assert!(matches!(
+        "opencl".parse::<HardwareAccelerationMode>(),
+        Err(UnbundleError::InvalidOptionValue { .. })
+    ));
+}
@@ -0,0 +1,185 @@
+//! Luma histogram integration tests.
+//!
+//! These tests require the fixture files generated by
+//! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
+
+use std::{path::Path, time::Duration};
+
+use unbundle::{ExtractOptions, FrameRange, MediaFile, PixelFormat};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+#[test]
+fn luma_histogram_counts_every_pixel() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let metadata = unbundler.metadata().video.clone().expect("video metadata");
+    let histogram = unbundler
+        .video()
+        .luma_histogram(0)
+        .expect("Failed to compute histogram");
+
+    assert_eq!(histogram.frame_number, 0);
+    assert_eq!(
+        histogram.pixel_count(),
+        u64::from(metadata.width) * u64::from(metadata.height)
+    );
+    let mean = histogram.mean();
+    assert!((0.0..=255.0).contains(&mean), "mean out of range: {mean}");
+}
+
+#[test]
+fn luma_histogram_matches_gray_frame() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let histogram = unbundler
+        .video()
+        .luma_histogram(15)
+        .expect("Failed to compute histogram");
+    // The same GRAY8 conversion the histogram uses. Converting an RGB
+    // frame with `to_luma8` would apply different luma weights.
+    let config = ExtractOptions::new().with_pixel_format(PixelFormat::Gray8);
+    let gray = unbundler
+        .video()
+        .frame_with_options(15, &config)
+        .expect("Failed to extract frame")
+        .into_luma8();
+
+    let mut values: Vec<u8> = gray.as_raw().clone();
+    values.sort_unstable();
+    let expected_median = values[(values.len() - 1) / 2];
+    let expected_mean =
+        values.iter().map(|&value| f64::from(value)).sum::<f64>() / values.len() as f64;
+
+    assert_eq!(histogram.median(), expected_median);
+    assert!((histogram.mean() - expected_mean).abs() < 1e-9);
+}
+
+#[test]
+fn luma_histograms_over_range() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let config = ExtractOptions::new().with_resolution(Some(64), Some(48));
+    let histograms = unbundler
+        .video()
+        .luma_histograms_with_options(FrameRange::Range(0, 4), &config)
+        .expect("Failed to compute histograms");
+
+    let frame_numbers: Vec<u64> = histograms.iter().map(|h| h.frame_number).collect();
+    assert_eq!(frame_numbers, vec![0, 1, 2, 3, 4]);
+    for histogram in &histograms {
+        assert_eq!(histogram.pixel_count(), 64 * 48);
+    }
+}
+
+#[test]
+fn color_timeline_samples_at_interval() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let duration = unbundler.metadata().duration;
+    let timeline = unbundler
+        .video()
+        .color_timeline(Duration::from_secs(1))
+        .expect("Failed to compute color timeline");
+
+    // One sample per second, including time zero.
+    let expected = duration.as_secs() as usize + 1;
+    assert!(
+        timeline.len().abs_diff(expected) <= 1,
+        "Expected about {expected} samples, got {}",
+        timeline.len()
+    );
+    for pair in timeline.windows(2) {
+        assert!(pair[0].0 < pair[1].0, "Timestamps must increase");
+    }
+}
+
+#[test]
+fn color_timeline_matches_full_frame_average() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let timeline = unbundler
+        .video()
+        .color_timeline(Duration::from_secs(10))
+        .expect("Failed to compute color timeline");
+    let frame = unbundler
+        .video()
+        .frame(0)
+        .expect("Failed to extract frame")
+        .into_rgb8();
+
+    let pixel_count = u64::from(frame.width()) * u64::from(frame.height());
+    let mut sums = [0u64; 3];
+    for pixel in frame.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += u64::from(channel);
+        }
+    }
+    // The timeline averages a downscaled copy, so allow a little slack.
+    let (_, sampled) = timeline[0];
+    for (full, sampled) in sums.map(|sum| sum / pixel_count).iter().zip(sampled) {
+        assert!(
+            full.abs_diff(u64::from(sampled)) <= 4,
+            "{full} vs {sampled}"
+        );
+    }
+}
+
+#[test]
+fn content_bounds_within_duration() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let duration = unbundler.metadata().duration;
+    let (start, end) = unbundler
+        .video()
+        .content_bounds(16.0)
+        .expect("Failed to find content bounds");
+
+    assert!(
+        start < end,
+        "Sample video should have content: {start:?}..{end:?}"
+    );
+    assert!(end <= duration + Duration::from_millis(100));
+}
+
+#[test]
+fn content_bounds_of_all_black_threshold_is_empty() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // No frame can have a mean luma above 255, so everything counts as black.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let bounds = unbundler
+        .video()
+        .content_bounds(255.0)
+        .expect("Failed to find content bounds");
+    assert_eq!(bounds, (Duration::ZERO, Duration::ZERO));
+}
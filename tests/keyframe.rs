@@ -1,8 +1,8 @@
 //! Keyframe and Group of Pictures analysis integration tests.
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
-use unbundle::MediaFile;
+use unbundle::{FrameRange, MediaFile, Remuxer, ThumbnailOptions, UnbundleError};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -63,6 +63,38 @@ fn group_of_pictures_analysis() {
     );
 }
 
+#[test]
+fn keyframe_intervals_in_seconds() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let duration = unbundler.metadata().duration;
+    let info = unbundler
+        .video()
+        .analyze_group_of_pictures()
+        .expect("group of pictures analysis");
+
+    let timed_keyframes = info.keyframes.iter().filter(|k| k.pts.is_some()).count();
+    assert_eq!(
+        info.keyframe_intervals.len(),
+        timed_keyframes.saturating_sub(1)
+    );
+
+    let total: Duration = info.keyframe_intervals.iter().sum();
+    assert!(
+        total <= duration,
+        "intervals span {total:?}, file is {duration:?}"
+    );
+    if !info.keyframe_intervals.is_empty() {
+        assert!(info.average_keyframe_interval > Duration::ZERO);
+        let max = info.keyframe_intervals.iter().max().unwrap();
+        assert!(info.average_keyframe_interval <= *max);
+    }
+}
+
 #[test]
 fn keyframes_have_timestamps() {
     let path = sample_video_path();
@@ -83,3 +115,276 @@ fn keyframes_have_timestamps() {
         }
     }
 }
+
+#[test]
+fn nearest_keyframe_image_returns_enclosing_keyframe() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let frames_per_second = unbundler
+        .metadata()
+        .video
+        .as_ref()
+        .unwrap()
+        .frames_per_second;
+    let keyframe_positions: Vec<f64> = unbundler
+        .video()
+        .keyframes()
+        .expect("keyframes")
+        .iter()
+        .filter_map(|keyframe| keyframe.timestamp)
+        .map(|timestamp| timestamp.as_secs_f64() * frames_per_second)
+        .collect();
+
+    let target = 45;
+    let (keyframe_number, image) = unbundler
+        .video()
+        .nearest_keyframe_image(target)
+        .expect("nearest keyframe image");
+
+    assert!(keyframe_number <= target);
+    assert!(
+        keyframe_positions
+            .iter()
+            .any(|&position| (position - keyframe_number as f64).abs() < 1.0),
+        "frame {keyframe_number} is not a keyframe"
+    );
+    assert!(image.width() > 0 && image.height() > 0);
+}
+
+#[test]
+fn frame_at_keyframe_decodes_a_keyframe() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let keyframe_count = unbundler.video().keyframes().expect("keyframes").len();
+    assert!(keyframe_count > 0);
+
+    let (image, info) = unbundler
+        .video()
+        .frame_at_keyframe(keyframe_count - 1)
+        .expect("frame at keyframe");
+    assert!(
+        info.is_keyframe,
+        "frame {} is not a keyframe",
+        info.frame_number
+    );
+    assert!(image.width() > 0 && image.height() > 0);
+
+    let result = unbundler.video().frame_at_keyframe(keyframe_count);
+    assert!(matches!(
+        result,
+        Err(UnbundleError::KeyframeOutOfRange { keyframe_index, keyframe_count: count })
+            if keyframe_index == keyframe_count && count == keyframe_count
+    ));
+}
+
+#[test]
+fn keyframe_index_has_scaled_images_and_offsets() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let keyframe_count = unbundler.video().keyframes().expect("keyframes").len();
+    let config = ThumbnailOptions::new(1, 1).with_thumbnail_width(160);
+    let index = unbundler
+        .video()
+        .keyframe_index(&config)
+        .expect("keyframe index");
+
+    // The earlier `keyframes()` call must not leave the scan short.
+    assert!(keyframe_count > 0);
+    assert!(!index.is_empty(), "keyframe index is empty");
+    assert!(index.len() <= keyframe_count);
+    for entry in &index {
+        assert_eq!(entry.image.width(), 160);
+        assert!(entry.position.is_some(), "MP4 packets have byte offsets");
+    }
+    for pair in index.windows(2) {
+        assert!(pair[0].frame_number < pair[1].frame_number);
+        assert!(pair[0].timestamp < pair[1].timestamp);
+    }
+}
+
+#[test]
+fn frames_by_gop_starts_each_group_at_a_keyframe() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let groups = unbundler
+        .video()
+        .frames_by_gop(FrameRange::Range(0, 59))
+        .expect("frames by gop");
+
+    assert!(!groups.is_empty());
+    let total: usize = groups.iter().map(|group| group.frames.len()).sum();
+    assert_eq!(total, 60);
+    for group in &groups {
+        let (_, first) = &group.frames[0];
+        assert!(first.is_keyframe, "group should open on a keyframe");
+        assert_eq!(group.keyframe_number, first.frame_number);
+        assert!(group.frames[1..].iter().all(|(_, info)| !info.is_keyframe));
+    }
+}
+
+#[test]
+fn frames_by_gop_keeps_sparse_frames_in_their_own_group() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    // Decode first so the keyframe scan has to rewind.
+    unbundler.video().frame(30).expect("frame");
+    let groups = unbundler
+        .video()
+        .frames_by_gop(FrameRange::Interval(7))
+        .expect("frames by gop");
+
+    assert!(!groups.is_empty());
+    for pair in groups.windows(2) {
+        assert!(pair[0].keyframe_number < pair[1].keyframe_number);
+        let next_keyframe = pair[1].keyframe_number;
+        assert!(
+            pair[0]
+                .frames
+                .iter()
+                .all(|(_, info)| info.frame_number < next_keyframe),
+            "group at {} holds frames of the next group",
+            pair[0].keyframe_number
+        );
+    }
+    for group in &groups {
+        assert!(
+            group
+                .frames
+                .iter()
+                .all(|(_, info)| info.frame_number >= group.keyframe_number)
+        );
+    }
+}
+
+#[test]
+fn seekability_report_scores_indexed_mp4() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let keyframe_count = unbundler.video().keyframes().expect("keyframes").len();
+    let report = unbundler.video().seekability_report().expect("report");
+
+    assert!(keyframe_count > 0);
+    assert!(report.has_index, "MP4 sample tables index every keyframe");
+    assert_eq!(report.keyframe_count, keyframe_count);
+    assert!(report.average_keyframe_interval > Duration::ZERO);
+    assert!(
+        (0.5..=1.0).contains(&report.score),
+        "score {}",
+        report.score
+    );
+}
+
+#[test]
+fn bitrate_timeline_sums_packets_into_windows() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let window = Duration::from_millis(500);
+    let timeline = unbundler
+        .video()
+        .bitrate_timeline(window)
+        .expect("timeline");
+
+    assert!(!timeline.is_empty());
+    for (index, (start, _)) in timeline.iter().enumerate() {
+        assert_eq!(*start, window.mul_f64(index as f64));
+    }
+    assert!(timeline.iter().map(|(_, bytes)| bytes).sum::<u64>() > 0);
+}
+
+#[test]
+fn bitrate_timeline_rejects_zero_window() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let result = unbundler.video().bitrate_timeline(Duration::ZERO);
+    assert!(matches!(result, Err(UnbundleError::InvalidInterval)));
+    let result = unbundler.video().bitrate_timeline(Duration::from_nanos(1));
+    assert!(matches!(result, Err(UnbundleError::InvalidInterval)));
+}
+
+#[test]
+fn bitrate_timeline_counts_from_stream_start() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // MPEG-TS timestamps start well past zero.
+    let dir = tempfile::tempdir().expect("tempdir");
+    let ts_path = dir.path().join("sample.ts");
+    Remuxer::new(path, &ts_path)
+        .expect("remuxer")
+        .run()
+        .expect("remux");
+
+    let window = Duration::from_millis(500);
+    let original = MediaFile::open(path)
+        .expect("open")
+        .video()
+        .bitrate_timeline(window)
+        .expect("timeline");
+    let shifted = MediaFile::open(&ts_path)
+        .expect("open remuxed")
+        .video()
+        .bitrate_timeline(window)
+        .expect("timeline");
+
+    assert!(
+        shifted[0].1 > 0,
+        "first window should hold the first packets"
+    );
+    assert!(shifted.len() <= original.len() + 1);
+    assert!(shifted.len() + 1 >= original.len());
+}
+
+#[test]
+fn bitrate_timeline_is_complete_after_earlier_reads() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let window = Duration::from_secs(1);
+    let first = unbundler
+        .video()
+        .bitrate_timeline(window)
+        .expect("timeline");
+    unbundler.video().frame(20).expect("frame");
+    let second = unbundler
+        .video()
+        .bitrate_timeline(window)
+        .expect("timeline");
+    assert_eq!(first, second);
+}
@@ -4,9 +4,9 @@
 
 #![cfg(feature = "loudness")]
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
-use unbundle::MediaFile;
+use unbundle::{AudioFormat, CancellationToken, ExtractOptions, MediaFile, UnbundleError};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -66,3 +66,178 @@ fn loudness_on_audio_only() {
 
     assert!(info.total_samples > 0);
 }
+
+#[test]
+fn loudness_with_options_honours_cancellation() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let config = ExtractOptions::new().with_cancellation(token);
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let result = unbundler.audio().analyze_loudness_with_options(&config);
+    assert!(matches!(result, Err(UnbundleError::Cancelled)));
+}
+
+#[test]
+fn detect_silence_spans_are_ordered_and_long_enough() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let min_silence = Duration::from_millis(200);
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let spans = unbundler
+        .audio()
+        .detect_silence(-40.0, min_silence)
+        .expect("Failed to detect silence");
+
+    for (start, end) in &spans {
+        assert!(
+            *end - *start >= min_silence,
+            "Span {start:?}..{end:?} is too short"
+        );
+    }
+    for pair in spans.windows(2) {
+        assert!(pair[0].1 <= pair[1].0, "Spans must not overlap");
+    }
+
+    // Nothing is quieter than negative infinity.
+    let none = unbundler
+        .audio()
+        .detect_silence(f64::NEG_INFINITY, Duration::ZERO)
+        .expect("Failed to detect silence");
+    assert!(none.is_empty());
+
+    // Later scans on the same file still see the whole track.
+    let repeated = unbundler
+        .audio()
+        .detect_silence(-40.0, min_silence)
+        .expect("Failed to detect silence");
+    assert_eq!(repeated, spans);
+    let everything = unbundler
+        .audio()
+        .detect_silence(f64::INFINITY, Duration::ZERO)
+        .expect("Failed to detect silence");
+    let duration = unbundler.metadata().duration;
+    assert_eq!(everything.len(), 1);
+    assert_eq!(everything[0].0, Duration::ZERO);
+    assert!(
+        everything[0].1 + Duration::from_millis(500) >= duration,
+        "Silence ends at {:?} of {duration:?}",
+        everything[0].1
+    );
+}
+
+#[test]
+fn split_on_silence_writes_one_file_per_segment() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let duration = unbundler.metadata().duration;
+    let segments = unbundler
+        .audio()
+        .split_on_silence(
+            -40.0,
+            Duration::from_millis(500),
+            directory.path(),
+            AudioFormat::Wav,
+        )
+        .expect("Failed to split on silence");
+
+    assert!(!segments.is_empty(), "Sample audio is not entirely silent");
+    for (index, (start, end)) in segments.iter().enumerate() {
+        assert!(start < end && *end <= duration);
+        let file = directory.path().join(format!("segment_{index:03}.wav"));
+        assert!(file.exists(), "Missing {}", file.display());
+    }
+}
+
+#[test]
+fn integrated_loudness_is_below_full_scale() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let lufs = unbundler
+        .audio()
+        .integrated_loudness()
+        .expect("Failed to measure loudness");
+    assert!(lufs < 0.0 && lufs > -70.0, "Unexpected loudness {lufs}");
+}
+
+#[test]
+fn extract_loudness_matched_reaches_target() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let original = unbundler
+        .audio()
+        .integrated_loudness()
+        .expect("Failed to measure loudness");
+    let target = original - 6.0;
+    let bytes = unbundler
+        .audio()
+        .extract_loudness_matched(target, AudioFormat::Wav)
+        .expect("Failed to extract");
+
+    let output = tempfile::Builder::new()
+        .suffix(".wav")
+        .tempfile()
+        .expect("Failed to create temp file");
+    std::fs::write(output.path(), &bytes).expect("Failed to write");
+    let mut matched = MediaFile::open(output.path()).expect("Failed to open output");
+    let measured = matched
+        .audio()
+        .integrated_loudness()
+        .expect("Failed to measure output");
+    assert!(
+        (measured - target).abs() < 0.5,
+        "Expected about {target:.2} LUFS, measured {measured:.2}"
+    );
+}
+
+#[test]
+fn extract_loudness_matched_after_earlier_read() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut fresh = MediaFile::open(path).expect("Failed to open");
+    let expected = fresh
+        .audio()
+        .extract_loudness_matched(-23.0, AudioFormat::Wav)
+        .expect("Failed to extract");
+
+    // Reading the track first leaves the demuxer at the end of the file.
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    unbundler
+        .audio()
+        .extract(AudioFormat::Wav)
+        .expect("Failed to extract audio");
+    let loudness = unbundler
+        .audio()
+        .integrated_loudness()
+        .expect("Failed to measure loudness");
+    assert!(loudness.is_finite(), "Measured {loudness} LUFS");
+    let matched = unbundler
+        .audio()
+        .extract_loudness_matched(-23.0, AudioFormat::Wav)
+        .expect("Failed to extract");
+    assert_eq!(matched, expected);
+}
@@ -3,9 +3,11 @@
 //! These tests require the fixture files generated by
 //! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
 
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
 
-use unbundle::MediaFile;
+use unbundle::{MediaFile, StreamKind};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -118,6 +120,32 @@ fn metadata_duration() {
     );
 }
 
+#[test]
+fn stream_durations_are_reported_for_mp4() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let metadata = unbundler.metadata();
+    let container = metadata.duration.as_secs_f64();
+
+    // MP4 records a duration per track; neither may exceed the container.
+    for (name, duration) in [
+        ("video", metadata.video.as_ref().and_then(|v| v.duration)),
+        ("audio", metadata.audio.as_ref().and_then(|a| a.duration)),
+    ] {
+        let seconds = duration
+            .unwrap_or_else(|| panic!("Expected a {name} stream duration"))
+            .as_secs_f64();
+        assert!(
+            seconds > 4.0 && seconds <= container + 0.05,
+            "Unexpected {name} duration {seconds}s (container {container}s)",
+        );
+    }
+}
+
 #[test]
 fn metadata_format_name() {
     let path = sample_video_path();
@@ -245,3 +273,191 @@ fn mkv_format_works() {
         metadata.format,
     );
 }
+
+#[test]
+fn unrotated_square_pixel_video_displays_at_stored_size() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let video = unbundler.metadata().video.as_ref().unwrap();
+    assert_eq!(video.rotation, 0);
+    assert!(matches!(video.sample_aspect_ratio, None | Some((1, 1))));
+    assert_eq!(video.display_dimensions(), (video.width, video.height));
+}
+
+#[test]
+fn streams_list_matches_track_metadata() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open");
+    let metadata = unbundler.metadata();
+    let streams = unbundler.streams();
+
+    for (position, stream) in streams.iter().enumerate() {
+        assert_eq!(
+            stream.index, position,
+            "Streams are listed in container order"
+        );
+    }
+
+    let video = metadata.video.as_ref().expect("Expected video");
+    let video_stream = streams
+        .iter()
+        .find(|stream| stream.kind == StreamKind::Video && stream.track_index == Some(0))
+        .expect("Expected a selectable video stream");
+    assert_eq!(video_stream.dimensions, Some((video.width, video.height)));
+    assert_eq!(video_stream.codec, video.codec);
+
+    let audio = metadata.audio.as_ref().expect("Expected audio");
+    let audio_stream = streams
+        .iter()
+        .find(|stream| stream.kind == StreamKind::Audio)
+        .expect("Expected an audio stream");
+    assert_eq!(audio_stream.sample_rate, Some(audio.sample_rate));
+    assert_eq!(audio_stream.channels, Some(audio.channels));
+}
+
+#[test]
+fn edit_list_offsets_are_reported_by_validation() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let metadata = unbundler.metadata();
+    let offsets: Vec<(bool, f64)> = metadata
+        .video_tracks
+        .iter()
+        .flatten()
+        .map(|track| (track.has_edit_list, track.edit_list_offset))
+        .chain(
+            metadata
+                .audio_tracks
+                .iter()
+                .flatten()
+                .map(|track| (track.has_edit_list, track.edit_list_offset)),
+        )
+        .collect();
+    for (has_edit_list, offset) in &offsets {
+        assert!(*has_edit_list || *offset == 0.0);
+    }
+
+    let shifted = offsets.iter().any(|(_, offset)| offset.abs() >= 0.001);
+    let warned = unbundler
+        .validate()
+        .warnings
+        .iter()
+        .any(|warning| warning.contains("edit list"));
+    assert_eq!(shifted, warned);
+}
+
+#[test]
+fn matroska_tracks_have_no_edit_list() {
+    let path = "tests/fixtures/sample_video.mkv";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let video = unbundler
+        .metadata()
+        .video
+        .as_ref()
+        .expect("No video metadata");
+    assert!(!video.has_edit_list);
+    assert_eq!(video.edit_list_offset, 0.0);
+}
+
+#[test]
+fn coded_dimensions_cover_display_dimensions() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let video = unbundler
+        .metadata()
+        .video
+        .clone()
+        .expect("No video metadata");
+    assert!(video.coded_width >= video.width);
+    assert!(video.coded_height >= video.height);
+    // Padding never exceeds one 16-pixel macroblock (two for field coding).
+    assert!(video.coded_height - video.height < 32);
+}
+
+#[test]
+fn open_reader_matches_open() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let opened = MediaFile::open(path).expect("Failed to open fixture");
+    let file = File::open(path).expect("Failed to open fixture file");
+    let mut from_reader = MediaFile::open_reader(file).expect("Failed to open reader");
+
+    let expected = opened.metadata();
+    let actual = from_reader.metadata();
+    assert_eq!(actual.format, expected.format);
+    assert_eq!(actual.duration, expected.duration);
+    let expected_video = expected.video.as_ref().expect("No video metadata");
+    let actual_video = actual
+        .video
+        .as_ref()
+        .expect("No video metadata from reader");
+    assert_eq!(actual_video.width, expected_video.width);
+    assert_eq!(actual_video.height, expected_video.height);
+    assert_eq!(actual_video.frame_count, expected_video.frame_count);
+    assert_eq!(actual_video.codec, expected_video.codec);
+    let expected_audio = expected.audio.as_ref().expect("No audio metadata");
+    let actual_audio = actual
+        .audio
+        .as_ref()
+        .expect("No audio metadata from reader");
+    assert_eq!(actual_audio.sample_rate, expected_audio.sample_rate);
+    assert_eq!(actual_audio.channels, expected_audio.channels);
+
+    // Seeking and decoding go through the reader as well.
+    let frame = from_reader
+        .video()
+        .frame(10)
+        .expect("Failed to decode frame");
+    assert!(frame.width() > 0);
+}
+
+#[test]
+fn open_reader_rejects_unrecognised_data() {
+    let result = MediaFile::open_reader(Cursor::new(vec![0u8; 4096]));
+    assert!(result.is_err());
+}
+
+/// A reader that panics on every call.
+struct PanickingReader;
+
+impl Read for PanickingReader {
+    fn read(&mut self, _buffer: &mut [u8]) -> std::io::Result<usize> {
+        panic!("reader failure");
+    }
+}
+
+impl Seek for PanickingReader {
+    fn seek(&mut self, _position: SeekFrom) -> std::io::Result<u64> {
+        panic!("reader failure");
+    }
+}
+
+#[test]
+fn open_reader_reports_a_panicking_reader_as_an_error() {
+    // The panic is caught in the I/O callbacks instead of aborting.
+    let result = MediaFile::open_reader(PanickingReader);
+    assert!(result.is_err());
+}
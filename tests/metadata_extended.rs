@@ -86,3 +86,79 @@ fn video_track_selection() {
     let err = unbundler.video_track(99);
     assert!(err.is_err(), "track 99 should be out of range");
 }
+
+#[test]
+fn best_audio_track_matches_default_audio() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("open");
+    let best = unbundler
+        .best_audio_track()
+        .expect("expected an audio track");
+    let audio = unbundler.metadata().audio.as_ref().expect("audio metadata");
+    assert_eq!(best, audio.track_index);
+}
+
+#[test]
+fn best_audio_track_is_none_without_audio() {
+    let path = "tests/fixtures/sample_video_only.mp4";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let unbundler = MediaFile::open(path).expect("open");
+    assert_eq!(unbundler.best_audio_track(), None);
+}
+
+#[test]
+fn mp4_has_no_programs() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    assert!(unbundler.programs().is_empty());
+
+    let result = unbundler.select_program(1);
+    assert!(matches!(
+        result,
+        Err(unbundle::UnbundleError::ProgramNotFound { program_id: 1 })
+    ));
+    assert!(unbundler.metadata().video.is_some());
+    assert!(unbundler.metadata().audio.is_some());
+}
+
+#[test]
+fn select_program_keeps_program_streams() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // The MPEG-TS muxer puts every stream into a single program.
+    let directory = tempfile::tempdir().expect("tempdir");
+    let transport_stream = directory.path().join("sample.ts");
+    unbundle::Remuxer::new(path, &transport_stream)
+        .expect("remuxer")
+        .run()
+        .expect("remux to MPEG-TS");
+
+    let mut unbundler = MediaFile::open(&transport_stream).expect("open ts");
+    let programs = unbundler.programs();
+    assert_eq!(programs.len(), 1);
+    assert_eq!(programs[0].stream_indices.len(), 2);
+
+    unbundler
+        .select_program(programs[0].id)
+        .expect("select program");
+    assert!(unbundler.metadata().video.is_some());
+    assert!(unbundler.metadata().audio.is_some());
+    unbundler
+        .video()
+        .frame(0)
+        .expect("frame from selected program");
+}
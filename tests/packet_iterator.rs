@@ -1,8 +1,8 @@
 //! Packet iterator integration tests.
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
-use unbundle::MediaFile;
+use unbundle::{MediaFile, UnbundleError};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -80,3 +80,39 @@ fn packet_iterator_has_consistent_stream_indices() {
         );
     }
 }
+
+#[test]
+fn byte_range_grows_with_the_window() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let stream_index = 0;
+    let file_size = std::fs::metadata(path).expect("stat").len();
+
+    let (short_start, short_end) = unbundler
+        .byte_range_for(stream_index, Duration::ZERO, Duration::from_secs(1))
+        .expect("short range");
+    let (long_start, long_end) = unbundler
+        .byte_range_for(stream_index, Duration::ZERO, Duration::from_secs(3))
+        .expect("long range");
+
+    assert!(short_start < short_end);
+    assert!(long_end <= file_size);
+    assert_eq!(short_start, long_start);
+    assert!(long_end > short_end);
+}
+
+#[test]
+fn byte_range_rejects_inverted_window() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let result = unbundler.byte_range_for(0, Duration::from_secs(2), Duration::from_secs(1));
+    assert!(matches!(result, Err(UnbundleError::InvalidRange { .. })));
+}
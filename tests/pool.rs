@@ -0,0 +1,68 @@
+//! MediaFilePool integration tests.
+//!
+//! These tests require fixtures generated by
+//! `tests/fixtures/generate_fixtures.sh`.
+
+use std::path::Path;
+
+use unbundle::{MediaFilePool, UnbundleError};
+
+const SAMPLE_VIDEO: &str = "tests/fixtures/sample_video.mp4";
+
+#[test]
+fn pool_reuses_returned_files() {
+    if !Path::new(SAMPLE_VIDEO).exists() {
+        return;
+    }
+
+    let pool = MediaFilePool::open(SAMPLE_VIDEO).unwrap();
+    assert_eq!(pool.idle_count(), 1);
+
+    {
+        let first = pool.get().unwrap();
+        assert_eq!(pool.idle_count(), 0);
+        let second = pool.get().unwrap();
+        assert!(first.metadata().video.is_some());
+        assert!(second.metadata().video.is_some());
+    }
+    assert_eq!(pool.idle_count(), 2);
+
+    let _file = pool.get().unwrap();
+    assert_eq!(pool.idle_count(), 1);
+}
+
+#[test]
+fn pool_serves_concurrent_frame_extraction() {
+    if !Path::new(SAMPLE_VIDEO).exists() {
+        return;
+    }
+
+    let pool = MediaFilePool::open(SAMPLE_VIDEO).unwrap();
+    let sizes = std::thread::scope(|scope| {
+        let workers: Vec<_> = [0, 10, 20, 30]
+            .into_iter()
+            .map(|frame_number| {
+                let pool = &pool;
+                scope.spawn(move || -> Result<(u32, u32), UnbundleError> {
+                    let mut file = pool.get()?;
+                    let image = file.video().frame(frame_number)?;
+                    Ok((image.width(), image.height()))
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(sizes.len(), 4);
+    assert!(sizes.iter().all(|size| *size == sizes[0]));
+    assert!((1..=4).contains(&pool.idle_count()));
+}
+
+#[test]
+fn pool_open_rejects_missing_file() {
+    let result = MediaFilePool::open("tests/fixtures/does_not_exist.mp4");
+    assert!(result.is_err());
+}
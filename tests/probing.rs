@@ -115,3 +115,30 @@ fn media_file_probe_only_matches_media_probe() {
     assert_eq!(via_media_file.format, via_probe.format);
     assert_eq!(via_media_file.duration, via_probe.duration);
 }
+
+#[test]
+fn regular_files_are_seekable() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        eprintln!("Skipping: fixture '{path}' not found.");
+        return;
+    }
+
+    assert!(MediaProbe::is_seekable(path).expect("Failed to probe"));
+    let unbundler = MediaFile::open(path).expect("Failed to open");
+    assert!(unbundler.is_seekable());
+}
+
+#[test]
+fn local_files_are_not_live() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        eprintln!("Skipping: fixture '{path}' not found.");
+        return;
+    }
+
+    assert!(!MediaProbe::is_live(path).expect("Failed to probe"));
+    let unbundler = MediaFile::open(path).expect("Failed to open");
+    assert!(!unbundler.is_live());
+    assert!(!unbundler.metadata().is_live);
+}
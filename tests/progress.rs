@@ -4,6 +4,7 @@
 
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use unbundle::{
     CancellationToken, ExtractOptions, FrameRange, MediaFile, OperationType, ProgressCallback,
@@ -278,3 +279,26 @@ fn progress_reports_stream_copy_subtitle_operation() {
 
     let _ = std::fs::remove_file(&output_path);
 }
+
+#[test]
+fn exhausted_time_budget_returns_partial_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let config = ExtractOptions::new().with_time_budget(Duration::ZERO);
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &config)
+        .expect("A spent budget should not be an error");
+    assert!(frames.len() < 10, "Expected extraction to stop early");
+
+    let config = ExtractOptions::new().with_time_budget(Duration::from_secs(600));
+    let frames = unbundler
+        .video()
+        .frames_with_options(FrameRange::Range(0, 9), &config)
+        .expect("Failed to extract");
+    assert_eq!(frames.len(), 10);
+}
@@ -6,6 +6,7 @@
 #![cfg(feature = "rayon")]
 
 use std::path::Path;
+use std::time::Duration;
 
 use image::DynamicImage;
 use unbundle::{ExtractOptions, FrameRange, MediaFile};
@@ -91,6 +92,41 @@ fn parallel_matches_sequential() {
     );
 }
 
+#[test]
+fn parallel_skips_frames_before_resume_point() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let config = ExtractOptions::new().with_resume_from(51);
+    let numbers: Vec<u64> = unbundler
+        .video()
+        .numbered_frames_parallel(FrameRange::Specific(vec![0, 1, 50, 51, 100, 101]), &config)
+        .unwrap()
+        .into_iter()
+        .map(|(number, _)| number)
+        .collect();
+
+    assert_eq!(numbers, vec![51, 100, 101]);
+}
+
+#[test]
+fn parallel_stops_when_time_budget_is_spent() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let config = ExtractOptions::new().with_time_budget(Duration::ZERO);
+    let frames = unbundler
+        .video()
+        .frames_parallel(FrameRange::Specific(vec![0, 50, 100]), &config)
+        .unwrap();
+
+    assert!(frames.is_empty(), "no chunk should start after the budget");
+}
+
 #[test]
 fn parallel_interval() {
     if skip_unless(SAMPLE_VIDEO) {
@@ -132,3 +168,36 @@ fn parallel_from_open_url_source_input() {
         assert!(frame.height() > 0);
     }
 }
+
+#[test]
+fn parallel_with_bounded_thread_pool() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let config = ExtractOptions::new().with_parallel_threads(2);
+    let frames = unbundler
+        .video()
+        .frames_parallel(FrameRange::Specific(vec![0, 40, 80, 120]), &config)
+        .unwrap();
+
+    assert_eq!(frames.len(), 4);
+}
+
+#[test]
+fn numbered_parallel_frames_keep_their_numbers() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let config = ExtractOptions::new();
+    let frames = unbundler
+        .video()
+        .numbered_frames_parallel(FrameRange::Specific(vec![50, 0, 10]), &config)
+        .unwrap();
+
+    let numbers: Vec<u64> = frames.iter().map(|(number, _)| *number).collect();
+    assert_eq!(numbers, vec![0, 10, 50]);
+}
@@ -256,3 +256,47 @@ fn scene_change_debug_and_clone() {
         "Debug should show frame number: {debug}"
     );
 }
+
+#[test]
+fn scene_range_covers_first_scene_and_rejects_out_of_range() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let scene_count = unbundler.video().detect_scenes(None).unwrap().len() + 1;
+
+    let (start, end) = unbundler.video().scene_range(0, None).unwrap();
+    assert_eq!(start, std::time::Duration::ZERO);
+    assert!(end >= start);
+
+    let (_, last_end) = unbundler
+        .video()
+        .scene_range(scene_count - 1, None)
+        .unwrap();
+    assert_eq!(last_end, unbundler.metadata().duration);
+
+    let result = unbundler.video().scene_range(scene_count, None);
+    assert!(matches!(
+        result,
+        Err(unbundle::UnbundleError::SceneOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn scene_colors_has_one_entry_per_sampled_scene() {
+    if skip_unless(SAMPLE_VIDEO) {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(SAMPLE_VIDEO).unwrap();
+    let changes = unbundler.video().detect_scenes(None).unwrap();
+    let colors = unbundler
+        .video()
+        .scene_colors(std::time::Duration::from_millis(100), None)
+        .unwrap();
+
+    assert!(!colors.is_empty());
+    assert!(colors.len() <= changes.len() + 1);
+    assert_eq!(colors[0].0, std::time::Duration::ZERO);
+}
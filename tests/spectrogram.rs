@@ -0,0 +1,109 @@
+//! Mel spectrogram integration tests.
+//!
+//! Requires the `spectrogram` feature and test fixtures.
+
+#![cfg(feature = "spectrogram")]
+
+use std::path::Path;
+
+use unbundle::{MediaFile, MelOptions, UnbundleError};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+#[test]
+fn mel_spectrogram_has_one_row_per_hop() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let duration = unbundler.metadata().duration.as_secs_f64();
+    let mel = unbundler
+        .audio()
+        .mel_spectrogram(&MelOptions::new())
+        .expect("mel spectrogram");
+
+    let expected_frames = duration * 16_000.0 / 160.0;
+    assert!(
+        (mel.len() as f64 - expected_frames).abs() < 20.0,
+        "expected about {expected_frames:.0} frames, got {}",
+        mel.len()
+    );
+    assert!(mel.iter().all(|row| row.len() == 80));
+    assert!(
+        mel.iter()
+            .flatten()
+            .all(|value| value.is_finite() && *value >= -10.0)
+    );
+}
+
+#[test]
+fn mel_spectrogram_honours_band_count() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let options = MelOptions::new().with_n_mels(128).with_hop_length(320);
+    let mel = unbundler
+        .audio()
+        .mel_spectrogram(&options)
+        .expect("mel spectrogram");
+
+    assert!(!mel.is_empty());
+    assert!(mel.iter().all(|row| row.len() == 128));
+}
+
+#[test]
+fn mel_spectrogram_whisper_normalization_limits_range() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let raw = unbundler
+        .audio()
+        .mel_spectrogram(&MelOptions::new())
+        .expect("mel spectrogram");
+    let normalized = unbundler
+        .audio()
+        .mel_spectrogram(&MelOptions::new().with_whisper_normalization(true))
+        .expect("normalized mel spectrogram");
+
+    let raw_max = raw
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    let max = normalized
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    let min = normalized
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f32::INFINITY, f32::min);
+    assert!((max - (raw_max + 4.0) / 4.0).abs() < 1e-5);
+    assert!(max - min <= 2.0 + 1e-5, "range {min}..{max} exceeds 80 dB");
+}
+
+#[test]
+fn mel_spectrogram_rejects_zero_hop() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let result = unbundler
+        .audio()
+        .mel_spectrogram(&MelOptions::new().hop_length(0));
+    assert!(matches!(result, Err(UnbundleError::SpectrogramError(_))));
+}
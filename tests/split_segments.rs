@@ -0,0 +1,178 @@
+//! Lossless segment splitting integration tests.
+//!
+//! These tests require fixture files generated by
+//! `tests/fixtures/generate_fixtures.sh` (or `.bat` on Windows).
+
+use std::{path::Path, time::Duration};
+
+use unbundle::{MediaFile, UnbundleError};
+
+fn sample_video_path() -> &'static str {
+    "tests/fixtures/sample_video.mp4"
+}
+
+#[test]
+fn split_segments_covers_whole_file() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let total = unbundler.metadata().duration;
+    let segments = unbundler
+        .split_segments(directory.path(), Duration::from_secs(2), "mp4")
+        .expect("Failed to split file");
+
+    assert!(!segments.is_empty());
+    assert!(segments[0].start < Duration::from_millis(100));
+    for pair in segments.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start, "Segments must be contiguous");
+    }
+    let last_end = segments.last().unwrap().end.as_secs_f64();
+    assert!(
+        (last_end - total.as_secs_f64()).abs() < 0.25,
+        "Last segment ends at {last_end}s, file is {total:?}",
+    );
+
+    for (index, segment) in segments.iter().enumerate() {
+        assert_eq!(
+            segment.path.file_name().unwrap().to_str().unwrap(),
+            format!("segment_{index:03}.mp4")
+        );
+        let piece = MediaFile::open(&segment.path).expect("Segment should be readable");
+        assert!(piece.metadata().video.is_some());
+        assert!(piece.metadata().audio.is_some());
+    }
+}
+
+#[test]
+fn split_segments_rejects_zero_duration() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let result = unbundler.split_segments(directory.path(), Duration::ZERO, "mp4");
+    assert!(matches!(result, Err(UnbundleError::InvalidInterval)));
+}
+
+#[test]
+fn split_segments_with_tiny_duration_cuts_at_every_keyframe() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    // Each keyframe is past many boundaries; they must be skipped at once.
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let keyframe_count = unbundler
+        .video()
+        .keyframes()
+        .expect("Failed to list keyframes")
+        .len();
+    let segments = unbundler
+        .split_segments(directory.path(), Duration::from_nanos(1), "mp4")
+        .expect("Failed to split file");
+    assert_eq!(segments.len(), keyframe_count);
+}
+
+#[test]
+fn concat_rejoins_split_segments() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let total = unbundler.metadata().duration;
+    let segments = unbundler
+        .split_segments(directory.path(), Duration::from_secs(2), "mp4")
+        .expect("Failed to split file");
+
+    let output = directory.path().join("joined.mp4");
+    let pieces: Vec<_> = segments.iter().map(|segment| &segment.path).collect();
+    MediaFile::concat(&pieces, &output).expect("Failed to concatenate segments");
+
+    let joined = MediaFile::open(&output).expect("Joined file should be readable");
+    let joined_duration = joined.metadata().duration.as_secs_f64();
+    assert!(
+        (joined_duration - total.as_secs_f64()).abs() < 0.25,
+        "Joined file is {joined_duration}s, source is {total:?}",
+    );
+}
+
+#[test]
+fn concat_names_incompatible_file() {
+    let path = sample_video_path();
+    let video_only = "tests/fixtures/sample_video_only.mp4";
+    if !Path::new(path).exists() || !Path::new(video_only).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = directory.path().join("joined.mp4");
+    let result = MediaFile::concat(&[path, path, video_only], &output);
+    match result {
+        Err(UnbundleError::ConcatIncompatible { path, .. }) => {
+            assert_eq!(path, Path::new(video_only));
+        }
+        other => panic!("Expected ConcatIncompatible, got {other:?}"),
+    }
+    assert!(!output.exists());
+}
+
+#[test]
+fn extract_clip_starts_streams_together() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = directory.path().join("clip.mp4");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let clip = unbundler
+        .extract_clip(Duration::from_secs(2), Duration::from_secs(4), &output)
+        .expect("Failed to extract clip");
+
+    assert!(clip.start <= Duration::from_secs(2));
+    assert!(clip.end > clip.start);
+
+    let extracted = MediaFile::open(&output).expect("Clip should be readable");
+    let metadata = extracted.metadata();
+    let video = metadata.video.as_ref().expect("Clip should keep video");
+    let audio = metadata.audio.as_ref().expect("Clip should keep audio");
+    let expected = (clip.end - clip.start).as_secs_f64();
+    assert!(
+        (metadata.duration.as_secs_f64() - expected).abs() < 0.25,
+        "Clip is {:?}, expected about {expected}s",
+        metadata.duration,
+    );
+    if let (Some(video_duration), Some(audio_duration)) = (video.duration, audio.duration) {
+        let drift = (video_duration.as_secs_f64() - audio_duration.as_secs_f64()).abs();
+        assert!(drift < 0.1, "Audio and video differ by {drift}s");
+    }
+}
+
+#[test]
+fn extract_clip_rejects_empty_range() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let result = unbundler.extract_clip(
+        Duration::from_secs(3),
+        Duration::from_secs(3),
+        directory.path().join("clip.mp4"),
+    );
+    assert!(matches!(result, Err(UnbundleError::InvalidRange { .. })));
+}
@@ -5,7 +5,7 @@
 
 use std::{path::Path, time::Duration};
 
-use unbundle::{MediaFile, SubtitleFormat, UnbundleError};
+use unbundle::{MediaFile, SubtitleFormat, SubtitleWriteOptions, UnbundleError};
 
 fn sample_with_subtitles() -> &'static str {
     "tests/fixtures/sample_with_subtitles.mkv"
@@ -138,6 +138,60 @@ fn no_subtitle_stream_error() {
     }
 }
 
+#[test]
+fn export_ass_bundle_rejects_non_ass_track() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let codec = unbundler
+        .metadata()
+        .subtitle
+        .as_ref()
+        .expect("Expected subtitle metadata")
+        .codec
+        .clone();
+    if matches!(codec.as_str(), "ass" | "ssa") {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output = directory.path().join("bundle");
+    match unbundler.subtitle().export_ass_bundle(&output) {
+        Err(UnbundleError::StreamCopyError(_)) => {}
+        Ok(_) => panic!("Expected StreamCopyError for a {codec} track"),
+        Err(other) => panic!("Expected StreamCopyError, got: {other}"),
+    }
+    assert!(!output.exists(), "Nothing should be written");
+}
+
+#[test]
+fn text_extraction_rejects_bitmap_subtitles() {
+    let path = "tests/fixtures/sample_with_bitmap_subtitles.mkv";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    match unbundler.subtitle().extract() {
+        Err(UnbundleError::BitmapSubtitleNotText { codec, .. }) => {
+            assert!(!codec.is_empty());
+        }
+        Ok(entries) => panic!(
+            "Expected BitmapSubtitleNotText, got {} entries",
+            entries.len()
+        ),
+        Err(other) => panic!("Expected BitmapSubtitleNotText, got: {other}"),
+    }
+    let bitmaps = unbundler
+        .subtitle()
+        .extract_bitmaps()
+        .expect("extract_bitmaps failed");
+    assert!(!bitmaps.is_empty());
+}
+
 // ── metadata tests ─────────────────────────────────────────────────
 
 #[test]
@@ -211,3 +265,301 @@ fn bitmap_event_as_image_accessor_compiles_and_returns_image() {
         assert!(image.height() > 0);
     }
 }
+
+#[test]
+fn frames_for_subtitles_pairs_each_event_with_a_frame() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    if unbundler.metadata().video.is_none() {
+        return;
+    }
+
+    let events = unbundler
+        .subtitle()
+        .extract()
+        .expect("Failed to extract subtitles");
+    let expected = events.len();
+
+    let pairs = unbundler
+        .video()
+        .frames_for_subtitles(events)
+        .expect("frames_for_subtitles failed");
+
+    assert_eq!(pairs.len(), expected, "Expected one frame per event");
+    for window in pairs.windows(2) {
+        assert!(
+            window[0].0.index < window[1].0.index,
+            "Pairs should keep event order"
+        );
+    }
+    for (_, image) in &pairs {
+        assert!(image.width() > 0);
+    }
+}
+
+struct DimensionsOcr;
+
+impl unbundle::BitmapOcr for DimensionsOcr {
+    fn recognize(&self, image: &image::DynamicImage) -> String {
+        format!("{}x{}", image.width(), image.height())
+    }
+}
+
+#[test]
+fn ocr_bitmaps_produces_timed_text_events() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let bitmap_count = unbundler
+        .subtitle()
+        .extract_bitmaps()
+        .expect("extract_bitmaps failed")
+        .len();
+    let events = unbundler
+        .subtitle()
+        .ocr_bitmaps(&DimensionsOcr)
+        .expect("ocr_bitmaps failed");
+
+    assert!(events.len() <= bitmap_count);
+    for (position, event) in events.iter().enumerate() {
+        assert_eq!(event.index, position);
+        assert!(!event.text.is_empty());
+        assert!(event.end_time >= event.start_time);
+    }
+}
+
+#[test]
+fn rescale_timing_scales_start_and_end() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let original = unbundler.subtitle().extract().expect("Failed to extract");
+    let factor = 24000.0 / 1001.0 / 25.0;
+    let rescaled = unbundler
+        .subtitle()
+        .rescale_timing_fps(24000.0 / 1001.0, 25.0)
+        .expect("Failed to rescale");
+
+    assert_eq!(original.len(), rescaled.len());
+    for (before, after) in original.iter().zip(&rescaled) {
+        assert_eq!(after.start_time, before.start_time.mul_f64(factor));
+        assert_eq!(after.end_time, before.end_time.mul_f64(factor));
+        assert_eq!(after.text, before.text);
+    }
+}
+
+#[test]
+fn rescale_timing_rejects_invalid_factor() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    // The last factor is finite but overflows every non-zero timestamp.
+    for factor in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MAX] {
+        let result = unbundler.subtitle().rescale_timing(factor);
+        assert!(matches!(
+            result,
+            Err(UnbundleError::InvalidRescaleFactor(_))
+        ));
+    }
+}
+
+#[test]
+fn rescale_timing_fps_rejects_invalid_rates() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    for fps in [0.0, -25.0, f64::NAN, f64::INFINITY] {
+        for (source_fps, target_fps) in [(fps, 25.0), (25.0, fps)] {
+            let result = unbundler
+                .subtitle()
+                .rescale_timing_fps(source_fps, target_fps);
+            assert!(matches!(
+                result,
+                Err(UnbundleError::InvalidRescaleFactor(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn save_rescaled_writes_srt() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let tmp = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+    let output_path = tmp.path().with_extension("srt");
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    unbundler
+        .subtitle()
+        .save_rescaled(&output_path, SubtitleFormat::Srt, 2.0)
+        .expect("Failed to save rescaled SRT");
+
+    let content = std::fs::read_to_string(&output_path).expect("Failed to read SRT");
+    assert!(content.contains("-->"));
+
+    let _ = std::fs::remove_file(&output_path);
+}
+
+#[test]
+fn subtitle_write_options_default_is_plain_lf() {
+    let options = SubtitleWriteOptions::default();
+    assert!(!options.bom);
+    assert!(!options.crlf);
+    assert_eq!(options.max_line_length, None);
+    assert_eq!(SubtitleWriteOptions::new(), options);
+    assert_eq!(
+        options.with_bom(true).with_crlf(true),
+        SubtitleWriteOptions::windows()
+    );
+}
+
+#[test]
+fn wrap_text_breaks_long_lines_at_word_boundaries() {
+    let options = SubtitleWriteOptions::new().with_max_line_length(16);
+    assert_eq!(
+        options.wrap_text("The quick brown fox jumps over the lazy dog"),
+        "The quick brown\nfox jumps over\nthe lazy dog"
+    );
+    // Explicit breaks are kept and short lines are left alone.
+    assert_eq!(
+        options.wrap_text("Short line\nanother line that is too long"),
+        "Short line\nanother line\nthat is too long"
+    );
+    // Words longer than the limit are not split.
+    assert_eq!(
+        options.wrap_text("a supercalifragilistic word"),
+        "a\nsupercalifragilistic\nword"
+    );
+    assert_eq!(
+        SubtitleWriteOptions::new().wrap_text("unchanged when no limit is set"),
+        "unchanged when no limit is set"
+    );
+}
+
+#[test]
+fn extract_text_with_windows_options_adds_bom_and_crlf() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let text = unbundler
+        .subtitle()
+        .extract_text_with_options(SubtitleFormat::Srt, &SubtitleWriteOptions::windows())
+        .expect("Failed to extract text");
+
+    assert!(
+        text.starts_with('\u{FEFF}'),
+        "Output should start with a BOM"
+    );
+    assert!(text.contains("\r\n"));
+    assert!(
+        !text.replace("\r\n", "").contains('\n'),
+        "Every line feed should be preceded by a carriage return"
+    );
+    assert!(!text.contains("\r\r"));
+
+    let plain = unbundler
+        .subtitle()
+        .extract_text(SubtitleFormat::Srt)
+        .expect("Failed to extract text");
+    assert!(!plain.starts_with('\u{FEFF}'));
+}
+
+#[test]
+fn frame_with_context_returns_active_line_and_neighbours() {
+    let path = sample_with_subtitles();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let entries = unbundler
+        .subtitle()
+        .extract()
+        .expect("Failed to extract subtitles");
+    if entries.len() < 3 {
+        return;
+    }
+    let middle = &entries[1];
+    let timestamp = middle.start_time + (middle.end_time - middle.start_time) / 2;
+
+    let (image, lines) = unbundler
+        .frame_with_context(timestamp, 1)
+        .expect("Failed to extract frame with context");
+    assert!(image.width() > 0);
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.start_time <= timestamp && line.end_time > timestamp),
+        "The line on screen should be included"
+    );
+    assert!(lines.iter().any(|line| line.index == entries[0].index));
+    assert!(lines.iter().any(|line| line.index == entries[2].index));
+    assert!(
+        lines
+            .windows(2)
+            .all(|pair| pair[0].start_time <= pair[1].start_time)
+    );
+
+    // A repeated call rereads the cues from the start.
+    let (_, again) = unbundler
+        .frame_with_context(timestamp, 1)
+        .expect("Failed to repeat frame with context");
+    let indices = |lines: &[unbundle::SubtitleEvent]| -> Vec<usize> {
+        lines.iter().map(|line| line.index).collect()
+    };
+    assert_eq!(indices(&again), indices(&lines));
+}
+
+fn bitmap_event(reference_width: u32, reference_height: u32) -> unbundle::BitmapSubtitleEvent {
+    unbundle::BitmapSubtitleEvent {
+        start_time: Duration::ZERO,
+        end_time: Duration::from_secs(1),
+        x: 100,
+        y: 900,
+        reference_width,
+        reference_height,
+        image: image::DynamicImage::new_rgba8(400, 100),
+        index: 0,
+    }
+}
+
+#[test]
+fn bitmap_display_rect_follows_rotation_and_scale() {
+    // A caption near the bottom-left of a 1920x1080 stored frame.
+    let event = bitmap_event(1920, 1080);
+
+    assert_eq!(event.display_rect(0, 1920, 1080), (100, 900, 400, 100));
+    assert_eq!(event.display_rect(0, 960, 540), (50, 450, 200, 50));
+    // Turned clockwise, the bottom edge becomes the left edge.
+    assert_eq!(event.display_rect(90, 1080, 1920), (80, 100, 100, 400));
+    assert_eq!(event.display_rect(180, 1920, 1080), (1420, 80, 400, 100));
+    assert_eq!(event.display_rect(270, 540, 960), (450, 710, 50, 200));
+}
+
+#[test]
+fn bitmap_display_rect_without_reference_only_rotates() {
+    let event = bitmap_event(0, 0);
+    assert_eq!(event.display_rect(90, 1080, 1920), (0, 100, 100, 400));
+}
@@ -74,6 +74,34 @@ fn thumbnail_preserves_aspect_ratio() {
     );
 }
 
+#[test]
+fn thumbnail_multi_size() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        eprintln!("Skipping: fixture '{path}' not found.");
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let video_meta = unbundler.metadata().video.as_ref().unwrap().clone();
+    let original_ratio = video_meta.width as f64 / video_meta.height as f64;
+
+    let sizes =
+        ThumbnailHandle::multi_size(&mut unbundler, Duration::from_secs(1), &[320, 160, 64])
+            .expect("Failed to generate thumbnails");
+
+    let widths: Vec<u32> = sizes.iter().map(|(width, _)| *width).collect();
+    assert_eq!(widths, [320, 160, 64]);
+    for (width, image) in &sizes {
+        assert_eq!(image.width(), *width);
+        let ratio = image.width() as f64 / image.height() as f64;
+        assert!(
+            (original_ratio - ratio).abs() < 0.1,
+            "{width}w thumbnail changed the aspect ratio"
+        );
+    }
+}
+
 #[test]
 fn thumbnail_dimensions() {
     let path = sample_video_path();
@@ -0,0 +1,150 @@
+//! SMPTE timecode parsing and timecode-addressed extraction tests.
+
+use std::path::Path;
+
+use unbundle::{MediaFile, Timecode, UnbundleError};
+
+#[test]
+fn parse_non_drop_and_drop_frame() {
+    let timecode = Timecode::parse("01:23:45:12").expect("parse");
+    assert_eq!(
+        (
+            timecode.hours,
+            timecode.minutes,
+            timecode.seconds,
+            timecode.frames
+        ),
+        (1, 23, 45, 12)
+    );
+    assert!(!timecode.drop_frame);
+    assert_eq!(timecode.to_string(), "01:23:45:12");
+
+    let drop_frame: Timecode = "01:23:45;12".parse().expect("parse");
+    assert!(drop_frame.drop_frame);
+    assert_eq!(drop_frame.to_string(), "01:23:45;12");
+    assert!(Timecode::parse("01;23;45;12").expect("parse").drop_frame);
+}
+
+#[test]
+fn parse_rejects_malformed_timecodes() {
+    for text in [
+        "",
+        "01:23:45",
+        "01:23:45:12:00",
+        "01:60:00:00",
+        "aa:00:00:00",
+        "-1:00:00:00",
+    ] {
+        assert!(
+            matches!(
+                Timecode::parse(text),
+                Err(UnbundleError::InvalidTimecode(_))
+            ),
+            "{text:?} should not parse"
+        );
+    }
+}
+
+#[test]
+fn non_drop_frame_counts_whole_seconds() {
+    let timecode = Timecode::parse("00:01:00:05").unwrap();
+    assert_eq!(timecode.to_frame_number(25.0).unwrap(), 60 * 25 + 5);
+    assert!(
+        Timecode::parse("00:00:00:25")
+            .unwrap()
+            .to_frame_number(25.0)
+            .is_err()
+    );
+}
+
+#[test]
+fn drop_frame_skips_numbers_each_minute() {
+    let fps = 30_000.0 / 1_001.0;
+    assert_eq!(
+        Timecode::parse("00:00:59;29")
+            .unwrap()
+            .to_frame_number(fps)
+            .unwrap(),
+        1799
+    );
+    assert_eq!(
+        Timecode::parse("00:01:00;02")
+            .unwrap()
+            .to_frame_number(fps)
+            .unwrap(),
+        1800
+    );
+    assert_eq!(
+        Timecode::parse("00:10:00;00")
+            .unwrap()
+            .to_frame_number(fps)
+            .unwrap(),
+        17982
+    );
+    assert_eq!(
+        Timecode::parse("01:00:00;00")
+            .unwrap()
+            .to_frame_number(fps)
+            .unwrap(),
+        107892
+    );
+
+    // Frame numbers 0 and 1 do not exist at the start of minute 1.
+    assert!(
+        Timecode::parse("00:01:00;00")
+            .unwrap()
+            .to_frame_number(fps)
+            .is_err()
+    );
+    // Drop-frame is undefined at 25 fps.
+    assert!(
+        Timecode::parse("00:00:01;00")
+            .unwrap()
+            .to_frame_number(25.0)
+            .is_err()
+    );
+}
+
+#[test]
+fn drop_frame_round_trips_through_frame_numbers() {
+    for fps in [30_000.0 / 1_001.0, 60_000.0 / 1_001.0] {
+        for frame_number in [0, 1799, 1800, 17981, 17982, 107_891, 107_892, 250_000] {
+            let timecode = Timecode::from_frame_number(frame_number, fps, true);
+            assert!(timecode.drop_frame);
+            assert_eq!(
+                timecode.to_frame_number(fps).unwrap(),
+                frame_number,
+                "{timecode}"
+            );
+        }
+    }
+    assert_eq!(
+        Timecode::from_frame_number(1800, 30_000.0 / 1_001.0, true).to_string(),
+        "00:01:00;02"
+    );
+}
+
+#[test]
+fn frame_at_timecode_matches_frame_number() {
+    let path = "tests/fixtures/sample_video.mp4";
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let video = unbundler.metadata().video.clone().expect("video metadata");
+    let start = video.start_timecode.map_or(0, |start| {
+        start.to_frame_number(video.frames_per_second).unwrap()
+    });
+    let timecode = Timecode::from_frame_number(start + 10, video.frames_per_second, false);
+
+    let by_timecode = unbundler
+        .video()
+        .frame_at_timecode(&timecode.to_string())
+        .expect("frame_at_timecode");
+    let by_number = unbundler.video().frame(10).expect("frame");
+    assert_eq!(by_timecode.as_bytes(), by_number.as_bytes());
+
+    let result = unbundler.video().frame_at_timecode("not a timecode");
+    assert!(matches!(result, Err(UnbundleError::InvalidTimecode(_))));
+}
@@ -2,7 +2,7 @@
 //!
 //! Tests require fixture files from `tests/fixtures/generate_fixtures.sh`.
 
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
 use unbundle::MediaFile;
 
@@ -129,3 +129,64 @@ fn validate_is_valid_when_no_errors() {
     };
     assert!(!bad_report.is_valid());
 }
+
+#[test]
+fn quick_check_passes_clean_file() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    unbundler
+        .quick_check(Duration::from_secs(2))
+        .expect("Clean fixture should decode");
+    // Longer than the file: checks to the end without error.
+    unbundler
+        .quick_check(Duration::from_secs(60))
+        .expect("Clean fixture should decode to the end");
+}
+
+#[test]
+fn quick_check_audio_only() {
+    let path = sample_audio_only_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    assert!(unbundler.quick_check(Duration::from_secs(1)).is_ok());
+}
+
+#[test]
+fn concat_compatibility_with_itself() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let first = MediaFile::open(path).expect("open");
+    let second = MediaFile::open(path).expect("open");
+    let report = first.concat_compatibility(&second);
+    assert!(report.can_stream_copy(), "unexpected mismatches: {report}");
+}
+
+#[test]
+fn concat_compatibility_reports_missing_audio() {
+    let path = sample_video_path();
+    let video_only_path = sample_video_only_path();
+    if !Path::new(path).exists() || !Path::new(video_only_path).exists() {
+        return;
+    }
+
+    let with_audio = MediaFile::open(path).expect("open");
+    let video_only = MediaFile::open(video_only_path).expect("open");
+    let report = with_audio.concat_compatibility(&video_only);
+    assert!(!report.can_stream_copy());
+    assert!(
+        report
+            .mismatches
+            .iter()
+            .any(|mismatch| mismatch.starts_with("Audio stream count differs"))
+    );
+}
@@ -55,3 +55,41 @@ fn vfr_analysis_field_consistency() {
     assert!(analysis.mean_frame_duration > 0.0);
     assert!(analysis.frame_duration_stddev >= 0.0);
 }
+
+#[test]
+fn predominant_frame_rate_on_cfr_video() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let analysis = unbundler
+        .video()
+        .analyze_variable_framerate()
+        .expect("vfr analysis");
+
+    assert!(
+        (analysis.predominant_frames_per_second - 30.0).abs() < 0.5,
+        "expected ~30 fps, got {}",
+        analysis.predominant_frames_per_second
+    );
+    let intervals: u64 = analysis
+        .frame_rate_histogram
+        .iter()
+        .map(|bucket| bucket.interval_count)
+        .sum();
+    assert_eq!(intervals, analysis.frames_analyzed - 1);
+    let fractions: f64 = analysis
+        .frame_rate_histogram
+        .iter()
+        .map(|bucket| bucket.fraction)
+        .sum();
+    assert!((fractions - 1.0).abs() < 1e-9);
+    assert!(
+        analysis
+            .frame_rate_histogram
+            .windows(2)
+            .all(|pair| pair[0].nominal_frames_per_second < pair[1].nominal_frames_per_second)
+    );
+}
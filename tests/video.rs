@@ -5,7 +5,7 @@
 
 use std::{path::Path, time::Duration};
 
-use unbundle::{FrameRange, MediaFile, UnbundleError};
+use unbundle::{FrameOutputOptions, FrameRange, MediaFile, PixelFormat, UnbundleError};
 
 /// Path to the standard test video fixture (5s, 640×480, 30 fps, with audio).
 fn sample_video_path() -> &'static str {
@@ -224,6 +224,36 @@ fn frame_out_of_range_returns_error() {
     );
 }
 
+#[test]
+fn last_frame_is_extractable() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let frame_count = unbundler.metadata().video.as_ref().unwrap().frame_count;
+    assert!(frame_count > 0, "Fixture should report a frame count");
+
+    // Repeat to cover both a fresh seek and the cached decoder state that
+    // was left at end of stream by the previous call.
+    for _ in 0..3 {
+        let frame = unbundler
+            .video()
+            .frame(frame_count - 1)
+            .expect("Failed to extract last frame");
+        assert!(frame.width() > 0);
+    }
+
+    let mut video = unbundler.video();
+    video
+        .frame(frame_count - 2)
+        .expect("Failed to extract second-to-last frame");
+    video
+        .frame(frame_count - 1)
+        .expect("Failed to extract last frame after its predecessor");
+}
+
 #[test]
 fn frame_dimensions_match_metadata() {
     let path = sample_video_path();
@@ -451,3 +481,281 @@ fn for_each_frame_matches_frames() {
         "for_each_frame and frames() should produce the same count",
     );
 }
+
+#[test]
+fn embedded_thumbnail_absent_returns_none() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let thumbnail = unbundler
+        .video()
+        .embedded_thumbnail()
+        .expect("Lookup should not fail on a file without cover art");
+    assert!(thumbnail.is_none());
+}
+
+#[test]
+fn frames_at_pts_match_exact_timestamps() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let reference = unbundler
+        .video()
+        .frames_and_metadata(FrameRange::Specific(vec![3, 40, 90]))
+        .expect("Failed to extract reference frames");
+    let pts_values: Vec<i64> = reference
+        .iter()
+        .map(|(_, info)| info.pts.expect("Fixture frames carry a PTS"))
+        .collect();
+
+    // Unsorted input with a duplicate and a PTS no frame carries.
+    let mut request = vec![pts_values[2], pts_values[0], pts_values[1], pts_values[0]];
+    request.push(pts_values[1] + 1);
+    let frames = unbundler
+        .video()
+        .frames_at_pts(&request)
+        .expect("Failed to extract frames by PTS");
+
+    let returned: Vec<i64> = frames.iter().map(|(pts, _)| *pts).collect();
+    assert_eq!(returned, pts_values);
+    for ((_, image), (expected, _)) in frames.iter().zip(&reference) {
+        assert_eq!(image.to_rgb8(), expected.to_rgb8());
+    }
+}
+
+#[test]
+fn decode_into_reuses_caller_buffer() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let expected = unbundler
+        .video()
+        .frame(10)
+        .expect("Failed to extract reference frame")
+        .to_rgb8();
+
+    let mut video = unbundler.video();
+    let output = FrameOutputOptions::default();
+    let mut buffer = Vec::new();
+    let layout = video
+        .decode_into(10, &mut buffer, &output)
+        .expect("Failed to decode into buffer");
+    assert_eq!((layout.width, layout.height), expected.dimensions());
+    assert_eq!(layout.stride, layout.width as usize * 3);
+    assert_eq!(layout.pixel_format, PixelFormat::Rgb8);
+    assert_eq!(buffer, expected.as_raw().as_slice());
+
+    let allocation = buffer.as_ptr();
+    video
+        .decode_into(11, &mut buffer, &output)
+        .expect("Failed to decode next frame into buffer");
+    assert_eq!(
+        buffer.as_ptr(),
+        allocation,
+        "Buffer should not be reallocated"
+    );
+    assert_eq!(buffer.len(), layout.stride * layout.height as usize);
+}
+
+#[test]
+fn save_frames_with_sidecar_writes_json_metadata() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let saved = unbundler
+        .video()
+        .save_frames_with_sidecar(FrameRange::Range(0, 2), directory.path())
+        .expect("Failed to save frames with sidecars");
+
+    assert_eq!(saved.len(), 3);
+    for (frame_number, image_path) in saved.iter().enumerate() {
+        assert_eq!(
+            image_path.file_name().and_then(|name| name.to_str()),
+            Some(format!("frame_{frame_number:06}.png").as_str())
+        );
+        assert!(image_path.exists());
+
+        let sidecar = std::fs::read_to_string(image_path.with_extension("json"))
+            .expect("Missing JSON sidecar");
+        let sidecar: serde_json::Value = serde_json::from_str(&sidecar).expect("Invalid JSON");
+        assert_eq!(sidecar["frame_number"], frame_number as u64);
+        assert!(sidecar["pts"].is_i64());
+        assert!(sidecar["is_keyframe"].is_boolean());
+        assert!(sidecar["frame_type"].is_string());
+    }
+
+    let first: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(directory.path().join("frame_000000.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(first["is_keyframe"], true);
+}
+
+#[cfg(feature = "encode")]
+#[test]
+fn range_frames_arrive_in_presentation_order() {
+    use unbundle::{VideoEncoder, VideoEncoderOptions};
+
+    // libx264 keeps its default of three B-frames; other H.264 encoders
+    // may not emit any, which would leave nothing to reorder.
+    ffmpeg_next::init().expect("Failed to initialise FFmpeg");
+    let encoder = ffmpeg_next::encoder::find(ffmpeg_next::codec::Id::H264);
+    if encoder.map(|codec| codec.name()) != Some("libx264") {
+        return;
+    }
+
+    // A square sliding across a flat background is cheap to predict from
+    // both sides, so most frames are coded as B-frames.
+    let frames: Vec<image::DynamicImage> = (0..60u32)
+        .map(|index| {
+            let image = image::RgbImage::from_fn(128, 96, |x, y| {
+                let inside = (index * 2..index * 2 + 16).contains(&x) && (40..56).contains(&y);
+                if inside {
+                    image::Rgb([240, 240, 240])
+                } else {
+                    image::Rgb([20, 60, 120])
+                }
+            });
+            image::DynamicImage::ImageRgb8(image)
+        })
+        .collect();
+    let file = tempfile::Builder::new()
+        .suffix(".mp4")
+        .tempfile()
+        .expect("Failed to create temp file");
+    VideoEncoder::new(VideoEncoderOptions::default())
+        .write(file.path(), &frames)
+        .expect("Failed to encode B-frame clip");
+
+    let mut unbundler = MediaFile::open(file.path()).expect("Failed to open B-frame clip");
+    let video_stream = unbundler.metadata().video.as_ref().unwrap().stream_index;
+    let mut decode_order_pts = Vec::new();
+    for packet in unbundler.packet_iter().expect("Failed to read packets") {
+        let packet = packet.expect("Failed to read packet");
+        if packet.stream_index == video_stream {
+            decode_order_pts.extend(packet.pts);
+        }
+    }
+    assert!(
+        decode_order_pts.windows(2).any(|pair| pair[1] < pair[0]),
+        "clip has no reordered frames: {decode_order_pts:?}"
+    );
+
+    let extracted = unbundler
+        .video()
+        .frames_and_metadata(FrameRange::Range(0, 59))
+        .expect("Failed to extract frames");
+    let timestamps: Vec<Duration> = extracted.iter().map(|(_, info)| info.timestamp).collect();
+    assert_eq!(timestamps.len(), 60);
+    assert!(
+        timestamps.windows(2).all(|pair| pair[0] < pair[1]),
+        "frames out of presentation order: {timestamps:?}"
+    );
+
+    let mut frame_numbers = Vec::new();
+    unbundler
+        .video()
+        .for_each_frame(FrameRange::Range(0, 59), |frame_number, _image| {
+            frame_numbers.push(frame_number);
+            Ok(())
+        })
+        .expect("Failed to iterate frames");
+    assert_eq!(frame_numbers, (0..60).collect::<Vec<u64>>());
+}
+
+#[test]
+fn display_frame_matches_display_dimensions() {
+    let path = sample_video_path();
+    require_fixture(path);
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let video_metadata = unbundler.metadata().video.clone().unwrap();
+    let (display_width, display_height) = video_metadata.display_dimensions();
+
+    let frame = unbundler
+        .video()
+        .display_frame(0)
+        .expect("Failed to extract display frame");
+    assert_eq!(
+        (frame.width(), frame.height()),
+        (display_width, display_height)
+    );
+
+    let config = unbundle::ExtractOptions::new().with_resolution(Some(320), None);
+    let scaled = unbundler
+        .video()
+        .display_frame_with_options(0, &config)
+        .expect("Failed to extract scaled display frame");
+    assert_eq!(scaled.width(), 320);
+    let expected_height = 320.0 * display_height as f64 / display_width as f64;
+    assert!((scaled.height() as f64 - expected_height).abs() <= 1.0);
+}
+
+#[test]
+fn storyboard_returns_evenly_spaced_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let duration = unbundler.metadata().duration;
+    let board = unbundler
+        .video()
+        .storyboard(5)
+        .expect("Failed to build storyboard");
+
+    assert_eq!(board.len(), 5);
+    assert!(board.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    // The first slot's midpoint is a tenth of the way in.
+    let first = board[0].0.as_secs_f64();
+    assert!((first - duration.as_secs_f64() / 10.0).abs() < 0.1);
+}
+
+#[test]
+fn keyframe_storyboard_returns_at_most_count_frames() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let keyframes = unbundler
+        .video()
+        .keyframes()
+        .expect("Failed to scan keyframes");
+    let board = unbundler
+        .video()
+        .keyframe_storyboard(5)
+        .expect("Failed to build keyframe storyboard");
+
+    assert!(!board.is_empty());
+    assert!(board.len() <= 5);
+    assert!(board.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    // Every entry is a keyframe, found by a scan that rewinds after the
+    // earlier one.
+    for (timestamp, _) in &board {
+        assert!(
+            keyframes.iter().any(|keyframe| keyframe
+                .timestamp
+                .is_some_and(|time| time.abs_diff(*timestamp) < Duration::from_millis(50))),
+            "{timestamp:?} is not a keyframe"
+        );
+    }
+}
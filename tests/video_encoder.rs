@@ -121,3 +121,45 @@ fn video_encoder_options_with_aliases_builder() {
     assert_eq!(config.crf, Some(18));
     assert_eq!(config.bitrate, Some(5_000_000));
 }
+
+#[test]
+fn padded_1080p_h264_extracts_display_height() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let frames = unbundler
+        .video()
+        .frames(FrameRange::Range(0, 3))
+        .expect("extract frames");
+
+    // H.264 codes 1080 lines as 68 macroblock rows (1088 lines) and
+    // crops the last 8 on decode.
+    let output = tempfile::Builder::new()
+        .suffix(".mp4")
+        .tempfile()
+        .expect("create temp file");
+    let config = VideoEncoderOptions::default()
+        .frames_per_second(10)
+        .resolution(1920, 1080)
+        .codec(VideoCodec::H264);
+    let result = VideoEncoder::new(config).write(output.path(), &frames);
+    if let Err(ref e) = result {
+        let msg = format!("{e}");
+        if msg.contains("cannot open encoder") || msg.contains("codec") {
+            eprintln!("Skipping: H264 encoder not available ({msg})");
+            return;
+        }
+    }
+    result.expect("write 1080p video");
+
+    let mut padded = MediaFile::open(output.path()).expect("open 1080p video");
+    let video = padded.metadata().video.clone().expect("video metadata");
+    assert_eq!((video.width, video.height), (1920, 1080));
+    assert_eq!((video.coded_width, video.coded_height), (1920, 1088));
+
+    let frame = padded.video().frame(0).expect("extract frame");
+    assert_eq!(frame.height(), 1080);
+}
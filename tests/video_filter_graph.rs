@@ -82,3 +82,112 @@ fn frame_with_filter_invalid_spec_returns_error() {
         "Expected FilterGraphError variant"
     );
 }
+
+#[test]
+fn probe_output_reports_filtered_dimensions() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let (width, height, _) = unbundler
+        .video()
+        .filter("scale=640:480,crop=320:240:10:20,hflip,transpose=1")
+        .probe_output()
+        .expect("Failed to probe filter chain");
+    assert_eq!((width, height), (240, 320));
+
+    // The probe must agree with what extraction actually produces.
+    let frame = unbundler
+        .video()
+        .filter("crop=iw/2:ih/3")
+        .frame(0)
+        .expect("Failed to extract filtered frame");
+    let (width, height, _) = unbundler
+        .video()
+        .filter("crop=iw/2:ih/3")
+        .probe_output()
+        .expect("Failed to probe filter chain");
+    assert_eq!((width, height), (frame.width(), frame.height()));
+}
+
+#[test]
+fn probe_output_without_filters_reports_source() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let (width, height, _) = unbundler
+        .video()
+        .filter("")
+        .probe_output()
+        .expect("Failed to probe empty chain");
+    assert_eq!((width, height), (640, 480));
+}
+
+#[test]
+fn probe_output_invalid_spec_returns_error() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let result = unbundler
+        .video()
+        .filter("this_filter_does_not_exist")
+        .probe_output();
+    assert!(matches!(result, Err(UnbundleError::FilterGraphError(_))));
+}
+
+#[test]
+fn filter_outputs_split_into_named_sinks() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let images = unbundler
+        .video()
+        .frame_with_filter_outputs(
+            0,
+            "split=3[full][a][b];[a]crop=320:240:0:0[crop];[b]scale=160:120[thumb]",
+            &["full", "crop", "thumb"],
+        )
+        .expect("Failed to extract split outputs");
+
+    let sizes: Vec<(u32, u32)> = images
+        .iter()
+        .map(|image| (image.width(), image.height()))
+        .collect();
+    assert_eq!(sizes, vec![(640, 480), (320, 240), (160, 120)]);
+
+    let plain = unbundler.video().frame(0).expect("Failed to extract frame");
+    assert_eq!(images[0].to_rgb8(), plain.to_rgb8());
+}
+
+#[test]
+fn filter_outputs_reject_unknown_or_reserved_names() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let mut video = unbundler.video();
+    let empty = video.frame_with_filter_outputs(0, "split[a][b]", &[]);
+    assert!(matches!(empty, Err(UnbundleError::FilterGraphError(_))));
+
+    let reserved = video.frame_with_filter_outputs(0, "split[in][b]", &["in", "b"]);
+    assert!(matches!(reserved, Err(UnbundleError::FilterGraphError(_))));
+
+    let mismatched = video.frame_with_filter_outputs(0, "split[a][b]", &["a", "c"]);
+    assert!(matches!(
+        mismatched,
+        Err(UnbundleError::FilterGraphError(_))
+    ));
+}
@@ -4,7 +4,7 @@
 
 use std::path::Path;
 
-use unbundle::{FrameOutputOptions, FrameRange, MediaFile, PixelFormat};
+use unbundle::{FrameOutputOptions, FrameRange, MediaFile, PixelFormat, UnbundleError};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -128,10 +128,8 @@ fn frame_iter_with_options_pixel_format() {
         return;
     }
 
-    let config = FrameOutputOptions {
-        pixel_format: PixelFormat::Gray8,
-        ..FrameOutputOptions::default()
-    };
+    let mut config = FrameOutputOptions::default();
+    config.pixel_format = PixelFormat::Gray8;
 
     let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
     let iter = unbundler
@@ -218,3 +216,87 @@ fn frame_iter_video_only_file() {
         "Should decode frames from video-only file"
     );
 }
+
+#[test]
+fn frame_iter_with_metadata_matches_eager_api() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let eager = unbundler
+        .video()
+        .frames_and_metadata(FrameRange::Range(0, 9))
+        .expect("Failed to extract frames");
+    let lazy: Vec<_> = unbundler
+        .video()
+        .frame_iter_with_metadata(FrameRange::Range(0, 9))
+        .expect("Failed to create iterator")
+        .collect::<Result<_, _>>()
+        .expect("Decode error");
+
+    assert_eq!(lazy.len(), eager.len());
+    for ((_, lazy_info), (_, eager_info)) in lazy.iter().zip(&eager) {
+        assert_eq!(lazy_info.frame_number, eager_info.frame_number);
+        assert_eq!(lazy_info.pts, eager_info.pts);
+        assert_eq!(lazy_info.is_keyframe, eager_info.is_keyframe);
+        assert_eq!(lazy_info.frame_type, eager_info.frame_type);
+    }
+    // The first frame of the file is always a keyframe.
+    assert!(lazy[0].1.is_keyframe);
+}
+
+#[test]
+fn resolve_frames_lists_sorted_unique_numbers() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let frame_count = unbundler.metadata().video.as_ref().unwrap().frame_count;
+    let mut video = unbundler.video();
+
+    assert_eq!(
+        video.resolve_frames(FrameRange::Range(3, 5)).unwrap(),
+        vec![3, 4, 5]
+    );
+    assert_eq!(
+        video
+            .resolve_frames(FrameRange::Specific(vec![9, 2, 9, 4]))
+            .unwrap(),
+        vec![2, 4, 9]
+    );
+    let interval = video.resolve_frames(FrameRange::Interval(10)).unwrap();
+    assert_eq!(interval.len() as u64, frame_count.div_ceil(10));
+    assert!(interval.iter().all(|number| number % 10 == 0));
+
+    assert!(matches!(
+        video.resolve_frames(FrameRange::Range(5, 3)),
+        Err(UnbundleError::InvalidRange { .. })
+    ));
+    assert!(matches!(
+        video.resolve_frames(FrameRange::Interval(0)),
+        Err(UnbundleError::InvalidInterval)
+    ));
+}
+
+#[test]
+fn resolve_frames_matches_iterator_output() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open");
+    let range = FrameRange::Specific(vec![0, 7, 15]);
+    let plan = unbundler.video().resolve_frames(range.clone()).unwrap();
+    let yielded: Vec<u64> = unbundler
+        .video()
+        .frame_iter(range)
+        .expect("Failed to create iterator")
+        .map(|result| result.expect("Failed to decode").0)
+        .collect();
+    assert_eq!(plan, yielded);
+}
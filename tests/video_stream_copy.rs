@@ -5,7 +5,7 @@
 
 use std::{path::Path, time::Duration};
 
-use unbundle::{MediaFile, UnbundleError};
+use unbundle::{ExtractOptions, MediaFile, UnbundleError};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -104,3 +104,37 @@ fn stream_copy_video_invalid_range_returns_error() {
         "Expected InvalidRange variant"
     );
 }
+
+#[test]
+fn stream_copy_video_range_with_summary() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let directory = tempfile::tempdir().expect("Failed to create temp dir");
+    let output_path = directory.path().join("clip.mp4");
+    let mut unbundler = MediaFile::open(path).expect("Failed to open test video");
+    let summary = unbundler
+        .video()
+        .stream_copy_range_with_summary(
+            &output_path,
+            Duration::from_millis(500),
+            Duration::from_secs(2),
+            &ExtractOptions::new(),
+        )
+        .expect("Failed to stream-copy video range");
+
+    let file_size = std::fs::metadata(&output_path)
+        .expect("Output missing")
+        .len();
+    assert_eq!(summary.bytes_written, file_size);
+    assert_eq!(summary.samples_written, None);
+    // The copy starts at a keyframe at or before the requested start.
+    assert!(summary.output_duration >= Duration::from_millis(1400));
+    assert!(summary.packets_written > 0);
+
+    let copied = MediaFile::open(&output_path).expect("Failed to open copy");
+    let frames = copied.metadata().video.as_ref().unwrap().frame_count;
+    assert_eq!(summary.packets_written, frames);
+}
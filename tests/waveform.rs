@@ -5,8 +5,12 @@
 #![cfg(feature = "waveform")]
 
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-use unbundle::{MediaFile, WaveformOptions};
+use unbundle::{
+    ExtractOptions, MediaFile, OperationType, ProgressCallback, ProgressInfo, UnbundleError,
+    WaveformOptions,
+};
 
 fn sample_video_path() -> &'static str {
     "tests/fixtures/sample_video.mp4"
@@ -96,3 +100,141 @@ fn waveform_with_aliases_builds() {
     assert_eq!(config.start, Some(std::time::Duration::from_secs(1)));
     assert_eq!(config.end, Some(std::time::Duration::from_secs(3)));
 }
+
+#[test]
+fn envelope_has_requested_rate() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let waveform = unbundler
+        .audio()
+        .generate_waveform(&WaveformOptions::default())
+        .expect("waveform");
+    let envelope = unbundler.audio().envelope(100.0).expect("envelope");
+
+    // One value per 10 ms of decoded audio.
+    let expected = (waveform.duration.as_secs_f64() * 100.0).ceil() as usize;
+    assert_eq!(envelope.len(), expected);
+    assert!(
+        envelope
+            .iter()
+            .all(|value| value.is_finite() && (0.0..=1.0).contains(value))
+    );
+    assert!(envelope.iter().any(|&value| value > 0.0));
+}
+
+#[test]
+fn envelope_rejects_invalid_rate() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let sample_rate = f64::from(unbundler.metadata().audio.as_ref().unwrap().sample_rate);
+    // Faster than the sample rate would leave windows with no samples.
+    for rate in [0.0, -1.0, f64::NAN, f64::INFINITY, sample_rate * 2.0] {
+        let result = unbundler.audio().envelope(rate);
+        assert!(matches!(result, Err(UnbundleError::InvalidInterval)));
+    }
+}
+
+struct RecordingProgress {
+    infos: Mutex<Vec<ProgressInfo>>,
+}
+
+impl ProgressCallback for RecordingProgress {
+    fn on_progress(&self, info: &ProgressInfo) {
+        self.infos.lock().unwrap().push(info.clone());
+    }
+}
+
+#[test]
+fn waveform_with_options_reports_sample_progress() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let recorder = Arc::new(RecordingProgress {
+        infos: Mutex::new(Vec::new()),
+    });
+    let config = ExtractOptions::new()
+        .with_progress(recorder.clone())
+        .with_batch_size(1);
+
+    let mut unbundler = MediaFile::open(path).expect("Failed to open fixture");
+    let waveform = unbundler
+        .audio()
+        .generate_waveform_with_options(&WaveformOptions::new(), &config)
+        .expect("Failed to generate waveform");
+
+    let infos = recorder.infos.lock().unwrap();
+    assert!(infos.len() > 1, "Expected progress during decoding");
+    assert!(
+        infos
+            .iter()
+            .all(|info| info.operation == OperationType::WaveformGeneration)
+    );
+    assert!(
+        infos
+            .windows(2)
+            .all(|pair| pair[0].current <= pair[1].current)
+    );
+    assert_eq!(infos.last().unwrap().current, waveform.total_samples);
+    assert!(infos.last().unwrap().total.is_some());
+}
+
+#[test]
+fn downsampled_waveform_decodes_a_fraction_of_the_track() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    if unbundler.metadata().duration.as_secs_f64() < 4.0 {
+        return;
+    }
+    let full = unbundler
+        .audio()
+        .generate_waveform(&WaveformOptions::new().with_bins(10))
+        .expect("full waveform");
+    let sampled = unbundler
+        .audio()
+        .generate_waveform(
+            &WaveformOptions::new()
+                .with_bins(10)
+                .with_downsample_stride(3),
+        )
+        .expect("sampled waveform");
+
+    assert_eq!(sampled.bins.len(), 10);
+    assert!(sampled.total_samples < full.total_samples / 2);
+    for bin in &sampled.bins {
+        assert!(bin.min <= bin.max && bin.rms.is_finite());
+    }
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn downsampled_waveform_rejects_unrepresentable_bin_count() {
+    let path = sample_video_path();
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let mut unbundler = MediaFile::open(path).expect("open");
+    let result = unbundler.audio().generate_waveform(
+        &WaveformOptions::new()
+            .with_bins(usize::MAX)
+            .with_downsample_stride(2),
+    );
+    assert!(
+        matches!(result, Err(UnbundleError::InvalidRange { .. })),
+        "Expected InvalidRange, got: {result:?}"
+    );
+}